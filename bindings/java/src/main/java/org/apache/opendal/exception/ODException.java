@@ -50,5 +50,7 @@ public class ODException extends RuntimeException {
         ConditionNotMatch,
         ContentTruncated,
         ContentIncomplete,
+        InsufficientStorage,
+        RangeNotSatisfied,
     }
 }
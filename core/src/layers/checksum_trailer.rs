@@ -0,0 +1,658 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Magic bytes at the beginning of every trailer.
+const MAGIC: &[u8; 4] = b"ODC1";
+/// Size of the trailer: magic (4) + crc32c (4).
+const TRAILER_SIZE: usize = 8;
+
+/// Add a CRC32C checksum trailer to every file for end-to-end integrity.
+///
+/// # Notes
+///
+/// ChecksumTrailerLayer calculates CRC32C of the content while writing and
+/// appends a trailer to it on `close`. The trailer will be verified and
+/// stripped transparently while reading. Services don't need to support
+/// any checksum features.
+///
+/// Stored files look like the following:
+///
+/// ```text
+/// +---------+-------+--------+
+/// | content | magic | crc32c |
+/// +---------+-------+--------+
+///              4B       4B
+/// ```
+///
+/// `magic` is `ODC1` and `crc32c` is the big-endian CRC32C (Castagnoli) of
+/// `content`.
+///
+/// Content is streamed to users while reading and the trailer is verified
+/// at the end, so a checksum mismatch will be returned as
+/// [`ErrorKind::Unexpected`] by the last read. Users must not trust the
+/// data until the reader returns EOF.
+///
+/// # Limitations
+///
+/// - Ranged read is rejected with [`ErrorKind::Unsupported`] since the
+///   checksum covers the whole content.
+/// - Seek on the returning reader is not supported.
+/// - `append` is not supported since appending would break the trailer.
+/// - `stat` and `read` report the logical content length without trailer,
+///   but entries returned by `list` still carry the stored content length.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ChecksumTrailerLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ChecksumTrailerLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ChecksumTrailerLayer;
+
+impl<A: Accessor> Layer<A> for ChecksumTrailerLayer {
+    type LayeredAccessor = ChecksumTrailerAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ChecksumTrailerAccessor { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChecksumTrailerAccessor<A: Accessor> {
+    inner: A,
+}
+
+impl<A: Accessor> ChecksumTrailerAccessor<A> {
+    fn check_read_args(&self, args: &OpRead) -> Result<()> {
+        if !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "ranged read is not supported on files with checksum trailer",
+            )
+            .with_operation(Operation::Read)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("range", args.range().to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn check_write_args(&self, args: &OpWrite) -> Result<()> {
+        if let Some(offset) = args.offset() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write at offset is not supported on files with checksum trailer",
+            )
+            .with_operation(Operation::Write)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("offset", offset.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn build_write_args(&self, args: OpWrite) -> OpWrite {
+        match args.content_length() {
+            Some(size) => args.with_content_length(size + TRAILER_SIZE as u64),
+            None => args,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ChecksumTrailerAccessor<A> {
+    type Inner = A;
+    type Reader = ChecksumTrailerReader<A::Reader>;
+    type BlockingReader = ChecksumTrailerReader<A::BlockingReader>;
+    type Writer = ChecksumTrailerWriter<A::Writer>;
+    type BlockingWriter = ChecksumTrailerWriter<A::BlockingWriter>;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    /// Remove capabilities that can't be supported with checksum trailer.
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.capability_mut();
+        cap.read_can_seek = false;
+        cap.read_with_range = false;
+        cap.copy_with_range = false;
+        cap.write_range = false;
+        cap.write_can_positional = false;
+        cap.write_can_resume = false;
+        cap.append = false;
+        cap.append_with_content_type = false;
+        cap.append_with_content_disposition = false;
+        cap.append_with_cache_control = false;
+        cap.presign_read = false;
+        cap.presign_write = false;
+        cap.select = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check_read_args(&args)?;
+
+        self.inner.read(path, args).await.map(|(rp, r)| {
+            let size = rp.metadata().content_length_raw();
+            (build_rp(rp), ChecksumTrailerReader::new(r, size))
+        })
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check_read_args(&args)?;
+
+        self.inner.blocking_read(path, args).map(|(rp, r)| {
+            let size = rp.metadata().content_length_raw();
+            (build_rp(rp), ChecksumTrailerReader::new(r, size))
+        })
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write_args(&args)?;
+
+        let args = self.build_write_args(args);
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, ChecksumTrailerWriter::new(w)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write_args(&args)?;
+
+        let args = self.build_write_args(args);
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, ChecksumTrailerWriter::new(w)))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "append is not supported on files with checksum trailer",
+        )
+        .with_operation(Operation::Append)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Read(_) | PresignOperation::Write(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign read or write would bypass checksum trailer",
+            )
+            .with_operation(Operation::Presign)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("path", path)),
+            _ => self.inner.presign(path, args).await,
+        }
+    }
+
+    async fn select(&self, path: &str, _: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "select would be evaluated against content with checksum trailer",
+        )
+        .with_operation(Operation::Select)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.stat(path, args).await?;
+        Ok(RpStat::new(build_logical_metadata(rp.into_metadata())?))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.blocking_stat(path, args)?;
+        Ok(RpStat::new(build_logical_metadata(rp.into_metadata())?))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+fn build_rp(rp: RpRead) -> RpRead {
+    let meta = rp.into_metadata();
+    match meta.content_length_raw() {
+        Some(size) => {
+            let size = size.saturating_sub(TRAILER_SIZE as u64);
+            RpRead::with_metadata(meta.with_content_length(size))
+        }
+        None => RpRead::with_metadata(meta),
+    }
+}
+
+fn build_logical_metadata(meta: Metadata) -> Result<Metadata> {
+    if !meta.is_file() {
+        return Ok(meta);
+    }
+
+    match meta.content_length_raw() {
+        Some(size) => {
+            let size = size
+                .checked_sub(TRAILER_SIZE as u64)
+                .ok_or_else(truncated_error)?;
+            Ok(meta.with_content_length(size))
+        }
+        None => Ok(meta),
+    }
+}
+
+fn truncated_error() -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "file is too short to carry a checksum trailer",
+    )
+}
+
+pub struct ChecksumTrailerReader<R> {
+    inner: R,
+    crc: u32,
+    /// Data that could belong to the trailer, at most `TRAILER_SIZE` bytes
+    /// are held back after every read.
+    tail: BytesMut,
+    content: Bytes,
+    /// The stored size of file if known, the trailer will be verified as
+    /// soon as all data has been read so that callers reading exactly the
+    /// logical content length still observe checksum errors.
+    size: Option<u64>,
+    read: u64,
+    finished: bool,
+}
+
+impl<R> ChecksumTrailerReader<R> {
+    fn new(inner: R, size: Option<u64>) -> Self {
+        Self {
+            inner,
+            crc: 0,
+            tail: BytesMut::new(),
+            content: Bytes::new(),
+            size,
+            read: 0,
+            finished: false,
+        }
+    }
+
+    /// Consume data read from storage, `None` means EOF has been reached.
+    ///
+    /// Returns whether the reader is finished.
+    fn consume(&mut self, bs: Option<Bytes>) -> Result<bool> {
+        match bs {
+            Some(bs) => {
+                self.read += bs.len() as u64;
+                self.tail.extend_from_slice(&bs);
+
+                let mut content = Bytes::new();
+                if self.tail.len() > TRAILER_SIZE {
+                    content = self.tail.split_to(self.tail.len() - TRAILER_SIZE).freeze();
+                    self.crc = crc32c::crc32c_append(self.crc, &content);
+                }
+
+                // Verify before returning the last part of content.
+                if !self.finished && self.size == Some(self.read) {
+                    self.verify()?;
+                    self.finished = true;
+                }
+
+                self.content = content;
+                Ok(false)
+            }
+            None if self.finished => Ok(true),
+            None => {
+                self.verify()?;
+                self.finished = true;
+                Ok(true)
+            }
+        }
+    }
+
+    fn verify(&self) -> Result<()> {
+        if self.tail.len() < TRAILER_SIZE {
+            return Err(truncated_error());
+        }
+        if &self.tail[..4] != MAGIC {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "file doesn't carry a checksum trailer",
+            ));
+        }
+
+        let expected = u32::from_be_bytes(self.tail[4..].try_into().expect("must be 4 bytes"));
+        let actual = self.crc;
+        if expected != actual {
+            return Err(Error::new(ErrorKind::Unexpected, "checksum mismatch")
+                .with_context("expected", format!("{expected:08x}"))
+                .with_context("actual", format!("{actual:08x}")));
+        }
+
+        Ok(())
+    }
+
+    fn copy_content(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.content.len());
+        buf[..n].copy_from_slice(&self.content[..n]);
+        self.content.advance(n);
+        n
+    }
+}
+
+impl<R: oio::Read> oio::Read for ChecksumTrailerReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if !self.content.is_empty() {
+                return Poll::Ready(Ok(self.copy_content(buf)));
+            }
+
+            let bs = ready!(self.inner.poll_next(cx)).transpose()?;
+            if self.consume(bs)? {
+                return Poll::Ready(Ok(0));
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "checksum trailer reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            if !self.content.is_empty() {
+                return Poll::Ready(Some(Ok(self.content.split_off(0))));
+            }
+
+            let bs = match ready!(self.inner.poll_next(cx)).transpose() {
+                Ok(bs) => bs,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            };
+            match self.consume(bs) {
+                Ok(true) => return Poll::Ready(None),
+                Ok(false) => continue,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for ChecksumTrailerReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if !self.content.is_empty() {
+                return Ok(self.copy_content(buf));
+            }
+
+            let bs = self.inner.next().transpose()?;
+            if self.consume(bs)? {
+                return Ok(0);
+            }
+        }
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let _ = pos;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "checksum trailer reader doesn't support seeking",
+        ))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            if !self.content.is_empty() {
+                return Some(Ok(self.content.split_off(0)));
+            }
+
+            let bs = match self.inner.next().transpose() {
+                Ok(bs) => bs,
+                Err(err) => return Some(Err(err)),
+            };
+            match self.consume(bs) {
+                Ok(true) => return None,
+                Ok(false) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+pub struct ChecksumTrailerWriter<W> {
+    inner: W,
+    crc: u32,
+}
+
+impl<W> ChecksumTrailerWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, crc: 0 }
+    }
+
+    fn trailer(&self) -> Bytes {
+        let mut trailer = Vec::with_capacity(TRAILER_SIZE);
+        trailer.extend_from_slice(MAGIC);
+        trailer.extend_from_slice(&self.crc.to_be_bytes());
+        trailer.into()
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for ChecksumTrailerWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.crc = crc32c::crc32c_append(self.crc, &bs);
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.write(self.trailer()).await?;
+        self.inner.close().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for ChecksumTrailerWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.crc = crc32c::crc32c_append(self.crc, &bs);
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.write(self.trailer())?;
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::layers::tests::NativeRangedWriteLayer;
+    use crate::services::Memory;
+
+    fn new_operator() -> (Operator, Operator) {
+        let raw = Operator::new(Memory::default()).unwrap().finish();
+        let op = raw.clone().layer(ChecksumTrailerLayer);
+        (raw, op)
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> anyhow::Result<()> {
+        let (raw, op) = new_operator();
+
+        for size in [0, 1, 7, 8, 9, 1000] {
+            let content: Vec<u8> = (0..size).map(|v| v as u8).collect();
+            let path = format!("file-{size}");
+
+            op.write(&path, content.clone()).await?;
+
+            let stored = raw.read(&path).await?;
+            assert_eq!(stored.len(), size + TRAILER_SIZE);
+            assert_eq!(stored[..size], content[..]);
+            assert_eq!(&stored[size..size + 4], MAGIC);
+
+            assert_eq!(op.read(&path).await?, content);
+            assert_eq!(op.stat(&path).await?.content_length(), size as u64);
+
+            let bs: Vec<Bytes> = op.reader(&path).await?.try_collect().await?;
+            assert_eq!(bs.concat(), content);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_range() -> anyhow::Result<()> {
+        let raw = Operator::new(Memory::default())
+            .unwrap()
+            .finish()
+            .layer(NativeRangedWriteLayer);
+        let op = raw.clone().layer(ChecksumTrailerLayer);
+        assert!(!op.info().capability().write_range);
+
+        let range = |offset| OpWriteRange::new(offset).with_part_size(4);
+        op.write_range_with("file", range(4), "efgh").await?;
+        op.write_range_with("file", range(0), "abcd").await?;
+        op.finalize_ranged_write("file").await?;
+
+        assert_eq!(op.read("file").await?, b"abcdefgh");
+        let stored = raw.read("file").await?;
+        assert_eq!(stored.len(), 8 + TRAILER_SIZE);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writer_without_content_length() -> anyhow::Result<()> {
+        let (_, op) = new_operator();
+
+        let mut w = op.writer("file").await?;
+        w.write(vec![1; 10]).await?;
+        w.write(vec![2; 30]).await?;
+        w.close().await?;
+
+        let mut expected = vec![1; 10];
+        expected.extend_from_slice(&[2; 30]);
+        assert_eq!(op.read("file").await?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_round_trip() -> anyhow::Result<()> {
+        let (_, op) = new_operator();
+        let op = op.blocking();
+
+        op.write("file", vec![1; 100])?;
+        assert_eq!(op.read("file")?, vec![1; 100]);
+        assert_eq!(op.stat("file")?.content_length(), 100);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_corrupted() -> anyhow::Result<()> {
+        let (raw, op) = new_operator();
+        op.write("file", vec![1; 100]).await?;
+        let stored = raw.read("file").await?;
+
+        let mut tampered = stored.clone();
+        tampered[3] ^= 1;
+        raw.write("tampered", tampered).await?;
+        let err = op.read("tampered").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        raw.write("truncated", stored[..stored.len() - 1].to_vec())
+            .await?;
+        let err = op.read("truncated").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        raw.write("plain", vec![1; 100]).await?;
+        let err = op.read("plain").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ranged_read_rejected() -> anyhow::Result<()> {
+        let (_, op) = new_operator();
+        op.write("file", vec![1; 100]).await?;
+
+        let err = op.range_read("file", 1..10).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+}
@@ -119,6 +119,9 @@ use crate::*;
 /// - if only `hierarchy`, with [`oio::to_hierarchy_pager`].
 /// - If neither not supported, something must be wrong.
 ///
+/// If services don't support list with prefix natively, CompleteLayer will
+/// filter returning entries by the prefix instead.
+///
 /// [`AccessorHint`]: crate::raw::AccessorHint
 pub struct CompleteLayer;
 
@@ -147,6 +150,174 @@ impl<A: Accessor> Debug for CompleteReaderAccessor<A> {
 }
 
 impl<A: Accessor> CompleteReaderAccessor<A> {
+    /// Make sure the copy args will not be ignored by services.
+    fn check_copy_args(&self, args: &OpCopy) -> Result<()> {
+        if args.source_bucket().is_some() && !self.meta.capability().copy_cross_bucket {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy from another bucket is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Copy));
+        }
+        if args.preserve_user_metadata().is_some()
+            && !self.meta.capability().copy_with_preserve_user_metadata
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with preserve user metadata is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Copy));
+        }
+        if args.preserve_tags().is_some() && !self.meta.capability().copy_with_preserve_tags {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with preserve tags is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Copy));
+        }
+        if args.if_match().is_some() && !self.meta.capability().copy_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with if match is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Copy));
+        }
+        if !args.range().is_full() && !self.meta.capability().copy_with_range {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "copy with range is not supported")
+                    .with_context("service", self.meta.scheme())
+                    .with_operation(Operation::Copy),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_delete_args(&self, args: &OpDelete) -> Result<()> {
+        if args.bypass_governance() && !self.meta.capability().delete_with_bypass_governance {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "delete with bypass governance is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Delete));
+        }
+        if args.if_match().is_some() && !self.meta.capability().delete_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "delete with if match is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Delete));
+        }
+
+        Ok(())
+    }
+
+    fn check_read_args(&self, args: &OpRead) -> Result<()> {
+        if args.if_match().is_some() && !self.meta.capability().read_with_if_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with if match is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Read));
+        }
+        if args.if_none_match().is_some() && !self.meta.capability().read_with_if_none_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with if none match is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Read));
+        }
+
+        Ok(())
+    }
+
+    fn check_write_args(&self, args: &OpWrite) -> Result<()> {
+        if args.offset().is_some() && !self.meta.capability().write_can_positional {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "write at offset is not supported")
+                    .with_context("service", self.meta.scheme())
+                    .with_operation(Operation::Write),
+            );
+        }
+        if args.if_generation_match().is_some()
+            && !self.meta.capability().write_with_if_generation_match
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with if generation match is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Write));
+        }
+        if args.content_language().is_some() && !self.meta.capability().write_with_content_language
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with content language is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Write));
+        }
+        if !args.http_headers().is_empty() {
+            if !self.meta.capability().write_with_http_header {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "write with http header is not supported",
+                )
+                .with_context("service", self.meta.scheme())
+                .with_operation(Operation::Write));
+            }
+            for (name, value) in args.http_headers() {
+                validate_http_header(name, value).map_err(|err| {
+                    err.with_context("service", self.meta.scheme())
+                        .with_operation(Operation::Write)
+                })?;
+            }
+        }
+        if args.blob_type().is_some() && !self.meta.capability().write_with_blob_type {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with blob type is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Write));
+        }
+        if !args.resume_parts().is_empty() && !self.meta.capability().write_can_resume {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "write with resume is not supported")
+                    .with_context("service", self.meta.scheme())
+                    .with_operation(Operation::Write),
+            );
+        }
+        if args.expire().is_some() && !self.meta.capability().write_with_expire {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "write with expire is not supported")
+                    .with_context("service", self.meta.scheme())
+                    .with_operation(Operation::Write),
+            );
+        }
+        if args.decompressed_size().is_some()
+            && !self.meta.capability().write_with_decompressed_size
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with decompressed size is not supported",
+            )
+            .with_context("service", self.meta.scheme())
+            .with_operation(Operation::Write));
+        }
+
+        Ok(())
+    }
+
     async fn complete_reader(
         &self,
         path: &str,
@@ -158,7 +329,9 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
 
         let range = args.range();
         let (rp, r) = self.inner.read(path, args).await?;
-        let content_length = rp.metadata().content_length();
+        // Services could omit content length for chunked responses, the
+        // size will be fetched lazily by range reader.
+        let content_length = rp.metadata().content_length_raw();
 
         match (seekable, streamable) {
             (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
@@ -171,17 +344,31 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                     (Some(offset), _) => (offset, content_length),
                     (None, None) => (0, content_length),
                     (None, Some(size)) => {
-                        // TODO: we can read content range to calculate
-                        // the total content length.
-                        let om = self.inner.stat(path, OpStat::new()).await?.into_metadata();
-                        let total_size = om.content_length();
+                        // Suffix read needs the total size to know the offset,
+                        // prefer content range of the response over an extra stat.
+                        let meta = rp.metadata();
+                        let total_size = match meta
+                            .bit()
+                            .contains(Metakey::ContentRange)
+                            .then(|| meta.content_range())
+                            .flatten()
+                            .and_then(|v| v.size())
+                        {
+                            Some(v) => v,
+                            None => self
+                                .inner
+                                .stat(path, OpStat::new())
+                                .await?
+                                .into_metadata()
+                                .content_length(),
+                        };
                         let (offset, size) = if size > total_size {
                             (0, total_size)
                         } else {
                             (total_size - size, size)
                         };
 
-                        (offset, size)
+                        (offset, Some(size))
                     }
                 };
                 let r = oio::into_reader::by_range(self.inner.clone(), path, r, offset, size);
@@ -220,11 +407,110 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         }
     }
 
+    /// Returns the full prefix to filter entries if services don't support
+    /// list with prefix natively.
+    ///
+    /// Prefix matched case-insensitively is always filtered here and
+    /// returned in lowercase.
+    fn complete_list_prefix(&self, path: &str, args: &OpList) -> Option<String> {
+        let case_insensitive = args.case_insensitive_prefix();
+        let prefix = match args.prefix() {
+            Some(_) if self.meta.capability().list_with_prefix && !case_insensitive => None,
+            Some(prefix) if path == "/" => Some(prefix.to_string()),
+            Some(prefix) => Some(format!("{path}{prefix}")),
+            None => None,
+        };
+
+        if case_insensitive {
+            prefix.map(|v| v.to_lowercase())
+        } else {
+            prefix
+        }
+    }
+
+    /// Get the start_after that should be filtered by [`CompletePager`].
+    ///
+    /// Emulated pagers and services without native support may return
+    /// entries before start_after, so we filter them here.
+    fn complete_list_start_after<P>(
+        &self,
+        p: &CompletePagerInner<A, P>,
+        start_after: Option<String>,
+    ) -> Option<String> {
+        match p {
+            CompletePagerInner::AlreadyComplete(_)
+                if self.meta.capability().list_with_start_after =>
+            {
+                None
+            }
+            _ => start_after,
+        }
+    }
+
+    /// Get the prune predicate that should be applied by [`CompletePager`].
+    ///
+    /// Emulated flat pagers never list pruned dirs, only keys returned by
+    /// native flat listing need to be filtered.
+    fn complete_list_prune<P>(
+        p: &CompletePagerInner<A, P>,
+        prune: Option<ListPrune>,
+    ) -> Option<ListPrune> {
+        match p {
+            CompletePagerInner::AlreadyComplete(_) => prune,
+            _ => None,
+        }
+    }
+
+    /// Check if the listing path exists before yielding it as the self entry.
+    ///
+    /// Services without dir markers return not found for dirs that only
+    /// contain children, [`CompletePager`] will confirm them by the listed
+    /// entries instead.
+    async fn complete_list_self_exists(&self, path: &str) -> Result<bool> {
+        if path == "/" {
+            return Ok(true);
+        }
+
+        match self.inner.stat(path, OpStat::new()).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     async fn complete_list(
         &self,
         path: &str,
         args: OpList,
     ) -> Result<(RpList, CompletePager<A, A::Pager>)> {
+        let prefix = self.complete_list_prefix(path, &args);
+        let case_insensitive = args.case_insensitive_prefix();
+        let include_self = args.include_self();
+        let args = if case_insensitive {
+            args.with_caseless_prefix()
+        } else {
+            args
+        };
+        let start_after = args.start_after().map(|v| v.to_string());
+        let prune = args.prune().cloned();
+        let self_exists = include_self && self.complete_list_self_exists(path).await?;
+        let (rp, p) = self.complete_list_inner(path, args).await?;
+        let start_after = self.complete_list_start_after(&p, start_after);
+        let prune = Self::complete_list_prune(&p, prune);
+        Ok((
+            rp,
+            CompletePager::new(p, path, prefix, case_insensitive, include_self)
+                .with_self_exists(self_exists)
+                .with_start_after(start_after)
+                .with_prune(prune),
+        ))
+    }
+
+    async fn complete_list_inner(
+        &self,
+        path: &str,
+        args: OpList,
+    ) -> Result<(RpList, CompletePagerInner<A, A::Pager>)> {
         let cap = self.meta.capability();
         if !cap.list {
             return Err(
@@ -239,25 +525,27 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         if delimiter.is_empty() {
             return if cap.list_without_delimiter {
                 let (rp, p) = self.inner.list(path, args).await?;
-                Ok((rp, CompletePager::AlreadyComplete(p)))
+                Ok((rp, CompletePagerInner::AlreadyComplete(p)))
             } else {
+                let prune = args.prune().cloned();
                 let p = to_flat_pager(
                     self.inner.clone(),
                     path,
                     args.with_delimiter("/").limit().unwrap_or(1000),
-                );
-                Ok((RpList::default(), CompletePager::NeedFlat(p)))
+                )
+                .with_prune(prune);
+                Ok((RpList::default(), CompletePagerInner::NeedFlat(p)))
             };
         }
 
         if delimiter == "/" {
             return if cap.list_with_delimiter_slash {
                 let (rp, p) = self.inner.list(path, args).await?;
-                Ok((rp, CompletePager::AlreadyComplete(p)))
+                Ok((rp, CompletePagerInner::AlreadyComplete(p)))
             } else {
                 let (_, p) = self.inner.list(path, args.with_delimiter("")).await?;
                 let p = to_hierarchy_pager(p, path);
-                Ok((RpList::default(), CompletePager::NeedHierarchy(p)))
+                Ok((RpList::default(), CompletePagerInner::NeedHierarchy(p)))
             };
         }
 
@@ -269,11 +557,51 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         .with_context("delimiter", delimiter))
     }
 
+    fn complete_blocking_list_self_exists(&self, path: &str) -> Result<bool> {
+        if path == "/" {
+            return Ok(true);
+        }
+
+        match self.inner.blocking_stat(path, OpStat::new()) {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     fn complete_blocking_list(
         &self,
         path: &str,
         args: OpList,
     ) -> Result<(RpList, CompletePager<A, A::BlockingPager>)> {
+        let prefix = self.complete_list_prefix(path, &args);
+        let case_insensitive = args.case_insensitive_prefix();
+        let include_self = args.include_self();
+        let args = if case_insensitive {
+            args.with_caseless_prefix()
+        } else {
+            args
+        };
+        let start_after = args.start_after().map(|v| v.to_string());
+        let prune = args.prune().cloned();
+        let self_exists = include_self && self.complete_blocking_list_self_exists(path)?;
+        let (rp, p) = self.complete_blocking_list_inner(path, args)?;
+        let start_after = self.complete_list_start_after(&p, start_after);
+        let prune = Self::complete_list_prune(&p, prune);
+        Ok((
+            rp,
+            CompletePager::new(p, path, prefix, case_insensitive, include_self)
+                .with_self_exists(self_exists)
+                .with_start_after(start_after)
+                .with_prune(prune),
+        ))
+    }
+
+    fn complete_blocking_list_inner(
+        &self,
+        path: &str,
+        args: OpList,
+    ) -> Result<(RpList, CompletePagerInner<A, A::BlockingPager>)> {
         let cap = self.meta.capability();
         if !cap.list {
             return Err(
@@ -288,26 +616,28 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         if delimiter.is_empty() {
             return if cap.list_without_delimiter {
                 let (rp, p) = self.inner.blocking_list(path, args)?;
-                Ok((rp, CompletePager::AlreadyComplete(p)))
+                Ok((rp, CompletePagerInner::AlreadyComplete(p)))
             } else {
+                let prune = args.prune().cloned();
                 let p = to_flat_pager(
                     self.inner.clone(),
                     path,
                     args.with_delimiter("/").limit().unwrap_or(1000),
-                );
-                Ok((RpList::default(), CompletePager::NeedFlat(p)))
+                )
+                .with_prune(prune);
+                Ok((RpList::default(), CompletePagerInner::NeedFlat(p)))
             };
         }
 
         if delimiter == "/" {
             return if cap.list_with_delimiter_slash {
                 let (rp, p) = self.inner.blocking_list(path, args)?;
-                Ok((rp, CompletePager::AlreadyComplete(p)))
+                Ok((rp, CompletePagerInner::AlreadyComplete(p)))
             } else {
                 let (_, p) = self.inner.blocking_list(path, args.with_delimiter(""))?;
                 let p: ToHierarchyPager<<A as Accessor>::BlockingPager> =
                     to_hierarchy_pager(p, path);
-                Ok((RpList::default(), CompletePager::NeedHierarchy(p)))
+                Ok((RpList::default(), CompletePagerInner::NeedHierarchy(p)))
             };
         }
 
@@ -336,10 +666,12 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check_read_args(&args)?;
         self.complete_reader(path, args).await
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check_read_args(&args)?;
         self.complete_blocking_reader(path, args)
     }
 
@@ -361,7 +693,42 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
         })
     }
 
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.validate_key(path)?;
+        self.inner.create_dir(path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.validate_key(path)?;
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_copy_args(&args)?;
+        self.inner.validate_key(to)?;
+        self.inner.copy(from, to, args).await
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.check_copy_args(&args)?;
+        self.inner.validate_key(to)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner.validate_key(to)?;
+        self.inner.rename(from, to, args).await
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner.validate_key(to)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write_args(&args)?;
+        self.inner.validate_key(path)?;
+
         let size = args.content_length();
         self.inner
             .write(path, args)
@@ -370,12 +737,34 @@ impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write_args(&args)?;
+        self.inner.validate_key(path)?;
+
         let size = args.content_length();
         self.inner
             .blocking_write(path, args)
             .map(|(rp, w)| (rp, CompleteWriter::new(w, size)))
     }
 
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_delete_args(&args)?;
+        self.inner.delete(path, args).await
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check_delete_args(&args)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        for (_, op) in args.operation() {
+            match op {
+                BatchOperation::Delete(op) => self.check_delete_args(op)?,
+            }
+        }
+        self.inner.batch(args).await
+    }
+
     async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
         self.inner
             .append(path, args)
@@ -415,6 +804,21 @@ where
         }
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [io::IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        use CompleteReader::*;
+
+        match self {
+            AlreadyComplete(r) => r.poll_read_vectored(cx, bufs),
+            NeedSeekable(r) => r.poll_read_vectored(cx, bufs),
+            NeedStreamable(r) => r.poll_read_vectored(cx, bufs),
+            NeedBoth(r) => r.poll_read_vectored(cx, bufs),
+        }
+    }
+
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
         use CompleteReader::*;
 
@@ -474,20 +878,174 @@ where
     }
 }
 
-pub enum CompletePager<A: Accessor, P> {
+pub struct CompletePager<A: Accessor, P> {
+    inner: CompletePagerInner<A, P>,
+    /// The listing path, the entry of it will be filtered out if
+    /// `include_self` is set.
+    path: String,
+    /// Whether the entry of listing path is requested.
+    include_self: bool,
+    /// Only entries starting with this prefix will be returned.
+    prefix: Option<String>,
+    /// Whether to match the prefix case-insensitively, the prefix is
+    /// already in lowercase if so.
+    case_insensitive: bool,
+    /// Only entries after this path will be returned.
+    start_after: Option<String>,
+    /// The entry of listing path that should be returned first.
+    self_entry: Option<Entry>,
+    /// Whether the listing path is known to exist, `self_entry` is only
+    /// returned after that.
+    self_exists: bool,
+    /// Entries under dirs pruned by it will be filtered out.
+    prune: Option<ListPrune>,
+    done: bool,
+}
+
+impl<A: Accessor, P> CompletePager<A, P> {
+    fn new(
+        inner: CompletePagerInner<A, P>,
+        path: &str,
+        prefix: Option<String>,
+        case_insensitive: bool,
+        include_self: bool,
+    ) -> Self {
+        let self_entry = include_self.then(|| Entry::new(path, Metadata::new(EntryMode::DIR)));
+
+        Self {
+            inner,
+            path: path.to_string(),
+            include_self,
+            prefix,
+            case_insensitive,
+            start_after: None,
+            self_entry,
+            self_exists: false,
+            prune: None,
+            done: false,
+        }
+    }
+
+    fn with_self_exists(mut self, self_exists: bool) -> Self {
+        self.self_exists = self_exists;
+        self
+    }
+
+    fn with_start_after(mut self, start_after: Option<String>) -> Self {
+        self.start_after = start_after;
+        self
+    }
+
+    fn with_prune(mut self, prune: Option<ListPrune>) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Check if any parent dir of path under the listing path is pruned.
+    fn is_pruned(&self, path: &str) -> bool {
+        let prune = match &self.prune {
+            Some(prune) => prune,
+            None => return false,
+        };
+        let start = if self.path == "/" { 0 } else { self.path.len() };
+
+        path.match_indices('/')
+            .map(|(idx, _)| &path[..=idx])
+            .filter(|dir| dir.len() > start && dir.len() < path.len())
+            .any(|dir| prune(dir))
+    }
+
+    fn filter(&mut self, entries: Option<Vec<Entry>>) -> Option<Vec<Entry>> {
+        let entries = match entries {
+            Some(entries) => entries,
+            None => {
+                self.done = true;
+                return self.take_self_entry().map(|de| vec![de]);
+            }
+        };
+
+        // Any entry listed under the path proves that the dir exists.
+        if !entries.is_empty() {
+            self.self_exists = true;
+        }
+
+        let entries = entries
+            .into_iter()
+            .filter(|e| !self.include_self || e.path() != self.path)
+            .filter(|e| match &self.prefix {
+                Some(prefix) if self.case_insensitive => {
+                    e.path().to_lowercase().starts_with(prefix.as_str())
+                }
+                Some(prefix) => e.path().starts_with(prefix.as_str()),
+                None => true,
+            })
+            .filter(|e| match &self.start_after {
+                Some(start_after) => e.path() > start_after.as_str(),
+                None => true,
+            })
+            .filter(|e| !self.is_pruned(e.path()))
+            .collect();
+
+        match self.take_self_entry() {
+            Some(de) => Some([de].into_iter().chain(entries).collect()),
+            None => Some(entries),
+        }
+    }
+
+    fn take_self_entry(&mut self) -> Option<Entry> {
+        if self.self_exists {
+            self.self_entry.take()
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl<A, P> oio::Page for CompletePager<A, P>
+where
+    A: Accessor<Pager = P>,
+    P: oio::Page,
+{
+    async fn next(&mut self) -> Result<Option<Vec<Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let entries = self.inner.next().await?;
+        Ok(self.filter(entries))
+    }
+}
+
+impl<A, P> oio::BlockingPage for CompletePager<A, P>
+where
+    A: Accessor<BlockingPager = P>,
+    P: oio::BlockingPage,
+{
+    fn next(&mut self) -> Result<Option<Vec<Entry>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let entries = self.inner.next()?;
+        Ok(self.filter(entries))
+    }
+}
+
+pub enum CompletePagerInner<A: Accessor, P> {
     AlreadyComplete(P),
     NeedFlat(ToFlatPager<Arc<A>, P>),
     NeedHierarchy(ToHierarchyPager<P>),
 }
 
 #[async_trait]
-impl<A, P> oio::Page for CompletePager<A, P>
+impl<A, P> oio::Page for CompletePagerInner<A, P>
 where
     A: Accessor<Pager = P>,
     P: oio::Page,
 {
     async fn next(&mut self) -> Result<Option<Vec<Entry>>> {
-        use CompletePager::*;
+        use CompletePagerInner::*;
 
         match self {
             AlreadyComplete(p) => p.next().await,
@@ -497,13 +1055,13 @@ where
     }
 }
 
-impl<A, P> oio::BlockingPage for CompletePager<A, P>
+impl<A, P> oio::BlockingPage for CompletePagerInner<A, P>
 where
     A: Accessor<BlockingPager = P>,
     P: oio::BlockingPage,
 {
     fn next(&mut self) -> Result<Option<Vec<Entry>>> {
-        use CompletePager::*;
+        use CompletePagerInner::*;
 
         match self {
             AlreadyComplete(p) => p.next(),
@@ -580,6 +1138,14 @@ where
         Ok(())
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        let w = self.inner.as_mut().ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
+        })?;
+
+        w.flush().await
+    }
+
     async fn close(&mut self) -> Result<()> {
         if let Some(size) = self.size {
             if self.written < size {
@@ -0,0 +1,735 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::io::Write;
+use std::mem;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use flate2::Decompress;
+use flate2::FlushDecompress;
+use flate2::Status;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Size of the buffer used to read compressed content.
+const INPUT_BUFFER_SIZE: usize = 64 * 1024;
+/// Max size of the decompressed chunks returned by `next`.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Add transparent compression for underlying storage services.
+///
+/// # Notes
+///
+/// CompressionLayer compresses content into raw deflate stream while
+/// writing and decompresses it while reading. Both directions are
+/// streaming: compressed content is sent to the underlying writer as soon
+/// as it's produced, and read content is inflated chunk by chunk.
+///
+/// The compressed size can't be known before all content has been
+/// written, so the underlying service must support write without content
+/// length.
+///
+/// On services that support [`OpWrite::with_decompressed_size`], like
+/// `s3` which stores it as user metadata `x-amz-meta-original-size`, the
+/// size before compression is stored with the object if it's known while
+/// writing, for example via [`Operator::write`]. It's returned by
+/// [`Metadata::decompressed_size`] on `stat` and `read`, used to
+/// preallocate the decompressed content and to verify it. Objects without
+/// it are still readable, just without preallocation.
+///
+/// # Limitations
+///
+/// - Ranged read is rejected with [`ErrorKind::Unsupported`], users must
+///   read the whole object.
+/// - Seek on the returning reader is not supported.
+/// - `append` is not supported.
+/// - `stat` and `list` still report the compressed content length, so
+///   ranges written by [`Operator::write_range`] can't be assembled.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CompressionLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(CompressionLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CompressionLayer;
+
+impl<A: Accessor> Layer<A> for CompressionLayer {
+    type LayeredAccessor = CompressionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        CompressionAccessor { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompressionAccessor<A: Accessor> {
+    inner: A,
+}
+
+impl<A: Accessor> CompressionAccessor<A> {
+    fn check_read_args(&self, args: &OpRead) -> Result<()> {
+        if !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "ranged read is not supported on compressed objects",
+            )
+            .with_operation(Operation::Read)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("range", args.range().to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn check_write_args(&self, args: &OpWrite) -> Result<()> {
+        if let Some(offset) = args.offset() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write at offset is not supported on compressed objects",
+            )
+            .with_operation(Operation::Write)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("offset", offset.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// The content length given by users is the decompressed size, store
+    /// it with object if possible.
+    fn build_write_args(&self, args: OpWrite) -> OpWrite {
+        let size = args.content_length();
+        let args = args.without_content_length();
+
+        match size {
+            Some(size) if self.inner.info().capability().write_with_decompressed_size => {
+                args.with_decompressed_size(size)
+            }
+            _ => args,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for CompressionAccessor<A> {
+    type Inner = A;
+    type Reader = CompressionReader<A::Reader>;
+    type BlockingReader = CompressionReader<A::BlockingReader>;
+    type Writer = CompressionWriter<A::Writer>;
+    type BlockingWriter = CompressionWriter<A::BlockingWriter>;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    /// Remove capabilities that can't be supported on compressed objects.
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.capability_mut();
+        cap.read_can_seek = false;
+        cap.read_with_range = false;
+        cap.copy_with_range = false;
+        cap.write_range = false;
+        cap.write_can_positional = false;
+        cap.write_can_resume = false;
+        cap.append = false;
+        cap.append_with_content_type = false;
+        cap.append_with_content_disposition = false;
+        cap.append_with_cache_control = false;
+        cap.presign_read = false;
+        cap.presign_write = false;
+        cap.select = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check_read_args(&args)?;
+
+        let (rp, r) = self.inner.read(path, args).await?;
+        let r = CompressionReader::new(r, decompressed_size(rp.metadata()));
+        Ok((r.build_rp(), r))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check_read_args(&args)?;
+
+        let (rp, r) = self.inner.blocking_read(path, args)?;
+        let r = CompressionReader::new(r, decompressed_size(rp.metadata()));
+        Ok((r.build_rp(), r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write_args(&args)?;
+
+        let size = args.content_length();
+        let args = self.build_write_args(args);
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, CompressionWriter::new(w, size)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write_args(&args)?;
+
+        let size = args.content_length();
+        let args = self.build_write_args(args);
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, CompressionWriter::new(w, size)))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "append is not supported on compressed objects",
+        )
+        .with_operation(Operation::Append)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Read(_) | PresignOperation::Write(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign read or write would bypass compression",
+            )
+            .with_operation(Operation::Presign)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("path", path)),
+            _ => self.inner.presign(path, args).await,
+        }
+    }
+
+    async fn select(&self, path: &str, _: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "select would be evaluated against compressed content",
+        )
+        .with_operation(Operation::Select)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// Returns the decompressed size stored with object if it's returned by
+/// the underlying service.
+fn decompressed_size(meta: &Metadata) -> Option<u64> {
+    if meta.bit().contains(Metakey::DecompressedSize) || meta.bit().contains(Metakey::Complete) {
+        meta.decompressed_size()
+    } else {
+        None
+    }
+}
+
+fn new_compress_error(err: io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "failed to compress content").set_source(err)
+}
+
+fn new_decompress_error(err: flate2::DecompressError) -> Error {
+    Error::new(ErrorKind::Unexpected, "failed to decompress content").set_source(err)
+}
+
+pub struct CompressionReader<R> {
+    inner: R,
+    /// The decompressed size stored with object.
+    size: Option<u64>,
+    decompress: Decompress,
+
+    /// Compressed content in `input[pos..filled]` is not consumed yet.
+    input: Vec<u8>,
+    pos: usize,
+    filled: usize,
+    eof: bool,
+    finished: bool,
+
+    chunk: Vec<u8>,
+}
+
+impl<R> CompressionReader<R> {
+    fn new(inner: R, size: Option<u64>) -> Self {
+        let chunk_size = size.map_or(CHUNK_SIZE, |v| (v as usize).clamp(1, CHUNK_SIZE));
+
+        Self {
+            inner,
+            size,
+            decompress: Decompress::new(false),
+
+            input: vec![0; INPUT_BUFFER_SIZE],
+            pos: 0,
+            filled: 0,
+            eof: false,
+            finished: false,
+
+            chunk: vec![0; chunk_size],
+        }
+    }
+
+    /// Returns the decompressed size as content length if it's known, so
+    /// that callers can preallocate.
+    fn build_rp(&self) -> RpRead {
+        match self.size {
+            Some(size) => RpRead::with_metadata(
+                Metadata::new(EntryMode::FILE)
+                    .with_content_length(size)
+                    .with_decompressed_size(size),
+            ),
+            None => RpRead::with_metadata(Metadata::new(EntryMode::FILE)),
+        }
+    }
+
+    /// Decompress buffered content into `buf`.
+    ///
+    /// Returns `None` if more compressed content is required to make
+    /// progress.
+    fn inflate(&mut self, buf: &mut [u8]) -> Result<Option<usize>> {
+        if self.finished {
+            return Ok(Some(0));
+        }
+
+        let flush = if self.eof {
+            FlushDecompress::Finish
+        } else {
+            FlushDecompress::None
+        };
+
+        let (total_in, total_out) = (self.decompress.total_in(), self.decompress.total_out());
+        let status = self
+            .decompress
+            .decompress(&self.input[self.pos..self.filled], buf, flush)
+            .map_err(new_decompress_error)?;
+        self.pos += (self.decompress.total_in() - total_in) as usize;
+        let n = (self.decompress.total_out() - total_out) as usize;
+
+        if status == Status::StreamEnd {
+            self.finished = true;
+            self.check_size()?;
+        }
+        if n > 0 || self.finished {
+            return Ok(Some(n));
+        }
+        if self.eof {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "compressed content is truncated",
+            ));
+        }
+
+        Ok(None)
+    }
+
+    fn check_size(&self) -> Result<()> {
+        match self.size {
+            Some(size) if size != self.decompress.total_out() => Err(Error::new(
+                ErrorKind::Unexpected,
+                "decompressed size doesn't match the stored size",
+            )
+            .with_context("expect", size.to_string())
+            .with_context("actual", self.decompress.total_out().to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Make room in `input` for more compressed content.
+    fn reserve_input(&mut self) {
+        if self.pos == self.filled {
+            self.pos = 0;
+            self.filled = 0;
+        } else if self.filled == self.input.len() {
+            self.input.copy_within(self.pos..self.filled, 0);
+            self.filled -= self.pos;
+            self.pos = 0;
+        }
+
+        // Not a single byte could be consumed from a full buffer.
+        if self.filled == self.input.len() {
+            self.input.resize(self.input.len() * 2, 0);
+        }
+    }
+
+    fn fill(&mut self, n: usize) {
+        if n == 0 {
+            self.eof = true;
+        } else {
+            self.filled += n;
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for CompressionReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if let Some(n) = self.inflate(buf)? {
+                return Poll::Ready(Ok(n));
+            }
+
+            self.reserve_input();
+            let n = ready!(self.inner.poll_read(cx, &mut self.input[self.filled..]))?;
+            self.fill(n);
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "compressed reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let mut chunk = mem::take(&mut self.chunk);
+        let res = oio::Read::poll_read(self, cx, &mut chunk).map(|res| match res {
+            Ok(0) => None,
+            Ok(n) => Some(Ok(Bytes::copy_from_slice(&chunk[..n]))),
+            Err(err) => Some(Err(err)),
+        });
+        self.chunk = chunk;
+        res
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for CompressionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if let Some(n) = self.inflate(buf)? {
+                return Ok(n);
+            }
+
+            self.reserve_input();
+            let n = self.inner.read(&mut self.input[self.filled..])?;
+            self.fill(n);
+        }
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let _ = pos;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "compressed reader doesn't support seeking",
+        ))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let mut chunk = mem::take(&mut self.chunk);
+        let res = match oio::BlockingRead::read(self, &mut chunk) {
+            Ok(0) => None,
+            Ok(n) => Some(Ok(Bytes::copy_from_slice(&chunk[..n]))),
+            Err(err) => Some(Err(err)),
+        };
+        self.chunk = chunk;
+        res
+    }
+}
+
+pub struct CompressionWriter<W> {
+    inner: W,
+    encoder: DeflateEncoder<Vec<u8>>,
+
+    /// The decompressed size given by users.
+    size: Option<u64>,
+    written: u64,
+}
+
+impl<W> CompressionWriter<W> {
+    fn new(inner: W, size: Option<u64>) -> Self {
+        Self {
+            inner,
+            encoder: DeflateEncoder::new(Vec::new(), Compression::default()),
+            size,
+            written: 0,
+        }
+    }
+
+    /// Compress given content, returns the compressed content that is
+    /// ready to be sent.
+    fn push(&mut self, bs: &[u8]) -> Result<Bytes> {
+        if let Some(size) = self.size {
+            if self.written + bs.len() as u64 > size {
+                return Err(Error::new(
+                    ErrorKind::ContentTruncated,
+                    &format!(
+                        "writer got too much data, expect: {size}, actual: {}",
+                        self.written + bs.len() as u64
+                    ),
+                ));
+            }
+        }
+
+        self.encoder.write_all(bs).map_err(new_compress_error)?;
+        self.written += bs.len() as u64;
+        Ok(self.take_output())
+    }
+
+    /// Returns all pending compressed content, the content sent so far
+    /// can be decompressed completely.
+    fn sync(&mut self) -> Result<Bytes> {
+        self.encoder.flush().map_err(new_compress_error)?;
+        Ok(self.take_output())
+    }
+
+    fn finish(&mut self) -> Result<Bytes> {
+        if let Some(size) = self.size {
+            if self.written < size {
+                return Err(Error::new(
+                    ErrorKind::ContentIncomplete,
+                    &format!(
+                        "writer got too less data, expect: {size}, actual: {}",
+                        self.written
+                    ),
+                ));
+            }
+        }
+
+        self.encoder.try_finish().map_err(new_compress_error)?;
+        Ok(self.take_output())
+    }
+
+    fn take_output(&mut self) -> Bytes {
+        Bytes::from(mem::take(self.encoder.get_mut()))
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for CompressionWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let bs = self.push(&bs)?;
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let bs = self.sync()?;
+        if !bs.is_empty() {
+            self.inner.write(bs).await?;
+        }
+
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let bs = self.finish()?;
+        self.inner.write(bs).await?;
+        self.inner.close().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for CompressionWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let bs = self.push(&bs)?;
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let bs = self.finish()?;
+        self.inner.write(bs)?;
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::layers::CompleteLayer;
+    use crate::services::Memory;
+
+    fn new_operator() -> (Operator, Operator) {
+        let raw = Operator::new(Memory::default()).unwrap().finish();
+        let op = raw.clone().layer(CompressionLayer);
+        (raw, op)
+    }
+
+    fn inflate(bs: &[u8]) -> Vec<u8> {
+        let mut content = Vec::new();
+        DeflateDecoder::new(bs)
+            .read_to_end(&mut content)
+            .expect("must be valid deflate stream");
+        content
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> anyhow::Result<()> {
+        let (raw, op) = new_operator();
+
+        for size in [0, 1, 15, 16, 1000, 100_000, 1_000_000] {
+            let content: Vec<u8> = (0..size).map(|v| (v % 7) as u8).collect();
+            let path = format!("file-{size}");
+
+            op.write(&path, content.clone()).await?;
+            let compressed = raw.read(&path).await?;
+            assert_eq!(inflate(&compressed), content);
+            assert_eq!(
+                op.stat(&path).await?.content_length(),
+                compressed.len() as u64
+            );
+
+            assert_eq!(op.read(&path).await?, content);
+            let bs: Vec<Bytes> = op.reader(&path).await?.try_collect().await?;
+            assert_eq!(bs.concat(), content);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writer_in_chunks() -> anyhow::Result<()> {
+        let (raw, op) = new_operator();
+
+        let mut w = op.writer("chunks").await?;
+        w.write("hello ").await?;
+        w.write("world").await?;
+        w.close().await?;
+        assert_eq!(op.read("chunks").await?, b"hello world");
+        assert_eq!(op.blocking().read("chunks")?, b"hello world");
+
+        let mut w = op.blocking().writer("blocking")?;
+        w.write("hello ")?;
+        w.write("world")?;
+        w.close()?;
+        assert_eq!(inflate(&raw.read("blocking").await?), b"hello world");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_without_decompressed_size() -> anyhow::Result<()> {
+        let acc = CompressionLayer.layer(CompleteLayer.layer(Memory::default().build()?));
+        assert!(!acc.info().capability().write_with_decompressed_size);
+
+        let (_, mut w) = Accessor::write(&acc, "file", OpWrite::new()).await?;
+        oio::Write::write(&mut w, Bytes::from("hello")).await?;
+        oio::Write::close(&mut w).await?;
+
+        // Fallback to reading without preallocation.
+        let (rp, mut r) = Accessor::read(&acc, "file", OpRead::new()).await?;
+        assert_eq!(rp.metadata().content_length_raw(), None);
+        let mut bs = Vec::new();
+        while let Some(v) = oio::ReadExt::next(&mut r).await {
+            bs.extend_from_slice(&v?);
+        }
+        assert_eq!(bs, b"hello");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_content_length_mismatch() -> anyhow::Result<()> {
+        let acc = CompressionLayer.layer(CompleteLayer.layer(Memory::default().build()?));
+
+        let args = OpWrite::new().with_content_length(5);
+        let (_, mut w) = Accessor::write(&acc, "file", args.clone()).await?;
+        let err = oio::Write::write(&mut w, Bytes::from("hello world"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ContentTruncated);
+
+        let (_, mut w) = Accessor::write(&acc, "file", args).await?;
+        oio::Write::write(&mut w, Bytes::from("hell")).await?;
+        let err = oio::Write::close(&mut w).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ContentIncomplete);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_truncated() -> anyhow::Result<()> {
+        let (raw, op) = new_operator();
+        let content: Vec<u8> = (0..10000).map(|v| (v % 251) as u8).collect();
+        op.write("file", content).await?;
+
+        let compressed = raw.read("file").await?;
+        raw.write("truncated", compressed[..compressed.len() - 2].to_vec())
+            .await?;
+        let err = op.read("truncated").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ranged_read_rejected() -> anyhow::Result<()> {
+        let (_, op) = new_operator();
+        op.write("file", "hello").await?;
+
+        let err = op.range_read("file", 0..2).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+}
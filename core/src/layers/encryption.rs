@@ -0,0 +1,1059 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::Aead;
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::Payload;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::KeyInit;
+use aes_gcm::Nonce;
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Magic bytes at the beginning of every encrypted object.
+const MAGIC: &[u8; 4] = b"ODE1";
+/// Size of the header: magic (4) + block size (4) + nonce prefix (8).
+const HEADER_SIZE: usize = 16;
+/// Size of the nonce prefix stored in header.
+const NONCE_PREFIX_SIZE: usize = 8;
+/// Size of the AES-GCM authentication tag.
+const TAG_SIZE: usize = 16;
+/// Default plaintext size of every block.
+const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+/// Max plaintext size of every block.
+///
+/// The block size in header is not authenticated until the first block is
+/// opened, so we must limit it before allocating any buffer.
+const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Add client side encryption for underlying storage services.
+///
+/// # Notes
+///
+/// EncryptionLayer encrypts data with AES-256-GCM while writing and
+/// decrypts it transparently while reading. Users provide a 32 bytes key
+/// and keep it safe by themselves, OpenDAL never persists it.
+///
+/// Encrypted objects are self-describing, they look like the following:
+///
+/// ```text
+/// +-------+------------+--------------+---------+-----+---------+
+/// | magic | block size | nonce prefix | block 0 | ... | block N |
+/// +-------+------------+--------------+---------+-----+---------+
+///    4B        4B            8B
+/// ```
+///
+/// Every block carries at most `block size` bytes of ciphertext followed
+/// by a 16 bytes authentication tag. The nonce of a block is built from
+/// the nonce prefix, the block index and a flag that marks the last
+/// block, so reordered, dropped or truncated blocks can be detected. The
+/// header is authenticated as associated data of every block.
+///
+/// Any authentication failure will be returned as [`ErrorKind::Unexpected`]
+/// and no unverified plaintext will be returned to users.
+///
+/// # Limitations
+///
+/// - Ranged read is rejected with [`ErrorKind::Unsupported`], users must
+///   read the whole object.
+/// - Seek on the returning reader is not supported.
+/// - `append` is not supported since appending would break the framing.
+/// - `stat` reports the plaintext content length, but entries returned by
+///   `list` still carry the ciphertext content length.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::EncryptionLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(EncryptionLayer::new([0; 32]))
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct EncryptionLayer {
+    key: [u8; 32],
+    block_size: usize,
+}
+
+impl Debug for EncryptionLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionLayer")
+            .field("key", &"<redacted>")
+            .field("block_size", &self.block_size)
+            .finish()
+    }
+}
+
+impl EncryptionLayer {
+    /// Create a new EncryptionLayer with given key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            block_size: DEFAULT_BLOCK_SIZE,
+        }
+    }
+
+    /// Set the plaintext block size used while writing.
+    ///
+    /// The block size is stored in the header, so objects written with
+    /// different block sizes can be read by the same layer.
+    ///
+    /// Default to 64 KiB.
+    ///
+    /// # Panics
+    ///
+    /// Input block_size must be larger than 0 and not larger than 16 MiB.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        assert!(
+            block_size > 0 && block_size <= MAX_BLOCK_SIZE,
+            "block_size must be larger than 0 and not larger than 16 MiB"
+        );
+
+        self.block_size = block_size;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for EncryptionLayer {
+    type LayeredAccessor = EncryptionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        EncryptionAccessor {
+            inner,
+            cipher: Aes256Gcm::new(&self.key.into()),
+            block_size: self.block_size,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EncryptionAccessor<A: Accessor> {
+    inner: A,
+    cipher: Aes256Gcm,
+    block_size: usize,
+}
+
+impl<A: Accessor> Debug for EncryptionAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionAccessor")
+            .field("inner", &self.inner)
+            .field("block_size", &self.block_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> EncryptionAccessor<A> {
+    fn check_read_args(&self, args: &OpRead) -> Result<()> {
+        if !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "ranged read is not supported on encrypted objects",
+            )
+            .with_operation(Operation::Read)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("range", args.range().to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn check_write_args(&self, args: &OpWrite) -> Result<()> {
+        if let Some(offset) = args.offset() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write at offset is not supported on encrypted objects",
+            )
+            .with_operation(Operation::Write)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("offset", offset.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn build_write_args(&self, args: OpWrite) -> OpWrite {
+        match args.content_length() {
+            Some(size) => args.with_content_length(ciphertext_size(size, self.block_size as u64)),
+            None => args,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for EncryptionAccessor<A> {
+    type Inner = A;
+    type Reader = EncryptionReader<A::Reader>;
+    type BlockingReader = EncryptionReader<A::BlockingReader>;
+    type Writer = EncryptionWriter<A::Writer>;
+    type BlockingWriter = EncryptionWriter<A::BlockingWriter>;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    /// Remove capabilities that can't be supported on encrypted objects.
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.capability_mut();
+        cap.read_can_seek = false;
+        cap.read_with_range = false;
+        cap.copy_with_range = false;
+        cap.write_range = false;
+        cap.write_can_positional = false;
+        cap.write_can_resume = false;
+        cap.append = false;
+        cap.append_with_content_type = false;
+        cap.append_with_content_disposition = false;
+        cap.append_with_cache_control = false;
+        cap.presign_read = false;
+        cap.presign_write = false;
+        cap.select = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check_read_args(&args)?;
+
+        let (rp, mut r) = self.inner.read(path, args).await?;
+
+        let header = read_header(&mut r)
+            .await
+            .map_err(|err| err.with_context("path", path))?;
+
+        let dec = Decryptor::new(self.cipher.clone(), header)?;
+        let rp = dec
+            .build_rp(rp)
+            .map_err(|err| err.with_context("path", path))?;
+        Ok((rp, EncryptionReader::new(r, dec)))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check_read_args(&args)?;
+
+        let (rp, mut r) = self.inner.blocking_read(path, args)?;
+
+        let header = blocking_read_header(&mut r).map_err(|err| err.with_context("path", path))?;
+
+        let dec = Decryptor::new(self.cipher.clone(), header)?;
+        let rp = dec
+            .build_rp(rp)
+            .map_err(|err| err.with_context("path", path))?;
+        Ok((rp, EncryptionReader::new(r, dec)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write_args(&args)?;
+
+        let args = self.build_write_args(args);
+        let enc = Encryptor::new(self.cipher.clone(), self.block_size);
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, EncryptionWriter::new(w, enc)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write_args(&args)?;
+
+        let args = self.build_write_args(args);
+        let enc = Encryptor::new(self.cipher.clone(), self.block_size);
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, EncryptionWriter::new(w, enc)))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "append is not supported on encrypted objects",
+        )
+        .with_operation(Operation::Append)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        match args.operation() {
+            PresignOperation::Read(_) | PresignOperation::Write(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign read or write would bypass encryption",
+            )
+            .with_operation(Operation::Presign)
+            .with_context("service", self.inner.info().scheme())
+            .with_context("path", path)),
+            _ => self.inner.presign(path, args).await,
+        }
+    }
+
+    async fn select(&self, path: &str, _: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "select would be evaluated against encrypted content",
+        )
+        .with_operation(Operation::Select)
+        .with_context("service", self.inner.info().scheme())
+        .with_context("path", path))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.stat(path, args).await?;
+        let meta = rp.into_metadata();
+        if !meta.is_file() {
+            return Ok(RpStat::new(meta));
+        }
+
+        if !has_header(&meta) {
+            return Ok(RpStat::new(meta));
+        }
+
+        // We need the block size stored in header to calculate the plaintext size.
+        let (_, mut r) = self.inner.read(path, header_op_read()).await?;
+        let header = read_header(&mut r)
+            .await
+            .map_err(|err| err.with_context("path", path))?;
+
+        build_stat_metadata(meta, &header)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rp = self.inner.blocking_stat(path, args)?;
+        let meta = rp.into_metadata();
+        if !meta.is_file() {
+            return Ok(RpStat::new(meta));
+        }
+
+        if !has_header(&meta) {
+            return Ok(RpStat::new(meta));
+        }
+
+        // We need the block size stored in header to calculate the plaintext size.
+        let (_, mut r) = self.inner.blocking_read(path, header_op_read())?;
+        let header = blocking_read_header(&mut r).map_err(|err| err.with_context("path", path))?;
+
+        build_stat_metadata(meta, &header)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+async fn read_header<R: oio::Read>(r: &mut R) -> Result<[u8; HEADER_SIZE]> {
+    let mut header = [0; HEADER_SIZE];
+    let mut filled = 0;
+    while filled < HEADER_SIZE {
+        let n = r.read(&mut header[filled..]).await?;
+        if n == 0 {
+            return Err(truncated_error());
+        }
+        filled += n;
+    }
+    Ok(header)
+}
+
+fn blocking_read_header<R: oio::BlockingRead>(r: &mut R) -> Result<[u8; HEADER_SIZE]> {
+    let mut header = [0; HEADER_SIZE];
+    let mut filled = 0;
+    while filled < HEADER_SIZE {
+        let n = r.read(&mut header[filled..])?;
+        if n == 0 {
+            return Err(truncated_error());
+        }
+        filled += n;
+    }
+    Ok(header)
+}
+
+/// Calculate the ciphertext size of given plaintext size.
+///
+/// Empty content still takes one (empty) block so that truncation can be
+/// detected.
+fn ciphertext_size(plaintext_size: u64, block_size: u64) -> u64 {
+    let blocks = ((plaintext_size + block_size - 1) / block_size).max(1);
+    HEADER_SIZE as u64 + plaintext_size + blocks * TAG_SIZE as u64
+}
+
+/// Calculate the plaintext size of given ciphertext size.
+///
+/// Returns `None` if the ciphertext size is not valid.
+fn plaintext_size(ciphertext_size: u64, block_size: u64) -> Option<u64> {
+    let body = ciphertext_size.checked_sub(HEADER_SIZE as u64)?;
+    let frame = block_size + TAG_SIZE as u64;
+    let blocks = ((body + frame - 1) / frame).max(1);
+    body.checked_sub(blocks * TAG_SIZE as u64)
+}
+
+/// Objects shorter than the header can't be written by EncryptionLayer.
+fn has_header(meta: &Metadata) -> bool {
+    meta.content_length_raw()
+        .map_or(true, |size| size >= HEADER_SIZE as u64)
+}
+
+/// Only the header is needed by stat, don't fetch the whole object.
+fn header_op_read() -> OpRead {
+    OpRead::new().with_range(BytesRange::new(Some(0), Some(HEADER_SIZE as u64)))
+}
+
+/// Build the plaintext metadata of stat.
+///
+/// Objects not written by EncryptionLayer are returned as is, reading them
+/// will still fail.
+fn build_stat_metadata(meta: Metadata, header: &[u8; HEADER_SIZE]) -> Result<RpStat> {
+    if &header[..4] != MAGIC {
+        return Ok(RpStat::new(meta));
+    }
+
+    let block_size = parse_header(header)?.0;
+    Ok(RpStat::new(build_plaintext_metadata(meta, block_size)?))
+}
+
+fn build_plaintext_metadata(meta: Metadata, block_size: usize) -> Result<Metadata> {
+    let size = match meta.content_length_raw() {
+        Some(size) => size,
+        None => return Ok(meta),
+    };
+
+    let size = plaintext_size(size, block_size as u64).ok_or_else(truncated_error)?;
+    Ok(meta.with_content_length(size))
+}
+
+fn parse_header(header: &[u8; HEADER_SIZE]) -> Result<(usize, [u8; NONCE_PREFIX_SIZE])> {
+    if &header[..4] != MAGIC {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "object is not encrypted by EncryptionLayer",
+        ));
+    }
+
+    let block_size = u32::from_be_bytes(header[4..8].try_into().expect("must be 4 bytes"));
+    if block_size == 0 || block_size as usize > MAX_BLOCK_SIZE {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "encrypted object has invalid block size",
+        )
+        .with_context("block_size", block_size.to_string()));
+    }
+
+    let mut prefix = [0; NONCE_PREFIX_SIZE];
+    prefix.copy_from_slice(&header[8..]);
+
+    Ok((block_size as usize, prefix))
+}
+
+fn build_nonce(prefix: &[u8; NONCE_PREFIX_SIZE], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0; 12];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    // The highest bit of counter is used to mark the last block.
+    let counter = if last { counter | 1 << 31 } else { counter };
+    nonce[NONCE_PREFIX_SIZE..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+fn truncated_error() -> Error {
+    Error::new(ErrorKind::Unexpected, "encrypted object is truncated")
+}
+
+fn authentication_error() -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "failed to decrypt object, the key is wrong or data has been tampered",
+    )
+}
+
+fn too_many_blocks_error() -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "encrypted object has too many blocks",
+    )
+}
+
+/// Encryptor splits plaintext into blocks and seals them.
+struct Encryptor {
+    cipher: Aes256Gcm,
+    header: [u8; HEADER_SIZE],
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    block_size: usize,
+
+    counter: u32,
+    header_written: bool,
+    buf: BytesMut,
+}
+
+impl Encryptor {
+    fn new(cipher: Aes256Gcm, block_size: usize) -> Self {
+        let mut prefix = [0; NONCE_PREFIX_SIZE];
+        OsRng.fill_bytes(&mut prefix);
+
+        let mut header = [0; HEADER_SIZE];
+        header[..4].copy_from_slice(MAGIC);
+        header[4..8].copy_from_slice(&(block_size as u32).to_be_bytes());
+        header[8..].copy_from_slice(&prefix);
+
+        Self {
+            cipher,
+            header,
+            prefix,
+            block_size,
+
+            counter: 0,
+            header_written: false,
+            buf: BytesMut::new(),
+        }
+    }
+
+    fn seal(&mut self, block: &[u8], last: bool, output: &mut BytesMut) -> Result<()> {
+        if self.counter >= 1 << 31 {
+            return Err(too_many_blocks_error());
+        }
+
+        let nonce = build_nonce(&self.prefix, self.counter, last);
+        let ct = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: block,
+                    aad: &self.header,
+                },
+            )
+            .map_err(|_| Error::new(ErrorKind::Unexpected, "failed to encrypt block"))?;
+
+        output.put_slice(&ct);
+        self.counter += 1;
+        Ok(())
+    }
+
+    fn output(&mut self) -> BytesMut {
+        let mut output = BytesMut::new();
+        if !self.header_written {
+            output.put_slice(&self.header);
+            self.header_written = true;
+        }
+        output
+    }
+
+    /// Push plaintext into encryptor and returns the sealed blocks.
+    ///
+    /// We always keep at least one block in buffer so that the last block can
+    /// be marked while finishing.
+    fn push(&mut self, bs: Bytes) -> Result<Bytes> {
+        self.buf.extend_from_slice(&bs);
+
+        let mut output = self.output();
+        while self.buf.len() > self.block_size {
+            let block = self.buf.split_to(self.block_size);
+            self.seal(&block, false, &mut output)?;
+        }
+
+        Ok(output.freeze())
+    }
+
+    /// Seal the remaining plaintext as the last block.
+    fn finish(&mut self) -> Result<Bytes> {
+        let mut output = self.output();
+        let block = self.buf.split();
+        self.seal(&block, true, &mut output)?;
+
+        Ok(output.freeze())
+    }
+}
+
+/// Decryptor verifies and opens blocks from ciphertext.
+struct Decryptor {
+    cipher: Aes256Gcm,
+    header: [u8; HEADER_SIZE],
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    block_size: usize,
+
+    counter: u32,
+    finished: bool,
+    buf: BytesMut,
+}
+
+impl Decryptor {
+    fn new(cipher: Aes256Gcm, header: [u8; HEADER_SIZE]) -> Result<Self> {
+        let (block_size, prefix) = parse_header(&header)?;
+
+        Ok(Self {
+            cipher,
+            header,
+            prefix,
+            block_size,
+
+            counter: 0,
+            finished: false,
+            buf: BytesMut::new(),
+        })
+    }
+
+    fn frame_size(&self) -> usize {
+        self.block_size + TAG_SIZE
+    }
+
+    fn build_rp(&self, rp: RpRead) -> Result<RpRead> {
+        let meta = rp.into_metadata();
+        match meta.content_length_raw() {
+            Some(size) => {
+                // The header has been consumed already.
+                let size =
+                    plaintext_size(size, self.block_size as u64).ok_or_else(truncated_error)?;
+                Ok(RpRead::with_metadata(meta.with_content_length(size)))
+            }
+            None => Ok(RpRead::with_metadata(meta)),
+        }
+    }
+
+    fn open(&mut self, frame: &[u8], last: bool) -> Result<Bytes> {
+        if self.counter >= 1 << 31 {
+            return Err(too_many_blocks_error());
+        }
+
+        let nonce = build_nonce(&self.prefix, self.counter, last);
+        let pt = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: frame,
+                    aad: &self.header,
+                },
+            )
+            .map_err(|_| authentication_error())?;
+
+        self.counter += 1;
+        Ok(Bytes::from(pt))
+    }
+
+    /// Push ciphertext into decryptor and returns the verified plaintext.
+    fn push(&mut self, bs: &[u8]) -> Result<Bytes> {
+        if self.finished {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "encrypted object has unexpected trailing data",
+            ));
+        }
+
+        self.buf.extend_from_slice(bs);
+
+        let mut output = BytesMut::new();
+        while self.buf.len() > self.frame_size() {
+            let frame = self.buf.split_to(self.frame_size());
+            let pt = self.open(&frame, false)?;
+            output.put(pt);
+        }
+
+        Ok(output.freeze())
+    }
+
+    /// Open the remaining ciphertext as the last block.
+    fn finish(&mut self) -> Result<Bytes> {
+        if self.finished {
+            return Ok(Bytes::new());
+        }
+        if self.buf.len() < TAG_SIZE {
+            return Err(truncated_error());
+        }
+
+        let frame = self.buf.split();
+        let pt = self.open(&frame, true)?;
+        self.finished = true;
+        Ok(pt)
+    }
+}
+
+pub struct EncryptionReader<R> {
+    inner: R,
+    dec: Decryptor,
+
+    chunk: Vec<u8>,
+    plaintext: Bytes,
+}
+
+impl<R> EncryptionReader<R> {
+    fn new(inner: R, dec: Decryptor) -> Self {
+        let chunk = vec![0; dec.frame_size()];
+        Self {
+            inner,
+            dec,
+            chunk,
+            plaintext: Bytes::new(),
+        }
+    }
+
+    /// Consume read ciphertext and returns whether we reach the end.
+    fn consume(&mut self, n: usize) -> Result<bool> {
+        if n == 0 {
+            if self.dec.finished {
+                return Ok(true);
+            }
+            self.plaintext = self.dec.finish()?;
+        } else {
+            self.plaintext = self.dec.push(&self.chunk[..n])?;
+        }
+        Ok(false)
+    }
+
+    fn copy_plaintext(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.plaintext.len());
+        buf[..n].copy_from_slice(&self.plaintext[..n]);
+        self.plaintext.advance(n);
+        n
+    }
+}
+
+impl<R: oio::Read> oio::Read for EncryptionReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if !self.plaintext.is_empty() {
+                return Poll::Ready(Ok(self.copy_plaintext(buf)));
+            }
+
+            let n = ready!(self.inner.poll_read(cx, &mut self.chunk))?;
+            if self.consume(n)? {
+                return Poll::Ready(Ok(0));
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "encrypted reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            if !self.plaintext.is_empty() {
+                return Poll::Ready(Some(Ok(self.plaintext.split_off(0))));
+            }
+
+            let n = match ready!(self.inner.poll_read(cx, &mut self.chunk)) {
+                Ok(n) => n,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            };
+            match self.consume(n) {
+                Ok(true) => return Poll::Ready(None),
+                Ok(false) => continue,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for EncryptionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if !self.plaintext.is_empty() {
+                return Ok(self.copy_plaintext(buf));
+            }
+
+            let n = self.inner.read(&mut self.chunk)?;
+            if self.consume(n)? {
+                return Ok(0);
+            }
+        }
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let _ = pos;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "encrypted reader doesn't support seeking",
+        ))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            if !self.plaintext.is_empty() {
+                return Some(Ok(self.plaintext.split_off(0)));
+            }
+
+            let n = match self.inner.read(&mut self.chunk) {
+                Ok(n) => n,
+                Err(err) => return Some(Err(err)),
+            };
+            match self.consume(n) {
+                Ok(true) => return None,
+                Ok(false) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+pub struct EncryptionWriter<W> {
+    inner: W,
+    enc: Encryptor,
+}
+
+impl<W> EncryptionWriter<W> {
+    fn new(inner: W, enc: Encryptor) -> Self {
+        Self { inner, enc }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for EncryptionWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let bs = self.enc.push(bs)?;
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    /// The last block is always kept in memory until `close`, so only
+    /// sealed blocks are flushed into storage.
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let bs = self.enc.finish()?;
+        self.inner.write(bs).await?;
+        self.inner.close().await
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for EncryptionWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let bs = self.enc.push(bs)?;
+        if bs.is_empty() {
+            return Ok(());
+        }
+
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let bs = self.enc.finish()?;
+        self.inner.write(bs)?;
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::layers::tests::NativeRangedWriteLayer;
+    use crate::services::Memory;
+
+    fn new_operator(key: [u8; 32], block_size: usize) -> (Operator, Operator) {
+        let raw = Operator::new(Memory::default()).unwrap().finish();
+        let op = raw
+            .clone()
+            .layer(EncryptionLayer::new(key).with_block_size(block_size));
+        (raw, op)
+    }
+
+    #[test]
+    fn test_size_calculation() {
+        for block_size in [1, 7, 16, 1024] {
+            for size in 0..4096 {
+                let ct = ciphertext_size(size, block_size);
+                assert_eq!(plaintext_size(ct, block_size), Some(size));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> anyhow::Result<()> {
+        let (raw, op) = new_operator([1; 32], 16);
+
+        for size in [0, 1, 15, 16, 17, 32, 33, 1000] {
+            let content: Vec<u8> = (0..size).map(|v| v as u8).collect();
+            let path = format!("file-{size}");
+
+            op.write(&path, content.clone()).await?;
+
+            let ct = raw.read(&path).await?;
+            assert_eq!(ct.len() as u64, ciphertext_size(size as u64, 16));
+            assert_ne!(ct[HEADER_SIZE..], content[..]);
+
+            assert_eq!(op.read(&path).await?, content);
+            assert_eq!(op.stat(&path).await?.content_length(), size as u64);
+
+            let bs: Vec<Bytes> = op.reader(&path).await?.try_collect().await?;
+            assert_eq!(bs.concat(), content);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_range() -> anyhow::Result<()> {
+        let raw = Operator::new(Memory::default())
+            .unwrap()
+            .finish()
+            .layer(NativeRangedWriteLayer);
+        let op = raw.clone().layer(EncryptionLayer::new([6; 32]));
+        assert!(!op.info().capability().write_range);
+
+        let range = |offset| OpWriteRange::new(offset).with_part_size(4);
+        op.write_range_with("file", range(4), "efgh").await?;
+        op.write_range_with("file", range(0), "abcd").await?;
+        op.finalize_ranged_write("file").await?;
+
+        assert_eq!(op.read("file").await?, b"abcdefgh");
+        let ct = raw.read("file").await?;
+        assert_eq!(
+            ct.len() as u64,
+            ciphertext_size(8, DEFAULT_BLOCK_SIZE as u64)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writer_without_content_length() -> anyhow::Result<()> {
+        let (_, op) = new_operator([2; 32], 16);
+
+        let mut w = op.writer("file").await?;
+        w.write(vec![1; 10]).await?;
+        w.write(vec![2; 30]).await?;
+        w.close().await?;
+
+        let mut expected = vec![1; 10];
+        expected.extend_from_slice(&[2; 30]);
+        assert_eq!(op.read("file").await?, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wrong_key() -> anyhow::Result<()> {
+        let (raw, op) = new_operator([3; 32], 16);
+        op.write("file", vec![1; 100]).await?;
+
+        let other = raw.layer(EncryptionLayer::new([4; 32]));
+        let err = other.read("file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tampered_and_truncated() -> anyhow::Result<()> {
+        let (raw, op) = new_operator([5; 32], 16);
+        op.write("file", vec![1; 100]).await?;
+        let ct = raw.read("file").await?;
+
+        let mut tampered = ct.clone();
+        tampered[HEADER_SIZE + 3] ^= 1;
+        raw.write("tampered", tampered).await?;
+        let err = op.read("tampered").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Drop the last block entirely.
+        raw.write("truncated", ct[..ct.len() - 100 % 16 - TAG_SIZE].to_vec())
+            .await?;
+        let err = op.read("truncated").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Forge a huge block size, it must be rejected before allocating.
+        let mut forged = ct.to_vec();
+        forged[4..8].copy_from_slice(&u32::MAX.to_be_bytes());
+        raw.write("forged", forged).await?;
+        let err = op.read("forged").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = op.stat("forged").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_plain_object() -> anyhow::Result<()> {
+        let (raw, op) = new_operator([7; 32], 16);
+        raw.write("short", "abc").await?;
+        raw.write("plain", vec![1; 100]).await?;
+
+        assert_eq!(op.stat("short").await?.content_length(), 3);
+        assert_eq!(op.stat("plain").await?.content_length(), 100);
+        assert_eq!(op.blocking().stat("plain")?.content_length(), 100);
+        assert_eq!(
+            op.read("plain").await.unwrap_err().kind(),
+            ErrorKind::Unexpected
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ranged_read_rejected() -> anyhow::Result<()> {
+        let (_, op) = new_operator([6; 32], 16);
+        op.write("file", vec![1; 100]).await?;
+
+        let err = op.range_read("file", 1..10).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+}
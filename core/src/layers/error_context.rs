@@ -17,6 +17,7 @@
 
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::io::IoSliceMut;
 use std::io::SeekFrom;
 use std::task::Context;
 use std::task::Poll;
@@ -236,6 +237,86 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.inner.select(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Select)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        self.inner.retention(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Retention)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        self.inner.legal_hold(path, args).await.map_err(|err| {
+            err.with_operation(Operation::LegalHold)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        self.inner.acl(path, args).await.map_err(|err| {
+            err.with_operation(Operation::Acl)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        let offset = args.offset();
+        self.inner.write_range(path, args, bs).await.map_err(|err| {
+            err.with_operation(Operation::WriteRange)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("offset", offset.to_string())
+        })
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        self.inner
+            .finalize_ranged_write(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::FinalizeRangedWrite)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        self.inner
+            .delete_all_versions(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::DeleteAllVersions)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.inner.list_versions(path, args).await.map_err(|err| {
+            err.with_operation(Operation::ListVersions)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         self.inner
             .batch(args)
@@ -380,6 +461,18 @@ impl<T: oio::Read> oio::Read for ErrorContextWrapper<T> {
         })
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        self.inner.poll_read_vectored(cx, bufs).map_err(|err| {
+            err.with_operation(ReadOperation::Read)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
+
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
         self.inner.poll_seek(cx, pos).map_err(|err| {
             err.with_operation(ReadOperation::Seek)
@@ -443,6 +536,14 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
         })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await.map_err(|err| {
+            err.with_operation(WriteOperation::Flush)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await.map_err(|err| {
             err.with_operation(WriteOperation::Close)
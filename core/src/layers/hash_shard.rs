@@ -0,0 +1,570 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use md5::Digest;
+use md5::Md5;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Add hash based sharding for paths to avoid hot partitions.
+///
+/// # Notes
+///
+/// Services like s3 partition keys by prefix, writing lots of files under
+/// the same prefix could make that partition hot. `HashShardLayer` will
+/// store every path under a short prefix derived from the hash of the
+/// path itself:
+///
+/// ```text
+/// dir/file => 0a/dir/file
+/// ```
+///
+/// - `read`, `stat`, `delete` and so on will resolve the same shard
+///   transparently.
+/// - `list` will list all shards and return entries in logical paths.
+///   Entries from different shards are returned shard by shard, so they
+///   are not sorted as a whole. Shards are listed one after another, the
+///   next shard will only be listed after the previous one is exhausted.
+/// - Dirs created by `create_dir` are sharded by their own paths like
+///   files, so they are likely to be stored in a different shard from
+///   their children. A dir that exists in many shards is listed only once.
+/// - Changing the number of shards will make existing files unreachable.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::HashShardLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(HashShardLayer::new(16))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashShardLayer {
+    shards: usize,
+}
+
+impl HashShardLayer {
+    /// Create a new HashShardLayer which spreads paths into `shards` shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn new(shards: usize) -> Self {
+        assert!(shards > 0, "shards of HashShardLayer must be larger than 0");
+
+        Self { shards }
+    }
+}
+
+impl<A: Accessor> Layer<A> for HashShardLayer {
+    type LayeredAccessor = HashShardAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let mut width = 1;
+        while 16usize.saturating_pow(width as u32) < self.shards {
+            width += 1;
+        }
+
+        HashShardAccessor {
+            inner: Arc::new(inner),
+            shards: self.shards,
+            width,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HashShardAccessor<A: Accessor> {
+    inner: Arc<A>,
+    shards: usize,
+    /// The width of shard prefix in hex.
+    width: usize,
+}
+
+impl<A: Accessor> HashShardAccessor<A> {
+    fn shard_prefix(&self, idx: usize) -> String {
+        format!("{:0width$x}/", idx, width = self.width)
+    }
+
+    /// Build the path in storage for given logical path.
+    fn shard_path(&self, path: &str) -> String {
+        if path.is_empty() || path == "/" {
+            return path.to_string();
+        }
+
+        let hash = Md5::digest(path.as_bytes());
+        let mut buf = [0; 8];
+        buf.copy_from_slice(&hash[..8]);
+        let idx = (u64::from_be_bytes(buf) % self.shards as u64) as usize;
+
+        format!("{}{}", self.shard_prefix(idx), path)
+    }
+
+    /// Build the list path and args of every shard for given logical path.
+    fn shard_lists(&self, path: &str, args: &OpList) -> VecDeque<(String, OpList)> {
+        let path = if path == "/" { "" } else { path };
+
+        (0..self.shards)
+            .map(|idx| {
+                let prefix = self.shard_prefix(idx);
+                let args = match args.start_after() {
+                    Some(v) => args.clone().with_start_after(&format!("{prefix}{v}")),
+                    None => args.clone(),
+                };
+                (format!("{prefix}{path}"), args)
+            })
+            .collect()
+    }
+
+    fn shard_batch(&self, args: OpBatch) -> OpBatch {
+        OpBatch::new(
+            args.into_operation()
+                .into_iter()
+                .map(|(path, op)| (self.shard_path(&path), op))
+                .collect(),
+        )
+    }
+
+    fn unshard_batch(&self, rp: RpBatch) -> RpBatch {
+        RpBatch::new(
+            rp.into_results()
+                .into_iter()
+                .map(|(path, res)| (unshard_path(&path).to_string(), res))
+                .collect(),
+        )
+    }
+}
+
+/// Remove the shard prefix from the path in storage.
+fn unshard_path(path: &str) -> &str {
+    match path.split_once('/') {
+        Some((_, p)) => p,
+        None => path,
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for HashShardAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = HashShardPager<A, A::Pager>;
+    type BlockingPager = HashShardPager<A, A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn validate_key(&self, path: &str) -> Result<()> {
+        // Shard prefix counts into the key in storage too.
+        self.inner.validate_key(&self.shard_path(path))
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+        // Paths in another bucket can't be sharded correctly.
+        meta.capability_mut().copy_cross_bucket = false;
+        meta
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.create_dir(&self.shard_path(path), args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.shard_path(path), args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.shard_path(path), args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner.append(&self.shard_path(path), args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&self.shard_path(from), &self.shard_path(to), args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&self.shard_path(from), &self.shard_path(to), args)
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.shard_path(path), args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.delete(&self.shard_path(path), args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let mut lists = self.shard_lists(path, &args);
+        // List the first shard eagerly to return errors like `NotADirectory` early.
+        let (path, args) = lists.pop_front().expect("shards must be larger than 0");
+        let (_, pager) = self.inner.list(&path, args).await?;
+
+        Ok((
+            RpList::default(),
+            HashShardPager::new(self.inner.clone(), pager, lists),
+        ))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let rp = self.inner.batch(self.shard_batch(args)).await?;
+        Ok(self.unshard_batch(rp))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.shard_path(path), args).await
+    }
+
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.inner.select(&self.shard_path(path), args).await
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        self.inner.retention(&self.shard_path(path), args).await
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        self.inner.legal_hold(&self.shard_path(path), args).await
+    }
+
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        self.inner.acl(&self.shard_path(path), args).await
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        self.inner
+            .write_range(&self.shard_path(path), args, bs)
+            .await
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        self.inner
+            .finalize_ranged_write(&self.shard_path(path), args)
+            .await
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        self.inner
+            .delete_all_versions(&self.shard_path(path), args)
+            .await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let paths = if args.exact() {
+            vec![self.shard_path(path)]
+        } else {
+            self.shard_lists(path, &OpList::new())
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect()
+        };
+
+        let mut versions = Vec::new();
+        for path in paths {
+            let rp = self.inner.list_versions(&path, args.clone()).await?;
+            versions.extend(rp.into_versions().into_iter().map(|mut v| {
+                let path = unshard_path(v.path()).to_string();
+                v.set_path(&path);
+                v
+            }));
+        }
+        Ok(RpListVersions::new(versions))
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.blocking_create_dir(&self.shard_path(path), args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(&self.shard_path(path), args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(&self.shard_path(path), args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(&self.shard_path(from), &self.shard_path(to), args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .blocking_rename(&self.shard_path(from), &self.shard_path(to), args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.blocking_stat(&self.shard_path(path), args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(&self.shard_path(path), args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let mut lists = self.shard_lists(path, &args);
+        // List the first shard eagerly to return errors like `NotADirectory` early.
+        let (path, args) = lists.pop_front().expect("shards must be larger than 0");
+        let (_, pager) = self.inner.blocking_list(&path, args)?;
+
+        Ok((
+            RpList::default(),
+            HashShardPager::new(self.inner.clone(), pager, lists),
+        ))
+    }
+}
+
+/// HashShardPager will list all shards one by one and convert entries
+/// back into logical paths.
+///
+/// The pager of next shard is only created after the current one is
+/// exhausted.
+pub struct HashShardPager<A: Accessor, P> {
+    inner: Arc<A>,
+    pager: Option<P>,
+    /// The list path and args of shards that haven't been listed.
+    lists: VecDeque<(String, OpList)>,
+    /// The same dir could exist in every shard, we only return it once.
+    dirs: HashSet<String>,
+}
+
+impl<A: Accessor, P> HashShardPager<A, P> {
+    fn new(inner: Arc<A>, pager: P, lists: VecDeque<(String, OpList)>) -> Self {
+        Self {
+            inner,
+            pager: Some(pager),
+            lists,
+            dirs: HashSet::new(),
+        }
+    }
+
+    fn unshard_entries(&mut self, entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        entries
+            .into_iter()
+            .filter_map(|mut entry| {
+                let path = unshard_path(entry.path()).to_string();
+                if entry.mode().is_dir() && !self.dirs.insert(path.clone()) {
+                    return None;
+                }
+
+                entry.set_path(&path);
+                Some(entry)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> oio::Page for HashShardPager<A, A::Pager> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        loop {
+            if let Some(pager) = self.pager.as_mut() {
+                if let Some(entries) = pager.next().await? {
+                    return Ok(Some(self.unshard_entries(entries)));
+                }
+                self.pager = None;
+            }
+
+            let (path, args) = match self.lists.pop_front() {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+            let (_, pager) = self.inner.list(&path, args).await?;
+            self.pager = Some(pager);
+        }
+    }
+}
+
+impl<A: Accessor> oio::BlockingPage for HashShardPager<A, A::BlockingPager> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        loop {
+            if let Some(pager) = self.pager.as_mut() {
+                if let Some(entries) = pager.next()? {
+                    return Ok(Some(self.unshard_entries(entries)));
+                }
+                self.pager = None;
+            }
+
+            let (path, args) = match self.lists.pop_front() {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+            let (_, pager) = self.inner.blocking_list(&path, args)?;
+            self.pager = Some(pager);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::layers::CompleteLayer;
+    use crate::services::Memory;
+    use crate::services::S3;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_hash_shard() -> Result<()> {
+        let raw = Operator::new(Memory::default())?.finish();
+        let op = raw.clone().layer(HashShardLayer::new(4));
+
+        let paths: Vec<String> = (0..16).map(|i| format!("dir/file-{i}")).collect();
+        for path in &paths {
+            op.write(path, path.clone()).await?;
+        }
+
+        // Files are stored under shard prefixes in underlying storage.
+        let mut shards = HashSet::new();
+        let mut lister = raw.scan("/").await?;
+        while let Some(entry) = lister.try_next().await? {
+            if !entry.path().ends_with('/') {
+                let (shard, path) = entry.path().split_once('/').unwrap();
+                assert!(paths.contains(&path.to_string()), "{}", entry.path());
+                shards.insert(shard.to_string());
+            }
+        }
+        assert!(shards.len() > 1, "paths must be spread across shards");
+
+        // Read and stat resolve shard transparently.
+        for path in &paths {
+            assert_eq!(op.read(path).await?, path.as_bytes());
+            assert_eq!(op.stat(path).await?.content_length(), path.len() as u64);
+        }
+
+        // List presents the logical view.
+        let mut listed: Vec<String> = op
+            .list("dir/")
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        listed.sort();
+        let mut expected = paths.clone();
+        expected.sort();
+        assert_eq!(listed, expected);
+
+        // Dirs existing in every shard are listed only once.
+        let listed: Vec<String> = op
+            .list("/")
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        assert_eq!(listed, vec!["dir/".to_string()]);
+
+        op.delete("dir/file-0").await?;
+        assert!(!op.is_exist("dir/file-0").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_shards_lazily() -> Result<()> {
+        let acc = HashShardLayer::new(4).layer(CompleteLayer.layer(Memory::default().build()?));
+
+        let (_, pager) = LayeredAccessor::list(&acc, "dir/", OpList::new()).await?;
+        assert!(pager.pager.is_some());
+        assert_eq!(pager.lists.len(), 3, "only the first shard is listed");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dir_marker() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .layer(HashShardLayer::new(16));
+
+        // Dir marker and its children are sharded separately.
+        op.create_dir("dir/").await?;
+        for i in 0..8 {
+            op.write(&format!("dir/file-{i}"), "hello").await?;
+        }
+        assert!(op.stat("dir/").await?.mode().is_dir());
+
+        let listed: Vec<String> = op
+            .list("/")
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        assert_eq!(listed, vec!["dir/".to_string()]);
+
+        let listed: Vec<_> = op.list("dir/").await?.try_collect().await?;
+        assert_eq!(
+            listed.iter().filter(|e| !e.path().ends_with('/')).count(),
+            8
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_key() -> Result<()> {
+        let mut builder = S3::default();
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.disable_config_load();
+        let acc = HashShardLayer::new(16).layer(builder.build()?);
+
+        // Key is valid by itself, but too long with the shard prefix.
+        let path = "a".repeat(1023);
+        assert!(acc.inner.validate_key(&path).is_ok());
+        let err = LayeredAccessor::validate_key(&acc, &path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        Ok(())
+    }
+}
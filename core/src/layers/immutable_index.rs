@@ -157,6 +157,7 @@ impl<A: Accessor> LayeredAccessor for ImmutableIndexAccessor<A> {
         cap.list = true;
         cap.list_with_delimiter_slash = true;
         cap.list_without_delimiter = true;
+        cap.list_with_prefix = true;
 
         meta
     }
@@ -170,6 +171,7 @@ impl<A: Accessor> LayeredAccessor for ImmutableIndexAccessor<A> {
         if path == "/" {
             path = ""
         }
+        let path = &format!("{path}{}", args.prefix().unwrap_or_default());
 
         let idx = if args.delimiter() == "/" {
             self.children_hierarchy(path)
@@ -206,6 +208,7 @@ impl<A: Accessor> LayeredAccessor for ImmutableIndexAccessor<A> {
         if path == "/" {
             path = ""
         }
+        let path = &format!("{path}{}", args.prefix().unwrap_or_default());
 
         let idx = if args.delimiter() == "/" {
             self.children_hierarchy(path)
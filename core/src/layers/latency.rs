@@ -0,0 +1,478 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::io;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::FutureExt;
+use rand::prelude::*;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Inject artificial latency into underlying services for load test.
+///
+/// # Latency
+///
+/// LatencyLayer will sleep a configured duration before every call to the
+/// underlying service, which makes a fast local service (like `memory` or
+/// `fs`) behave like a slow or distant one. It's useful to validate timeouts
+/// and backpressure under realistic conditions.
+///
+/// Unlike `ChaosLayer`, this layer only adds delay and
+/// never returns errors.
+///
+/// # Notes
+///
+/// - The latency is `latency + rand(0..=jitter)` for every call.
+/// - Readers and writers are not throttled by default, use
+///   [`LatencyLayer::with_io_latency`] to add latency to every read/write
+///   call on them too.
+/// - This layer is a no-op while both latency and jitter are zero.
+/// - Blocking operations will block current thread while sleeping.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::LatencyLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         LatencyLayer::new(Duration::from_millis(50))
+///             .with_jitter(Duration::from_millis(20)),
+///     )
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyLayer {
+    latency: Latency,
+    io_latency: bool,
+}
+
+impl LatencyLayer {
+    /// Create a new latency layer which adds `latency` before every call.
+    pub fn new(latency: Duration) -> Self {
+        Self {
+            latency: Latency {
+                latency,
+                jitter: Duration::ZERO,
+            },
+            io_latency: false,
+        }
+    }
+
+    /// Set the jitter of latency.
+    ///
+    /// Every call will sleep `latency` plus a uniformly distributed random
+    /// duration between `0` and `jitter`.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.latency.jitter = jitter;
+        self
+    }
+
+    /// Set whether to add latency to every read/write call on readers,
+    /// writers and appenders too.
+    ///
+    /// Default to `false`.
+    pub fn with_io_latency(mut self, enabled: bool) -> Self {
+        self.io_latency = enabled;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for LatencyLayer {
+    type LayeredAccessor = LatencyAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        LatencyAccessor {
+            inner,
+            latency: self.latency,
+            io_latency: self.io_latency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Latency {
+    latency: Duration,
+    jitter: Duration,
+}
+
+impl Latency {
+    fn is_zero(&self) -> bool {
+        self.latency.is_zero() && self.jitter.is_zero()
+    }
+
+    fn next(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return self.latency;
+        }
+        self.latency + self.jitter.mul_f64(thread_rng().gen_range(0.0..=1.0))
+    }
+
+    async fn sleep(&self) {
+        if !self.is_zero() {
+            tokio::time::sleep(self.next()).await
+        }
+    }
+
+    fn blocking_sleep(&self) {
+        if !self.is_zero() {
+            std::thread::sleep(self.next())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LatencyAccessor<A> {
+    inner: A,
+    latency: Latency,
+    io_latency: bool,
+}
+
+impl<A> LatencyAccessor<A> {
+    /// Get the latency for readers and writers, zero if io latency is
+    /// not enabled.
+    fn io_latency(&self) -> Latency {
+        if self.io_latency {
+            self.latency
+        } else {
+            Latency {
+                latency: Duration::ZERO,
+                jitter: Duration::ZERO,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for LatencyAccessor<A> {
+    type Inner = A;
+    type Reader = LatencyWrapper<A::Reader>;
+    type BlockingReader = LatencyWrapper<A::BlockingReader>;
+    type Writer = LatencyWrapper<A::Writer>;
+    type BlockingWriter = LatencyWrapper<A::BlockingWriter>;
+    type Appender = LatencyWrapper<A::Appender>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.latency.sleep().await;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.latency.sleep().await;
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, LatencyWrapper::new(r, self.io_latency())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.latency.sleep().await;
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, LatencyWrapper::new(w, self.io_latency())))
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.latency.sleep().await;
+        self.inner
+            .append(path, args)
+            .await
+            .map(|(rp, a)| (rp, LatencyWrapper::new(a, self.io_latency())))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.latency.sleep().await;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.latency.sleep().await;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.latency.sleep().await;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.latency.sleep().await;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.latency.sleep().await;
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.latency.sleep().await;
+        self.inner.batch(args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_create_dir(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.latency.blocking_sleep();
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, LatencyWrapper::new(r, self.io_latency())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.latency.blocking_sleep();
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, LatencyWrapper::new(w, self.io_latency())))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.latency.blocking_sleep();
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// LatencyWrapper will add latency to every read/write call.
+pub struct LatencyWrapper<R> {
+    inner: R,
+    latency: Latency,
+
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// Whether the sleep of current call has finished, which makes sure
+    /// we only sleep once for a call even if inner returns pending.
+    slept: bool,
+}
+
+impl<R> LatencyWrapper<R> {
+    fn new(inner: R, latency: Latency) -> Self {
+        Self {
+            inner,
+            latency,
+            sleep: None,
+            slept: false,
+        }
+    }
+
+    fn poll_sleep(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.slept || self.latency.is_zero() {
+            return Poll::Ready(());
+        }
+
+        let latency = self.latency;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(latency.next())));
+        ready!(sleep.poll_unpin(cx));
+        self.sleep = None;
+        self.slept = true;
+        Poll::Ready(())
+    }
+}
+
+impl<R: oio::Read> oio::Read for LatencyWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        ready!(self.poll_sleep(cx));
+        let res = ready!(self.inner.poll_read(cx, buf));
+        self.slept = false;
+        Poll::Ready(res)
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        ready!(self.poll_sleep(cx));
+        let res = ready!(self.inner.poll_seek(cx, pos));
+        self.slept = false;
+        Poll::Ready(res)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_sleep(cx));
+        let res = ready!(self.inner.poll_next(cx));
+        self.slept = false;
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for LatencyWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.latency.blocking_sleep();
+        self.inner.read(buf)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        self.latency.blocking_sleep();
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        self.latency.blocking_sleep();
+        self.inner.next()
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for LatencyWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.abort().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.close().await
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for LatencyWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.latency.blocking_sleep();
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.latency.blocking_sleep();
+        self.inner.close()
+    }
+}
+
+#[async_trait]
+impl<R: oio::Append> oio::Append for LatencyWrapper<R> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.append(bs).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.latency.sleep().await;
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_latency() -> Result<()> {
+        let latency = Duration::from_millis(50);
+        let op = Operator::new(Memory::default())?
+            .layer(LatencyLayer::new(latency).with_jitter(Duration::from_millis(10)))
+            .finish();
+
+        let now = Instant::now();
+        op.write("file", "Hello, World!").await?;
+        assert!(now.elapsed() >= latency, "write must be delayed");
+
+        let now = Instant::now();
+        op.stat("file").await?;
+        assert!(now.elapsed() >= latency, "stat must be delayed");
+
+        let now = Instant::now();
+        op.blocking().read("file")?;
+        assert!(now.elapsed() >= latency, "blocking read must be delayed");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_io_latency() -> Result<()> {
+        let latency = Duration::from_millis(20);
+        let op = Operator::new(Memory::default())?
+            .layer(LatencyLayer::new(latency).with_io_latency(true))
+            .finish();
+        op.write("file", "Hello, World!").await?;
+
+        let mut r = op.reader("file").await?;
+        let now = Instant::now();
+        let mut buf = Vec::new();
+        futures::AsyncReadExt::read_to_end(&mut r, &mut buf)
+            .await
+            .unwrap();
+        assert!(now.elapsed() >= latency, "read must be delayed");
+        assert_eq!(buf, b"Hello, World!");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_zero_latency() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(LatencyLayer::new(Duration::ZERO).with_io_latency(true))
+            .finish();
+
+        op.write("file", "Hello, World!").await?;
+        assert_eq!(op.read("file").await?, b"Hello, World!");
+
+        Ok(())
+    }
+}
@@ -1374,6 +1374,36 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        match self.inner.flush().await {
+            Ok(_) => {
+                trace!(
+                    target: LOGGING_TARGET,
+                    "service={} operation={} path={} written={} -> flush writer",
+                    self.scheme,
+                    WriteOperation::Flush,
+                    self.path,
+                    self.written,
+                );
+                Ok(())
+            }
+            Err(err) => {
+                if let Some(lvl) = self.failure_level {
+                    log!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        "service={} operation={} path={} written={} -> flush writer failed: {err:?}",
+                        self.scheme,
+                        WriteOperation::Flush,
+                        self.path,
+                        self.written,
+                    )
+                }
+                Err(err)
+            }
+        }
+    }
+
     async fn close(&mut self) -> Result<()> {
         match self.inner.close().await {
             Ok(_) => {
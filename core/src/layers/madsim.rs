@@ -338,6 +338,11 @@ impl oio::Write for MadsimWriter {
         ))
     }
 
+    /// Every write is sent to the sim server directly, nothing is buffered.
+    async fn flush(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+
     async fn close(&mut self) -> crate::Result<()> {
         Ok(())
     }
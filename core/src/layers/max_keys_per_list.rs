@@ -0,0 +1,223 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Add a hard cap on the total entries a single list could return.
+///
+/// # Notes
+///
+/// Once a `list` or `scan` has returned more than `max_keys` entries in
+/// total, the next page will fail with [`ErrorKind::RateLimited`] and no
+/// more entries will be returned by it.
+///
+/// Unlike [`OpList::with_limit`] which is a hint for page size, this limit
+/// is enforced by the layer and can't be overridden by callers. It's useful
+/// to protect the host from running out of memory while passing operators
+/// to untrusted code that may collect entire listings.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::MaxKeysPerListLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(MaxKeysPerListLayer::new(100_000))
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxKeysPerListLayer {
+    max_keys: usize,
+}
+
+impl MaxKeysPerListLayer {
+    /// Create a new MaxKeysPerListLayer with the max entries a list could return.
+    pub fn new(max_keys: usize) -> Self {
+        Self { max_keys }
+    }
+}
+
+impl<A: Accessor> Layer<A> for MaxKeysPerListLayer {
+    type LayeredAccessor = MaxKeysPerListAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MaxKeysPerListAccessor {
+            inner,
+            max_keys: self.max_keys,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MaxKeysPerListAccessor<A: Accessor> {
+    inner: A,
+    max_keys: usize,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for MaxKeysPerListAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = MaxKeysPager<A::Pager>;
+    type BlockingPager = MaxKeysPager<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner.append(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner
+            .list(path, args)
+            .await
+            .map(|(rp, p)| (rp, MaxKeysPager::new(p, path, self.max_keys)))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner
+            .blocking_list(path, args)
+            .map(|(rp, p)| (rp, MaxKeysPager::new(p, path, self.max_keys)))
+    }
+}
+
+pub struct MaxKeysPager<P> {
+    inner: P,
+    path: String,
+    max_keys: usize,
+    returned: usize,
+}
+
+impl<P> MaxKeysPager<P> {
+    fn new(inner: P, path: &str, max_keys: usize) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            max_keys,
+            returned: 0,
+        }
+    }
+
+    fn check(&mut self, entries: Option<Vec<oio::Entry>>) -> Result<Option<Vec<oio::Entry>>> {
+        if let Some(entries) = &entries {
+            self.returned += entries.len();
+        }
+
+        if self.returned > self.max_keys {
+            return Err(Error::new(ErrorKind::RateLimited, "listing too large")
+                .with_operation(Operation::List)
+                .with_context("path", &self.path)
+                .with_context("max_keys", self.max_keys.to_string()));
+        }
+
+        Ok(entries)
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for MaxKeysPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next().await?;
+        self.check(entries)
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for MaxKeysPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next()?;
+        self.check(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_max_keys_per_list() -> anyhow::Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(MaxKeysPerListLayer::new(10))
+            .finish();
+
+        for i in 0..10 {
+            op.write(&format!("small/{i}"), "content").await?;
+        }
+        let entries: Vec<_> = op.list("small/").await?.try_collect().await?;
+        assert_eq!(entries.len(), 10);
+
+        for i in 0..11 {
+            op.write(&format!("large/{i}"), "content").await?;
+        }
+        let err = op
+            .list("large/")
+            .await?
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        let err = op
+            .scan("/")
+            .await?
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        let err = op
+            .blocking()
+            .list("large/")?
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,530 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// The max entries of negative cache, expired entries will be purged
+/// while reaching this limit.
+const MAX_NEGATIVE_ENTRIES: usize = 10_000;
+
+/// Add metadata cache for underlying storage services.
+///
+/// # Notes
+///
+/// Only `NotFound` results of `stat` will be cached for now, which can be
+/// enabled by [`MetadataCacheLayer::with_cache_negative_ttl`]. Checking the
+/// existence of a missing path repeatedly will only send one request to
+/// storage within the ttl.
+///
+/// Only `stat` with default [`OpStat`] will be served from and fill the
+/// cache, `stat` with conditions, metakey or version always reaches the
+/// storage.
+///
+/// Cached results will be invalidated while writing to the same path
+/// (or its children) through this operator. Changes made by others are
+/// only visible after the ttl.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::MetadataCacheLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(MetadataCacheLayer::new().with_cache_negative_ttl(Duration::from_secs(1)))
+///     .finish();
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct MetadataCacheLayer {
+    negative_ttl: Option<Duration>,
+}
+
+impl MetadataCacheLayer {
+    /// Create a new MetadataCacheLayer which caches nothing by default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache `NotFound` results of `stat` for given ttl.
+    pub fn with_cache_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = Some(ttl);
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for MetadataCacheLayer {
+    type LayeredAccessor = MetadataCacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MetadataCacheAccessor {
+            inner,
+            negative: Arc::new(NegativeCache {
+                ttl: self.negative_ttl,
+                entries: Mutex::default(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NegativeCache {
+    ttl: Option<Duration>,
+    entries: Mutex<NegativeEntries>,
+}
+
+#[derive(Debug, Default)]
+struct NegativeEntries {
+    /// Paths that are not found and the time they are cached.
+    paths: HashMap<String, Instant>,
+    /// Bumped by every invalidation, so that `stat` started before the
+    /// invalidation will not cache its stale `NotFound`.
+    generation: u64,
+}
+
+impl NegativeCache {
+    fn contains(&self, path: &str) -> bool {
+        let ttl = match self.ttl {
+            Some(ttl) => ttl,
+            None => return false,
+        };
+
+        let mut entries = self.entries.lock();
+        match entries.paths.get(path) {
+            Some(at) if at.elapsed() < ttl => true,
+            Some(_) => {
+                entries.paths.remove(path);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Get the generation before sending `stat`, which should be passed to
+    /// `insert` after `stat` returned `NotFound`.
+    fn generation(&self) -> u64 {
+        self.entries.lock().generation
+    }
+
+    fn insert(&self, path: &str, generation: u64) {
+        let ttl = match self.ttl {
+            Some(ttl) => ttl,
+            None => return,
+        };
+
+        let mut entries = self.entries.lock();
+        // Path could have been written since stat started.
+        if entries.generation != generation {
+            return;
+        }
+        if entries.paths.len() >= MAX_NEGATIVE_ENTRIES {
+            entries.paths.retain(|_, at| at.elapsed() < ttl);
+        }
+        if entries.paths.len() < MAX_NEGATIVE_ENTRIES {
+            entries.paths.insert(path.to_string(), Instant::now());
+        }
+    }
+
+    /// Remove cached results of given path and all its parents.
+    fn invalidate(&self, path: &str) {
+        if self.ttl.is_none() {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        entries.generation += 1;
+        entries.paths.remove(path);
+        let mut path = path;
+        while path != "/" {
+            path = get_parent(path);
+            entries.paths.remove(path);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetadataCacheAccessor<A: Accessor> {
+    inner: A,
+    negative: Arc<NegativeCache>,
+}
+
+/// Only `NotFound` of the current object with complete metadata could be
+/// shared between `stat` calls, other args must skip the cache.
+fn is_cacheable(args: &OpStat) -> bool {
+    args.if_match().is_none()
+        && args.if_none_match().is_none()
+        && args.version().is_none()
+        && args.metakey().contains(Metakey::Complete)
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for MetadataCacheAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = MetadataCacheWrapper<A::Writer>;
+    type BlockingWriter = MetadataCacheWrapper<A::BlockingWriter>;
+    type Appender = MetadataCacheWrapper<A::Appender>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let rp = self.inner.create_dir(path, args).await;
+        self.negative.invalidate(path);
+        rp
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.negative.invalidate(path);
+        self.inner.write(path, args).await.map(|(rp, w)| {
+            (
+                rp,
+                MetadataCacheWrapper::new(w, path, self.negative.clone()),
+            )
+        })
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.negative.invalidate(path);
+        self.inner.append(path, args).await.map(|(rp, a)| {
+            (
+                rp,
+                MetadataCacheWrapper::new(a, path, self.negative.clone()),
+            )
+        })
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.copy(from, to, args).await;
+        self.negative.invalidate(to);
+        rp
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.rename(from, to, args).await;
+        self.negative.invalidate(to);
+        rp
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_cacheable(&args) {
+            return self.inner.stat(path, args).await;
+        }
+        if self.negative.contains(path) {
+            return Err(Error::new(ErrorKind::NotFound, "path not found (cached)")
+                .with_context("path", path));
+        }
+
+        let generation = self.negative.generation();
+        let rp = self.inner.stat(path, args).await;
+        if matches!(&rp, Err(err) if err.kind() == ErrorKind::NotFound) {
+            self.negative.insert(path, generation);
+        }
+        rp
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        let rp = self.inner.write_range(path, args, bs).await;
+        self.negative.invalidate(path);
+        rp
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        let rp = self.inner.finalize_ranged_write(path, args).await;
+        self.negative.invalidate(path);
+        rp
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let rp = self.inner.blocking_create_dir(path, args);
+        self.negative.invalidate(path);
+        rp
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.negative.invalidate(path);
+        self.inner.blocking_write(path, args).map(|(rp, w)| {
+            (
+                rp,
+                MetadataCacheWrapper::new(w, path, self.negative.clone()),
+            )
+        })
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.blocking_copy(from, to, args);
+        self.negative.invalidate(to);
+        rp
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.blocking_rename(from, to, args);
+        self.negative.invalidate(to);
+        rp
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if !is_cacheable(&args) {
+            return self.inner.blocking_stat(path, args);
+        }
+        if self.negative.contains(path) {
+            return Err(Error::new(ErrorKind::NotFound, "path not found (cached)")
+                .with_context("path", path));
+        }
+
+        let generation = self.negative.generation();
+        let rp = self.inner.blocking_stat(path, args);
+        if matches!(&rp, Err(err) if err.kind() == ErrorKind::NotFound) {
+            self.negative.insert(path, generation);
+        }
+        rp
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// MetadataCacheWrapper will invalidate cached results after the file
+/// has been written.
+///
+/// `stat` between opening and closing writer could cache `NotFound` again,
+/// so we must invalidate the cache after close too.
+pub struct MetadataCacheWrapper<W> {
+    inner: W,
+    path: String,
+    negative: Arc<NegativeCache>,
+}
+
+impl<W> MetadataCacheWrapper<W> {
+    fn new(inner: W, path: &str, negative: Arc<NegativeCache>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            negative,
+        }
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for MetadataCacheWrapper<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.negative.invalidate(&self.path);
+        res
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for MetadataCacheWrapper<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.negative.invalidate(&self.path);
+        res
+    }
+}
+
+#[async_trait]
+impl<W: oio::Append> oio::Append for MetadataCacheWrapper<W> {
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.append(bs).await;
+        self.negative.invalidate(&self.path);
+        res
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.negative.invalidate(&self.path);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::tests::NativeRangedWriteLayer;
+    use crate::services::Memory;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_negative_cache() -> Result<()> {
+        let raw = Operator::new(Memory::default())?.finish();
+        let op = raw
+            .clone()
+            .layer(MetadataCacheLayer::new().with_cache_negative_ttl(Duration::from_secs(60)));
+
+        assert!(!op.is_exist("test").await?);
+
+        // Writing by others will not be seen until ttl.
+        raw.write("test", "Hello, World!").await?;
+        assert!(!op.is_exist("test").await?);
+        raw.delete("test").await?;
+
+        // Writing by this operator will invalidate the cache.
+        op.write("test", "Hello, World!").await?;
+        assert!(op.is_exist("test").await?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_negative_cache_invalidate() {
+        let cache = NegativeCache {
+            ttl: Some(Duration::from_secs(60)),
+            entries: Mutex::default(),
+        };
+
+        let generation = cache.generation();
+        for path in ["a/b/c", "a/b/", "a/", "a/d"] {
+            cache.insert(path, generation);
+        }
+        cache.invalidate("a/b/c");
+        assert!(!cache.contains("a/b/c"));
+        assert!(!cache.contains("a/b/"));
+        assert!(!cache.contains("a/"));
+        assert!(cache.contains("a/d"));
+
+        // Stat started before invalidation must not cache stale result.
+        let generation = cache.generation();
+        cache.invalidate("a/b/c");
+        cache.insert("a/b/c", generation);
+        assert!(!cache.contains("a/b/c"));
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache_skip_args() -> Result<()> {
+        let raw = Operator::new(Memory::default())?.finish();
+        let op = raw
+            .clone()
+            .layer(MetadataCacheLayer::new().with_cache_negative_ttl(Duration::from_secs(60)));
+
+        // NotFound of stat with args must not be cached.
+        assert!(op
+            .stat_with("test", OpStat::new().with_if_match("\"etag\""))
+            .await
+            .is_err());
+        raw.write("test", "Hello, World!").await?;
+        assert!(op.is_exist("test").await?);
+
+        // Cached NotFound must not be used by stat with args.
+        assert!(!op.is_exist("other").await?);
+        raw.write("other", "Hello, World!").await?;
+        let args = OpStat::new().with_metakey(Metakey::ContentLength);
+        assert!(op.stat_with("other", args).await.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache_ranged_write() -> Result<()> {
+        let raw = Operator::new(Memory::default())?
+            .layer(NativeRangedWriteLayer)
+            .finish();
+        let op = raw
+            .clone()
+            .layer(MetadataCacheLayer::new().with_cache_negative_ttl(Duration::from_secs(60)));
+
+        assert!(!op.is_exist("test").await?);
+        raw.write("test", "Hello, World!").await?;
+
+        // The underlying ranged write fails, but the cache must still be
+        // invalidated since the object could have been created.
+        let _ = op.finalize_ranged_write("test").await;
+        assert!(op.is_exist("test").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache_expired() -> Result<()> {
+        let raw = Operator::new(Memory::default())?.finish();
+        let op = raw
+            .clone()
+            .layer(MetadataCacheLayer::new().with_cache_negative_ttl(Duration::from_millis(10)));
+
+        assert!(!op.is_exist("test").await?);
+        raw.write("test", "Hello, World!").await?;
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(op.is_exist("test").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negative_cache_disabled() -> Result<()> {
+        let raw = Operator::new(Memory::default())?.finish();
+        let op = raw.clone().layer(MetadataCacheLayer::new());
+
+        assert!(!op.is_exist("test").await?);
+        raw.write("test", "Hello, World!").await?;
+        assert!(op.is_exist("test").await?);
+
+        Ok(())
+    }
+}
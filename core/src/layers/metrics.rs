@@ -64,6 +64,10 @@ static LABEL_ERROR: &str = "error";
 
 /// Add [metrics](https://docs.rs/metrics/) for every operations.
 ///
+/// Metrics are emitted via the `metrics` facade instead of a specific
+/// registry, so any exporter (prometheus, opentelemetry, statsd and so on)
+/// can be plugged in. It records the same signals as `PrometheusLayer`.
+///
 /// # Metrics
 ///
 /// - `opendal_requests_total`: Total request numbers.
@@ -872,6 +876,13 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
         })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await.map_err(|err| {
+            self.handle.increment_errors_total(self.op, err.kind());
+            err
+        })
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await.map_err(|err| {
             self.handle.increment_errors_total(self.op, err.kind());
@@ -899,3 +910,146 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+
+    use metrics::CounterFn;
+    use metrics::Gauge;
+    use metrics::HistogramFn;
+    use metrics::Key;
+    use metrics::KeyName;
+    use metrics::Recorder;
+    use metrics::SharedString;
+    use metrics::Unit;
+    use once_cell::sync::Lazy;
+
+    use super::*;
+    use crate::services;
+
+    /// CapturingRecorder records the value of counters and the number of
+    /// observations of histograms by their keys.
+    #[derive(Default)]
+    struct CapturingRecorder {
+        values: Mutex<HashMap<String, Arc<CapturedValue>>>,
+    }
+
+    #[derive(Default)]
+    struct CapturedValue(AtomicU64);
+
+    impl CounterFn for CapturedValue {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+
+        fn absolute(&self, value: u64) {
+            self.0.fetch_max(value, Ordering::Relaxed);
+        }
+    }
+
+    impl HistogramFn for CapturedValue {
+        fn record(&self, _: f64) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    impl CapturingRecorder {
+        fn value(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+            let mut labels: Vec<_> = labels.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            labels.sort();
+            let key = format!("{name}{{{}}}", labels.join(","));
+
+            self.values
+                .lock()
+                .unwrap()
+                .get(&key)
+                .map(|v| v.0.load(Ordering::Relaxed))
+                .unwrap_or_default()
+        }
+
+        fn register(&self, key: &Key) -> Arc<CapturedValue> {
+            let mut labels: Vec<_> = key
+                .labels()
+                .map(|l| format!("{}={}", l.key(), l.value()))
+                .collect();
+            labels.sort();
+            let key = format!("{}{{{}}}", key.name(), labels.join(","));
+
+            self.values.lock().unwrap().entry(key).or_default().clone()
+        }
+    }
+
+    impl Recorder for CapturingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key) -> Counter {
+            Counter::from_arc(self.register(key))
+        }
+
+        fn register_gauge(&self, _: &Key) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key) -> Histogram {
+            Histogram::from_arc(self.register(key))
+        }
+    }
+
+    static RECORDER: Lazy<&'static CapturingRecorder> = Lazy::new(|| {
+        let recorder: &'static CapturingRecorder = Box::leak(Box::default());
+        metrics::set_recorder(recorder).expect("recorder must be set only once");
+        recorder
+    });
+
+    #[tokio::test]
+    async fn test_metrics_layer() -> Result<()> {
+        let recorder = *RECORDER;
+        let op = Operator::new(services::Memory::default())?
+            .layer(MetricsLayer)
+            .finish();
+
+        op.write("test", "Hello, World!").await?;
+        let bs = op.read("test").await?;
+        assert_eq!(bs, b"Hello, World!");
+        let err = op.stat("not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let service = Scheme::Memory.into_static();
+        for op in [Operation::Write, Operation::Read] {
+            let labels = [
+                (LABEL_SERVICE, service),
+                (LABEL_OPERATION, op.into_static()),
+            ];
+            assert_eq!(recorder.value(METRIC_REQUESTS_TOTAL, &labels), 1);
+            assert_eq!(recorder.value(METRIC_REQUESTS_DURATION_SECONDS, &labels), 1);
+            assert_eq!(recorder.value(METRIC_BYTES_TOTAL, &labels), 13);
+        }
+
+        let labels = [
+            (LABEL_SERVICE, service),
+            (LABEL_OPERATION, Operation::Stat.into_static()),
+        ];
+        assert_eq!(recorder.value(METRIC_REQUESTS_TOTAL, &labels), 1);
+        assert_eq!(
+            recorder.value(
+                METRICS_ERRORS_TOTAL,
+                &[
+                    (LABEL_SERVICE, service),
+                    (LABEL_OPERATION, Operation::Stat.into_static()),
+                    (LABEL_ERROR, ErrorKind::NotFound.into_static()),
+                ]
+            ),
+            1
+        );
+
+        Ok(())
+    }
+}
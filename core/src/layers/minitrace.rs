@@ -334,6 +334,16 @@ impl<R: oio::Write> oio::Write for MinitraceWrapper<R> {
             .await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner
+            .flush()
+            .in_span(Span::enter_with_parent(
+                WriteOperation::Flush.into_static(),
+                &self.span,
+            ))
+            .await
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.inner
             .close()
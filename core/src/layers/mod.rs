@@ -23,14 +23,49 @@ pub use concurrent_limit::ConcurrentLimitLayer;
 mod immutable_index;
 pub use immutable_index::ImmutableIndexLayer;
 
+mod hash_shard;
+pub use hash_shard::HashShardLayer;
+
 mod logging;
 pub use logging::LoggingLayer;
 
+mod max_keys_per_list;
+pub use max_keys_per_list::MaxKeysPerListLayer;
+
+mod metadata_cache;
+pub use metadata_cache::MetadataCacheLayer;
+
+mod snapshot_metadata;
+pub use snapshot_metadata::SnapshotMetadataLayer;
+
+mod time_travel;
+pub use time_travel::TimeTravelLayer;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
 pub use chaos::ChaosLayer;
 
+#[cfg(feature = "layers-latency")]
+mod latency;
+#[cfg(feature = "layers-latency")]
+pub use latency::LatencyLayer;
+
+#[cfg(feature = "layers-compression")]
+mod compression;
+#[cfg(feature = "layers-compression")]
+pub use compression::CompressionLayer;
+
+#[cfg(feature = "layers-checksum-trailer")]
+mod checksum_trailer;
+#[cfg(feature = "layers-checksum-trailer")]
+pub use checksum_trailer::ChecksumTrailerLayer;
+
+#[cfg(feature = "layers-encryption")]
+mod encryption;
+#[cfg(feature = "layers-encryption")]
+pub use encryption::EncryptionLayer;
+
 #[cfg(feature = "layers-metrics")]
 mod metrics;
 #[cfg(feature = "layers-metrics")]
@@ -54,6 +89,11 @@ mod minitrace;
 #[cfg(feature = "layers-minitrace")]
 pub use self::minitrace::MinitraceLayer;
 
+mod stack;
+pub use stack::LayerKind;
+pub use stack::LayerStack;
+pub use stack::OrderedLayer;
+
 mod type_eraser;
 pub(crate) use type_eraser::TypeEraseLayer;
 
@@ -75,3 +115,109 @@ pub use self::madsim::MadsimServer;
 mod oteltrace;
 #[cfg(feature = "layers-otel-trace")]
 pub use self::oteltrace::OtelTraceLayer;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use async_trait::async_trait;
+    use bytes::Bytes;
+
+    use crate::ops::*;
+    use crate::raw::*;
+    use crate::*;
+
+    /// NativeRangedWriteLayer claims native `write_range` support on top
+    /// of services without it, and fails if any ranged write reaches it.
+    ///
+    /// Used to check that layers changing content won't pass ranged
+    /// writes to services directly.
+    pub(crate) struct NativeRangedWriteLayer;
+
+    impl<A: Accessor> Layer<A> for NativeRangedWriteLayer {
+        type LayeredAccessor = NativeRangedWriteAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            NativeRangedWriteAccessor { inner }
+        }
+    }
+
+    #[derive(Debug)]
+    pub(crate) struct NativeRangedWriteAccessor<A> {
+        inner: A,
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for NativeRangedWriteAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Appender = A::Appender;
+        type Pager = A::Pager;
+        type BlockingPager = A::BlockingPager;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        fn metadata(&self) -> AccessorInfo {
+            let mut meta = self.inner.info();
+            meta.capability_mut().write_range = true;
+            meta
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.inner.write(path, args).await
+        }
+
+        async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+            self.inner.append(path, args).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            self.inner.list(path, args).await
+        }
+
+        async fn write_range(&self, _: &str, _: OpWriteRange, _: Bytes) -> Result<RpWriteRange> {
+            Err(Error::new(
+                ErrorKind::Unexpected,
+                "ranged write must not reach services",
+            ))
+        }
+
+        async fn finalize_ranged_write(
+            &self,
+            _: &str,
+            _: OpFinalizeRangedWrite,
+        ) -> Result<RpFinalizeRangedWrite> {
+            Err(Error::new(
+                ErrorKind::Unexpected,
+                "ranged write must not reach services",
+            ))
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+}
@@ -327,6 +327,10 @@ impl<R: oio::Write> oio::Write for OtelTraceWrapper<R> {
         self.inner.abort().await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
@@ -692,6 +692,13 @@ impl<R: oio::Write> oio::Write for PrometheusMetricWrapper<R> {
         })
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await.map_err(|err| {
+            self.stats.increment_errors_total(self.op, err.kind());
+            err
+        })
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await.map_err(|err| {
             self.stats.increment_errors_total(self.op, err.kind());
@@ -50,7 +50,18 @@ use crate::*;
 /// returns true. If operation still failed, this layer will set error to
 /// `Persistent` which means error has been retried.
 ///
-/// `write` and `blocking_write` don't support retry so far, visit [this issue](https://github.com/apache/incubator-opendal/issues/1223) for more details.
+/// Only idempotent operations like `read`, `stat`, `list` and `delete` will
+/// be retried by default. Data written by `write` or `append` could have been
+/// persisted partially before a timeout, replaying them could duplicate data
+/// on some services. So they will be retried only if:
+///
+/// - the write is marked as idempotent via [`OpWrite::with_idempotent`], for
+///   example, [`Operator::write_with`] owns the whole buffer and writes it in
+///   one shot, or
+/// - retry writes is enabled by [`RetryLayer::with_retry_writes`].
+///
+/// Opening writers and aborting writes will always be retried since they
+/// don't carry data.
 ///
 /// # Examples
 ///
@@ -67,7 +78,10 @@ use crate::*;
 ///     .finish();
 /// ```
 #[derive(Default, Clone)]
-pub struct RetryLayer(ExponentialBuilder);
+pub struct RetryLayer {
+    builder: ExponentialBuilder,
+    retry_writes: bool,
+}
 
 impl RetryLayer {
     /// Create a new retry layer.
@@ -93,7 +107,7 @@ impl RetryLayer {
     /// If jitter is enabled, ExponentialBackoff will add a random jitter in `[0, min_delay)
     /// to current delay.
     pub fn with_jitter(mut self) -> Self {
-        self.0 = self.0.with_jitter();
+        self.builder = self.builder.with_jitter();
         self
     }
 
@@ -103,13 +117,13 @@ impl RetryLayer {
     ///
     /// This function will panic if input factor smaller than `1.0`.
     pub fn with_factor(mut self, factor: f32) -> Self {
-        self.0 = self.0.with_factor(factor);
+        self.builder = self.builder.with_factor(factor);
         self
     }
 
     /// Set min_delay of current backoff.
     pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
-        self.0 = self.0.with_min_delay(min_delay);
+        self.builder = self.builder.with_min_delay(min_delay);
         self
     }
 
@@ -117,7 +131,7 @@ impl RetryLayer {
     ///
     /// Delay will not increasing if current delay is larger than max_delay.
     pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
-        self.0 = self.0.with_max_delay(max_delay);
+        self.builder = self.builder.with_max_delay(max_delay);
         self
     }
 
@@ -125,7 +139,16 @@ impl RetryLayer {
     ///
     /// Backoff will return `None` if max times is reaching.
     pub fn with_max_times(mut self, max_times: usize) -> Self {
-        self.0 = self.0.with_max_times(max_times);
+        self.builder = self.builder.with_max_times(max_times);
+        self
+    }
+
+    /// Set whether non-idempotent writes and appends should be retried.
+    ///
+    /// Default to `false`. Only enable this if the underlying service
+    /// makes sure retried writes will not duplicate data.
+    pub fn with_retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
         self
     }
 }
@@ -136,7 +159,8 @@ impl<A: Accessor> Layer<A> for RetryLayer {
     fn layer(&self, inner: A) -> Self::LayeredAccessor {
         RetryAccessor {
             inner,
-            builder: self.0.clone(),
+            builder: self.builder.clone(),
+            retry_writes: self.retry_writes,
         }
     }
 }
@@ -145,6 +169,7 @@ impl<A: Accessor> Layer<A> for RetryLayer {
 pub struct RetryAccessor<A: Accessor> {
     inner: A,
     builder: ExponentialBuilder,
+    retry_writes: bool,
 }
 
 impl<A: Accessor> Debug for RetryAccessor<A> {
@@ -205,6 +230,8 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let retry_writes = self.retry_writes || args.idempotent();
+
         { || self.inner.write(path, args.clone()) }
             .retry(&self.builder)
             .when(|e| e.is_temporary())
@@ -215,8 +242,12 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
                     Operation::Write, dur.as_secs_f64(), err)
             })
             .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
+                v.map(|(rp, r)| {
+                    let w = RetryWrapper::new(r, path, self.builder.clone())
+                        .with_retry_writes(retry_writes);
+                    (rp, w)
+                })
+                .map_err(|e| e.set_persistent())
             })
             .await
     }
@@ -232,8 +263,12 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
                     Operation::Append, dur.as_secs_f64(), err)
             })
             .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
+                v.map(|(rp, r)| {
+                    let a = RetryWrapper::new(r, path, self.builder.clone())
+                        .with_retry_writes(self.retry_writes);
+                    (rp, a)
+                })
+                .map_err(|e| e.set_persistent())
             })
             .await
     }
@@ -368,6 +403,8 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let retry_writes = self.retry_writes || args.idempotent();
+
         { || self.inner.blocking_write(path, args.clone()) }
             .retry(&self.builder)
             .when(|e| e.is_temporary())
@@ -378,7 +415,11 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
                     Operation::BlockingWrite, dur.as_secs_f64(), err)
             })
             .call()
-            .map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
+            .map(|(rp, r)| {
+                let w = RetryWrapper::new(r, path, self.builder.clone())
+                    .with_retry_writes(retry_writes);
+                (rp, w)
+            })
             .map_err(|e| e.set_persistent())
     }
 
@@ -435,6 +476,8 @@ pub struct RetryWrapper<R> {
     builder: ExponentialBuilder,
     current_backoff: Option<ExponentialBackoff>,
     sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// Whether data carrying write operations could be retried.
+    retry_writes: bool,
 }
 
 impl<R> RetryWrapper<R> {
@@ -445,8 +488,14 @@ impl<R> RetryWrapper<R> {
             builder: backoff,
             current_backoff: None,
             sleep: None,
+            retry_writes: false,
         }
     }
+
+    fn with_retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
 }
 
 impl<R: oio::Read> oio::Read for RetryWrapper<R> {
@@ -634,7 +683,7 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
         loop {
             match self.inner.write(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !e.is_temporary() || !self.retry_writes => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
@@ -670,13 +719,34 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        let mut backoff = self.builder.build();
+
+        loop {
+            match self.inner.flush().await {
+                Ok(v) => return Ok(v),
+                Err(e) if !e.is_temporary() || !self.retry_writes => return Err(e),
+                Err(e) => match backoff.next() {
+                    None => return Err(e),
+                    Some(dur) => {
+                        warn!(target: "opendal::service",
+                              "operation={} path={} -> pager retry after {}s: error={:?}",
+                              WriteOperation::Flush, self.path, dur.as_secs_f64(), e);
+                        tokio::time::sleep(dur).await;
+                        continue;
+                    }
+                },
+            }
+        }
+    }
+
     async fn close(&mut self) -> Result<()> {
         let mut backoff = self.builder.build();
 
         loop {
             match self.inner.close().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !e.is_temporary() || !self.retry_writes => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
@@ -694,6 +764,10 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     fn write(&mut self, bs: Bytes) -> Result<()> {
+        if !self.retry_writes {
+            return self.inner.write(bs);
+        }
+
         { || self.inner.write(bs.clone()) }
             .retry(&self.builder)
             .when(|e| e.is_temporary())
@@ -708,6 +782,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     }
 
     fn close(&mut self) -> Result<()> {
+        if !self.retry_writes {
+            return self.inner.close();
+        }
+
         { || self.inner.close() }
             .retry(&self.builder)
             .when(|e| e.is_temporary())
@@ -730,7 +808,7 @@ impl<A: oio::Append> oio::Append for RetryWrapper<A> {
         loop {
             match self.inner.append(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !e.is_temporary() || !self.retry_writes => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
@@ -751,7 +829,7 @@ impl<A: oio::Append> oio::Append for RetryWrapper<A> {
         loop {
             match self.inner.close().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
+                Err(e) if !e.is_temporary() || !self.retry_writes => return Err(e),
                 Err(e) => match backoff.next() {
                     None => return Err(e),
                     Some(dur) => {
@@ -852,7 +930,7 @@ mod tests {
     impl Accessor for MockService {
         type Reader = MockReader;
         type BlockingReader = ();
-        type Writer = ();
+        type Writer = MockWriter;
         type BlockingWriter = ();
         type Appender = ();
         type Pager = MockPager;
@@ -861,6 +939,8 @@ mod tests {
         fn info(&self) -> AccessorInfo {
             let mut am = AccessorInfo::default();
             am.set_capability(Capability {
+                write: true,
+                write_without_content_length: true,
                 list: true,
                 list_with_delimiter_slash: true,
                 list_without_delimiter: true,
@@ -881,6 +961,15 @@ mod tests {
             ))
         }
 
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((
+                RpWrite::default(),
+                MockWriter {
+                    attempt: self.attempt.clone(),
+                },
+            ))
+        }
+
         async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
             let pager = MockPager::default();
             Ok((RpList::default(), pager))
@@ -999,6 +1088,35 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Default)]
+    struct MockWriter {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, _: Bytes) -> Result<()> {
+            let mut attempt = self.attempt.lock().unwrap();
+            *attempt += 1;
+
+            match *attempt {
+                1 => Err(
+                    Error::new(ErrorKind::Unexpected, "retryable_error from writer")
+                        .set_temporary(),
+                ),
+                _ => Ok(()),
+            }
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
     #[derive(Debug, Clone, Default)]
     struct MockPager {
         attempt: usize,
@@ -1109,4 +1227,52 @@ mod tests {
         op.remove(paths).await.expect("batch must succeed");
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+
+    #[tokio::test]
+    async fn test_retry_idempotent_write() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .finish();
+
+        op.write("retryable_error", "Hello, World!")
+            .await
+            .expect("idempotent write must be retried");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_non_idempotent_write() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .finish();
+
+        let mut w = op.writer("retryable_error").await.unwrap();
+        let err = w.write("Hello, World!").await.unwrap_err();
+        assert!(err.is_temporary());
+        assert_eq!(*builder.attempt.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_writes_enabled() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(1))
+                    .with_retry_writes(true),
+            )
+            .finish();
+
+        let mut w = op.writer("retryable_error").await.unwrap();
+        w.write("Hello, World!")
+            .await
+            .expect("write must be retried");
+        w.close().await.unwrap();
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
 }
@@ -0,0 +1,272 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Serve `stat` from a preloaded metadata snapshot.
+///
+/// Useful for tests that need deterministic metadata, or for serving a
+/// precomputed manifest without sending requests to storage.
+///
+/// # Notes
+///
+/// Unlike [`MetadataCacheLayer`](super::MetadataCacheLayer), the snapshot
+/// is populated explicitly and never expires or gets invalidated, even if
+/// the path has been written through this operator.
+///
+/// Paths missing in the snapshot will be passed to underlying services by
+/// default. Use [`SnapshotMetadataLayer::with_miss_as_not_found`] to return
+/// `NotFound` for them instead.
+///
+/// Conditions of `stat` like `if_match` are not checked for paths in the
+/// snapshot.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::SnapshotMetadataLayer;
+/// use opendal::services;
+/// use opendal::EntryMode;
+/// use opendal::Metadata;
+/// use opendal::Operator;
+///
+/// let mut layer = SnapshotMetadataLayer::new();
+/// layer.insert(
+///     "dir/file",
+///     Metadata::new(EntryMode::FILE).with_content_length(1024),
+/// );
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(layer.with_miss_as_not_found(true))
+///     .finish();
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SnapshotMetadataLayer {
+    snapshot: HashMap<String, Metadata>,
+    miss_as_not_found: bool,
+}
+
+impl SnapshotMetadataLayer {
+    /// Create a new SnapshotMetadataLayer with an empty snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the metadata of a path into snapshot.
+    ///
+    /// Dir paths must end with `/`.
+    pub fn insert(&mut self, path: &str, meta: Metadata) {
+        self.snapshot.insert(normalize_path(path), meta);
+    }
+
+    /// Insert metadata of paths from iter.
+    pub fn extend_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (String, Metadata)>,
+    {
+        for (path, meta) in iter {
+            self.insert(&path, meta);
+        }
+    }
+
+    /// Return `NotFound` for paths missing in the snapshot instead of
+    /// passing them to underlying services.
+    pub fn with_miss_as_not_found(mut self, miss_as_not_found: bool) -> Self {
+        self.miss_as_not_found = miss_as_not_found;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for SnapshotMetadataLayer {
+    type LayeredAccessor = SnapshotMetadataAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        SnapshotMetadataAccessor {
+            inner,
+            snapshot: Arc::new(self.snapshot.clone()),
+            miss_as_not_found: self.miss_as_not_found,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotMetadataAccessor<A: Accessor> {
+    inner: A,
+    snapshot: Arc<HashMap<String, Metadata>>,
+    miss_as_not_found: bool,
+}
+
+impl<A: Accessor> SnapshotMetadataAccessor<A> {
+    /// Lookup path in snapshot, returns `None` if it should be passed to
+    /// underlying services.
+    fn lookup(&self, path: &str) -> Option<Result<RpStat>> {
+        match self.snapshot.get(path) {
+            Some(meta) => Some(Ok(RpStat::new(meta.clone()))),
+            None if self.miss_as_not_found => Some(Err(Error::new(
+                ErrorKind::NotFound,
+                "path not found in metadata snapshot",
+            )
+            .with_operation(Operation::Stat)
+            .with_context("path", path))),
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for SnapshotMetadataAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        self.inner.append(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.lookup(path) {
+            Some(rp) => rp,
+            None => self.inner.stat(path, args).await,
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.lookup(path) {
+            Some(rp) => rp,
+            None => self.inner.blocking_stat(path, args),
+        }
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    /// MockService counts `stat` calls and returns a file of 1 byte.
+    #[derive(Debug, Default)]
+    struct MockService {
+        stats: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capability(Capability {
+                stat: true,
+                ..Default::default()
+            });
+            am
+        }
+
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            self.stats.fetch_add(1, Ordering::SeqCst);
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(1),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_metadata() -> Result<()> {
+        let stats = Arc::new(AtomicUsize::new(0));
+        let new_op = |layer: SnapshotMetadataLayer| {
+            let srv = MockService {
+                stats: stats.clone(),
+            };
+            OperatorBuilder::new(srv).layer(layer).finish()
+        };
+
+        let mut layer = SnapshotMetadataLayer::new();
+        layer.insert(
+            "dir/file",
+            Metadata::new(EntryMode::FILE).with_content_length(1024),
+        );
+        layer.insert("/dir/", Metadata::new(EntryMode::DIR));
+        let op = new_op(layer.clone());
+
+        let meta = op.stat("dir/file").await?;
+        assert_eq!(meta.content_length(), 1024);
+        assert!(op.stat("dir/").await?.is_dir());
+        assert_eq!(stats.load(Ordering::SeqCst), 0);
+
+        // Missing paths are passed to services.
+        assert_eq!(op.stat("other").await?.content_length(), 1);
+        assert_eq!(stats.load(Ordering::SeqCst), 1);
+
+        let op = new_op(layer.with_miss_as_not_found(true));
+        let err = op.stat("other").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(stats.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,513 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use crate::raw::*;
+use crate::*;
+
+/// LayerKind describes where a layer should be placed in a [`LayerStack`].
+///
+/// Kinds are listed from the innermost (closest to the service) to the
+/// outermost (closest to users).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum LayerKind {
+    /// Layers that change where data is stored in the service, like
+    /// [`HashShardLayer`][crate::layers::HashShardLayer].
+    ///
+    /// They are placed innermost so that all other layers only see
+    /// logical paths.
+    Layout,
+    /// Layers that change what the service looks like, like
+    /// [`ImmutableIndexLayer`][crate::layers::ImmutableIndexLayer].
+    ///
+    /// They are placed above layout layers so that all other layers
+    /// observe the same view of the service.
+    Index,
+    /// Layers that transform the data or results, like `EncryptionLayer`
+    /// and `ChecksumTrailerLayer`.
+    ///
+    /// They are placed above index layers so that the data seen by
+    /// observe layers is the data users read and write.
+    Transform,
+    /// Layers that cache results, like
+    /// [`MetadataCacheLayer`][crate::layers::MetadataCacheLayer] and
+    /// [`SnapshotMetadataLayer`][crate::layers::SnapshotMetadataLayer].
+    ///
+    /// They are placed above transform layers so that cached results are
+    /// the results users see, and below chaos layers so that injected
+    /// errors will never be cached.
+    Cache,
+    /// Layers that inject errors or latency, like `ChaosLayer` and
+    /// `LatencyLayer`.
+    ///
+    /// They are placed below observe and retry layers so that injected
+    /// errors and latency can be recorded and retried like real ones.
+    Chaos,
+    /// Layers that observe operations, like
+    /// [`LoggingLayer`][crate::layers::LoggingLayer], `MetricsLayer` and
+    /// `TracingLayer`.
+    ///
+    /// They are placed below retry layers so that every attempt will be
+    /// recorded instead of only the final result.
+    Observe,
+    /// Layers that retry failed operations, like
+    /// [`RetryLayer`][crate::layers::RetryLayer].
+    Retry,
+    /// Layers that limit operations, like
+    /// [`ConcurrentLimitLayer`][crate::layers::ConcurrentLimitLayer].
+    ///
+    /// They are placed outermost so that retries of an operation will
+    /// not acquire extra permits.
+    Limit,
+}
+
+impl LayerKind {
+    /// Returns `true` if a stack can only contain one layer of this kind.
+    ///
+    /// For example, stacking two retry layers will multiply the retry
+    /// times which is never what users want.
+    fn is_exclusive(&self) -> bool {
+        matches!(
+            self,
+            LayerKind::Layout | LayerKind::Index | LayerKind::Retry | LayerKind::Limit
+        )
+    }
+}
+
+/// OrderedLayer is a layer that knows its [`LayerKind`].
+///
+/// All layers provided by OpenDAL implement this trait. Users can implement
+/// it for their own layers or use [`LayerStack::push_with_kind`] instead.
+pub trait OrderedLayer: Layer<FusedAccessor> + Send + 'static {
+    /// Returns the kind of this layer.
+    fn kind(&self) -> LayerKind;
+}
+
+struct BoxedLayer {
+    kind: LayerKind,
+    name: &'static str,
+    apply: Box<dyn FnOnce(Operator) -> Operator + Send>,
+}
+
+/// Build and apply a list of layers in the canonical order.
+///
+/// # Notes
+///
+/// The order of layers matters: `op.layer(a).layer(b)` makes `b` the outer
+/// layer which will see all operations before `a`. Getting it wrong silently
+/// changes the behavior, for example, a retry layer placed below a logging
+/// layer will hide every failed attempt from logs.
+///
+/// LayerStack sorts pushed layers by their [`LayerKind`] from the innermost
+/// to the outermost:
+///
+/// 1. [`LayerKind::Layout`]
+/// 2. [`LayerKind::Index`]
+/// 3. [`LayerKind::Transform`]
+/// 4. [`LayerKind::Cache`]
+/// 5. [`LayerKind::Chaos`]
+/// 6. [`LayerKind::Observe`]
+/// 7. [`LayerKind::Retry`]
+/// 8. [`LayerKind::Limit`]
+///
+/// Layers of the same kind are applied in the order they are pushed.
+///
+/// The internal complete layer, which fills the missing capabilities like
+/// `stat` of dirs and `list` recursively, is attached by
+/// [`OperatorBuilder::new`] right above the service. So all layers in the
+/// stack, including cache layers, always sit above it and only cache
+/// completed results.
+///
+/// [`LayerStack::apply`] will return [`ErrorKind::ConfigInvalid`] if the
+/// stack contains more than one layer of `Layout`, `Index`, `Retry` or
+/// `Limit` kind.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ConcurrentLimitLayer;
+/// use opendal::layers::LayerStack;
+/// use opendal::layers::LoggingLayer;
+/// use opendal::layers::RetryLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?.finish();
+///
+/// // Layers will be applied as `logging -> retry -> concurrent limit`.
+/// let op = LayerStack::new()
+///     .push(ConcurrentLimitLayer::new(1024))
+///     .push(RetryLayer::new())
+///     .push(LoggingLayer::default())
+///     .apply(op)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct LayerStack {
+    layers: Vec<BoxedLayer>,
+}
+
+impl Debug for LayerStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.layers.iter().map(|v| (v.kind, v.name)))
+            .finish()
+    }
+}
+
+impl LayerStack {
+    /// Create a new empty LayerStack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a layer into the stack.
+    pub fn push<L: OrderedLayer>(self, layer: L) -> Self {
+        let kind = layer.kind();
+        self.push_with_kind(kind, layer)
+    }
+
+    /// Push a layer into the stack with specified kind.
+    ///
+    /// This is useful for layers that don't implement [`OrderedLayer`].
+    pub fn push_with_kind<L>(mut self, kind: LayerKind, layer: L) -> Self
+    where
+        L: Layer<FusedAccessor> + Send + 'static,
+    {
+        self.layers.push(BoxedLayer {
+            kind,
+            name: std::any::type_name::<L>(),
+            apply: Box::new(move |op| op.layer(layer)),
+        });
+        self
+    }
+
+    /// Validate the stack and apply all layers on given operator in the
+    /// canonical order.
+    pub fn apply(mut self, op: Operator) -> Result<Operator> {
+        // Sort is stable, so layers of the same kind will keep their order.
+        self.layers.sort_by_key(|v| v.kind);
+
+        for pair in self.layers.windows(2) {
+            if pair[0].kind == pair[1].kind && pair[0].kind.is_exclusive() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "layer stack contains more than one layer of the same exclusive kind",
+                )
+                .with_operation("LayerStack::apply")
+                .with_context("kind", format!("{:?}", pair[0].kind))
+                .with_context("layers", format!("{}, {}", pair[0].name, pair[1].name)));
+            }
+        }
+
+        Ok(self.layers.into_iter().fold(op, |op, v| (v.apply)(op)))
+    }
+}
+
+impl OrderedLayer for super::HashShardLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Layout
+    }
+}
+
+impl OrderedLayer for super::MetadataCacheLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Cache
+    }
+}
+
+impl OrderedLayer for super::MaxKeysPerListLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Transform
+    }
+}
+
+impl OrderedLayer for super::ImmutableIndexLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Index
+    }
+}
+
+impl OrderedLayer for super::SnapshotMetadataLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Cache
+    }
+}
+
+impl OrderedLayer for super::TimeTravelLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Index
+    }
+}
+
+#[cfg(feature = "layers-checksum-trailer")]
+impl OrderedLayer for super::ChecksumTrailerLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Transform
+    }
+}
+
+#[cfg(feature = "layers-encryption")]
+impl OrderedLayer for super::EncryptionLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Transform
+    }
+}
+
+#[cfg(feature = "layers-compression")]
+impl OrderedLayer for super::CompressionLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Transform
+    }
+}
+
+#[cfg(feature = "layers-chaos")]
+impl OrderedLayer for super::ChaosLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Chaos
+    }
+}
+
+#[cfg(feature = "layers-latency")]
+impl OrderedLayer for super::LatencyLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Chaos
+    }
+}
+
+impl OrderedLayer for super::LoggingLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+#[cfg(feature = "layers-metrics")]
+impl OrderedLayer for super::MetricsLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+#[cfg(feature = "layers-prometheus")]
+impl OrderedLayer for super::PrometheusLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+#[cfg(feature = "layers-tracing")]
+impl OrderedLayer for super::TracingLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+#[cfg(feature = "layers-minitrace")]
+impl OrderedLayer for super::MinitraceLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+#[cfg(feature = "layers-otel-trace")]
+impl OrderedLayer for super::OtelTraceLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Observe
+    }
+}
+
+impl OrderedLayer for super::RetryLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Retry
+    }
+}
+
+impl OrderedLayer for super::ConcurrentLimitLayer {
+    fn kind(&self) -> LayerKind {
+        LayerKind::Limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::layers::ConcurrentLimitLayer;
+    use crate::layers::ImmutableIndexLayer;
+    use crate::layers::LoggingLayer;
+    use crate::layers::MetadataCacheLayer;
+    use crate::layers::RetryLayer;
+    use crate::layers::SnapshotMetadataLayer;
+    use crate::ops::*;
+    use crate::services::Memory;
+
+    /// RecordLayer records the order of stat calls.
+    #[derive(Clone)]
+    struct RecordLayer {
+        name: &'static str,
+        records: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl<A: Accessor> Layer<A> for RecordLayer {
+        type LayeredAccessor = RecordAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            RecordAccessor {
+                inner,
+                layer: self.clone(),
+            }
+        }
+    }
+
+    struct RecordAccessor<A> {
+        inner: A,
+        layer: RecordLayer,
+    }
+
+    impl<A: Accessor> Debug for RecordAccessor<A> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RecordAccessor")
+                .field("name", &self.layer.name)
+                .finish()
+        }
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for RecordAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Appender = A::Appender;
+        type Pager = A::Pager;
+        type BlockingPager = A::BlockingPager;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.inner.write(path, args).await
+        }
+
+        async fn append(&self, path: &str, args: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+            self.inner.append(path, args).await
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            self.layer.records.lock().push(self.layer.name);
+            self.inner.stat(path, args).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_in_canonical_order() -> anyhow::Result<()> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let record = |name| RecordLayer {
+            name,
+            records: records.clone(),
+        };
+
+        let op = Operator::new(Memory::default())?.finish();
+        let op = LayerStack::new()
+            .push_with_kind(LayerKind::Limit, record("limit"))
+            .push_with_kind(LayerKind::Observe, record("observe-1"))
+            .push_with_kind(LayerKind::Index, record("index"))
+            .push_with_kind(LayerKind::Cache, record("cache"))
+            .push_with_kind(LayerKind::Retry, record("retry"))
+            .push_with_kind(LayerKind::Observe, record("observe-2"))
+            .apply(op)?;
+
+        op.write("file", "hello").await?;
+        op.stat("file").await?;
+
+        // Outer layers will be called first.
+        assert_eq!(
+            *records.lock(),
+            vec!["limit", "retry", "observe-2", "observe-1", "cache", "index"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reject_duplicated_exclusive_layers() -> anyhow::Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+
+        let err = LayerStack::new()
+            .push(RetryLayer::new())
+            .push(LoggingLayer::default())
+            .push(RetryLayer::new())
+            .apply(op.clone())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let res = LayerStack::new()
+            .push(ConcurrentLimitLayer::new(8))
+            .push(LoggingLayer::default())
+            .push(LoggingLayer::default())
+            .apply(op.clone());
+        assert!(res.is_ok());
+
+        // Snapshot is a cache instead of an index, it can be used with one.
+        let res = LayerStack::new()
+            .push(ImmutableIndexLayer::default())
+            .push(SnapshotMetadataLayer::new())
+            .push(MetadataCacheLayer::new())
+            .apply(op);
+        assert!(res.is_ok());
+        Ok(())
+    }
+}
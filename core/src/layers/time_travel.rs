@@ -0,0 +1,402 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::mem;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Pin all reads to the versions that were current at given time.
+///
+/// Every `read`, `stat` and `list` resolves the version of objects via
+/// [`Accessor::list_versions`] and reads exactly that version, so the
+/// operator behaves like a read-only snapshot of storage at that time.
+///
+/// Use [`Operator::snapshot_at`] to build it from an existing operator.
+///
+/// # Notes
+///
+/// - Services must support `list_versions` and `read_with_version`.
+/// - All operations that change storage like `write` and `delete` are
+///   rejected with [`ErrorKind::PermissionDenied`].
+/// - Objects deleted before the snapshot time (the newest version is a
+///   delete marker) are treated as not found.
+/// - Blocking operations are not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anyhow::Result;
+/// use chrono::TimeZone;
+/// use chrono::Utc;
+/// use opendal::layers::TimeTravelLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// let at = Utc.with_ymd_and_hms(2023, 5, 1, 0, 0, 0).unwrap();
+///
+/// let _ = Operator::new(services::S3::default())?
+///     .layer(TimeTravelLayer::new(at))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeTravelLayer {
+    at: DateTime<Utc>,
+}
+
+impl TimeTravelLayer {
+    /// Create a new TimeTravelLayer pinned at given time.
+    pub fn new(at: DateTime<Utc>) -> Self {
+        Self { at }
+    }
+}
+
+impl<A: Accessor> Layer<A> for TimeTravelLayer {
+    type LayeredAccessor = TimeTravelAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        TimeTravelAccessor { inner, at: self.at }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimeTravelAccessor<A: Accessor> {
+    inner: A,
+    at: DateTime<Utc>,
+}
+
+impl<A: Accessor> TimeTravelAccessor<A> {
+    /// Resolve the versions that are live at snapshot time of all objects
+    /// under given path, sorted by path.
+    async fn live_versions(&self, path: &str) -> Result<Vec<ObjectVersion>> {
+        let path = if path == "/" { "" } else { path };
+
+        let rp = self
+            .inner
+            .list_versions(path, OpListVersions::new())
+            .await?;
+
+        // Versions of the same path are sorted from newest to oldest, so
+        // the first one before snapshot time is the live one.
+        let mut live = BTreeMap::new();
+        for v in rp.into_versions() {
+            if v.last_modified() <= self.at && !live.contains_key(v.path()) {
+                live.insert(v.path().to_string(), v);
+            }
+        }
+
+        Ok(live
+            .into_values()
+            .filter(|v| !v.is_delete_marker())
+            .collect())
+    }
+
+    /// Resolve the live version of the exact path.
+    async fn resolve(&self, path: &str) -> Result<ObjectVersion> {
+        let rp = self
+            .inner
+            .list_versions(path, OpListVersions::new().with_exact(true))
+            .await?;
+
+        // Versions are sorted from newest to oldest, so the first one
+        // before snapshot time is the live one.
+        rp.into_versions()
+            .into_iter()
+            .find(|v| v.last_modified() <= self.at)
+            .filter(|v| !v.is_delete_marker())
+            .ok_or_else(|| {
+                Error::new(ErrorKind::NotFound, "path not found at snapshot time")
+                    .with_context("path", path)
+                    .with_context("at", self.at.to_rfc3339())
+            })
+    }
+
+    fn read_only(&self, op: Operation) -> Error {
+        Error::new(ErrorKind::PermissionDenied, "snapshot is read-only")
+            .with_operation(op)
+            .with_context("at", self.at.to_rfc3339())
+    }
+
+    /// Versions can't be resolved without async `list_versions`.
+    fn blocking_unsupported(&self, op: Operation) -> Error {
+        Error::new(
+            ErrorKind::Unsupported,
+            "snapshot doesn't support blocking operations",
+        )
+        .with_operation(op)
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for TimeTravelAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Appender = A::Appender;
+    type Pager = TimeTravelPager;
+    type BlockingPager = ();
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    /// Remove all capabilities that change storage.
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.capability_mut();
+        cap.write = false;
+        cap.append = false;
+        cap.create_dir = false;
+        cap.delete = false;
+        cap.delete_all_versions = false;
+        cap.copy = false;
+        cap.rename = false;
+        cap.batch = false;
+        cap.batch_delete = false;
+        cap.presign_write = false;
+        cap.write_range = false;
+        cap.list_with_limit = false;
+        cap.blocking = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if args.version().is_some() {
+            return self.inner.read(path, args).await;
+        }
+
+        let v = self.resolve(path).await?;
+        self.inner.read(path, args.with_version(v.version())).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        if path == "/" {
+            return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
+        }
+
+        if path.ends_with('/') {
+            return if self.live_versions(path).await?.is_empty() {
+                Err(
+                    Error::new(ErrorKind::NotFound, "path not found at snapshot time")
+                        .with_context("path", path)
+                        .with_context("at", self.at.to_rfc3339()),
+                )
+            } else {
+                Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+            };
+        }
+
+        let v = self.resolve(path).await?;
+        let rp = self
+            .inner
+            .stat(path, args.with_version(v.version()))
+            .await?;
+
+        let mut meta = rp.into_metadata();
+        meta.set_version(v.version());
+        Ok(RpStat::new(meta))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let path = if path == "/" { "" } else { path };
+        let path = &format!("{path}{}", args.prefix().unwrap_or_default());
+
+        let hierarchy = if args.delimiter() == "/" {
+            true
+        } else if args.delimiter().is_empty() {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!("delimiter {} is not supported", args.delimiter()),
+            ));
+        };
+
+        let mut dirs = BTreeSet::new();
+        let mut entries = Vec::new();
+        for v in self.live_versions(path).await? {
+            // Skip the dir marker of path itself.
+            if v.path() == path {
+                continue;
+            }
+
+            let rest = &v.path()[path.len()..];
+            match rest.find('/') {
+                Some(idx) if hierarchy && idx + 1 != rest.len() => {
+                    dirs.insert(format!("{path}{}", &rest[..=idx]));
+                }
+                _ if v.path().ends_with('/') => {
+                    dirs.insert(v.path().to_string());
+                }
+                _ => {
+                    let meta = Metadata::new(EntryMode::FILE)
+                        .with_content_length(v.content_length())
+                        .with_last_modified(v.last_modified())
+                        .with_version(v.version().to_string());
+                    entries.push(oio::Entry::new(v.path(), meta));
+                }
+            }
+        }
+        entries.extend(
+            dirs.into_iter()
+                .map(|v| oio::Entry::with(v, Metadata::new(EntryMode::DIR))),
+        );
+
+        if let Some(start_after) = args.start_after() {
+            entries.retain(|v| v.path() > start_after);
+        }
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+
+        Ok((RpList::default(), TimeTravelPager::new(entries)))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let op = match args.operation() {
+            PresignOperation::Stat(v) => {
+                let version = self.resolve(path).await?;
+                PresignOperation::Stat(v.clone().with_version(version.version()))
+            }
+            PresignOperation::Read(v) => {
+                let version = self.resolve(path).await?;
+                PresignOperation::Read(v.clone().with_version(version.version()))
+            }
+            PresignOperation::Write(_) => return Err(self.read_only(Operation::Presign)),
+        };
+
+        self.inner
+            .presign(path, OpPresign::new(op, args.expire()))
+            .await
+    }
+
+    async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Err(self.read_only(Operation::Write))
+    }
+
+    async fn append(&self, _: &str, _: OpAppend) -> Result<(RpAppend, Self::Appender)> {
+        Err(self.read_only(Operation::Append))
+    }
+
+    async fn create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.read_only(Operation::CreateDir))
+    }
+
+    async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.read_only(Operation::Copy))
+    }
+
+    async fn rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.read_only(Operation::Rename))
+    }
+
+    async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.read_only(Operation::Delete))
+    }
+
+    async fn batch(&self, _: OpBatch) -> Result<RpBatch> {
+        Err(self.read_only(Operation::Batch))
+    }
+
+    async fn write_range(&self, _: &str, _: OpWriteRange, _: Bytes) -> Result<RpWriteRange> {
+        Err(self.read_only(Operation::WriteRange))
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        _: &str,
+        _: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        Err(self.read_only(Operation::FinalizeRangedWrite))
+    }
+
+    async fn delete_all_versions(
+        &self,
+        _: &str,
+        _: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        Err(self.read_only(Operation::DeleteAllVersions))
+    }
+
+    fn blocking_create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.read_only(Operation::BlockingCreateDir))
+    }
+
+    fn blocking_read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        Err(self.blocking_unsupported(Operation::BlockingRead))
+    }
+
+    fn blocking_write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Err(self.read_only(Operation::BlockingWrite))
+    }
+
+    fn blocking_copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.read_only(Operation::BlockingCopy))
+    }
+
+    fn blocking_rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.read_only(Operation::BlockingRename))
+    }
+
+    fn blocking_stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+        Err(self.blocking_unsupported(Operation::BlockingStat))
+    }
+
+    fn blocking_delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.read_only(Operation::BlockingDelete))
+    }
+
+    fn blocking_list(&self, _: &str, _: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        Err(self.blocking_unsupported(Operation::BlockingList))
+    }
+}
+
+/// TimeTravelPager returns all resolved entries in one page.
+pub struct TimeTravelPager {
+    entries: Vec<oio::Entry>,
+}
+
+impl TimeTravelPager {
+    fn new(entries: Vec<oio::Entry>) -> Self {
+        Self { entries }
+    }
+}
+
+#[async_trait]
+impl oio::Page for TimeTravelPager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.entries.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(mem::take(&mut self.entries)))
+    }
+}
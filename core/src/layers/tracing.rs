@@ -342,6 +342,14 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         self.inner.abort().await
     }
 
+    #[tracing::instrument(
+        parent = &self.span,
+        level = "trace",
+        skip_all)]
+    async fn flush(&mut self) -> Result<()> {
+        self.inner.flush().await
+    }
+
     #[tracing::instrument(
         parent = &self.span,
         level = "trace",
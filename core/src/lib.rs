@@ -89,8 +89,8 @@ mod tests {
     #[test]
     fn assert_size() {
         assert_eq!(24, size_of::<Operator>());
-        assert_eq!(240, size_of::<Entry>());
-        assert_eq!(216, size_of::<Metadata>());
+        assert_eq!(288, size_of::<Entry>());
+        assert_eq!(240, size_of::<Metadata>());
         assert_eq!(1, size_of::<EntryMode>());
         assert_eq!(24, size_of::<Scheme>());
     }
@@ -19,6 +19,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 
 use crate::ops::*;
 use crate::raw::*;
@@ -87,6 +88,17 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
     /// - capabilities: declare the capabilities of current backend.
     fn info(&self) -> AccessorInfo;
 
+    /// Validate the key before `write`, `create_dir`, `copy` and `rename`.
+    ///
+    /// The default implementation checks input path with the key constraints
+    /// declared in [`Capability`]. Services can override it to check the full
+    /// key with root or to add their own rules.
+    ///
+    /// Invalid key SHOULD return [`ErrorKind::ConfigInvalid`].
+    fn validate_key(&self, path: &str) -> Result<()> {
+        validate_key(&self.info().capability(), path)
+    }
+
     /// Invoke the `create` operation on the specified path
     ///
     /// Require [`Capability::create_dir`]
@@ -266,6 +278,151 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `select` operation on the specified path.
+    ///
+    /// Require [`Capability::select`]
+    ///
+    /// # Behavior
+    ///
+    /// - Services should evaluate the query and return only the selected
+    ///   records in the output format via the returning reader.
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `retention` operation on the specified path.
+    ///
+    /// Require [`Capability::retention`]
+    ///
+    /// # Behavior
+    ///
+    /// - Services should return `None` if the object doesn't have retention.
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `legal_hold` operation on the specified path.
+    ///
+    /// Require [`Capability::legal_hold`]
+    ///
+    /// # Behavior
+    ///
+    /// - Services should return `false` if the legal hold is never set.
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `acl` operation on the specified path.
+    ///
+    /// Require [`Capability::acl`]
+    ///
+    /// # Behavior
+    ///
+    /// - This operation only fetches the ACL of the object, other fields of
+    ///   metadata are not required to be set.
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `write_range` operation on the specified path.
+    ///
+    /// Require [`Capability::write_range`]
+    ///
+    /// # Behavior
+    ///
+    /// - Input range is already aligned with part size, DON'T NEED to check.
+    /// - Ranges of the same path could be written concurrently and in any
+    ///   order, they MUST NOT be visible until `finalize_ranged_write`.
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        let (_, _, _) = (path, args, bs);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `finalize_ranged_write` operation on the specified path.
+    ///
+    /// Require [`Capability::write_range`]
+    ///
+    /// # Behavior
+    ///
+    /// - Assemble all written ranges in the order of offset into the object.
+    /// - Finalize without any written range SHOULD return `NotFound`.
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `delete_all_versions` operation on the specified path.
+    ///
+    /// Require [`Capability::delete_all_versions`]
+    ///
+    /// # Behavior
+    ///
+    /// - Delete every version of the object including delete markers.
+    /// - Return the count of deleted versions, `0` if there is none.
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `list_versions` operation on the specified path.
+    ///
+    /// Require [`Capability::list_versions`]
+    ///
+    /// # Behavior
+    ///
+    /// - Return every version and delete marker of objects whose path
+    ///   starts with `path`, including objects in sub dirs.
+    /// - Versions of the same path MUST be sorted from the newest to the
+    ///   oldest.
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `blocking_create` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::create_dir`]
@@ -399,6 +556,7 @@ impl Accessor for () {
             scheme: Scheme::Custom("dummy"),
             root: "".to_string(),
             name: "dummy".to_string(),
+            endpoint: "".to_string(),
             capability: Capability::default(),
         }
     }
@@ -420,6 +578,10 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
         self.as_ref().info()
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        self.as_ref().validate_key(path)
+    }
+
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().create_dir(path, args).await
     }
@@ -461,6 +623,46 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
         self.as_ref().presign(path, args).await
     }
 
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.as_ref().select(path, args).await
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        self.as_ref().retention(path, args).await
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        self.as_ref().legal_hold(path, args).await
+    }
+
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        self.as_ref().acl(path, args).await
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        self.as_ref().write_range(path, args, bs).await
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        self.as_ref().finalize_ranged_write(path, args).await
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        self.as_ref().delete_all_versions(path, args).await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.as_ref().list_versions(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.as_ref().blocking_create_dir(path, args)
     }
@@ -509,6 +711,7 @@ pub struct AccessorInfo {
     scheme: Scheme,
     root: String,
     name: String,
+    endpoint: String,
 
     capability: Capability,
 }
@@ -554,6 +757,18 @@ impl AccessorInfo {
         self
     }
 
+    /// Endpoint of backend, could be empty if underlying backend doesn't
+    /// talk to a remote endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Set endpoint of this backend.
+    pub fn set_endpoint(&mut self, endpoint: &str) -> &mut Self {
+        self.endpoint = endpoint.to_string();
+        self
+    }
+
     /// Get backend's capabilities.
     pub fn capability(&self) -> Capability {
         self.capability
@@ -26,6 +26,7 @@ use crate::EntryMode;
 use crate::Error;
 use crate::ErrorKind;
 use crate::Metadata;
+use crate::Metakey;
 use crate::Result;
 use crate::Scheme;
 
@@ -111,6 +112,15 @@ impl Value {
         }
     }
 
+    /// Check whether this value has expired.
+    pub fn is_expired(&self) -> bool {
+        self.metadata.bit().contains(Metakey::ExpiresAt)
+            && self
+                .metadata
+                .expires_at()
+                .map_or(false, |v| v <= Utc::now())
+    }
+
     /// Size returns the in-memory size of Value.
     pub fn size(&self) -> usize {
         size_of::<Metadata>() + self.value.len()
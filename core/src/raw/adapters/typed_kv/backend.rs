@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::Utc;
 
 use super::Adapter;
 use super::Value;
@@ -81,6 +82,7 @@ impl<S: Adapter> Accessor for Backend<S> {
 
         if kv_cap.set {
             cap.write = true;
+            cap.write_with_expire = true;
             cap.create_dir = true;
         }
 
@@ -112,7 +114,7 @@ impl<S: Adapter> Accessor for Backend<S> {
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let p = build_abs_path(&self.root, path);
 
-        let bs = match self.kv.get(&p).await? {
+        let bs = match self.get(&p).await? {
             // TODO: we can reuse the metadata in value to build content range.
             Some(bs) => bs.value,
             None => return Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
@@ -127,7 +129,7 @@ impl<S: Adapter> Accessor for Backend<S> {
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
         let p = build_abs_path(&self.root, path);
 
-        let bs = match self.kv.blocking_get(&p)? {
+        let bs = match self.blocking_get(&p)? {
             // TODO: we can reuse the metadata in value to build content range.
             Some(bs) => bs.value,
             None => return Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
@@ -155,7 +157,7 @@ impl<S: Adapter> Accessor for Backend<S> {
         if p.is_empty() || p.ends_with('/') {
             Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
         } else {
-            let bs = self.kv.get(&p).await?;
+            let bs = self.get(&p).await?;
             match bs {
                 Some(bs) => Ok(RpStat::new(bs.metadata)),
                 None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
@@ -169,7 +171,7 @@ impl<S: Adapter> Accessor for Backend<S> {
         if p.is_empty() || p.ends_with('/') {
             Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
         } else {
-            let bs = self.kv.blocking_get(&p)?;
+            let bs = self.blocking_get(&p)?;
             match bs {
                 Some(bs) => Ok(RpStat::new(bs.metadata)),
                 None => Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
@@ -226,6 +228,34 @@ impl<S> Backend<S>
 where
     S: Adapter,
 {
+    /// Get a value that has not expired.
+    ///
+    /// Expired values are removed lazily while getting.
+    async fn get(&self, path: &str) -> Result<Option<Value>> {
+        match self.kv.get(path).await? {
+            Some(v) if v.is_expired() => {
+                if self.kv.info().capabilities().delete {
+                    self.kv.delete(path).await?;
+                }
+                Ok(None)
+            }
+            v => Ok(v),
+        }
+    }
+
+    /// Get a value that has not expired in blocking way.
+    fn blocking_get(&self, path: &str) -> Result<Option<Value>> {
+        match self.kv.blocking_get(path)? {
+            Some(v) if v.is_expired() => {
+                if self.kv.info().capabilities().delete {
+                    self.kv.blocking_delete(path)?;
+                }
+                Ok(None)
+            }
+            v => Ok(v),
+        }
+    }
+
     fn apply_range(&self, mut bs: Bytes, br: BytesRange) -> Bytes {
         match (br.offset(), br.size()) {
             (Some(offset), Some(size)) => {
@@ -322,6 +352,15 @@ impl<S> KvWriter<S> {
         } else {
             metadata.set_content_length(self.buf.len() as u64);
         }
+        if let Some(v) = self.op.expire() {
+            // Expire that is too far away is treated as never expire.
+            if let Some(v) = chrono::Duration::from_std(v)
+                .ok()
+                .and_then(|v| Utc::now().checked_add_signed(v))
+            {
+                metadata.set_expires_at(v);
+            }
+        }
 
         Value {
             metadata,
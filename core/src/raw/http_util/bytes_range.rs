@@ -89,6 +89,46 @@ impl BytesRange {
         self.0.unwrap_or_default() == 0 && self.1.is_none()
     }
 
+    /// Check if this range could be satisfied by services.
+    ///
+    /// Returns [`ErrorKind::RangeNotSatisfied`] if this range is empty or
+    /// inverted (start > end), so callers can reject it before sending a
+    /// `Range` header that services will refuse.
+    pub fn validate(&self) -> Result<()> {
+        match (self.0, self.1) {
+            (_, Some(0)) => Err(Error::new(
+                ErrorKind::RangeNotSatisfied,
+                "range is empty or its start is larger than its end",
+            )
+            .with_operation("BytesRange::validate")
+            .with_context("range", format!("{:?}", self))),
+            (Some(offset), Some(size)) if offset.checked_add(size).is_none() => Err(Error::new(
+                ErrorKind::RangeNotSatisfied,
+                "range end overflows",
+            )
+            .with_operation("BytesRange::validate")
+            .with_context("range", format!("{:?}", self))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Clamp this range to the content of `len` bytes.
+    ///
+    /// Returns `None` if no bytes of content is in this range.
+    pub fn clamp(&self, len: u64) -> Option<BytesRange> {
+        match (self.0, self.1) {
+            (None, Some(size)) => (len > 0).then(|| BytesRange(None, Some(size.min(len)))),
+            (offset, size) => {
+                let offset = offset.unwrap_or_default();
+                if offset >= len {
+                    return None;
+                }
+                let size = size.map(|v| v.min(len - offset));
+                Some(BytesRange(Some(offset), size))
+            }
+        }
+    }
+
     /// Convert bytes range into Range header.
     ///
     /// # NOTE
@@ -151,9 +191,12 @@ impl FromStr for BytesRange {
 
     fn from_str(value: &str) -> Result<Self> {
         let s = value.strip_prefix("bytes=").ok_or_else(|| {
-            Error::new(ErrorKind::Unexpected, "header range is invalid")
-                .with_operation("BytesRange::from_str")
-                .with_context("value", value)
+            Error::new(
+                ErrorKind::RangeNotSatisfied,
+                "header range unit is not bytes",
+            )
+            .with_operation("BytesRange::from_str")
+            .with_context("value", value)
         })?;
 
         if s.contains(',') {
@@ -186,17 +229,31 @@ impl FromStr for BytesRange {
             // -<suffix-length>
             Ok(BytesRange::new(
                 None,
-                Some(v[1].parse::<u64>().map_err(parse_int_error)? + 1),
+                Some(
+                    v[1].parse::<u64>()
+                        .map_err(parse_int_error)?
+                        .saturating_add(1),
+                ),
             ))
         } else {
             // <range-start>-<range-end>
             let start: u64 = v[0].parse().map_err(parse_int_error)?;
             let end: u64 = v[1].parse().map_err(parse_int_error)?;
+            if start > end {
+                return Err(Error::new(
+                    ErrorKind::RangeNotSatisfied,
+                    "header range start is larger than end",
+                )
+                .with_operation("BytesRange::from_str")
+                .with_context("value", value));
+            }
             Ok(BytesRange::new(Some(start), Some(end - start + 1)))
         }
     }
 }
 
+/// Inverted ranges like `20..10` are converted into an empty range which
+/// will be rejected by [`BytesRange::validate`].
 impl<T> From<T> for BytesRange
 where
     T: RangeBounds<u64>,
@@ -204,12 +261,15 @@ where
     fn from(range: T) -> Self {
         let offset = match range.start_bound().cloned() {
             Bound::Included(n) => Some(n),
-            Bound::Excluded(n) => Some(n + 1),
+            Bound::Excluded(n) => Some(n.saturating_add(1)),
             Bound::Unbounded => None,
         };
         let size = match range.end_bound().cloned() {
-            Bound::Included(n) => Some(n + 1 - offset.unwrap_or_default()),
-            Bound::Excluded(n) => Some(n - offset.unwrap_or_default()),
+            Bound::Included(n) => Some(
+                n.saturating_add(1)
+                    .saturating_sub(offset.unwrap_or_default()),
+            ),
+            Bound::Excluded(n) => Some(n.saturating_sub(offset.unwrap_or_default())),
             Bound::Unbounded => None,
         };
 
@@ -298,6 +358,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bytes_range_from_str_invalid() {
+        let cases = vec![("inverted", "bytes=124-123"), ("other unit", "items=0-10")];
+
+        for (name, input) in cases {
+            let err = input.parse::<BytesRange>().expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_bytes_range_clamp() {
+        let cases = vec![
+            (
+                "in range",
+                BytesRange::new(Some(10), Some(10)),
+                100,
+                Some((Some(10), Some(10))),
+            ),
+            (
+                "cross end",
+                BytesRange::new(Some(90), Some(20)),
+                100,
+                Some((Some(90), Some(10))),
+            ),
+            (
+                "beyond end",
+                BytesRange::new(Some(100), Some(10)),
+                100,
+                None,
+            ),
+            (
+                "open end",
+                BytesRange::new(Some(10), None),
+                100,
+                Some((Some(10), None)),
+            ),
+            (
+                "suffix",
+                BytesRange::new(None, Some(200)),
+                100,
+                Some((None, Some(100))),
+            ),
+            ("suffix of empty", BytesRange::new(None, Some(10)), 0, None),
+        ];
+
+        for (name, input, len, expected) in cases {
+            let actual = input.clamp(len).map(|v| (v.offset(), v.size()));
+            assert_eq!(expected, actual, "{name}")
+        }
+    }
+
+    #[test]
+    fn test_bytes_range_validate() {
+        let cases = vec![
+            ("full", BytesRange::from(..), true),
+            ("range", BytesRange::from(10..20), true),
+            ("one byte", BytesRange::from(10..=10), true),
+            ("suffix", BytesRange::from(..10), true),
+            #[allow(clippy::reversed_empty_ranges)]
+            ("inverted", BytesRange::from(20..10), false),
+            #[allow(clippy::reversed_empty_ranges)]
+            ("inverted inclusive", BytesRange::from(20..=10), false),
+            ("zero length", BytesRange::from(10..10), false),
+            ("zero suffix", BytesRange::from(..0), false),
+            ("overflow", BytesRange::new(Some(u64::MAX), Some(2)), false),
+        ];
+
+        for (name, input, valid) in cases {
+            let res = input.validate();
+            assert_eq!(res.is_ok(), valid, "{name}");
+            if let Err(err) = res {
+                assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied, "{name}");
+            }
+        }
+    }
+
     #[test]
     fn test_apply_on_bytes() -> Result<()> {
         let bs = Bytes::from_static("Hello, World!".as_bytes());
@@ -22,16 +22,20 @@ use chrono::Utc;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
+use http::header::CONTENT_LANGUAGE;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::ETAG;
+use http::header::EXPIRES;
 use http::header::LAST_MODIFIED;
 use http::header::LOCATION;
 use http::HeaderMap;
 use http::HeaderValue;
 use md5::Digest;
 
+use crate::ops::OpWrite;
 use crate::raw::*;
 use crate::EntryMode;
 use crate::Error;
@@ -131,6 +135,24 @@ pub fn parse_content_type(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse header value to str by given name.
+///
+/// Returns `None` if the header doesn't exist.
+pub fn parse_header_to_str<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>> {
+    match headers.get(name) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_header_to_str")
+            .with_context("header", name)
+            .set_source(e)
+        })?)),
+    }
+}
+
 /// Parse content range from header map.
 pub fn parse_content_range(headers: &HeaderMap) -> Result<Option<BytesContentRange>> {
     match headers.get(CONTENT_RANGE) {
@@ -199,6 +221,59 @@ pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse content language from header map.
+pub fn parse_content_language(headers: &HeaderMap) -> Result<Option<&str>> {
+    parse_header_to_str(headers, CONTENT_LANGUAGE.as_str())
+}
+
+/// Standard http headers that could be stored with objects.
+const STORABLE_HTTP_HEADERS: [HeaderName; 6] = [
+    CACHE_CONTROL,
+    CONTENT_DISPOSITION,
+    CONTENT_ENCODING,
+    CONTENT_LANGUAGE,
+    CONTENT_TYPE,
+    EXPIRES,
+];
+
+/// Validate the http header set by [`OpWrite::with_http_header`].
+///
+/// Only standard headers stored with objects are allowed, others like
+/// hop-by-hop headers will be rejected.
+pub fn validate_http_header(name: &str, value: &str) -> Result<()> {
+    let allowed = HeaderName::from_bytes(name.as_bytes())
+        .map(|name| STORABLE_HTTP_HEADERS.contains(&name))
+        .unwrap_or_default();
+    if !allowed {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "http header is not allowed to be stored with object",
+        )
+        .with_context("header", name));
+    }
+
+    build_header_value(value).map_err(|err| err.with_context("header", name))?;
+    Ok(())
+}
+
+/// Build headers for write args that services don't take as typed args,
+/// including content language and extra http headers.
+///
+/// Extra http headers override the ones with the same name.
+pub fn build_write_headers(op: &OpWrite) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    if let Some(v) = op.content_language() {
+        headers.insert(CONTENT_LANGUAGE, build_header_value(v)?);
+    }
+    for (name, value) in op.http_headers() {
+        validate_http_header(name, value)?;
+        // The name has been validated, it's safe to unwrap.
+        let name = HeaderName::from_bytes(name.as_bytes()).expect("must be valid header name");
+        headers.insert(name, build_header_value(value)?);
+    }
+    Ok(headers)
+}
+
 /// parse_into_metadata will parse standards http headers into Metadata.
 ///
 /// # Notes
@@ -246,6 +321,10 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
         m.set_content_disposition(v);
     }
 
+    if let Some(v) = parse_content_language(headers)? {
+        m.set_content_language(v);
+    }
+
     Ok(m)
 }
 
@@ -31,18 +31,23 @@ pub use body::IncomingAsyncBody;
 
 mod header;
 pub use header::build_header_value;
+pub use header::build_write_headers;
 pub use header::format_authorization_by_basic;
 pub use header::format_authorization_by_bearer;
 pub use header::format_content_md5;
+pub use header::parse_cache_control;
 pub use header::parse_content_disposition;
+pub use header::parse_content_language;
 pub use header::parse_content_length;
 pub use header::parse_content_md5;
 pub use header::parse_content_range;
 pub use header::parse_content_type;
 pub use header::parse_etag;
+pub use header::parse_header_to_str;
 pub use header::parse_into_metadata;
 pub use header::parse_last_modified;
 pub use header::parse_location;
+pub use header::validate_http_header;
 
 mod uri;
 pub use uri::percent_encode_path;
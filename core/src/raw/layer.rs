@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 
 use crate::ops::*;
 use crate::raw::*;
@@ -149,6 +150,10 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().info()
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        self.inner().validate_key(path)
+    }
+
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner().create_dir(path, args).await
     }
@@ -185,6 +190,46 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().presign(path, args).await
     }
 
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        self.inner().select(path, args).await
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        self.inner().retention(path, args).await
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        self.inner().legal_hold(path, args).await
+    }
+
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        self.inner().acl(path, args).await
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        self.inner().write_range(path, args, bs).await
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        self.inner().finalize_ranged_write(path, args).await
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        self.inner().delete_all_versions(path, args).await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        self.inner().list_versions(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner().blocking_create_dir(path, args)
     }
@@ -226,6 +271,10 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).metadata()
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        (self as &L).validate_key(path)
+    }
+
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         (self as &L).create_dir(path, args).await
     }
@@ -270,6 +319,46 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).presign(path, args).await
     }
 
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        (self as &L).select(path, args).await
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        (self as &L).retention(path, args).await
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        (self as &L).legal_hold(path, args).await
+    }
+
+    async fn acl(&self, path: &str, args: OpAcl) -> Result<RpAcl> {
+        (self as &L).acl(path, args).await
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        (self as &L).write_range(path, args, bs).await
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        args: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        (self as &L).finalize_ranged_write(path, args).await
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        args: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        (self as &L).delete_all_versions(path, args).await
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        (self as &L).list_versions(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         (self as &L).blocking_create_dir(path, args)
     }
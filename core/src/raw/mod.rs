@@ -45,6 +45,7 @@ pub use path::get_basename;
 pub use path::get_parent;
 pub use path::normalize_path;
 pub use path::normalize_root;
+pub use path::validate_key;
 pub use path::validate_path;
 
 mod operation;
@@ -16,6 +16,7 @@
 // under the License.
 
 use std::collections::VecDeque;
+use std::io::IoSliceMut;
 use std::io::Read;
 use std::io::SeekFrom;
 use std::task::Context;
@@ -73,6 +74,20 @@ impl oio::Read for Cursor {
         Poll::Ready(Ok(n))
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        _: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        let n = Read::read_vectored(&mut self.remaining_slice(), bufs).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "read data from Cursor")
+                .with_context("source", "Cursor")
+                .set_source(err)
+        })?;
+        self.pos += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
     fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
         let (base, amt) = match pos {
             SeekFrom::Start(n) => (0, n as i64),
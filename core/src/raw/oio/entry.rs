@@ -25,12 +25,23 @@ use crate::*;
 ///
 /// - `crate::Entry` is the user's public API and have less public methods.
 /// - `oio::Entry` is the raw API and doesn't expose to users.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Entry {
     path: String,
     meta: Metadata,
+    /// The operator this entry is listed from, only set by
+    /// `Operator::merge_list` so that later metadata lookups go to it.
+    source: Option<Operator>,
 }
 
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.meta == other.meta
+    }
+}
+
+impl Eq for Entry {}
+
 impl Entry {
     /// Create a new entry by its corresponding underlying storage.
     pub fn new(path: &str, meta: Metadata) -> Entry {
@@ -46,7 +57,11 @@ impl Entry {
             path
         );
 
-        Entry { path, meta }
+        Entry {
+            path,
+            meta,
+            source: None,
+        }
     }
 
     /// Set path for entry.
@@ -75,10 +90,21 @@ impl Entry {
         self.meta.mode()
     }
 
+    /// Get entry's metadata.
+    pub(crate) fn metadata(&self) -> &Metadata {
+        &self.meta
+    }
+
+    /// Set the operator this entry is listed from.
+    pub(crate) fn set_source(&mut self, op: Operator) -> &mut Self {
+        self.source = Some(op);
+        self
+    }
+
     /// Consume self to convert into an Entry.
     ///
     /// NOTE: implement this by hand to avoid leaking raw entry to end-users.
     pub(crate) fn into_entry(self) -> crate::Entry {
-        crate::Entry::new_with(self.path, self.meta)
+        crate::Entry::new_with(self.path, self.meta, self.source)
     }
 }
@@ -37,12 +37,16 @@ use crate::*;
 ///
 /// This operation is not zero cost. If the accessor already returns a
 /// seekable reader, please don't use this.
+///
+/// If `size` is `None`, reader will read till the end of file. The size
+/// will be fetched by `stat` at the first `SeekFrom::End` and cached for
+/// later seeks.
 pub fn by_range<A: Accessor>(
     acc: Arc<A>,
     path: &str,
     reader: A::Reader,
     offset: u64,
-    size: u64,
+    size: Option<u64>,
 ) -> RangeReader<A> {
     RangeReader {
         acc,
@@ -53,6 +57,7 @@ pub fn by_range<A: Accessor>(
         state: State::Reading(reader),
         last_seek_pos: None,
         sink: Vec::new(),
+        stat_fut: None,
     }
 }
 
@@ -62,7 +67,7 @@ pub struct RangeReader<A: Accessor> {
     path: String,
 
     offset: u64,
-    size: u64,
+    size: Option<u64>,
     cur: u64,
     state: State<A::Reader>,
 
@@ -74,6 +79,8 @@ pub struct RangeReader<A: Accessor> {
     last_seek_pos: Option<u64>,
     /// sink is to consume bytes for seek optimize.
     sink: Vec<u8>,
+    /// The stat future to fetch size for `SeekFrom::End`.
+    stat_fut: Option<BoxFuture<'static, Result<RpStat>>>,
 }
 
 enum State<R: oio::Read> {
@@ -85,18 +92,44 @@ enum State<R: oio::Read> {
 /// Safety: State will only be accessed under &mut.
 unsafe impl<R: oio::Read> Sync for State<R> {}
 
+/// Safety: RangeReader will only be accessed under &mut, `stat_fut` is
+/// never shared.
+unsafe impl<A: Accessor> Sync for RangeReader<A> {}
+
 impl<A: Accessor> RangeReader<A> {
     fn read_future(&self) -> BoxFuture<'static, Result<(RpRead, A::Reader)>> {
         let acc = self.acc.clone();
         let path = self.path.clone();
         let op = OpRead::default().with_range(BytesRange::new(
             Some(self.offset + self.cur),
-            Some(self.size - self.cur),
+            self.size.map(|size| size - self.cur),
         ));
 
         Box::pin(async move { acc.read(&path, op).await })
     }
 
+    /// Fetch the size of range via stat if it's unknown.
+    ///
+    /// Size is cached so that only the first `SeekFrom::End` sends stat.
+    fn poll_size(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64>> {
+        if let Some(size) = self.size {
+            return Poll::Ready(Ok(size));
+        }
+
+        let fut = self.stat_fut.get_or_insert_with(|| {
+            let acc = self.acc.clone();
+            let path = self.path.clone();
+            Box::pin(async move { acc.stat(&path, OpStat::new()).await })
+        });
+        let res = ready!(fut.as_mut().poll(cx));
+        self.stat_fut = None;
+
+        let total = res?.into_metadata().content_length();
+        let size = total.saturating_sub(self.offset);
+        self.size = Some(size);
+        Poll::Ready(Ok(size))
+    }
+
     /// calculate the seek position.
     ///
     /// This operation will not update the `self.cur`.
@@ -107,7 +140,7 @@ impl<A: Accessor> RangeReader<A> {
 
         let (base, amt) = match pos {
             SeekFrom::Start(n) => (0, n as i64),
-            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::End(n) => (self.size.expect("size must be known") as i64, n),
             SeekFrom::Current(n) => (self.cur as i64, n),
         };
 
@@ -128,7 +161,7 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
         match &mut self.state {
             State::Idle => {
-                if self.cur >= self.size {
+                if self.size.map_or(false, |size| self.cur >= size) {
                     return Poll::Ready(Ok(0));
                 }
 
@@ -168,6 +201,10 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
     }
 
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        if let SeekFrom::End(_) = pos {
+            ready!(self.poll_size(cx))?;
+        }
+
         let seek_pos = self.seek_pos(pos)?;
         self.last_seek_pos = Some(seek_pos);
 
@@ -236,7 +273,7 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<bytes::Bytes>>> {
         match &mut self.state {
             State::Idle => {
-                if self.cur >= self.size {
+                if self.size.map_or(false, |size| self.cur >= size) {
                     return Poll::Ready(None);
                 }
 
@@ -278,6 +315,8 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
 #[cfg(test)]
 mod tests {
     use std::io::SeekFrom;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
 
     use async_trait::async_trait;
     use bytes::Bytes;
@@ -304,11 +343,15 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockReadService {
         data: Bytes,
+        stats: Arc<AtomicUsize>,
     }
 
     impl MockReadService {
         fn new(data: Bytes) -> Self {
-            Self { data }
+            Self {
+                data,
+                stats: Arc::default(),
+            }
         }
     }
 
@@ -332,6 +375,14 @@ mod tests {
             am
         }
 
+        async fn stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            self.stats.fetch_add(1, Ordering::SeqCst);
+
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(self.data.len() as u64),
+            ))
+        }
+
         async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
             let bs = args.range().apply_on_bytes(self.data.clone());
 
@@ -388,7 +439,7 @@ mod tests {
         let r = MockReader {
             inner: futures::io::Cursor::new(bs.to_vec()),
         };
-        let mut r = Box::new(by_range(acc, "x", r, 0, bs.len() as u64)) as oio::Reader;
+        let mut r = Box::new(by_range(acc, "x", r, 0, Some(bs.len() as u64))) as oio::Reader;
 
         let mut buf = Vec::new();
         r.read_to_end(&mut buf).await?;
@@ -422,7 +473,7 @@ mod tests {
         let r = MockReader {
             inner: futures::io::Cursor::new(bs[4096..4096 + 4096].to_vec()),
         };
-        let mut r = Box::new(by_range(acc, "x", r, 4096, 4096)) as oio::Reader;
+        let mut r = Box::new(by_range(acc, "x", r, 4096, Some(4096))) as oio::Reader;
 
         let mut buf = Vec::new();
         r.read_to_end(&mut buf).await?;
@@ -469,4 +520,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_seek_from_end_with_unknown_size() -> anyhow::Result<()> {
+        let (bs, size) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: futures::io::Cursor::new(bs.to_vec()),
+        };
+        let mut r = Box::new(by_range(acc.clone(), "x", r, 0, None)) as oio::Reader;
+
+        // Read the footer like parquet: length first, then the content.
+        let n = r.seek(SeekFrom::End(-8)).await?;
+        assert_eq!(n, size as u64 - 8);
+        let mut footer = vec![0; 8];
+        r.read_exact(&mut footer).await?;
+        assert_eq!(footer, bs[size - 8..]);
+
+        let n = r.seek(SeekFrom::End(-(8 + 4096))).await?;
+        assert_eq!(n, size as u64 - 8 - 4096);
+        let mut buf = vec![0; 4096];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(buf, bs[size - 8 - 4096..size - 8]);
+
+        assert_eq!(acc.stats.load(Ordering::SeqCst), 1, "size must be cached");
+
+        Ok(())
+    }
 }
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io::IoSliceMut;
 use std::io::SeekFrom;
 use std::task::ready;
 use std::task::Context;
@@ -47,6 +48,14 @@ impl<R: oio::Read> oio::Read for IntoStreamableReader<R> {
         self.r.poll_read(cx, buf)
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        self.r.poll_read_vectored(cx, bufs)
+    }
+
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
         self.r.poll_seek(cx, pos)
     }
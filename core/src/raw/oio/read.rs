@@ -18,6 +18,7 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::io::IoSliceMut;
 use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::task::Context;
@@ -92,6 +93,35 @@ pub trait Read: Unpin + Send + Sync {
     /// Read bytes asynchronously.
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>>;
 
+    /// Read bytes into multiple buffers asynchronously.
+    ///
+    /// Readers that can fill multiple buffers at once should override this.
+    /// The default implementation fills buffers sequentially via
+    /// [`Read::poll_read`] and returns as soon as a buffer is partially
+    /// filled or the underlying reader is not ready.
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        let mut total = 0;
+        for buf in bufs.iter_mut().filter(|b| !b.is_empty()) {
+            match self.poll_read(cx, buf) {
+                Poll::Ready(Ok(n)) => {
+                    total += n;
+                    if n < buf.len() {
+                        break;
+                    }
+                }
+                // Bytes have been read, return them first and let the
+                // error or pending show up in the next call.
+                Poll::Ready(Err(_)) | Poll::Pending if total > 0 => break,
+                res => return res,
+            }
+        }
+        Poll::Ready(Ok(total))
+    }
+
     /// Seek asynchronously.
     ///
     /// Returns `Unsupported` error if underlying reader doesn't support seek.
@@ -140,6 +170,14 @@ impl<T: Read + ?Sized> Read for Box<T> {
         (**self).poll_read(cx, buf)
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        (**self).poll_read_vectored(cx, bufs)
+    }
+
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
         (**self).poll_seek(cx, pos)
     }
@@ -159,6 +197,16 @@ impl futures::AsyncRead for dyn Read {
         this.poll_read(cx, buf)
             .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
     }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let this: &mut dyn Read = &mut *self;
+        this.poll_read_vectored(cx, bufs)
+            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+    }
 }
 
 impl futures::AsyncSeek for dyn Read {
@@ -42,6 +42,7 @@ pub fn to_flat_pager<A: Accessor, P>(acc: A, path: &str, size: usize) -> ToFlatP
         dirs: VecDeque::from([oio::Entry::new(path, Metadata::new(EntryMode::DIR))]),
         pagers: vec![],
         res: Vec::with_capacity(size),
+        prune: None,
     }
 }
 
@@ -88,6 +89,20 @@ pub struct ToFlatPager<A: Accessor, P> {
     dirs: VecDeque<oio::Entry>,
     pagers: Vec<(P, oio::Entry, Vec<oio::Entry>)>,
     res: Vec<oio::Entry>,
+    prune: Option<ListPrune>,
+}
+
+impl<A: Accessor, P> ToFlatPager<A, P> {
+    /// Skip listing dirs that the predicate returns `true` for, the dirs
+    /// themselves will still be returned.
+    pub fn with_prune(mut self, prune: Option<ListPrune>) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    fn is_pruned(&self, de: &oio::Entry) -> bool {
+        self.prune.as_ref().map_or(false, |prune| prune(de.path()))
+    }
 }
 
 #[async_trait]
@@ -131,7 +146,9 @@ where
             let mut buf = VecDeque::from(buf);
             loop {
                 if let Some(oe) = buf.pop_front() {
-                    if oe.mode().is_dir() {
+                    if oe.mode().is_dir() && self.is_pruned(&oe) {
+                        self.res.push(oe)
+                    } else if oe.mode().is_dir() {
                         self.dirs.push_back(oe);
                         self.pagers.push((pager, de, buf.into()));
                         break;
@@ -191,7 +208,9 @@ where
             let mut buf = VecDeque::from(buf);
             loop {
                 if let Some(oe) = buf.pop_front() {
-                    if oe.mode().is_dir() {
+                    if oe.mode().is_dir() && self.is_pruned(&oe) {
+                        self.res.push(oe)
+                    } else if oe.mode().is_dir() {
                         self.dirs.push_back(oe);
                         self.pagers.push((pager, de, buf.into()));
                         break;
@@ -214,6 +233,7 @@ where
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::sync::Arc;
     use std::vec;
 
     use log::debug;
@@ -315,4 +335,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blocking_list_with_prune() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mut acc = MockService::new();
+        // Listing pruned dirs will panic.
+        acc.map.retain(|k, _| *k == "x/");
+        let mut pager =
+            to_flat_pager(acc, "x/", 10).with_prune(Some(Arc::new(|dir| dir == "x/x/")));
+
+        let mut entries = Vec::default();
+
+        while let Some(e) = pager.next()? {
+            entries.extend_from_slice(&e)
+        }
+
+        assert_eq!(
+            entries,
+            vec![oio::Entry::new("x/x/", Metadata::new(EntryMode::DIR))]
+        );
+
+        Ok(())
+    }
 }
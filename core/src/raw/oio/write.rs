@@ -31,6 +31,8 @@ pub enum WriteOperation {
     Write,
     /// Operation for [`Write::abort`]
     Abort,
+    /// Operation for [`Write::flush`]
+    Flush,
     /// Operation for [`Write::close`]
     Close,
     /// Operation for [`BlockingWrite::write`]
@@ -59,6 +61,7 @@ impl From<WriteOperation> for &'static str {
         match v {
             Write => "Writer::write",
             Abort => "Writer::abort",
+            Flush => "Writer::flush",
             Close => "Writer::close",
             BlockingWrite => "BlockingWriter::write",
             BlockingClose => "BlockingWriter::close",
@@ -96,6 +99,18 @@ pub trait Write: Unpin + Send + Sync {
     /// Abort the pending writer.
     async fn abort(&mut self) -> Result<()>;
 
+    /// Flush buffered data into storage without finishing the writer.
+    ///
+    /// Services that buffer data internally (like multipart uploads)
+    /// should send all buffered data to storage while keeping the writer
+    /// available for following writes.
+    ///
+    /// The default implementation is a no-op which fits services that
+    /// don't buffer data or can only commit the whole content at once.
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     /// Close the writer and make sure all data has been flushed.
     async fn close(&mut self) -> Result<()>;
 }
@@ -136,6 +151,10 @@ impl<T: Write + ?Sized> Write for Box<T> {
         (**self).abort().await
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        (**self).flush().await
+    }
+
     async fn close(&mut self) -> Result<()> {
         (**self).close().await
     }
@@ -47,6 +47,22 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Accessor::presign`]
     Presign,
+    /// Operation for [`crate::raw::Accessor::select`]
+    Select,
+    /// Operation for [`crate::raw::Accessor::retention`]
+    Retention,
+    /// Operation for [`crate::raw::Accessor::legal_hold`]
+    LegalHold,
+    /// Operation for [`crate::raw::Accessor::acl`]
+    Acl,
+    /// Operation for [`crate::raw::Accessor::write_range`]
+    WriteRange,
+    /// Operation for [`crate::raw::Accessor::finalize_ranged_write`]
+    FinalizeRangedWrite,
+    /// Operation for [`crate::raw::Accessor::delete_all_versions`]
+    DeleteAllVersions,
+    /// Operation for [`crate::raw::Accessor::list_versions`]
+    ListVersions,
     /// Operation for [`crate::raw::Accessor::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Accessor::blocking_read`]
@@ -93,6 +109,14 @@ impl From<Operation> for &'static str {
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::Select => "select",
+            Operation::Retention => "retention",
+            Operation::LegalHold => "legal_hold",
+            Operation::Acl => "acl",
+            Operation::WriteRange => "write_range",
+            Operation::FinalizeRangedWrite => "finalize_ranged_write",
+            Operation::DeleteAllVersions => "delete_all_versions",
+            Operation::ListVersions => "list_versions",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::EntryMode;
+use crate::*;
 
 /// build_abs_path will build an absolute path with root.
 ///
@@ -222,6 +222,32 @@ pub fn validate_path(path: &str, mode: EntryMode) -> bool {
     }
 }
 
+/// Validate given key with the key constraints declared in [`Capability`].
+///
+/// Returns [`ErrorKind::ConfigInvalid`] with the violated rule if key is invalid.
+pub fn validate_key(cap: &Capability, path: &str) -> Result<()> {
+    let invalid = |rule: String| {
+        Error::new(ErrorKind::ConfigInvalid, &format!("key is invalid: {rule}"))
+            .with_context("path", path)
+    };
+
+    if let Some(max) = cap.key_max_len {
+        if path.len() > max {
+            return Err(invalid(format!("length exceeds {max} bytes")));
+        }
+    }
+    if let Some(max) = cap.key_max_segment_len {
+        if path.split('/').any(|v| v.len() > max) {
+            return Err(invalid(format!("segment length exceeds {max} bytes")));
+        }
+    }
+    if cap.key_forbid_control_chars && path.chars().any(|c| c.is_control()) {
+        return Err(invalid("control characters are not allowed".to_string()));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,6 +377,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_key() {
+        let cap = Capability {
+            key_max_len: Some(8),
+            key_max_segment_len: Some(4),
+            key_forbid_control_chars: true,
+            ..Default::default()
+        };
+
+        assert!(validate_key(&cap, "abc/def").is_ok());
+        assert!(validate_key(&Capability::default(), "abcdefghi\t").is_ok());
+
+        let cases = vec![
+            ("too long", "abc/def/g", "length exceeds 8 bytes"),
+            (
+                "segment too long",
+                "abcde",
+                "segment length exceeds 4 bytes",
+            ),
+            ("control char", "ab\tc", "control characters"),
+        ];
+        for (name, input, expected) in cases {
+            let err = validate_key(&cap, input).expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{name}");
+            assert!(err.to_string().contains(expected), "{name}: {err}");
+        }
+    }
+
     #[test]
     fn test_validate_path() {
         let cases = vec![
@@ -15,8 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use chrono::DateTime;
+use chrono::Utc;
 use http::Request;
 
+use crate::ops::ObjectRetention;
 use crate::*;
 
 /// Reply for `create_dir` operation
@@ -31,6 +34,199 @@ pub struct RpDelete {}
 #[derive(Debug, Clone, Default)]
 pub struct RpList {}
 
+/// Reply for `select` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSelect {}
+
+/// Reply for `retention` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpRetention {
+    retention: Option<ObjectRetention>,
+}
+
+impl RpRetention {
+    /// Create a new reply for `retention`.
+    ///
+    /// `None` means the object doesn't have retention.
+    pub fn new(retention: Option<ObjectRetention>) -> Self {
+        Self { retention }
+    }
+
+    /// Consume reply to get the retention of object.
+    pub fn into_retention(self) -> Option<ObjectRetention> {
+        self.retention
+    }
+}
+
+/// Reply for `legal_hold` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpLegalHold {
+    enabled: bool,
+}
+
+impl RpLegalHold {
+    /// Create a new reply for `legal_hold`.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Whether the legal hold of object is on.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Reply for `write_range` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpWriteRange {}
+
+/// Reply for `finalize_ranged_write` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpFinalizeRangedWrite {}
+
+/// Reply for `delete_all_versions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpDeleteAllVersions {
+    deleted: usize,
+}
+
+impl RpDeleteAllVersions {
+    /// Create a new reply for `delete_all_versions`.
+    pub fn new(deleted: usize) -> Self {
+        Self { deleted }
+    }
+
+    /// Get the count of deleted versions.
+    pub fn deleted(&self) -> usize {
+        self.deleted
+    }
+}
+
+/// Reply for `list_versions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListVersions {
+    versions: Vec<ObjectVersion>,
+}
+
+impl RpListVersions {
+    /// Create a new reply for `list_versions`.
+    pub fn new(versions: Vec<ObjectVersion>) -> Self {
+        Self { versions }
+    }
+
+    /// Get the listed versions.
+    pub fn versions(&self) -> &[ObjectVersion] {
+        &self.versions
+    }
+
+    /// Consume reply to get the listed versions.
+    pub fn into_versions(self) -> Vec<ObjectVersion> {
+        self.versions
+    }
+}
+
+/// A version or delete marker of an object returned by `list_versions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectVersion {
+    path: String,
+    version: String,
+    last_modified: DateTime<Utc>,
+    content_length: u64,
+    delete_marker: bool,
+}
+
+impl ObjectVersion {
+    /// Create a new version of path.
+    pub fn new(path: &str, version: &str, last_modified: DateTime<Utc>) -> Self {
+        Self {
+            path: path.to_string(),
+            version: version.to_string(),
+            last_modified,
+            content_length: 0,
+            delete_marker: false,
+        }
+    }
+
+    /// Set the content length of this version.
+    pub fn with_content_length(mut self, content_length: u64) -> Self {
+        self.content_length = content_length;
+        self
+    }
+
+    /// Mark this version as a delete marker.
+    pub fn with_delete_marker(mut self, delete_marker: bool) -> Self {
+        self.delete_marker = delete_marker;
+        self
+    }
+
+    /// Set path of the object.
+    pub fn set_path(&mut self, path: &str) -> &mut Self {
+        self.path = path.to_string();
+        self
+    }
+
+    /// Path of the object.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Opaque version id used by [`OpRead::with_version`].
+    ///
+    /// [`OpRead::with_version`]: crate::ops::OpRead::with_version
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Time when this version became current.
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        self.last_modified
+    }
+
+    /// Content length of this version, `0` for delete markers.
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// Whether this version is a delete marker, which means the object
+    /// doesn't exist since [`ObjectVersion::last_modified`].
+    pub fn is_delete_marker(&self) -> bool {
+        self.delete_marker
+    }
+}
+
+/// Reply for `acl` operation.
+#[derive(Debug, Clone)]
+pub struct RpAcl {
+    summary: AclSummary,
+    owner: Option<String>,
+}
+
+impl RpAcl {
+    /// Create a new reply for `acl`.
+    pub fn new(summary: AclSummary) -> Self {
+        Self {
+            summary,
+            owner: None,
+        }
+    }
+
+    /// Get the summary of the object's ACL.
+    pub fn summary(&self) -> AclSummary {
+        self.summary
+    }
+
+    /// Set the owner of the object.
+    pub fn with_owner(mut self, owner: String) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Get the owner of the object.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+}
+
 /// Reply for `presign` operation.
 #[derive(Debug, Clone)]
 pub struct RpPresign {
@@ -32,8 +32,11 @@ use super::batch::parse_batch_delete_response;
 use super::error::parse_error;
 use super::pager::AzblobPager;
 use super::writer::AzblobWriter;
+use super::writer::PAGE_SIZE;
 use crate::ops::*;
 use crate::raw::*;
+use crate::services::azblob::core::parse_creation_time;
+use crate::services::azblob::core::parse_encryption;
 use crate::services::azblob::core::AzblobCore;
 use crate::types::Metadata;
 use crate::*;
@@ -258,6 +261,14 @@ impl AzblobBuilder {
 impl Builder for AzblobBuilder {
     const SCHEME: Scheme = Scheme::Azblob;
     type Accessor = AzblobBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "container",
+        "endpoint",
+        "account_name",
+        "account_key",
+        "sas_token",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = AzblobBuilder::default();
@@ -397,6 +408,7 @@ impl Accessor for AzblobBackend {
                 write: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
+                write_with_blob_type: true,
 
                 delete: true,
                 create_dir: true,
@@ -415,12 +427,29 @@ impl Accessor for AzblobBackend {
                 batch_delete: true,
                 batch_max_operations: Some(AZBLOB_BATCH_LIMIT),
 
+                key_max_len: Some(1024),
+
                 ..Default::default()
             });
 
         am
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        let key = build_abs_path(&self.core.root, path);
+        validate_key(&self.info().capability(), &key)?;
+
+        // Azure strips the trailing dots of blob name silently.
+        if key.trim_end_matches('/').ends_with('.') {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "key is invalid: blob name must not end with dot",
+            )
+            .with_context("path", path));
+        }
+        Ok(())
+    }
+
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let mut req =
             self.core
@@ -473,6 +502,28 @@ impl Accessor for AzblobBackend {
             ));
         }
 
+        match args.blob_type() {
+            Some(BlobType::Append | BlobType::Page)
+                if !args.resume_parts().is_empty()
+                    || args.adaptive_parts()
+                    || args.part_callback().is_some() =>
+            {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "multipart write is only supported by block blob",
+                )
+                .with_context("blob_type", format!("{:?}", args.blob_type())));
+            }
+            Some(BlobType::Page) if args.content_length().unwrap_or_default() % PAGE_SIZE != 0 => {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "content length of page blob must be aligned to 512 bytes",
+                )
+                .with_context("content_length", format!("{:?}", args.content_length())));
+            }
+            _ => {}
+        }
+
         Ok((
             RpWrite::default(),
             AzblobWriter::new(self.core.clone(), args, path.to_string()),
@@ -507,7 +558,16 @@ impl Accessor for AzblobBackend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut m = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_encryption(resp.headers())? {
+                    m.set_encryption(v);
+                }
+                if let Some(v) = parse_creation_time(resp.headers())? {
+                    m.set_created(v);
+                }
+                Ok(RpStat::new(m))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -628,6 +688,8 @@ impl Accessor for AzblobBackend {
 #[cfg(test)]
 mod tests {
     use super::AzblobBuilder;
+    use crate::ops::BlobType;
+    use crate::ops::OpWrite;
     use crate::services::azblob::backend::infer_storage_name_from_endpoint;
     use crate::Builder;
 
@@ -791,4 +853,240 @@ SharedAccessSignature=sv=2021-01-01&ss=b&srt=c&sp=rwdlaciytfx&se=2022-01-01T11:0
         assert_eq!(builder.account_name, None);
         assert_eq!(builder.account_key, None);
     }
+
+    #[tokio::test]
+    async fn test_validate_key() {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint("http://127.0.0.1:10000/devstoreaccount1")
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key("account-key");
+        let op = crate::Operator::new(builder)
+            .expect("build azblob must succeed")
+            .finish();
+
+        let err = op.write(&"a".repeat(1025), "x").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::ConfigInvalid);
+        let err = op.write("trailing.", "x").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::ConfigInvalid);
+        let err = op.create_dir("dir./").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_stat_created() -> crate::Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let created = "Thu, 01 Sep 2022 07:26:49 GMT";
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/container/blob"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("last-modified", created)
+                    .insert_header("x-ms-creation-time", created),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        // The blob has been overwritten.
+        Mock::given(method("HEAD"))
+            .and(path("/container/blob"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "6")
+                    .insert_header("last-modified", "Fri, 02 Sep 2022 08:00:00 GMT")
+                    .insert_header("x-ms-creation-time", created),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/blob"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key("YWNjb3VudC1rZXk=");
+        let op = crate::Operator::new(builder)?.finish();
+
+        let created = crate::raw::parse_datetime_from_rfc2822(created)?;
+        let meta = op.stat("blob").await?;
+        assert_eq!(meta.created(), Some(created));
+        assert_eq!(meta.last_modified(), Some(created));
+
+        op.write("blob", "second").await?;
+        let meta = op.stat("blob").await?;
+        assert_eq!(meta.created(), Some(created));
+        assert!(meta.last_modified().unwrap() > created);
+
+        Ok(())
+    }
+
+    fn blob_type_operator(mock_server: &wiremock::MockServer) -> crate::Result<crate::Operator> {
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&mock_server.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key("YWNjb3VudC1rZXk=");
+        Ok(crate::Operator::new(builder)?.finish())
+    }
+
+    #[tokio::test]
+    async fn test_write_block_blob() -> crate::Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/blob"))
+            .and(header("x-ms-blob-type", "BlockBlob"))
+            .and(header("content-length", "5"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let op = blob_type_operator(&mock_server)?;
+        op.write_with(
+            "blob",
+            OpWrite::new().with_blob_type(BlobType::Block),
+            "hello",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_append_blob() -> crate::Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/log"))
+            .and(query_param("comp", "appendblock"))
+            .and(header("x-ms-blob-condition-appendpos", "0"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/log"))
+            .and(query_param("comp", "appendblock"))
+            .and(header("x-ms-blob-condition-appendpos", "6"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/log"))
+            .and(header("x-ms-blob-type", "AppendBlob"))
+            .and(header("content-length", "0"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let op = blob_type_operator(&mock_server)?;
+        let mut w = op
+            .writer_with(
+                "log",
+                OpWrite::new()
+                    .with_content_length(11)
+                    .with_blob_type(BlobType::Append),
+            )
+            .await?;
+        w.write("hello ").await?;
+        w.write("world").await?;
+        w.close().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_page_blob() -> crate::Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/container/disk.vhd"))
+            .and(query_param("comp", "page"))
+            .and(header("x-ms-page-write", "update"))
+            .and(header("x-ms-range", "bytes=0-1023"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/container/disk.vhd"))
+            .and(header("x-ms-blob-type", "PageBlob"))
+            .and(header("x-ms-blob-content-length", "1024"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let op = blob_type_operator(&mock_server)?;
+        op.write_with(
+            "disk.vhd",
+            OpWrite::new().with_blob_type(BlobType::Page),
+            vec![0; 1024],
+        )
+        .await?;
+
+        // Page blobs must be aligned to 512 bytes.
+        let err = op
+            .write_with(
+                "disk.vhd",
+                OpWrite::new().with_blob_type(BlobType::Page),
+                vec![0; 1000],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::ConfigInvalid);
+
+        // Multipart args are only supported by block blobs.
+        let err = op
+            .write_with(
+                "disk.vhd",
+                OpWrite::new()
+                    .with_blob_type(BlobType::Page)
+                    .with_adaptive_parts(true),
+                vec![0; 1024],
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::ConfigInvalid);
+
+        Ok(())
+    }
 }
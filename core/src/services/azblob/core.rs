@@ -21,17 +21,21 @@ use std::fmt::Formatter;
 use std::fmt::Write;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
 use http::Request;
 use http::Response;
 use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 
+use crate::ops::BlobType;
 use crate::raw::*;
 use crate::*;
 
@@ -39,6 +43,32 @@ mod constants {
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_SERVER_ENCRYPTED: &str = "x-ms-server-encrypted";
+    pub const X_MS_ENCRYPTION_KEY_SHA256: &str = "x-ms-encryption-key-sha256";
+    pub const X_MS_CREATION_TIME: &str = "x-ms-creation-time";
+    pub const X_MS_BLOB_CONTENT_LENGTH: &str = "x-ms-blob-content-length";
+    pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
+    pub const X_MS_PAGE_WRITE: &str = "x-ms-page-write";
+    pub const X_MS_RANGE: &str = "x-ms-range";
+}
+
+/// Parse creation time of blob from response headers.
+pub fn parse_creation_time(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>> {
+    parse_header_to_str(headers, constants::X_MS_CREATION_TIME)?
+        .map(parse_datetime_from_rfc2822)
+        .transpose()
+}
+
+/// Parse server side encryption of blob from response headers.
+pub fn parse_encryption(headers: &HeaderMap) -> Result<Option<EncryptionInfo>> {
+    if parse_header_to_str(headers, constants::X_MS_ENCRYPTION_KEY_SHA256)?.is_some() {
+        return Ok(Some(EncryptionInfo::SseC));
+    }
+
+    match parse_header_to_str(headers, constants::X_MS_SERVER_ENCRYPTED)? {
+        Some("true") => Ok(Some(EncryptionInfo::SseS3)),
+        _ => Ok(None),
+    }
 }
 
 pub struct AzblobCore {
@@ -196,6 +226,49 @@ impl AzblobCore {
         cache_control: Option<&str>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
+        let mut req =
+            self.put_blob_request_builder(path, BlobType::Block, content_type, cache_control);
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size)
+        }
+
+        // Set body
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    /// Create an empty append or page blob, page blobs must be created
+    /// with their size.
+    pub fn azblob_create_blob_request(
+        &self,
+        path: &str,
+        blob_type: BlobType,
+        size: Option<u64>,
+        content_type: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> Result<Request<AsyncBody>> {
+        let mut req = self
+            .put_blob_request_builder(path, blob_type, content_type, cache_control)
+            .header(CONTENT_LENGTH, 0);
+        if let Some(size) = size {
+            req = req.header(constants::X_MS_BLOB_CONTENT_LENGTH, size);
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    fn put_blob_request_builder(
+        &self,
+        path: &str,
+        blob_type: BlobType,
+        content_type: Option<&str>,
+        cache_control: Option<&str>,
+    ) -> http::request::Builder {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -209,21 +282,77 @@ impl AzblobCore {
         if let Some(cache_control) = cache_control {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
-        if let Some(size) = size {
-            req = req.header(CONTENT_LENGTH, size)
-        }
 
         if let Some(ty) = content_type {
             req = req.header(CONTENT_TYPE, ty)
         }
 
-        req = req.header(
+        let blob_type = match blob_type {
+            BlobType::Block => "BlockBlob",
+            BlobType::Append => "AppendBlob",
+            BlobType::Page => "PageBlob",
+        };
+        req.header(
             HeaderName::from_static(constants::X_MS_BLOB_TYPE),
-            "BlockBlob",
+            blob_type,
+        )
+    }
+
+    /// Append a block at `offset` to the end of an append blob.
+    ///
+    /// The block will be rejected if the blob's length isn't `offset`,
+    /// so that a retried append will not be applied twice.
+    pub fn azblob_append_block_request(
+        &self,
+        path: &str,
+        offset: u64,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=appendblock",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
         );
 
-        // Set body
-        let req = req.body(body).map_err(new_request_build_error)?;
+        let req = Request::put(&url)
+            .header(CONTENT_LENGTH, size)
+            .header(constants::X_MS_BLOB_CONDITION_APPENDPOS, offset)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    /// Write pages of `size` bytes at `offset` into a page blob.
+    pub fn azblob_put_page_request(
+        &self,
+        path: &str,
+        offset: u64,
+        size: u64,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=page",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::put(&url)
+            .header(CONTENT_LENGTH, size)
+            .header(constants::X_MS_PAGE_WRITE, "update")
+            .header(
+                constants::X_MS_RANGE,
+                BytesRange::new(Some(offset), Some(size)).to_header(),
+            )
+            .body(body)
+            .map_err(new_request_build_error)?;
 
         Ok(req)
     }
@@ -107,7 +107,7 @@ impl oio::Page for AzblobPager {
                 continue;
             }
 
-            let meta = Metadata::new(EntryMode::FILE)
+            let mut meta = Metadata::new(EntryMode::FILE)
                 // Keep fit with ETag header.
                 .with_etag(format!("\"{}\"", object.properties.etag.as_str()))
                 .with_content_length(object.properties.content_length)
@@ -116,6 +116,11 @@ impl oio::Page for AzblobPager {
                 .with_last_modified(parse_datetime_from_rfc2822(
                     object.properties.last_modified.as_str(),
                 )?);
+            if !object.properties.creation_time.is_empty() {
+                meta.set_created(parse_datetime_from_rfc2822(
+                    object.properties.creation_time.as_str(),
+                )?);
+            }
 
             let de = oio::Entry::new(&build_rel_path(&self.core.root, &object.name), meta);
 
@@ -161,6 +166,8 @@ struct Properties {
     content_length: u64,
     #[serde(rename = "Last-Modified")]
     last_modified: String,
+    #[serde(rename = "Creation-Time")]
+    creation_time: String,
     #[serde(rename = "Content-MD5")]
     content_md5: String,
     #[serde(rename = "Content-Type")]
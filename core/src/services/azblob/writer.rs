@@ -19,31 +19,43 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use http::Request;
 use http::StatusCode;
 
 use super::core::AzblobCore;
 use super::error::parse_error;
+use crate::ops::BlobType;
 use crate::ops::OpWrite;
 use crate::raw::*;
 use crate::*;
 
+/// The max size of an append block or a put page request.
+const MAX_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+/// Page blobs are written in pages of 512 bytes.
+pub const PAGE_SIZE: u64 = 512;
+
 pub struct AzblobWriter {
     core: Arc<AzblobCore>,
 
     op: OpWrite,
     path: String,
+    /// The offset of next write for append and page blobs, `None` if the
+    /// blob has not been created yet.
+    offset: Option<u64>,
 }
 
 impl AzblobWriter {
     pub fn new(core: Arc<AzblobCore>, op: OpWrite, path: String) -> Self {
-        AzblobWriter { core, op, path }
+        AzblobWriter {
+            core,
+            op,
+            path,
+            offset: None,
+        }
     }
-}
 
-#[async_trait]
-impl oio::Write for AzblobWriter {
-    async fn write(&mut self, bs: Bytes) -> Result<()> {
-        let mut req = self.core.azblob_put_blob_request(
+    async fn put_block_blob(&self, bs: Bytes) -> Result<()> {
+        let req = self.core.azblob_put_blob_request(
             &self.path,
             Some(bs.len()),
             self.op.content_type(),
@@ -51,6 +63,78 @@ impl oio::Write for AzblobWriter {
             AsyncBody::Bytes(bs),
         )?;
 
+        self.send(req).await
+    }
+
+    /// Create the append or page blob before the first write.
+    async fn create_blob(&mut self, blob_type: BlobType) -> Result<u64> {
+        if let Some(offset) = self.offset {
+            return Ok(offset);
+        }
+
+        let size = match blob_type {
+            BlobType::Page => self.op.content_length(),
+            _ => None,
+        };
+        let req = self.core.azblob_create_blob_request(
+            &self.path,
+            blob_type,
+            size,
+            self.op.content_type(),
+            self.op.cache_control(),
+        )?;
+        self.send(req).await?;
+
+        self.offset = Some(0);
+        Ok(0)
+    }
+
+    async fn append_blocks(&mut self, bs: Bytes) -> Result<()> {
+        let mut offset = self.create_blob(BlobType::Append).await?;
+
+        for chunk in chunks(bs) {
+            let size = chunk.len() as u64;
+            let req = self.core.azblob_append_block_request(
+                &self.path,
+                offset,
+                size,
+                AsyncBody::Bytes(chunk),
+            )?;
+            self.send(req).await?;
+
+            offset += size;
+            self.offset = Some(offset);
+        }
+        Ok(())
+    }
+
+    async fn put_pages(&mut self, bs: Bytes) -> Result<()> {
+        if bs.len() as u64 % PAGE_SIZE != 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "write to page blob must be aligned to 512 bytes",
+            )
+            .with_context("size", bs.len().to_string()));
+        }
+        let mut offset = self.create_blob(BlobType::Page).await?;
+
+        for chunk in chunks(bs) {
+            let size = chunk.len() as u64;
+            let req = self.core.azblob_put_page_request(
+                &self.path,
+                offset,
+                size,
+                AsyncBody::Bytes(chunk),
+            )?;
+            self.send(req).await?;
+
+            offset += size;
+            self.offset = Some(offset);
+        }
+        Ok(())
+    }
+
+    async fn send(&self, mut req: Request<AsyncBody>) -> Result<()> {
         self.core.sign(&mut req).await?;
 
         let resp = self.core.send(req).await?;
@@ -65,12 +149,42 @@ impl oio::Write for AzblobWriter {
             _ => Err(parse_error(resp).await?),
         }
     }
+}
+
+/// Split bytes into chunks that could be sent in one request.
+fn chunks(mut bs: Bytes) -> Vec<Bytes> {
+    let mut chunks = Vec::with_capacity(bs.len() / MAX_BLOCK_SIZE + 1);
+    while bs.len() > MAX_BLOCK_SIZE {
+        chunks.push(bs.split_to(MAX_BLOCK_SIZE));
+    }
+    if !bs.is_empty() {
+        chunks.push(bs);
+    }
+    chunks
+}
+
+#[async_trait]
+impl oio::Write for AzblobWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        match self.op.blob_type().unwrap_or(BlobType::Block) {
+            BlobType::Block => self.put_block_blob(bs).await,
+            BlobType::Append => self.append_blocks(bs).await,
+            BlobType::Page => self.put_pages(bs).await,
+        }
+    }
 
     async fn abort(&mut self) -> Result<()> {
         Ok(())
     }
 
     async fn close(&mut self) -> Result<()> {
-        Ok(())
+        // Create the blob if nothing has been written.
+        match self.op.blob_type() {
+            Some(blob_type @ (BlobType::Append | BlobType::Page)) => {
+                self.create_blob(blob_type).await?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
     }
 }
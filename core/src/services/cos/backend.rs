@@ -203,6 +203,8 @@ impl CosBuilder {
 impl Builder for CosBuilder {
     const SCHEME: Scheme = Scheme::Cos;
     type Accessor = CosBackend;
+    const CONFIG_KEYS: &'static [&'static str] =
+        &["root", "bucket", "endpoint", "secret_id", "secret_key"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = CosBuilder::default();
@@ -57,6 +57,8 @@ use crate::*;
 /// # Configuration
 ///
 /// - `root`: Set the work dir for backend.
+/// - `atomic_write_dir`: Set the temp dir for atomic write.
+/// - `min_free_space`: Set the min free space (in bytes) that must be kept after write.
 ///
 /// Refer to [`FsBuilder`]'s public API docs for more information.
 ///
@@ -91,6 +93,9 @@ pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    min_free_space: Option<u64>,
+    /// Unparsable `min_free_space` from map, reported by `build`.
+    invalid_min_free_space: Option<String>,
 }
 
 impl FsBuilder {
@@ -127,11 +132,33 @@ impl FsBuilder {
 
         self
     }
+
+    /// Set the min free space (in bytes) that must be kept on the file
+    /// system after write.
+    ///
+    /// If set, `write` will check the available space of the file system
+    /// before writing. Writes that declare a content length which would
+    /// breach this threshold will be rejected with
+    /// [`ErrorKind::InsufficientStorage`] up front instead of failing in
+    /// the middle.
+    ///
+    /// # Notes
+    ///
+    /// - This check is fs specific and only works on unix platforms.
+    /// - Writes without content length (like [`Writer`] created without
+    ///   [`OpWrite::with_content_length`]) will not be checked.
+    /// - Space could still be consumed by others after our check.
+    pub fn min_free_space(&mut self, size: u64) -> &mut Self {
+        self.min_free_space = Some(size);
+
+        self
+    }
 }
 
 impl Builder for FsBuilder {
     const SCHEME: Scheme = Scheme::Fs;
     type Accessor = FsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "atomic_write_dir", "min_free_space"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = FsBuilder::default();
@@ -139,6 +166,14 @@ impl Builder for FsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("atomic_write_dir")
             .map(|v| builder.atomic_write_dir(v));
+        if let Some(v) = map.get("min_free_space") {
+            match v.parse::<u64>() {
+                Ok(v) => {
+                    builder.min_free_space(v);
+                }
+                Err(_) => builder.invalid_min_free_space = Some(v.clone()),
+            }
+        }
 
         builder
     }
@@ -146,6 +181,15 @@ impl Builder for FsBuilder {
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("backend build started: {:?}", &self);
 
+        if let Some(v) = self.invalid_min_free_space.take() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "min_free_space is not a valid number of bytes",
+            )
+            .with_operation("Builder::build")
+            .with_context("min_free_space", v));
+        }
+
         let root = match self.root.take() {
             Some(root) => Ok(root),
             None => Err(Error::new(
@@ -216,6 +260,8 @@ impl Builder for FsBuilder {
             root,
             atomic_write_dir,
             enable_path_check: self.enable_path_check,
+            min_free_space: self.min_free_space,
+            available_space,
         })
     }
 }
@@ -226,6 +272,37 @@ pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    min_free_space: Option<u64>,
+    /// Probe the available space of the file system that given path belongs to.
+    available_space: fn(&Path) -> std::io::Result<u64>,
+}
+
+/// Get the available space of the file system that given path belongs to.
+#[cfg(unix)]
+fn available_space(path: &Path) -> std::io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // Safety: path is a valid C string and stat is a valid pointer.
+    let ret = unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Safety: statvfs returns 0 which means stat has been filled.
+    let stat = unsafe { stat.assume_init() };
+
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// Available space is not supported on this platform, we will skip the check.
+#[cfg(not(unix))]
+fn available_space(_: &Path) -> std::io::Result<u64> {
+    Ok(u64::MAX)
 }
 
 #[inline]
@@ -237,6 +314,37 @@ fn tmp_file_of(path: &str) -> String {
 }
 
 impl FsBackend {
+    /// Check if there is enough space for writing `size` bytes into `path`
+    /// under `dir`.
+    ///
+    /// `dir` must exist, so that we can check before creating parent dirs
+    /// of path.
+    fn check_free_space(&self, dir: &Path, path: &str, size: Option<u64>) -> Result<()> {
+        let (min_free_space, size) = match (self.min_free_space, size) {
+            (Some(min_free_space), Some(size)) => (min_free_space, size),
+            _ => return Ok(()),
+        };
+
+        let available = (self.available_space)(dir).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "get available space failed")
+                .with_context("path", dir.to_string_lossy())
+                .set_source(err)
+        })?;
+
+        if available.saturating_sub(size) < min_free_space {
+            return Err(Error::new(
+                ErrorKind::InsufficientStorage,
+                "write would leave less free space than min_free_space",
+            )
+            .with_context("path", path)
+            .with_context("size", size.to_string())
+            .with_context("available", available.to_string())
+            .with_context("min_free_space", min_free_space.to_string()));
+        }
+
+        Ok(())
+    }
+
     // Synchronously build write path and ensure the parent dirs created
     fn blocking_ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
@@ -315,6 +423,7 @@ impl Accessor for FsBackend {
 
                 write: true,
                 write_without_content_length: true,
+                write_can_positional: true,
                 create_dir: true,
                 delete: true,
 
@@ -327,12 +436,27 @@ impl Accessor for FsBackend {
                 rename: true,
                 blocking: true,
 
+                key_max_segment_len: Some(255),
+
                 ..Default::default()
             });
 
         am
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        validate_key(&self.info().capability(), path)?;
+
+        if path.contains('\0') {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "key is invalid: nul character is not allowed",
+            )
+            .with_context("path", path));
+        }
+        Ok(())
+    }
+
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -415,7 +539,32 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // Positional write updates the file in place, atomic write dir can't be used.
+        if let Some(offset) = args.offset() {
+            self.check_free_space(&self.root, path, args.content_length())?;
+            let p = Self::ensure_write_abs_path(&self.root, path).await?;
+
+            let f = tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(false)
+                .open(&p)
+                .await
+                .map_err(parse_io_error)?;
+
+            return Ok((
+                RpWrite::new(),
+                FsWriter::new(p, None, f).with_offset(offset),
+            ));
+        }
+
+        self.check_free_space(
+            self.atomic_write_dir.as_ref().unwrap_or(&self.root),
+            path,
+            args.content_length(),
+        )?;
+
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
             let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
             let tmp_path =
@@ -497,13 +646,17 @@ impl Accessor for FsBackend {
         } else {
             EntryMode::Unknown
         };
-        let m = Metadata::new(mode)
+        let mut m = Metadata::new(mode)
             .with_content_length(meta.len())
             .with_last_modified(
                 meta.modified()
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        // Birth time is not available on all platforms and filesystems.
+        if let Ok(created) = meta.created() {
+            m.set_created(created.into());
+        }
 
         Ok(RpStat::new(m))
     }
@@ -615,7 +768,31 @@ impl Accessor for FsBackend {
         Ok((RpRead::new(end - start), r))
     }
 
-    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        // Positional write updates the file in place, atomic write dir can't be used.
+        if let Some(offset) = args.offset() {
+            self.check_free_space(&self.root, path, args.content_length())?;
+            let p = Self::blocking_ensure_write_abs_path(&self.root, path)?;
+
+            let f = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(false)
+                .open(&p)
+                .map_err(parse_io_error)?;
+
+            return Ok((
+                RpWrite::new(),
+                FsWriter::new(p, None, f).with_offset(offset),
+            ));
+        }
+
+        self.check_free_space(
+            self.atomic_write_dir.as_ref().unwrap_or(&self.root),
+            path,
+            args.content_length(),
+        )?;
+
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
             let target_path = Self::blocking_ensure_write_abs_path(&self.root, path)?;
             let tmp_path =
@@ -682,13 +859,17 @@ impl Accessor for FsBackend {
         } else {
             EntryMode::Unknown
         };
-        let m = Metadata::new(mode)
+        let mut m = Metadata::new(mode)
             .with_content_length(meta.len())
             .with_last_modified(
                 meta.modified()
                     .map(DateTime::from)
                     .map_err(parse_io_error)?,
             );
+        // Birth time is not available on all platforms and filesystems.
+        if let Ok(created) = meta.created() {
+            m.set_created(created.into());
+        }
 
         Ok(RpStat::new(m))
     }
@@ -735,6 +916,8 @@ impl Accessor for FsBackend {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
 
     #[test]
@@ -751,4 +934,102 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[tokio::test]
+    async fn test_min_free_space() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy()).min_free_space(1024);
+        let mut backend = builder.build()?;
+        // Simulate a file system that only has 4096 bytes available.
+        backend.available_space = |_| Ok(4096);
+
+        let op = OpWrite::new().with_content_length(4096 - 1024);
+        assert!(backend.write("ok", op).await.is_ok());
+
+        let op = OpWrite::new().with_content_length(4096 - 1024 + 1);
+        let err = backend.write("dir/too_large", op).await.err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InsufficientStorage);
+        // Parent dirs must not be created for rejected writes.
+        assert!(!root.join("dir").exists());
+        let err = backend
+            .blocking_write("too_large", OpWrite::new().with_content_length(4096))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::InsufficientStorage);
+
+        // Writes without content length will not be checked.
+        assert!(backend.write("unsized", OpWrite::new()).await.is_ok());
+
+        std::fs::remove_dir_all(root).map_err(parse_io_error)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_min_free_space() {
+        let map = HashMap::from([
+            ("root".to_string(), "/tmp".to_string()),
+            ("min_free_space".to_string(), "1GiB".to_string()),
+        ]);
+        let err = FsBuilder::from_map(map).build().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_available_space() {
+        let space = available_space(&std::env::temp_dir()).expect("statvfs must succeed");
+        assert!(space > 0);
+    }
+
+    #[tokio::test]
+    async fn test_validate_key() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+
+        let err = op
+            .write(&format!("dir/{}", "a".repeat(256)), "x")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op.rename("from", "nul\0char").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op.blocking().write("nul\0char", "x").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        op.write(&"a".repeat(255), "x").await?;
+
+        std::fs::remove_dir_all(root).map_err(parse_io_error)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_created() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).map_err(parse_io_error)?;
+        // Skip if birth time is not supported by the filesystem.
+        if std::fs::metadata(&root).and_then(|m| m.created()).is_err() {
+            return Ok(());
+        }
+
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)?.finish();
+
+        op.write("file", "first").await?;
+        let created = op.stat("file").await?.created().expect("must have created");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        op.write("file", "second").await?;
+        let meta = op.stat("file").await?;
+        assert_eq!(meta.created(), Some(created));
+        assert!(meta.last_modified().expect("must have last modified") > created);
+
+        std::fs::remove_dir_all(root).map_err(parse_io_error)?;
+        Ok(())
+    }
 }
@@ -45,6 +45,12 @@ impl<F> FsWriter<F> {
             pos: 0,
         }
     }
+
+    /// Start writing at the given offset of file.
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.pos = offset;
+        self
+    }
 }
 
 #[async_trait]
@@ -71,6 +77,10 @@ impl oio::Write for FsWriter<tokio::fs::File> {
         ))
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.f.sync_data().await.map_err(parse_io_error)
+    }
+
     async fn close(&mut self) -> Result<()> {
         self.f.sync_all().await.map_err(parse_io_error)?;
 
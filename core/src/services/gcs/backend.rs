@@ -278,6 +278,15 @@ impl Debug for GcsBuilder {
 impl Builder for GcsBuilder {
     const SCHEME: Scheme = Scheme::Gcs;
     type Accessor = GcsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "credential",
+        "scope",
+        "predefined_acl",
+        "default_storage_class",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = GcsBuilder::default();
@@ -419,8 +428,10 @@ impl Accessor for GcsBackend {
                 write: true,
                 write_with_content_type: true,
                 write_without_content_length: true,
+                write_with_if_generation_match: true,
                 delete: true,
                 copy: true,
+                acl: true,
 
                 list: true,
                 list_with_limit: true,
@@ -433,15 +444,25 @@ impl Accessor for GcsBackend {
                 presign_read: true,
                 presign_write: true,
 
+                key_max_len: Some(1024),
+                key_forbid_control_chars: true,
+
                 ..Default::default()
             });
         am
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        validate_key(
+            &self.info().capability(),
+            &build_abs_path(&self.core.root, path),
+        )
+    }
+
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let mut req = self
-            .core
-            .gcs_insert_object_request(path, Some(0), None, AsyncBody::Empty)?;
+        let mut req =
+            self.core
+                .gcs_insert_object_request(path, Some(0), None, None, AsyncBody::Empty)?;
 
         self.core.sign(&mut req).await?;
 
@@ -514,6 +535,12 @@ impl Accessor for GcsBackend {
 
             m.set_etag(&meta.etag);
             m.set_content_md5(&meta.md5_hash);
+            if !meta.generation.is_empty() {
+                let generation = meta.generation.parse::<i64>().map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "parse generation").set_source(e)
+                })?;
+                m.set_generation(generation);
+            }
 
             let size = meta
                 .size
@@ -525,6 +552,7 @@ impl Accessor for GcsBackend {
             }
 
             m.set_last_modified(parse_datetime_from_rfc3339(&meta.updated)?);
+            m.set_encryption(meta.encryption());
 
             Ok(RpStat::new(m))
         } else if resp.status() == StatusCode::NOT_FOUND && path.ends_with('/') {
@@ -545,6 +573,25 @@ impl Accessor for GcsBackend {
         }
     }
 
+    async fn acl(&self, path: &str, _: OpAcl) -> Result<RpAcl> {
+        let resp = self.core.gcs_get_object_acl(path).await?;
+
+        if resp.status().is_success() {
+            let slc = resp.into_body().bytes().await?;
+
+            let out: GetObjectAclJsonResponse =
+                serde_json::from_slice(&slc).map_err(new_json_deserialize_error)?;
+
+            let mut rp = RpAcl::new(out.summary());
+            if let Some(owner) = out.owner {
+                rp = rp.with_owner(owner.entity);
+            }
+            Ok(rp)
+        } else {
+            Err(parse_error(resp).await?)
+        }
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         Ok((
             RpList::default(),
@@ -605,6 +652,10 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"size": "56535"`
     size: String,
+    /// GCS will return generation in string.
+    ///
+    /// For example: `"generation": "1660563214863653"`
+    generation: String,
     /// etag is not quoted.
     ///
     /// For example: `"etag": "CKWasoTgyPkCEAE="`
@@ -621,10 +672,79 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"contentType": "image/png",`
     content_type: String,
+    /// Name of the kms key that encrypts this object.
+    ///
+    /// For example: `"kmsKeyName": "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1"`
+    kms_key_name: String,
+    /// Only returned if object is encrypted with customer-supplied key.
+    customer_encryption: Option<serde_json::Value>,
+}
+
+impl GetObjectJsonResponse {
+    /// GCS always encrypts objects, google-managed keys are used by default.
+    fn encryption(&self) -> EncryptionInfo {
+        if self.customer_encryption.is_some() {
+            EncryptionInfo::SseC
+        } else if !self.kms_key_name.is_empty() {
+            EncryptionInfo::SseKms {
+                key_id: Some(self.kms_key_name.clone()),
+            }
+        } else {
+            EncryptionInfo::SseS3
+        }
+    }
+}
+
+/// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/objects/get)
+/// with `projection=full`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct GetObjectAclJsonResponse {
+    /// Owner is only returned with full projection.
+    owner: Option<ObjectOwner>,
+    /// ACL is empty if uniform bucket-level access is enabled.
+    acl: Vec<ObjectAccessControl>,
+}
+
+impl GetObjectAclJsonResponse {
+    /// Summarize the acl granted to `allUsers` and `allAuthenticatedUsers`.
+    fn summary(&self) -> AclSummary {
+        let (mut public_read, mut public_write) = (false, false);
+
+        for acl in &self.acl {
+            if !matches!(acl.entity.as_str(), "allUsers" | "allAuthenticatedUsers") {
+                continue;
+            }
+
+            match acl.role.as_str() {
+                "READER" => public_read = true,
+                "OWNER" => (public_read, public_write) = (true, true),
+                _ => {}
+            }
+        }
+
+        AclSummary::new(public_read, public_write)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ObjectOwner {
+    /// For example: `"entity": "user-liam@example.com"`
+    entity: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ObjectAccessControl {
+    entity: String,
+    role: String,
 }
 
 #[cfg(test)]
 mod tests {
+    use reqsign::GoogleToken;
+
     use super::*;
 
     #[test]
@@ -657,5 +777,154 @@ mod tests {
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.generation, "1660563214863653");
+        assert_eq!(meta.encryption(), EncryptionInfo::SseS3);
+
+        let meta: GetObjectJsonResponse = serde_json::from_str(
+            r#"{"kmsKeyName": "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1"}"#,
+        )
+        .expect("json Deserialize must succeed");
+        assert_eq!(
+            meta.encryption(),
+            EncryptionInfo::SseKms {
+                key_id: Some(
+                    "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1"
+                        .to_string()
+                )
+            }
+        );
+
+        let meta: GetObjectJsonResponse = serde_json::from_str(
+            r#"{"customerEncryption": {"encryptionAlgorithm": "AES256", "keySha256": "H8LzoG"}}"#,
+        )
+        .expect("json Deserialize must succeed");
+        assert_eq!(meta.encryption(), EncryptionInfo::SseC);
+    }
+
+    #[derive(Debug)]
+    struct MockTokenLoader;
+
+    #[async_trait]
+    impl GoogleTokenLoad for MockTokenLoader {
+        async fn load(&self, _: reqwest::Client) -> anyhow::Result<Option<GoogleToken>> {
+            Ok(Some(GoogleToken::new("token", 3600, DEFAULT_GCS_SCOPE)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_with_if_generation_match() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let object = |generation: &str| {
+            ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"size": "5", "generation": "{generation}", "updated": "2022-08-15T11:33:34.866Z"}}"#
+            ))
+        };
+
+        let mock_server = MockServer::start().await;
+        // The object is at generation 1 until it's mutated by another writer.
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/bucket/o/file"))
+            .respond_with(object("1"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/bucket/o/file"))
+            .respond_with(object("2"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/upload/storage/v1/b/bucket/o"))
+            .and(query_param_is_missing("ifGenerationMatch"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/upload/storage/v1/b/bucket/o"))
+            .and(query_param("ifGenerationMatch", "1"))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/upload/storage/v1/b/bucket/o"))
+            .and(query_param("ifGenerationMatch", "2"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = GcsBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.customed_token_loader(Box::new(MockTokenLoader));
+        let op = Operator::new(builder)?.finish();
+
+        let generation = op.stat("file").await?.generation();
+        assert_eq!(generation, Some(1));
+
+        // Another writer mutates the object between our read and write.
+        op.write("file", "world").await?;
+
+        let err = op
+            .write_with("file", OpWrite::new().with_if_generation_match(1), "hello")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let generation = op.stat("file").await?.generation();
+        assert_eq!(generation, Some(2));
+        op.write_with("file", OpWrite::new().with_if_generation_match(2), "hello")
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_object_owner() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/b/bucket/o/file"))
+            .and(query_param("projection", "full"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+  "name": "file",
+  "owner": {"entity": "user-liam@example.com", "entityId": "00b4903a97"},
+  "acl": [
+    {"entity": "user-liam@example.com", "role": "OWNER"},
+    {"entity": "allUsers", "role": "READER"}
+  ]
+}"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = GcsBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.customed_token_loader(Box::new(MockTokenLoader));
+        let op = Operator::new(builder)?.finish();
+
+        let meta = op.get_object_owner("file").await?;
+        assert_eq!(meta.owner(), Some("user-liam@example.com"));
+
+        let acl = op.get_object_acl("file").await?.acl_summary().unwrap();
+        assert!(acl.public_read());
+        assert!(!acl.public_write());
+
+        Ok(())
     }
 }
@@ -209,6 +209,7 @@ impl GcsCore {
         path: &str,
         size: Option<usize>,
         content_type: Option<&str>,
+        if_generation_match: Option<i64>,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -228,6 +229,9 @@ impl GcsCore {
         if let Some(acl) = &self.predefined_acl {
             write!(&mut url, "&predefinedAcl={}", acl).unwrap();
         }
+        if let Some(generation) = if_generation_match {
+            write!(&mut url, "&ifGenerationMatch={}", generation).unwrap();
+        }
 
         let mut req = Request::post(&url);
 
@@ -370,6 +374,26 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Get object metadata with full projection which carries owner and acl.
+    pub async fn gcs_get_object_acl(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/storage/v1/b/{}/o/{}?projection=full",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
     pub async fn gcs_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -466,12 +490,16 @@ impl GcsCore {
     pub async fn gcs_initiate_resumable_upload(
         &self,
         path: &str,
+        if_generation_match: Option<i64>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
-        let url = format!(
+        let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType=resumable&name={}",
             self.endpoint, self.bucket, p
         );
+        if let Some(generation) = if_generation_match {
+            write!(&mut url, "&ifGenerationMatch={}", generation).unwrap();
+        }
 
         let mut req = Request::post(&url)
             .header(CONTENT_LENGTH, 0)
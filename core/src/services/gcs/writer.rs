@@ -58,6 +58,7 @@ impl GcsWriter {
             &percent_encode_path(&self.path),
             Some(bs.len()),
             self.op.content_type(),
+            self.op.if_generation_match(),
             AsyncBody::Bytes(bs),
         )?;
 
@@ -77,7 +78,10 @@ impl GcsWriter {
     }
 
     async fn initiate_upload(&self) -> Result<String> {
-        let resp = self.core.gcs_initiate_resumable_upload(&self.path).await?;
+        let resp = self
+            .core
+            .gcs_initiate_resumable_upload(&self.path, self.op.if_generation_match())
+            .await?;
         let status = resp.status();
 
         match status {
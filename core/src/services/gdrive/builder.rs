@@ -119,6 +119,7 @@ impl Builder for GdriveBuilder {
     const SCHEME: Scheme = Scheme::Gdrive;
 
     type Accessor = GdriveBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "access_token"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = Self::default();
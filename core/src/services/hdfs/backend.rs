@@ -175,6 +175,7 @@ impl HdfsBuilder {
 impl Builder for HdfsBuilder {
     const SCHEME: Scheme = Scheme::Hdfs;
     type Accessor = HdfsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "name_node"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = HdfsBuilder::default();
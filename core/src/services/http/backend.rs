@@ -173,6 +173,8 @@ impl HttpBuilder {
 impl Builder for HttpBuilder {
     const SCHEME: Scheme = Scheme::Http;
     type Accessor = HttpBackend;
+    const CONFIG_KEYS: &'static [&'static str] =
+        &["root", "endpoint", "username", "password", "token"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = HttpBuilder::default();
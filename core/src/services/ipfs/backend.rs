@@ -144,6 +144,7 @@ impl IpfsBuilder {
 impl Builder for IpfsBuilder {
     const SCHEME: Scheme = Scheme::Ipfs;
     type Accessor = IpfsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "endpoint"];
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = IpfsBuilder::default();
 
@@ -111,6 +111,7 @@ impl IpmfsBuilder {
 impl Builder for IpmfsBuilder {
     const SCHEME: Scheme = Scheme::Ipmfs;
     type Accessor = IpmfsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "endpoint"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = IpmfsBuilder::default();
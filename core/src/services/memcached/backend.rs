@@ -120,6 +120,7 @@ impl MemcachedBuilder {
 impl Builder for MemcachedBuilder {
     const SCHEME: Scheme = Scheme::Memcached;
     type Accessor = MemcachedBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "endpoint"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = MemcachedBuilder::default();
@@ -58,6 +58,7 @@ impl MemoryBuilder {
 impl Builder for MemoryBuilder {
     const SCHEME: Scheme = Scheme::Memory;
     type Accessor = MemoryBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = Self::default();
@@ -153,6 +154,7 @@ impl typed_kv::Adapter for Adapter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ops::OpWrite;
     use crate::raw::*;
 
     #[test]
@@ -163,4 +165,31 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[tokio::test]
+    async fn test_write_with_expire() -> Result<()> {
+        let op = Operator::new(MemoryBuilder::default())?.finish();
+
+        op.write_with(
+            "ephemeral",
+            OpWrite::new().with_expire(std::time::Duration::from_millis(100)),
+            "Hello, World!",
+        )
+        .await?;
+        op.write("persistent", "Hello, World!").await?;
+
+        let meta = op.stat("ephemeral").await?;
+        assert!(meta.expires_at().is_some());
+        assert_eq!(op.read("ephemeral").await?, b"Hello, World!");
+
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let err = op.stat("ephemeral").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let err = op.read("ephemeral").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(op.read("persistent").await?, b"Hello, World!");
+
+        Ok(())
+    }
 }
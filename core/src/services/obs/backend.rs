@@ -189,6 +189,13 @@ impl ObsBuilder {
 impl Builder for ObsBuilder {
     const SCHEME: Scheme = Scheme::Obs;
     type Accessor = ObsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "access_key_id",
+        "secret_access_key",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = ObsBuilder::default();
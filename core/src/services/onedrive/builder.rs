@@ -117,6 +117,7 @@ impl Builder for OnedriveBuilder {
     const SCHEME: Scheme = Scheme::Onedrive;
 
     type Accessor = OnedriveBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "access_token"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = Self::default();
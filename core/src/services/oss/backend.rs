@@ -314,6 +314,17 @@ impl OssBuilder {
 impl Builder for OssBuilder {
     const SCHEME: Scheme = Scheme::Oss;
     type Accessor = OssBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "presign_endpoint",
+        "access_key_id",
+        "access_key_secret",
+        "server_side_encryption",
+        "server_side_encryption_key_id",
+        "write_min_size",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = OssBuilder::default();
@@ -28,6 +28,9 @@ use crate::ops::OpWrite;
 use crate::raw::*;
 use crate::*;
 
+/// Aliyun OSS requires parts except the last one to be at least 100 KiB.
+const MIN_PART_SIZE: usize = 100 * 1024;
+
 pub struct OssWriter {
     core: Arc<OssCore>,
 
@@ -183,6 +186,29 @@ impl oio::Write for OssWriter {
         ))
     }
 
+    /// Upload all buffered data as a new part.
+    ///
+    /// Parts except the last one must be at least 100 KiB, so smaller data
+    /// is kept in buffer until following writes or `close`.
+    async fn flush(&mut self) -> Result<()> {
+        let upload_id = if let Some(upload_id) = &self.upload_id {
+            upload_id
+        } else {
+            return Ok(());
+        };
+
+        if self.buffer.len() < MIN_PART_SIZE {
+            return Ok(());
+        }
+
+        let bs = self.buffer.peak_exact(self.buffer.len());
+        let part = self.write_part(upload_id, bs).await?;
+        self.buffer.clear();
+        self.parts.push(part);
+
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
             upload_id
@@ -195,6 +195,8 @@ impl RedisBuilder {
 impl Builder for RedisBuilder {
     const SCHEME: Scheme = Scheme::Redis;
     type Accessor = RedisBackend;
+    const CONFIG_KEYS: &'static [&'static str] =
+        &["root", "endpoint", "username", "password", "db"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = RedisBuilder::default();
@@ -19,12 +19,17 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::ops::Range;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use bytes::Buf;
+use bytes::Bytes;
+use http::header::HeaderName;
+use http::HeaderMap;
+use http::HeaderValue;
 use http::StatusCode;
 use log::debug;
 use md5::Digest;
@@ -36,9 +41,14 @@ use reqsign::AwsLoader;
 use reqsign::AwsV4Signer;
 
 use super::core::*;
+use super::error::is_cross_endpoint_error;
 use super::error::parse_error;
+use super::error::parse_error_code;
+use super::error::parse_error_with_parts;
 use super::error::parse_s3_error_code;
+use super::error::NO_SUCH_OBJECT_LOCK_CONFIGURATION;
 use super::pager::S3Pager;
+use super::select::S3SelectReader;
 use super::writer::S3Writer;
 use crate::ops::*;
 use crate::raw::*;
@@ -56,6 +66,14 @@ static ENDPOINT_TEMPLATES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new
 });
 
 const DEFAULT_WRITE_MIN_SIZE: usize = 8 * 1024 * 1024;
+/// AWS S3 allows at most 5 GiB to be uploaded in a single PUT.
+const WRITE_SINGLE_MAX_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// AWS S3 allows at most 5 GiB to be copied in a single CopyObject.
+const COPY_SINGLE_MAX_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// The part size of multipart copy, which is also the progress granularity.
+const COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+/// AWS S3 allows at most 10000 parts in a multipart upload.
+const MAX_PARTS: u64 = 10000;
 /// Aws S3 and compatible services (including minio, digitalocean space, Tencent Cloud Object Storage(COS) and so on) support.
 /// For more information about s3-compatible services, refer to [Compatible Services](#compatible-services).
 ///
@@ -737,6 +755,27 @@ impl S3Builder {
 impl Builder for S3Builder {
     const SCHEME: Scheme = Scheme::S3;
     type Accessor = S3Backend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "region",
+        "access_key_id",
+        "secret_access_key",
+        "security_token",
+        "role_arn",
+        "external_id",
+        "server_side_encryption",
+        "server_side_encryption_aws_kms_key_id",
+        "server_side_encryption_customer_algorithm",
+        "server_side_encryption_customer_key",
+        "server_side_encryption_customer_key_md5",
+        "disable_config_load",
+        "disable_ec2_metadata",
+        "enable_virtual_host_style",
+        "allow_anonymous",
+        "default_storage_class",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = S3Builder::default();
@@ -947,6 +986,421 @@ pub struct S3Backend {
     core: Arc<S3Core>,
 }
 
+impl S3Backend {
+    async fn get_retention(&self, path: &str) -> Result<RpRetention> {
+        let resp = self.core.s3_get_object_retention(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: ObjectRetentionConfiguration =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                let mode = match out.mode.as_str() {
+                    "GOVERNANCE" => RetentionMode::Governance,
+                    "COMPLIANCE" => RetentionMode::Compliance,
+                    v => {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "unexpected retention mode returned by s3",
+                        )
+                        .with_context("mode", v))
+                    }
+                };
+                let retain_until = parse_datetime_from_rfc3339(&out.retain_until_date)?;
+
+                Ok(RpRetention::new(Some(ObjectRetention::new(
+                    mode,
+                    retain_until,
+                ))))
+            }
+            // Object lock is enabled for bucket but the object doesn't have retention.
+            StatusCode::NOT_FOUND => {
+                let (parts, body) = resp.into_parts();
+                let bs = body.bytes().await?;
+                if parse_error_code(&bs).as_deref() == Some(NO_SUCH_OBJECT_LOCK_CONFIGURATION) {
+                    Ok(RpRetention::new(None))
+                } else {
+                    Err(parse_error_with_parts(parts, bs))
+                }
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn get_legal_hold(&self, path: &str) -> Result<RpLegalHold> {
+        let resp = self.core.s3_get_object_legal_hold(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: ObjectLegalHold =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                Ok(RpLegalHold::new(out.status == "ON"))
+            }
+            // Object lock is enabled for bucket but legal hold is never set.
+            StatusCode::NOT_FOUND => {
+                let (parts, body) = resp.into_parts();
+                let bs = body.bytes().await?;
+                if parse_error_code(&bs).as_deref() == Some(NO_SUCH_OBJECT_LOCK_CONFIGURATION) {
+                    Ok(RpLegalHold::new(false))
+                } else {
+                    Err(parse_error_with_parts(parts, bs))
+                }
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Copy object in a single CopyObject request.
+    async fn copy_once(&self, from: &str, to: &str, args: &OpCopy) -> Result<RpCopy> {
+        let resp = self
+            .core
+            .s3_copy_object(
+                args.source_bucket(),
+                from,
+                to,
+                args.preserve_user_metadata(),
+                args.preserve_tags(),
+                args.if_match(),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                // According to the documentation, when using copy_object, a 200 error may occur and we need to detect it.
+                // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CopyObject.html#API_CopyObject_RequestSyntax
+                resp.into_body().consume().await?;
+
+                Ok(RpCopy::default())
+            }
+            _ if args.source_bucket().is_some() => {
+                let (parts, body) = resp.into_parts();
+                let bs = body.bytes().await?;
+                let code = parse_error_code(&bs);
+                let err = parse_error_with_parts(parts, bs);
+                if is_cross_endpoint_error(status, code.as_deref()) {
+                    // Allow callers to fall back to streaming copy.
+                    Err(Error::new(
+                        ErrorKind::Unsupported,
+                        "source bucket is not reachable from the endpoint of target bucket",
+                    )
+                    .set_source(err))
+                } else {
+                    Err(err)
+                }
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn initiate_upload(
+        &self,
+        to: &str,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        cache_control: Option<&str>,
+        headers: HeaderMap,
+    ) -> Result<String> {
+        let resp = self
+            .core
+            .s3_initiate_multipart_upload(
+                to,
+                content_type,
+                content_disposition,
+                cache_control,
+                headers,
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let result: InitiateMultipartUploadResult =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                Ok(result.upload_id)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Build the user metadata and tags headers of CreateMultipartUpload
+    /// for copy by parts.
+    ///
+    /// Unlike CopyObject, multipart upload doesn't copy them from source, so
+    /// we forward them unless the copy asks to drop them.
+    async fn copy_preserved_headers(
+        &self,
+        from: &str,
+        source: &HeaderMap,
+        args: &OpCopy,
+    ) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+
+        if args.preserve_user_metadata() != Some(false) {
+            headers.extend(
+                source
+                    .iter()
+                    .filter(|(k, _)| k.as_str().starts_with(constants::X_AMZ_META_PREFIX))
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+        }
+
+        let tagging_count = source
+            .get(constants::X_AMZ_TAGGING_COUNT)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or_default();
+        if args.preserve_tags() != Some(false) && tagging_count > 0 {
+            let resp = self.core.s3_get_object_tagging(from).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+            let bs = resp.into_body().bytes().await?;
+            let tagging: ObjectTagging =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+            let value = HeaderValue::from_str(&tagging.to_header_value()).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "header value is invalid").set_source(err)
+            })?;
+            headers.insert(HeaderName::from_static(constants::X_AMZ_TAGGING), value);
+        }
+
+        Ok(headers)
+    }
+
+    /// Copy `range` of `from` by parts and report progress after each part
+    /// finished.
+    async fn copy_parts(
+        &self,
+        from: &str,
+        to: &str,
+        upload_id: &str,
+        args: &OpCopy,
+        range: Range<u64>,
+        progress: &CopyProgress,
+    ) -> Result<Vec<CompleteMultipartUploadRequestPart>> {
+        let total = range.end - range.start;
+        // Make sure parts count won't exceed the limit for huge objects.
+        let part_size = COPY_PART_SIZE.max((total + MAX_PARTS - 1) / MAX_PARTS);
+
+        let mut parts = Vec::new();
+        let mut offset = 0;
+        while offset < total {
+            let size = part_size.min(total - offset);
+            // AWS S3 requires part number must between [1..=10000]
+            let part_number = parts.len() + 1;
+
+            let resp = self
+                .core
+                .s3_upload_part_copy(
+                    from,
+                    to,
+                    upload_id,
+                    part_number,
+                    BytesRange::new(Some(range.start + offset), Some(size)),
+                    args.if_match(),
+                )
+                .await?;
+
+            match resp.status() {
+                StatusCode::OK => {
+                    let bs = resp.into_body().bytes().await?;
+
+                    // UploadPartCopy could return 200 with an error body too.
+                    let result: CopyPartResult = quick_xml::de::from_reader(bs.reader())
+                        .map_err(new_xml_deserialize_error)?;
+                    if result.etag.is_empty() {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "ETag not present in copy part result",
+                        )
+                        .with_context("part_number", part_number.to_string()));
+                    }
+
+                    parts.push(CompleteMultipartUploadRequestPart {
+                        part_number,
+                        etag: result.etag,
+                    });
+                }
+                _ => return Err(parse_error(resp).await?),
+            }
+
+            offset += size;
+            progress(offset, total);
+        }
+
+        Ok(parts)
+    }
+
+    async fn complete_upload(
+        &self,
+        to: &str,
+        upload_id: &str,
+        parts: &[CompleteMultipartUploadRequestPart],
+    ) -> Result<()> {
+        let resp = self
+            .core
+            .s3_complete_multipart_upload(to, upload_id, parts)
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// List on-going multipart uploads of path, the earliest initiated
+    /// comes first.
+    ///
+    /// Workers of ranged write could initiate uploads concurrently, all of
+    /// them will agree on the first one.
+    async fn ranged_write_uploads(&self, path: &str) -> Result<Vec<String>> {
+        let key = build_abs_path(&self.core.root, path);
+
+        let mut uploads = Vec::new();
+        let mut key_marker = String::new();
+        let mut upload_id_marker = String::new();
+        loop {
+            let resp = self
+                .core
+                .s3_list_multipart_uploads(path, &key_marker, &upload_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let out: ListMultipartUploadsResult =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+            // Uploads are listed by prefix, filter out other keys.
+            uploads.extend(out.upload.into_iter().filter(|v| v.key == key));
+
+            if !out.is_truncated {
+                break;
+            }
+            if out.next_key_marker.is_empty() && out.next_upload_id_marker.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "truncated multipart uploads listing doesn't return next marker",
+                ));
+            }
+            key_marker = out.next_key_marker;
+            upload_id_marker = out.next_upload_id_marker;
+        }
+
+        uploads.sort_by(|a, b| (&a.initiated, &a.upload_id).cmp(&(&b.initiated, &b.upload_id)));
+        Ok(uploads.into_iter().map(|v| v.upload_id).collect())
+    }
+
+    async fn list_parts(&self, path: &str, upload_id: &str) -> Result<Vec<ListPartsResultPart>> {
+        let mut parts = Vec::new();
+        let mut marker = 0;
+        loop {
+            let resp = self.core.s3_list_parts(path, upload_id, marker).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let out: ListPartsResult =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+            parts.extend(out.part);
+            if !out.is_truncated {
+                return Ok(parts);
+            }
+            marker = out.next_part_number_marker;
+        }
+    }
+
+    /// List all versions and delete markers of objects under the prefix,
+    /// or only the ones of the exact path if `exact` is set.
+    ///
+    /// Returned versions are sorted by path, versions of the same path
+    /// are sorted from newest to oldest.
+    async fn list_object_versions(&self, path: &str, exact: bool) -> Result<Vec<ObjectVersion>> {
+        let key = build_abs_path(&self.core.root, path);
+
+        let mut versions = Vec::new();
+        let (mut key_marker, mut version_id_marker) = (String::new(), String::new());
+        loop {
+            let resp = self
+                .core
+                .s3_list_object_versions(path, &key_marker, &version_id_marker)
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let out: ListObjectVersionsResult =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+            // Keys are returned in order and `key` is the smallest one under
+            // itself, so all its versions have been listed once we reach
+            // another key.
+            let reached_other_key = out
+                .version
+                .iter()
+                .map(|v| &v.key)
+                .chain(out.delete_marker.iter().map(|v| &v.key))
+                .any(|v| v != &key);
+
+            for v in out.version {
+                if exact && v.key != key {
+                    continue;
+                }
+                let version = ObjectVersion::new(
+                    &build_rel_path(&self.core.root, &v.key),
+                    &v.version_id,
+                    parse_datetime_from_rfc3339(&v.last_modified)?,
+                );
+                versions.push(version.with_content_length(v.size));
+            }
+            for v in out.delete_marker {
+                if exact && v.key != key {
+                    continue;
+                }
+                let version = ObjectVersion::new(
+                    &build_rel_path(&self.core.root, &v.key),
+                    &v.version_id,
+                    parse_datetime_from_rfc3339(&v.last_modified)?,
+                );
+                versions.push(version.with_delete_marker(true));
+            }
+            if !out.is_truncated || (exact && reached_other_key) {
+                // Versions and delete markers are returned in separate lists,
+                // sort them again to merge them.
+                versions.sort_by(|a, b| {
+                    a.path()
+                        .cmp(b.path())
+                        .then_with(|| b.last_modified().cmp(&a.last_modified()))
+                });
+                return Ok(versions);
+            }
+            key_marker = out.next_key_marker;
+            version_id_marker = out.next_version_id_marker;
+        }
+    }
+}
+
 #[async_trait]
 impl Accessor for S3Backend {
     type Reader = IncomingAsyncBody;
@@ -962,10 +1416,12 @@ impl Accessor for S3Backend {
         am.set_scheme(Scheme::S3)
             .set_root(&self.core.root)
             .set_name(&self.core.bucket)
+            .set_endpoint(&self.core.endpoint)
             .set_capability(Capability {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_version: true,
 
                 read: true,
                 read_can_next: true,
@@ -974,20 +1430,42 @@ impl Accessor for S3Backend {
                 read_with_if_none_match: true,
                 read_with_override_cache_control: true,
                 read_with_override_content_disposition: true,
+                read_with_version: true,
 
                 write: true,
                 write_with_cache_control: true,
+                write_with_content_language: true,
+                write_with_http_header: true,
                 write_with_content_type: true,
+                write_with_decompressed_size: true,
                 write_without_content_length: true,
+                write_can_resume: true,
+                // Content can't be larger than usize::MAX in memory anyway.
+                write_single_max_size: usize::try_from(WRITE_SINGLE_MAX_SIZE).ok(),
                 create_dir: true,
                 delete: true,
                 copy: true,
+                copy_cross_bucket: true,
+                copy_with_preserve_user_metadata: true,
+                copy_with_preserve_tags: true,
+                copy_with_if_match: true,
+                copy_with_progress: true,
+                copy_with_range: true,
+                delete_all_versions: true,
+                delete_with_bypass_governance: true,
+                delete_with_if_match: true,
+                retention: true,
+                legal_hold: true,
+                acl: true,
+                write_range: true,
 
                 list: true,
                 list_with_limit: true,
                 list_with_start_after: true,
                 list_without_delimiter: true,
+                list_with_prefix: true,
                 list_with_delimiter_slash: true,
+                list_versions: true,
 
                 presign: true,
                 presign_stat: true,
@@ -997,12 +1475,24 @@ impl Accessor for S3Backend {
                 batch: true,
                 batch_max_operations: Some(1000),
 
+                select: true,
+
+                key_max_len: Some(1024),
+                key_forbid_control_chars: true,
+
                 ..Default::default()
             });
 
         am
     }
 
+    fn validate_key(&self, path: &str) -> Result<()> {
+        validate_key(
+            &self.info().capability(),
+            &build_abs_path(&self.core.root, path),
+        )
+    }
+
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let mut req =
             self.core
@@ -1024,22 +1514,19 @@ impl Accessor for S3Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self
-            .core
-            .s3_get_object(
-                path,
-                args.range(),
-                args.if_none_match(),
-                args.if_match(),
-                args.override_content_disposition(),
-            )
-            .await?;
+        let resp = self.core.s3_get_object(path, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                let meta = parse_into_metadata(path, resp.headers())?;
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_decompressed_size(resp.headers())? {
+                    meta.set_decompressed_size(v);
+                }
+                if let Some(v) = parse_version(resp.headers())? {
+                    meta.set_version(v);
+                }
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
             _ => Err(parse_error(resp).await?),
@@ -1049,24 +1536,104 @@ impl Accessor for S3Backend {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         Ok((
             RpWrite::default(),
-            S3Writer::new(self.core.clone(), path, args),
+            S3Writer::new(self.core.clone(), path, args)?,
         ))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let range = args.range();
+        if !range.is_full() && args.source_bucket().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with range from another bucket is not supported",
+            ));
+        }
 
-        let status = resp.status();
+        let progress: CopyProgress = match args.progress() {
+            Some(progress) => progress.clone(),
+            None if range.is_full() => return self.copy_once(from, to, &args).await,
+            None => Arc::new(|_, _| {}),
+        };
 
-        match status {
-            StatusCode::OK => {
-                // According to the documentation, when using copy_object, a 200 error may occur and we need to detect it.
-                // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CopyObject.html#API_CopyObject_RequestSyntax
-                resp.into_body().consume().await?;
+        // Objects in another bucket can't be stat, report after copy.
+        if args.source_bucket().is_some() {
+            self.copy_once(from, to, &args).await?;
+            let size = self
+                .stat(to, OpStat::new())
+                .await?
+                .into_metadata()
+                .content_length();
+            progress(size, size);
+            return Ok(RpCopy::default());
+        }
 
-                Ok(RpCopy::default())
+        let resp = self
+            .core
+            .s3_head_object(from, None, args.if_match(), None)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+        let total = parse_content_length(resp.headers())?.unwrap_or_default();
+
+        // CopyObject doesn't support range, copy the range by parts instead.
+        let range = range.clamp(total).ok_or_else(|| {
+            Error::new(
+                ErrorKind::RangeNotSatisfied,
+                "copy range is out of the source object",
+            )
+            .with_context("range", range.to_string())
+            .with_context("size", total.to_string())
+        })?;
+        let (offset, size) = match (range.offset(), range.size()) {
+            (None, Some(size)) => (total - size, size),
+            (offset, size) => {
+                let offset = offset.unwrap_or_default();
+                (offset, size.unwrap_or(total - offset))
+            }
+        };
+
+        if size == total && total <= COPY_SINGLE_MAX_SIZE {
+            self.copy_once(from, to, &args).await?;
+            progress(total, total);
+            return Ok(RpCopy::default());
+        }
+
+        let headers = resp.headers();
+        let extra = self.copy_preserved_headers(from, headers, &args).await?;
+        let upload_id = self
+            .initiate_upload(
+                to,
+                parse_content_type(headers)?,
+                parse_content_disposition(headers)?,
+                parse_cache_control(headers)?,
+                extra,
+            )
+            .await?;
+
+        match self
+            .copy_parts(
+                from,
+                to,
+                &upload_id,
+                &args,
+                offset..offset + size,
+                &progress,
+            )
+            .await
+        {
+            Ok(parts) => self
+                .complete_upload(to, &upload_id, &parts)
+                .await
+                .map(|_| RpCopy::default()),
+            Err(err) => {
+                // Abort the upload so that copied parts won't be charged,
+                // the original error will be returned anyway.
+                if let Ok(resp) = self.core.s3_abort_multipart_upload(to, &upload_id).await {
+                    let _ = resp.into_body().consume().await;
+                }
+                Err(err)
             }
-            _ => Err(parse_error(resp).await?),
         }
     }
 
@@ -1078,13 +1645,25 @@ impl Accessor for S3Backend {
 
         let resp = self
             .core
-            .s3_head_object(path, args.if_none_match(), args.if_match())
+            .s3_head_object(path, args.if_none_match(), args.if_match(), args.version())
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_decompressed_size(resp.headers())? {
+                    meta.set_decompressed_size(v);
+                }
+                if let Some(v) = parse_encryption(resp.headers())? {
+                    meta.set_encryption(v);
+                }
+                if let Some(v) = parse_version(resp.headers())? {
+                    meta.set_version(v);
+                }
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -1092,8 +1671,11 @@ impl Accessor for S3Backend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.s3_delete_object(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self
+            .core
+            .s3_delete_object(path, args.bypass_governance(), args.if_match())
+            .await?;
 
         let status = resp.status();
 
@@ -1104,11 +1686,17 @@ impl Accessor for S3Backend {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let path = match args.prefix() {
+            Some(prefix) if path == "/" => prefix.to_string(),
+            Some(prefix) => format!("{path}{prefix}"),
+            None => path.to_string(),
+        };
+
         Ok((
             RpList::default(),
             S3Pager::new(
                 self.core.clone(),
-                path,
+                &path,
                 args.delimiter(),
                 args.limit(),
                 args.start_after(),
@@ -1119,18 +1707,13 @@ impl Accessor for S3Backend {
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
-            PresignOperation::Stat(v) => {
-                self.core
-                    .s3_head_object_request(path, v.if_none_match(), v.if_match())?
-            }
-            PresignOperation::Read(v) => self.core.s3_get_object_request(
+            PresignOperation::Stat(v) => self.core.s3_head_object_request(
                 path,
-                v.range(),
-                v.override_content_disposition(),
-                v.override_cache_control(),
                 v.if_none_match(),
                 v.if_match(),
+                v.version(),
             )?,
+            PresignOperation::Read(v) => self.core.s3_get_object_request(path, v)?,
             PresignOperation::Write(_) => {
                 self.core
                     .s3_put_object_request(path, None, None, None, None, AsyncBody::Empty)?
@@ -1149,6 +1732,241 @@ impl Accessor for S3Backend {
         )))
     }
 
+    async fn select(&self, path: &str, args: OpSelect) -> Result<(RpSelect, oio::Reader)> {
+        let resp = self.core.s3_select_object_content(path, &args).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => Ok((
+                RpSelect::default(),
+                Box::new(S3SelectReader::new(resp.into_body())),
+            )),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn retention(&self, path: &str, args: OpRetention) -> Result<RpRetention> {
+        let retention = match args {
+            OpRetention::Set(retention) => retention,
+            OpRetention::Get => return self.get_retention(path).await,
+        };
+
+        let resp = self.core.s3_put_object_retention(path, &retention).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpRetention::new(Some(retention)))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn legal_hold(&self, path: &str, args: OpLegalHold) -> Result<RpLegalHold> {
+        let enabled = match args {
+            OpLegalHold::Set(enabled) => enabled,
+            OpLegalHold::Get => return self.get_legal_hold(path).await,
+        };
+
+        let resp = self.core.s3_put_object_legal_hold(path, enabled).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(RpLegalHold::new(enabled))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn acl(&self, path: &str, _: OpAcl) -> Result<RpAcl> {
+        let resp = self.core.s3_get_object_acl(path).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body().bytes().await?;
+
+                let out: AccessControlPolicy =
+                    quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+                let mut rp = RpAcl::new(out.summary());
+                if let Some(owner) = out.owner() {
+                    rp = rp.with_owner(owner.to_string());
+                }
+                Ok(rp)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn write_range(&self, path: &str, args: OpWriteRange, bs: Bytes) -> Result<RpWriteRange> {
+        // AWS S3 requires part number must between [1..=10000]
+        let part_number = args.part_index() + 1;
+        if part_number > MAX_PARTS {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "s3 services only allow up to 10000 parts, use a larger part size",
+            )
+            .with_context("part_number", part_number.to_string()));
+        }
+
+        let upload_id = match self.ranged_write_uploads(path).await?.into_iter().next() {
+            Some(upload_id) => upload_id,
+            None => {
+                self.initiate_upload(path, None, None, None, HeaderMap::new())
+                    .await?;
+                // Other workers could initiate at the same time, pick the
+                // earliest one again instead of ours.
+                self.ranged_write_uploads(path)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::Unexpected, "initiated upload is not listed")
+                    })?
+            }
+        };
+
+        let size = bs.len() as u64;
+        let mut req = self.core.s3_upload_part_request(
+            path,
+            &upload_id,
+            part_number as usize,
+            Some(size),
+            AsyncBody::Bytes(bs),
+        )?;
+        self.core.sign(&mut req).await?;
+        let resp = self.core.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+
+                Ok(RpWriteRange::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn finalize_ranged_write(
+        &self,
+        path: &str,
+        _: OpFinalizeRangedWrite,
+    ) -> Result<RpFinalizeRangedWrite> {
+        let mut uploads = self.ranged_write_uploads(path).await?.into_iter();
+        let upload_id = uploads
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "no range has been written"))?;
+
+        // Workers that lost the race never upload parts to their uploads,
+        // uploads with parts belong to other writers of path.
+        let others: Vec<_> = uploads.collect();
+        for other in &others {
+            if !self.list_parts(path, other).await?.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "path has other multipart uploads in progress",
+                )
+                .with_context("upload_id", other));
+            }
+        }
+
+        let parts = self.list_parts(path, &upload_id).await?;
+        // Parts must be contiguous and have the same size except the last one.
+        let part_size = parts.first().map(|v| v.size).unwrap_or_default();
+        for (idx, part) in parts.iter().enumerate() {
+            if part.part_number != idx + 1 {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "ranges have gap")
+                    .with_context("expected_part_number", (idx + 1).to_string())
+                    .with_context("actual_part_number", part.part_number.to_string()));
+            }
+            if part.size > part_size || (idx + 1 < parts.len() && part.size != part_size) {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "range size doesn't match part size",
+                )
+                .with_context("part_number", part.part_number.to_string())
+                .with_context("part_size", part_size.to_string())
+                .with_context("size", part.size.to_string()));
+            }
+        }
+
+        let parts: Vec<_> = parts
+            .into_iter()
+            .map(|v| CompleteMultipartUploadRequestPart {
+                part_number: v.part_number,
+                etag: v.etag,
+            })
+            .collect();
+        self.complete_upload(path, &upload_id, &parts).await?;
+
+        // Clean up uploads initiated by workers that lost the race.
+        for upload_id in others {
+            let resp = self
+                .core
+                .s3_abort_multipart_upload(path, &upload_id)
+                .await?;
+            match resp.status() {
+                StatusCode::NO_CONTENT | StatusCode::OK => resp.into_body().consume().await?,
+                _ => return Err(parse_error(resp).await?),
+            }
+        }
+
+        Ok(RpFinalizeRangedWrite::default())
+    }
+
+    async fn delete_all_versions(
+        &self,
+        path: &str,
+        _: OpDeleteAllVersions,
+    ) -> Result<RpDeleteAllVersions> {
+        let versions: Vec<String> = self
+            .list_object_versions(path, true)
+            .await?
+            .into_iter()
+            .map(|v| v.version().to_string())
+            .collect();
+
+        // DeleteObjects accepts up to 1000 keys in one request.
+        for chunk in versions.chunks(1000) {
+            let resp = self
+                .core
+                .s3_delete_object_versions(path, chunk.to_vec())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let result: DeleteObjectsResult =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+            if let Some(err) = result.error.first() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "failed to delete some versions of the object",
+                )
+                .with_context("code", &err.code)
+                .with_context("message", &err.message)
+                .with_context("failed", result.error.len().to_string()));
+            }
+        }
+
+        Ok(RpDeleteAllVersions::new(versions.len()))
+    }
+
+    async fn list_versions(&self, path: &str, args: OpListVersions) -> Result<RpListVersions> {
+        let versions = self.list_object_versions(path, args.exact()).await?;
+
+        Ok(RpListVersions::new(versions))
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let ops = args.into_operation();
         if ops.len() > 1000 {
@@ -1159,9 +1977,35 @@ impl Accessor for S3Backend {
             .with_context("length", ops.len().to_string()));
         }
 
+        // Bypass governance is set by header which applies to the whole request.
+        let bypass_governance = ops.iter().any(|(_, op)| match op {
+            BatchOperation::Delete(op) => op.bypass_governance(),
+        });
+        if bypass_governance
+            && !ops.iter().all(|(_, op)| match op {
+                BatchOperation::Delete(op) => op.bypass_governance(),
+            })
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "s3 services don't allow mixing bypass governance in one batch delete",
+            ));
+        }
+        if ops.iter().any(|(_, op)| match op {
+            BatchOperation::Delete(op) => op.if_match().is_some(),
+        }) {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "s3 services don't support if match in batch delete",
+            ));
+        }
+
         let paths = ops.into_iter().map(|(p, _)| p).collect();
 
-        let resp = self.core.s3_delete_objects(paths).await?;
+        let resp = self
+            .core
+            .s3_delete_objects(paths, bypass_governance)
+            .await?;
 
         let status = resp.status();
 
@@ -1257,4 +2101,1670 @@ mod tests {
             assert_eq!(endpoint, "https://test.s3.us-east-2.amazonaws.com");
         }
     }
+
+    #[tokio::test]
+    async fn test_list_with_prefix() -> Result<()> {
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("prefix", "reports/2024-01"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Contents>
+                        <Key>reports/2024-01-01.csv</Key>
+                        <Size>1</Size>
+                        <LastModified>2023-01-01T00:00:00.000Z</LastModified>
+                        <ETag>"etag"</ETag>
+                    </Contents>
+                </ListBucketResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let paths: Vec<String> = op
+            .list_with("reports/", OpList::new().with_prefix("2024-01"))
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        assert_eq!(paths, vec!["reports/2024-01-01.csv".to_string()]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_select_csv() -> Result<()> {
+        use futures::TryStreamExt;
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use super::super::select::tests::encode_message;
+        use super::super::select::tests::end_event;
+        use super::super::select::tests::records_event;
+
+        // The object is `name,age\nalice,17\nbob,30\ncarol,45\n`, S3 only
+        // returns the matched rows.
+        let mut body = records_event(b"bob,30\n");
+        body.extend(encode_message(
+            &[(":message-type", "event"), (":event-type", "Stats")],
+            b"<Stats></Stats>",
+        ));
+        body.extend(records_event(b"carol,45\n"));
+        body.extend(end_event());
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/people.csv"))
+            .and(query_param("select-type", "2"))
+            .and(body_string_contains(
+                "<Expression>SELECT * FROM S3Object s WHERE s.age &gt; &apos;18&apos;</Expression>",
+            ))
+            .and(body_string_contains(
+                "<CSV><FileHeaderInfo>USE</FileHeaderInfo></CSV>",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let query = OpSelect::new(
+            "SELECT * FROM S3Object s WHERE s.age > '18'",
+            SelectInput::Csv { has_header: true },
+            SelectOutput::Csv,
+        );
+        let bs: Vec<Bytes> = op.select("people.csv", query).await?.try_collect().await?;
+        assert_eq!(bs.concat(), b"bob,30\ncarol,45\n");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_cross_bucket() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/dst/backup/b.txt"))
+            .and(header("x-amz-copy-source", "src/data/a.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<CopyObjectResult>
+                    <ETag>"etag"</ETag>
+                    <LastModified>2023-01-01T00:00:00.000Z</LastModified>
+                </CopyObjectResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let new_operator = |bucket: &str, root: &str| {
+            let mut builder = S3Builder::default();
+            builder.endpoint(&mock_server.uri());
+            builder.bucket(bucket);
+            builder.root(root);
+            builder.region("us-east-1");
+            builder.access_key_id("access_key_id");
+            builder.secret_access_key("secret_access_key");
+            builder.disable_config_load();
+            Operator::new(builder).map(|op| op.finish())
+        };
+        let src = new_operator("src", "/data")?;
+        let dst = new_operator("dst", "/backup")?;
+
+        src.copy_to("a.txt", &dst, "b.txt").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_cross_bucket_fallback() -> Result<()> {
+        use wiremock::matchers::body_string;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/dst/backup/b.txt"))
+            .and(header("x-amz-copy-source", "src/data/a.txt"))
+            .respond_with(ResponseTemplate::new(301).set_body_string(
+                r#"<Error><Code>PermanentRedirect</Code><Message>Use another endpoint</Message></Error>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/src/data/a.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dst/backup/b.txt"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/dst/backup/b.txt"))
+            .and(query_param("uploadId", "upload"))
+            .and(body_string("hello"))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dst/backup/b.txt"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let new_operator = |bucket: &str, root: &str| {
+            let mut builder = S3Builder::default();
+            builder.endpoint(&mock_server.uri());
+            builder.bucket(bucket);
+            builder.root(root);
+            builder.region("us-east-1");
+            builder.access_key_id("access_key_id");
+            builder.secret_access_key("secret_access_key");
+            builder.disable_config_load();
+            Operator::new(builder).map(|op| op.finish())
+        };
+        let src = new_operator("src", "/data")?;
+        let dst = new_operator("dst", "/backup")?;
+
+        src.copy_to("a.txt", &dst, "b.txt").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_cross_bucket_error() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/dst/backup/b.txt"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"<Error><Code>AccessDenied</Code><Message>Access Denied</Message></Error>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Other errors must not fall back to streaming.
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let new_operator = |bucket: &str, root: &str| {
+            let mut builder = S3Builder::default();
+            builder.endpoint(&mock_server.uri());
+            builder.bucket(bucket);
+            builder.root(root);
+            builder.region("us-east-1");
+            builder.access_key_id("access_key_id");
+            builder.secret_access_key("secret_access_key");
+            builder.disable_config_load();
+            Operator::new(builder).map(|op| op.finish())
+        };
+        let src = new_operator("src", "/data")?;
+        let dst = new_operator("dst", "/backup")?;
+
+        let err = src.copy_to("a.txt", &dst, "b.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_progress() -> Result<()> {
+        use std::sync::Mutex;
+
+        use wiremock::matchers::header_exists;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let large = 6 * 1024 * 1024 * 1024u64;
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/large"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "6442450944"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/small"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/large.bak"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/large.bak"))
+            .and(query_param("uploadId", "upload"))
+            .and(header_exists("x-amz-copy-source-range"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<CopyPartResult><ETag>"etag"</ETag></CopyPartResult>"#),
+            )
+            .expect(12)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/large.bak"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/small.bak"))
+            .and(header_exists("x-amz-copy-source"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<CopyObjectResult><ETag>"etag"</ETag></CopyObjectResult>"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let progress: CopyProgress = {
+            let calls = calls.clone();
+            Arc::new(move |done, total| calls.lock().unwrap().push((done, total)))
+        };
+
+        // Progress is reported per 512 MiB part.
+        op.copy_with(
+            "large",
+            "large.bak",
+            OpCopy::new().with_progress(progress.clone()),
+        )
+        .await?;
+        let part = 512 * 1024 * 1024;
+        let expected: Vec<_> = (1..=12).map(|i| (i * part, large)).collect();
+        assert_eq!(*calls.lock().unwrap(), expected);
+
+        // Single-shot copy reports once.
+        calls.lock().unwrap().clear();
+        op.copy_with("small", "small.bak", OpCopy::new().with_progress(progress))
+            .await?;
+        assert_eq!(*calls.lock().unwrap(), vec![(1024, 1024)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_range() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/src"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "1024"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/slice"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/slice"))
+            .and(query_param("uploadId", "upload"))
+            .and(header("x-amz-copy-source-range", "bytes=100-199"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<CopyPartResult><ETag>"etag"</ETag></CopyPartResult>"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/slice"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        op.copy_with(
+            "src",
+            "slice",
+            OpCopy::new().with_range(BytesRange::from(100..200)),
+        )
+        .await?;
+
+        let err = op
+            .copy_with(
+                "src",
+                "slice",
+                OpCopy::new().with_range(BytesRange::from(2048..)),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_by_parts_with_user_metadata_and_tags() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/src"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "1024")
+                    .insert_header("x-amz-meta-owner", "alice")
+                    .insert_header("x-amz-tagging-count", "2"),
+            )
+            .mount(&mock_server)
+            .await;
+        // Tags are only fetched when they will be preserved.
+        Mock::given(method("GET"))
+            .and(path("/bucket/src"))
+            .and(query_param("tagging", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<Tagging>
+                    <TagSet>
+                        <Tag><Key>team</Key><Value>data eng</Value></Tag>
+                        <Tag><Key>env</Key><Value>prod</Value></Tag>
+                    </TagSet>
+                </Tagging>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/keep"))
+            .and(query_param("uploads", ""))
+            .and(header("x-amz-meta-owner", "alice"))
+            .and(header("x-amz-tagging", "team=data%20eng&env=prod"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/drop"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<CopyPartResult><ETag>"etag"</ETag></CopyPartResult>"#),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let progress: CopyProgress = Arc::new(|_, _| {});
+        op.copy_with(
+            "src",
+            "keep",
+            OpCopy::new()
+                .with_range(BytesRange::from(0..100))
+                .with_progress(progress.clone()),
+        )
+        .await?;
+        op.copy_with(
+            "src",
+            "drop",
+            OpCopy::new()
+                .with_range(BytesRange::from(0..100))
+                .with_progress(progress)
+                .with_preserve_user_metadata(false)
+                .with_preserve_tags(false),
+        )
+        .await?;
+
+        let reqs = mock_server.received_requests().await.unwrap_or_default();
+        let initiate = reqs
+            .iter()
+            .find(|r| r.url.path() == "/bucket/drop" && r.url.query() == Some("uploads"))
+            .expect("upload of drop must be initiated");
+        let names: Vec<_> = initiate.headers.iter().map(|(k, _)| k.as_str()).collect();
+        assert!(!names.contains(&"x-amz-meta-owner"), "{names:?}");
+        assert!(!names.contains(&"x-amz-tagging"), "{names:?}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_bypass_governance() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/a.txt"))
+            .and(header("x-amz-bypass-governance-retention", "true"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/"))
+            .and(query_param("delete", ""))
+            .and(header("x-amz-bypass-governance-retention", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<DeleteResult>
+                    <Deleted><Key>b.txt</Key></Deleted>
+                    <Deleted><Key>c.txt</Key></Deleted>
+                </DeleteResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/locked.txt"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"<Error>
+                    <Code>AccessDenied</Code>
+                    <Message>Access Denied</Message>
+                </Error>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let args = OpDelete::new().with_bypass_governance(true);
+        op.delete_with("a.txt", args.clone()).await?;
+        op.remove_with(vec!["b.txt".to_string(), "c.txt".to_string()], args)
+            .await?;
+
+        let err = op.delete("locked.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_preserve_user_metadata_and_tags() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let copy_result = || {
+            ResponseTemplate::new(200).set_body_string(
+                r#"<CopyObjectResult>
+                    <ETag>"etag"</ETag>
+                    <LastModified>2023-01-01T00:00:00.000Z</LastModified>
+                </CopyObjectResult>"#,
+            )
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/keep.txt"))
+            .and(header("x-amz-metadata-directive", "COPY"))
+            .and(header("x-amz-tagging-directive", "COPY"))
+            .respond_with(copy_result())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/drop.txt"))
+            .and(header("x-amz-metadata-directive", "REPLACE"))
+            .and(header("x-amz-tagging-directive", "REPLACE"))
+            .respond_with(copy_result())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let args = OpCopy::new()
+            .with_preserve_user_metadata(true)
+            .with_preserve_tags(true);
+        op.copy_with("a.txt", "keep.txt", args).await?;
+        let args = OpCopy::new()
+            .with_preserve_user_metadata(false)
+            .with_preserve_tags(false);
+        op.copy_with("a.txt", "drop.txt", args).await?;
+
+        // Other services don't support specifying them.
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("a.txt", "Hello, World!").await?;
+        let err = op
+            .copy_with("a.txt", "b.txt", OpCopy::new().with_preserve_tags(true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+
+    /// ObjectLockResponder keeps the body of last put request of an object
+    /// lock subresource and returns it in get requests.
+    #[derive(Default)]
+    struct ObjectLockResponder {
+        body: std::sync::Mutex<Option<Vec<u8>>>,
+    }
+
+    impl wiremock::Respond for ObjectLockResponder {
+        fn respond(&self, req: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let mut body = self.body.lock().unwrap();
+            if req.method == wiremock::http::Method::Put {
+                *body = Some(req.body.clone());
+                return wiremock::ResponseTemplate::new(200);
+            }
+
+            match body.as_ref() {
+                Some(bs) => wiremock::ResponseTemplate::new(200).set_body_bytes(bs.clone()),
+                None => wiremock::ResponseTemplate::new(404).set_body_string(
+                    r#"<Error>
+                        <Code>NoSuchObjectLockConfiguration</Code>
+                        <Message>The specified object does not have a ObjectLock configuration</Message>
+                    </Error>"#,
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_object_retention_and_legal_hold() -> Result<()> {
+        use chrono::TimeZone;
+        use chrono::Utc;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/bucket/data/a.txt"))
+            .and(query_param("retention", ""))
+            .respond_with(ObjectLockResponder::default())
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/bucket/data/a.txt"))
+            .and(query_param("legal-hold", ""))
+            .respond_with(ObjectLockResponder::default())
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.root("/data");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        assert_eq!(op.get_object_retention("a.txt").await?, None);
+        let retain_until = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+        op.set_object_retention("a.txt", RetentionMode::Governance, retain_until)
+            .await?;
+        assert_eq!(
+            op.get_object_retention("a.txt").await?,
+            Some(ObjectRetention::new(
+                RetentionMode::Governance,
+                retain_until
+            ))
+        );
+
+        assert!(!op.get_legal_hold("a.txt").await?);
+        op.set_legal_hold("a.txt", true).await?;
+        assert!(op.get_legal_hold("a.txt").await?);
+        op.set_legal_hold("a.txt", false).await?;
+        assert!(!op.get_legal_hold("a.txt").await?);
+
+        // Other services don't support object lock.
+        let op = Operator::new(services::Memory::default())?.finish();
+        let err = op.get_legal_hold("a.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_if_none_match() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Matched etag returns the not modified signal before any body read.
+        let args = OpRead::new().with_if_none_match("\"v1\"");
+        let err = op.reader_with("file", args.clone()).await.err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let err = op.read_with("file", args).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let args = OpRead::new().with_if_none_match("\"v0\"");
+        assert_eq!(op.read_with("file", args).await?, b"Hello, World!");
+
+        // Services without conditional read return Unsupported.
+        let op = Operator::new(services::Memory::default())?.finish();
+        let args = OpRead::new().with_if_none_match("\"v1\"");
+        let err = op.read_with("file", args).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_with_skip_if_identical() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .insert_header("etag", "\"65a8e27d8879283831b664bd8b7f0ad4\""),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/multipart"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .insert_header("etag", "\"65a8e27d8879283831b664bd8b7f0ad4-2\""),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        // Only the changed content, the multipart object and the new object are uploaded.
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let args = OpWrite::new().with_skip_if_identical(true);
+        let skipped = op
+            .write_with_skipped("file", args.clone(), "Hello, World!")
+            .await?;
+        assert!(skipped);
+        let skipped = op
+            .write_with_skipped("file", args.clone(), "Hello, world!")
+            .await?;
+        assert!(!skipped);
+        // Multipart etag is not the MD5 of content.
+        let skipped = op
+            .write_with_skipped("multipart", args.clone(), "Hello, World!")
+            .await?;
+        assert!(!skipped);
+        let skipped = op
+            .write_with_skipped("not_exist", args, "Hello, World!")
+            .await?;
+        assert!(!skipped);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_with_require_parent() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        // `dir/` only has objects under it, there is no directory marker.
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/dir/"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/dir/file"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Missing directory marker is treated as an existing dir.
+        op.write_with(
+            "dir/file",
+            OpWrite::new().with_require_parent(true),
+            "Hello, World!",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_object_acl() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/public.txt"))
+            .and(query_param("acl", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+                <AccessControlPolicy xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+                  <Owner>
+                    <ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID>
+                    <DisplayName>owner</DisplayName>
+                  </Owner>
+                  <AccessControlList>
+                    <Grant>
+                      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="CanonicalUser">
+                        <ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID>
+                        <DisplayName>owner</DisplayName>
+                      </Grantee>
+                      <Permission>FULL_CONTROL</Permission>
+                    </Grant>
+                    <Grant>
+                      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Group">
+                        <URI>http://acs.amazonaws.com/groups/global/AllUsers</URI>
+                      </Grantee>
+                      <Permission>READ</Permission>
+                    </Grant>
+                  </AccessControlList>
+                </AccessControlPolicy>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/private.txt"))
+            .and(query_param("acl", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<AccessControlPolicy>
+                  <Owner><ID>owner-id</ID></Owner>
+                  <AccessControlList>
+                    <Grant>
+                      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="CanonicalUser">
+                        <ID>owner</ID>
+                      </Grantee>
+                      <Permission>FULL_CONTROL</Permission>
+                    </Grant>
+                  </AccessControlList>
+                </AccessControlPolicy>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let acl = op
+            .get_object_acl("public.txt")
+            .await?
+            .acl_summary()
+            .unwrap();
+        assert!(acl.is_public());
+        assert!(acl.public_read());
+        assert!(!acl.public_write());
+
+        let acl = op
+            .get_object_acl("private.txt")
+            .await?
+            .acl_summary()
+            .unwrap();
+        assert!(!acl.is_public());
+
+        let meta = op.get_object_owner("public.txt").await?;
+        assert_eq!(meta.owner(), Some("owner"));
+        // Fallback to canonical id without display name.
+        let meta = op.get_object_owner("private.txt").await?;
+        assert_eq!(meta.owner(), Some("owner-id"));
+
+        // Other services don't support acl.
+        let op = Operator::new(services::Memory::default())?.finish();
+        let err = op.get_object_acl("a.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "layers-compression")]
+    #[tokio::test]
+    async fn test_compression_round_trip() -> Result<()> {
+        use std::io::Write;
+
+        use flate2::write::DeflateEncoder;
+        use flate2::Compression;
+        use wiremock::matchers::body_bytes;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        use crate::layers::CompressionLayer;
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello world").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploads", ""))
+            .and(header("x-amz-meta-original-size", "11"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<InitiateMultipartUploadResult>
+                    <Bucket>bucket</Bucket>
+                    <Key>file</Key>
+                    <UploadId>upload</UploadId>
+                </InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/file"))
+            .and(query_param("partNumber", "1"))
+            .and(query_param("uploadId", "upload"))
+            .and(body_bytes(compressed.clone()))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        for m in ["HEAD", "GET"] {
+            Mock::given(method(m))
+                .and(path("/bucket/file"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("x-amz-meta-original-size", "11")
+                        .set_body_bytes(compressed.clone()),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method(m))
+                .and(path("/bucket/plain"))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(compressed.clone()))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let new_builder = || {
+            let mut builder = S3Builder::default();
+            builder.endpoint(&mock_server.uri());
+            builder.bucket("bucket");
+            builder.region("us-east-1");
+            builder.access_key_id("access_key_id");
+            builder.secret_access_key("secret_access_key");
+            builder.disable_config_load();
+            builder
+        };
+        let op = Operator::new(new_builder())?
+            .layer(CompressionLayer)
+            .finish();
+
+        op.write("file", "hello world").await?;
+
+        let meta = op.stat("file").await?;
+        assert_eq!(meta.decompressed_size(), Some(11));
+        assert_eq!(meta.content_length(), compressed.len() as u64);
+        assert_eq!(op.read("file").await?, b"hello world");
+        assert_eq!(op.read("plain").await?, b"hello world");
+
+        let acc = CompressionLayer.layer(new_builder().build()?);
+        let (rp, _) = Accessor::read(&acc, "file", OpRead::new()).await?;
+        assert_eq!(rp.metadata().content_length(), 11);
+        let (rp, _) = Accessor::read(&acc, "plain", OpRead::new()).await?;
+        assert_eq!(rp.metadata().content_length_raw(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_key() -> Result<()> {
+        use wiremock::MockServer;
+
+        let mock_server = MockServer::start().await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.root("/root/");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Root is counted in key length.
+        let long = "a".repeat(1024 - "root/".len() + 1);
+        let err = op.write(&long, "x").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op.create_dir(&format!("{long}/")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op.copy("from", &long).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let err = op.write("line\nbreak", "x").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(err.to_string().contains("control characters"));
+
+        // No request should be sent for invalid keys.
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_range() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("uploads", ""))
+            .and(query_param("prefix", "file"))
+            .and(query_param_is_missing("key-marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListMultipartUploadsResult>
+                  <IsTruncated>true</IsTruncated>
+                  <NextKeyMarker>file</NextKeyMarker>
+                  <NextUploadIdMarker>late</NextUploadIdMarker>
+                  <Upload><Key>file</Key><UploadId>late</UploadId><Initiated>2023-06-01T00:00:01.000Z</Initiated></Upload>
+                </ListMultipartUploadsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("uploads", ""))
+            .and(query_param("prefix", "file"))
+            .and(query_param("key-marker", "file"))
+            .and(query_param("upload-id-marker", "late"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListMultipartUploadsResult>
+                  <IsTruncated>false</IsTruncated>
+                  <Upload><Key>file</Key><UploadId>upload</UploadId><Initiated>2023-06-01T00:00:00.000Z</Initiated></Upload>
+                  <Upload><Key>file2</Key><UploadId>other</UploadId><Initiated>2023-05-01T00:00:00.000Z</Initiated></Upload>
+                </ListMultipartUploadsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        for part_number in ["1", "2", "3"] {
+            Mock::given(method("PUT"))
+                .and(path("/bucket/file"))
+                .and(query_param("uploadId", "upload"))
+                .and(query_param("partNumber", part_number))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                  <IsTruncated>false</IsTruncated>
+                  <Part><PartNumber>1</PartNumber><ETag>"1"</ETag><Size>5242880</Size></Part>
+                  <Part><PartNumber>2</PartNumber><ETag>"2"</ETag><Size>5242880</Size></Part>
+                  <Part><PartNumber>3</PartNumber><ETag>"3"</ETag><Size>1</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "late"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<ListPartsResult><IsTruncated>false</IsTruncated></ListPartsResult>",
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/file"))
+            .and(query_param("uploadId", "late"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // Another writer of `busy` has uploaded parts.
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("uploads", ""))
+            .and(query_param("prefix", "busy"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListMultipartUploadsResult>
+                  <IsTruncated>false</IsTruncated>
+                  <Upload><Key>busy</Key><UploadId>upload</UploadId><Initiated>2023-06-01T00:00:00.000Z</Initiated></Upload>
+                  <Upload><Key>busy</Key><UploadId>writer</UploadId><Initiated>2023-06-01T00:00:01.000Z</Initiated></Upload>
+                </ListMultipartUploadsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/busy"))
+            .and(query_param("uploadId", "writer"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                  <IsTruncated>false</IsTruncated>
+                  <Part><PartNumber>1</PartNumber><ETag>"1"</ETag><Size>5242880</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Ranges are uploaded into the earliest upload out of order.
+        let part_size = 5 * 1024 * 1024;
+        for (offset, size) in [(2 * part_size, 1), (0, part_size), (part_size, part_size)] {
+            let args = OpWriteRange::new(offset).with_part_size(part_size);
+            op.write_range_with("file", args, vec![0; size as usize])
+                .await?;
+        }
+        op.finalize_ranged_write("file").await?;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        // The upload is completed on the key directly.
+        assert!(requests
+            .iter()
+            .filter(|v| v.method == wiremock::http::Method::Put)
+            .all(|v| v.url.query().unwrap_or_default().contains("uploadId")));
+        // Complete request must list parts in order.
+        let complete = requests
+            .iter()
+            .find(|v| v.method == wiremock::http::Method::Post)
+            .expect("complete request must be sent");
+        let body = String::from_utf8_lossy(&complete.body);
+        let idx: Vec<_> = ["<PartNumber>1<", "<PartNumber>2<", "<PartNumber>3<"]
+            .iter()
+            .map(|v| body.find(v).expect("part must be completed"))
+            .collect();
+        assert!(idx.windows(2).all(|v| v[0] < v[1]), "{body}");
+
+        // Uploads with parts of other writers are never aborted.
+        let err = op.finalize_ranged_write("busy").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(requests
+            .iter()
+            .filter(|v| v.url.path() == "/bucket/busy")
+            .all(|v| v.method == wiremock::http::Method::Get));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_encryption() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let key_id = "arn:aws:kms:us-east-1:123456789012:key/example";
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/kms"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-amz-server-side-encryption", "aws:kms")
+                    .insert_header("x-amz-server-side-encryption-aws-kms-key-id", key_id),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/sse-s3"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("x-amz-server-side-encryption", "AES256"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/sse-c"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-amz-server-side-encryption-customer-algorithm", "AES256"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/plain"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let meta = op.stat("kms").await?;
+        assert_eq!(
+            meta.encryption(),
+            Some(&EncryptionInfo::SseKms {
+                key_id: Some(key_id.to_string())
+            })
+        );
+        let meta = op.stat("sse-s3").await?;
+        assert_eq!(meta.encryption(), Some(&EncryptionInfo::SseS3));
+        let meta = op.stat("sse-c").await?;
+        assert_eq!(meta.encryption(), Some(&EncryptionInfo::SseC));
+        let meta = op.stat("plain").await?;
+        assert_eq!(meta.encryption(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_clamp_range() -> Result<()> {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        // The object has been truncated to 50 bytes.
+        for range in ["bytes=0-99", "bytes=100-199"] {
+            Mock::given(method("GET"))
+                .and(path("/bucket/log"))
+                .and(header("range", range))
+                .respond_with(
+                    ResponseTemplate::new(416).insert_header("content-range", "bytes */50"),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/log"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "50"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/log"))
+            .and(header("range", "bytes=0-49"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 0-49/50")
+                    .set_body_bytes(vec![1; 50]),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Strict by default.
+        let err = op.range_read("log", 0..100).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+
+        let args = OpRead::new().with_clamp_range(true);
+        let bs = op.range_read_with("log", 0..100, args.clone()).await?;
+        assert_eq!(bs, vec![1; 50]);
+        let bs = op.range_read_with("log", 100..200, args).await?;
+        assert!(bs.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_versions() -> Result<()> {
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        // Versions of `doc` are listed before other keys under it, so the
+        // page after `doc.bak` must not be requested.
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("versions", ""))
+            .and(query_param("prefix", "doc"))
+            .and(query_param("key-marker", "doc.bak"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<ListVersionsResult><IsTruncated>false</IsTruncated></ListVersionsResult>",
+            ))
+            .expect(0)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("versions", ""))
+            .and(query_param("prefix", "doc"))
+            .and(query_param("key-marker", "doc"))
+            .and(query_param("version-id-marker", "v3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListVersionsResult>
+                    <IsTruncated>true</IsTruncated>
+                    <NextKeyMarker>doc.bak</NextKeyMarker>
+                    <NextVersionIdMarker>b1</NextVersionIdMarker>
+                    <Version><Key>doc</Key><VersionId>v2</VersionId><LastModified>2023-05-02T00:00:00.000Z</LastModified></Version>
+                    <Version><Key>doc</Key><VersionId>v1</VersionId><LastModified>2023-05-01T00:00:00.000Z</LastModified></Version>
+                    <Version><Key>doc.bak</Key><VersionId>b1</VersionId><LastModified>2023-05-01T00:00:00.000Z</LastModified></Version>
+                </ListVersionsResult>"#,
+            ))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("versions", ""))
+            .and(query_param("prefix", "doc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListVersionsResult>
+                    <IsTruncated>true</IsTruncated>
+                    <NextKeyMarker>doc</NextKeyMarker>
+                    <NextVersionIdMarker>v3</NextVersionIdMarker>
+                    <DeleteMarker><Key>doc</Key><VersionId>v4</VersionId><LastModified>2023-05-04T00:00:00.000Z</LastModified></DeleteMarker>
+                    <Version><Key>doc</Key><VersionId>v3</VersionId><LastModified>2023-05-03T00:00:00.000Z</LastModified></Version>
+                </ListVersionsResult>"#,
+            ))
+            .up_to_n_times(1)
+            .with_priority(2)
+            .mount(&mock_server)
+            .await;
+        // All versions have been purged.
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("versions", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<ListVersionsResult><IsTruncated>false</IsTruncated></ListVersionsResult>",
+            ))
+            .with_priority(3)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/"))
+            .and(query_param("delete", ""))
+            .and(body_string_contains("<VersionId>v4</VersionId>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<DeleteResult>
+                    <Deleted><Key>doc</Key><VersionId>v1</VersionId></Deleted>
+                    <Deleted><Key>doc</Key><VersionId>v2</VersionId></Deleted>
+                    <Deleted><Key>doc</Key><VersionId>v3</VersionId></Deleted>
+                    <Deleted><Key>doc</Key><VersionId>v4</VersionId></Deleted>
+                </DeleteResult>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/doc"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        assert_eq!(op.delete_all_versions("doc").await?, 4);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body = requests
+            .iter()
+            .find(|req| req.method == wiremock::http::Method::Post)
+            .map(|req| String::from_utf8(req.body.clone()).unwrap())
+            .unwrap();
+        for v in ["v1", "v2", "v3", "v4"] {
+            assert!(body.contains(&format!("<VersionId>{v}</VersionId>")));
+        }
+        assert!(!body.contains("b1"), "versions of other keys must be kept");
+
+        assert_eq!(op.delete_all_versions("doc").await?, 0);
+        assert!(!op.is_exist("doc").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_at() -> Result<()> {
+        use chrono::TimeZone;
+        use chrono::Utc;
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        // `file` is written at 05-01 and overwritten at 05-03, `dir/file`
+        // is written at 05-03 and deleted at 05-04.
+        Mock::given(method("GET"))
+            .and(path("/bucket"))
+            .and(query_param("versions", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListVersionsResult>
+                    <IsTruncated>false</IsTruncated>
+                    <DeleteMarker><Key>dir/file</Key><VersionId>d2</VersionId><LastModified>2023-05-04T00:00:00.000Z</LastModified></DeleteMarker>
+                    <Version><Key>dir/file</Key><VersionId>d1</VersionId><LastModified>2023-05-03T00:00:00.000Z</LastModified><Size>1</Size></Version>
+                    <Version><Key>file</Key><VersionId>v2</VersionId><LastModified>2023-05-03T00:00:00.000Z</LastModified><Size>3</Size></Version>
+                    <Version><Key>file</Key><VersionId>v1</VersionId><LastModified>2023-05-01T00:00:00.000Z</LastModified><Size>3</Size></Version>
+                </ListVersionsResult>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+        for (version, content) in [("v1", "old"), ("v2", "new"), ("d1", "d")] {
+            let object = if version == "d1" {
+                "/bucket/dir/file"
+            } else {
+                "/bucket/file"
+            };
+            Mock::given(method("GET"))
+                .and(path(object))
+                .and(query_param("versionId", version))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("x-amz-version-id", version)
+                        .set_body_string(content),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path(object))
+                .and(query_param("versionId", version))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("x-amz-version-id", version)
+                        .insert_header("content-length", content.len().to_string().as_str()),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+
+        let mut builder = S3Builder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        // Pinned between two writes of `file`.
+        let snapshot = op.snapshot_at(Utc.with_ymd_and_hms(2023, 5, 2, 0, 0, 0).unwrap());
+        assert_eq!(snapshot.read("file").await?, b"old");
+        let meta = snapshot.stat("file").await?;
+        assert_eq!(meta.version(), Some("v1"));
+        assert_eq!(meta.content_length(), 3);
+        let err = snapshot.stat("dir/file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        let entries: Vec<_> = snapshot.list("/").await?.try_collect().await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["file"]);
+
+        let err = snapshot.write("file", "x").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = snapshot.delete("file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // `dir/file` is alive between its write and delete.
+        let snapshot = op.snapshot_at(Utc.with_ymd_and_hms(2023, 5, 3, 12, 0, 0).unwrap());
+        assert_eq!(snapshot.read("file").await?, b"new");
+        assert_eq!(snapshot.read("dir/file").await?, b"d");
+        let entries: Vec<_> = snapshot.list("/").await?.try_collect().await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/", "file"]);
+        assert!(snapshot.stat("dir/").await?.is_dir());
+
+        // Deleted after the delete marker.
+        let snapshot = op.snapshot_at(Utc.with_ymd_and_hms(2023, 5, 5, 0, 0, 0).unwrap());
+        let err = snapshot.read("dir/file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Nothing exists before the first write.
+        let snapshot = op.snapshot_at(Utc.with_ymd_and_hms(2023, 4, 30, 0, 0, 0).unwrap());
+        let err = snapshot.read("file").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
 }
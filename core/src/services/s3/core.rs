@@ -22,6 +22,7 @@ use std::fmt::Write;
 use std::time::Duration;
 
 use bytes::Bytes;
+use chrono::SecondsFormat;
 use http::header::HeaderName;
 use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
@@ -29,20 +30,31 @@ use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use reqsign::AwsCredential;
 use reqsign::AwsLoader;
 use reqsign::AwsV4Signer;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::ops::*;
 use crate::raw::*;
 use crate::*;
 
-mod constants {
+pub(super) mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_IF_MATCH: &str = "x-amz-copy-source-if-match";
+    pub const X_AMZ_COPY_SOURCE_RANGE: &str = "x-amz-copy-source-range";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
+    pub const X_AMZ_TAGGING_DIRECTIVE: &str = "x-amz-tagging-directive";
+    pub const X_AMZ_TAGGING: &str = "x-amz-tagging";
+    pub const X_AMZ_TAGGING_COUNT: &str = "x-amz-tagging-count";
+    pub const X_AMZ_BYPASS_GOVERNANCE_RETENTION: &str = "x-amz-bypass-governance-retention";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -62,6 +74,10 @@ mod constants {
     pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5: &str =
         "x-amz-copy-source-server-side-encryption-customer-key-md5";
 
+    pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
+    pub const X_AMZ_META_ORIGINAL_SIZE: &str = "x-amz-meta-original-size";
+    pub const X_AMZ_VERSION_ID: &str = "x-amz-version-id";
+
     pub const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
     pub const RESPONSE_CACHE_CONTROL: &str = "response-cache-control";
 }
@@ -205,6 +221,51 @@ impl S3Core {
 
         req
     }
+
+    /// Insert SSE-C headers of the copy source, so that objects encrypted
+    /// by customer key could be read by copy.
+    pub fn insert_copy_source_sse_headers(
+        &self,
+        mut req: http::request::Builder,
+    ) -> http::request::Builder {
+        if let Some(v) = &self.server_side_encryption_customer_algorithm {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                ),
+                v,
+            )
+        }
+
+        if let Some(v) = &self.server_side_encryption_customer_key {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+                ),
+                v,
+            )
+        }
+
+        if let Some(v) = &self.server_side_encryption_customer_key_md5 {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                ),
+                v,
+            )
+        }
+
+        req
+    }
 }
 
 impl S3Core {
@@ -213,10 +274,15 @@ impl S3Core {
         path: &str,
         if_none_match: Option<&str>,
         if_match: Option<&str>,
+        version: Option<&str>,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+
+        if let Some(version) = version {
+            url.push_str(&format!("?versionId={}", percent_encode_path(version)));
+        }
 
         let mut req = Request::head(&url);
 
@@ -237,15 +303,7 @@ impl S3Core {
         Ok(req)
     }
 
-    pub fn s3_get_object_request(
-        &self,
-        path: &str,
-        range: BytesRange,
-        override_content_disposition: Option<&str>,
-        override_cache_control: Option<&str>,
-        if_none_match: Option<&str>,
-        if_match: Option<&str>,
-    ) -> Result<Request<AsyncBody>> {
+    pub fn s3_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         // Construct headers to add to the request
@@ -253,35 +311,39 @@ impl S3Core {
 
         // Add query arguments to the URL based on response overrides
         let mut query_args = Vec::new();
-        if let Some(override_content_disposition) = override_content_disposition {
+        if let Some(override_content_disposition) = args.override_content_disposition() {
             query_args.push(format!(
                 "{}={}",
                 constants::RESPONSE_CONTENT_DISPOSITION,
                 percent_encode_path(override_content_disposition)
             ))
         }
-        if let Some(override_cache_control) = override_cache_control {
+        if let Some(override_cache_control) = args.override_cache_control() {
             query_args.push(format!(
                 "{}={}",
                 constants::RESPONSE_CACHE_CONTROL,
                 percent_encode_path(override_cache_control)
             ))
         }
+        if let Some(version) = args.version() {
+            query_args.push(format!("versionId={}", percent_encode_path(version)))
+        }
         if !query_args.is_empty() {
             url.push_str(&format!("?{}", query_args.join("&")));
         }
 
         let mut req = Request::get(&url);
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
-        if let Some(if_none_match) = if_none_match {
+        if let Some(if_none_match) = args.if_none_match() {
             req = req.header(IF_NONE_MATCH, if_none_match);
         }
 
-        if let Some(if_match) = if_match {
+        if let Some(if_match) = args.if_match() {
             req = req.header(IF_MATCH, if_match);
         }
         // Set SSE headers.
@@ -298,19 +360,9 @@ impl S3Core {
     pub async fn s3_get_object(
         &self,
         path: &str,
-        range: BytesRange,
-        if_none_match: Option<&str>,
-        if_match: Option<&str>,
-        override_content_disposition: Option<&str>,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.s3_get_object_request(
-            path,
-            range,
-            override_content_disposition,
-            None,
-            if_none_match,
-            if_match,
-        )?;
+        let mut req = self.s3_get_object_request(path, args)?;
 
         self.sign(&mut req).await?;
 
@@ -367,20 +419,36 @@ impl S3Core {
         path: &str,
         if_none_match: Option<&str>,
         if_match: Option<&str>,
+        version: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.s3_head_object_request(path, if_none_match, if_match)?;
+        let mut req = self.s3_head_object_request(path, if_none_match, if_match, version)?;
 
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
-    pub async fn s3_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    pub async fn s3_delete_object(
+        &self,
+        path: &str,
+        bypass_governance: bool,
+        if_match: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
 
-        let mut req = Request::delete(&url)
+        let mut req = Request::delete(&url);
+
+        if bypass_governance {
+            req = req.header(constants::X_AMZ_BYPASS_GOVERNANCE_RETENTION, "true");
+        }
+
+        if let Some(if_match) = if_match {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -389,15 +457,31 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// Copy object from `from` to `to`.
+    ///
+    /// If `source_bucket` is set, `from` is an absolute path inside that
+    /// bucket and will not be joined with root.
     pub async fn s3_copy_object(
         &self,
+        source_bucket: Option<&str>,
         from: &str,
         to: &str,
+        preserve_user_metadata: Option<bool>,
+        preserve_tags: Option<bool>,
+        if_match: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let from = build_abs_path(&self.root, from);
+        let source = match source_bucket {
+            Some(bucket) => {
+                let from = from.trim_start_matches('/');
+                format!("{}/{}", bucket, percent_encode_path(from))
+            }
+            None => {
+                let from = build_abs_path(&self.root, from);
+                format!("{}/{}", self.bucket, percent_encode_path(&from))
+            }
+        };
         let to = build_abs_path(&self.root, to);
 
-        let source = format!("{}/{}", self.bucket, percent_encode_path(&from));
         let target = format!("{}/{}", self.endpoint, percent_encode_path(&to));
 
         let mut req = Request::put(&target);
@@ -405,44 +489,66 @@ impl S3Core {
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
-        if let Some(v) = &self.server_side_encryption_customer_algorithm {
-            let mut v = v.clone();
-            v.set_sensitive(true);
-
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
-                ),
-                v,
-            )
+        // `REPLACE` without any user metadata or tags in request will drop them.
+        let directive = |preserve: bool| if preserve { "COPY" } else { "REPLACE" };
+        if let Some(v) = preserve_user_metadata {
+            req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, directive(v));
+        }
+        if let Some(v) = preserve_tags {
+            req = req.header(constants::X_AMZ_TAGGING_DIRECTIVE, directive(v));
+        }
+        if let Some(v) = if_match {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
         }
 
-        if let Some(v) = &self.server_side_encryption_customer_key {
-            let mut v = v.clone();
-            v.set_sensitive(true);
+        // Set SSE-C headers of copy source.
+        req = self.insert_copy_source_sse_headers(req);
 
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
-                ),
-                v,
-            )
-        }
+        let mut req = req
+            .header(constants::X_AMZ_COPY_SOURCE, percent_encode_path(&source))
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
 
-        if let Some(v) = &self.server_side_encryption_customer_key_md5 {
-            let mut v = v.clone();
-            v.set_sensitive(true);
+        self.sign(&mut req).await?;
 
-            req = req.header(
-                HeaderName::from_static(
-                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
-                ),
-                v,
-            )
+        self.send(req).await
+    }
+
+    /// Copy a range of `from` as a part of multipart upload to `to`.
+    pub async fn s3_upload_part_copy(
+        &self,
+        from: &str,
+        to: &str,
+        upload_id: &str,
+        part_number: usize,
+        range: BytesRange,
+        if_match: Option<&str>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let from = build_abs_path(&self.root, from);
+        let source = format!("{}/{}", self.bucket, percent_encode_path(&from));
+        let to = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            percent_encode_path(&to),
+            part_number,
+            percent_encode_path(upload_id)
+        );
+
+        let mut req = Request::put(&url);
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+        req = self.insert_copy_source_sse_headers(req);
+
+        if let Some(v) = if_match {
+            req = req.header(constants::X_AMZ_COPY_SOURCE_IF_MATCH, v);
         }
 
         let mut req = req
             .header(constants::X_AMZ_COPY_SOURCE, percent_encode_path(&source))
+            .header(constants::X_AMZ_COPY_SOURCE_RANGE, range.to_header())
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
@@ -505,6 +611,7 @@ impl S3Core {
         content_type: Option<&str>,
         content_disposition: Option<&str>,
         cache_control: Option<&str>,
+        headers: HeaderMap,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -535,6 +642,7 @@ impl S3Core {
         let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
+        req.headers_mut().extend(headers);
 
         self.sign(&mut req).await?;
 
@@ -612,6 +720,101 @@ impl S3Core {
         self.send(req).await
     }
 
+    /// List on-going multipart uploads of the exact path.
+    pub async fn s3_list_multipart_uploads(
+        &self,
+        path: &str,
+        key_marker: &str,
+        upload_id_marker: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?uploads&prefix={}",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+        if !key_marker.is_empty() {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if !upload_id_marker.is_empty() {
+            write!(
+                url,
+                "&upload-id-marker={}",
+                percent_encode_path(upload_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// List versions and delete markers of the exact path.
+    pub async fn s3_list_object_versions(
+        &self,
+        path: &str,
+        key_marker: &str,
+        version_id_marker: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?versions&prefix={}",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+        if !key_marker.is_empty() {
+            write!(url, "&key-marker={}", percent_encode_path(key_marker))
+                .expect("write into string must succeed");
+        }
+        if !version_id_marker.is_empty() {
+            write!(
+                url,
+                "&version-id-marker={}",
+                percent_encode_path(version_id_marker)
+            )
+            .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// List uploaded parts of a multipart upload.
+    pub async fn s3_list_parts(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number_marker: usize,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            percent_encode_path(upload_id)
+        );
+        if part_number_marker > 0 {
+            write!(url, "&part-number-marker={part_number_marker}")
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     /// Abort an on-going multipart upload.
     pub async fn s3_abort_multipart_upload(
         &self,
@@ -637,20 +840,53 @@ impl S3Core {
     pub async fn s3_delete_objects(
         &self,
         paths: Vec<String>,
+        bypass_governance: bool,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let objects = paths
+            .into_iter()
+            .map(|path| DeleteObjectsRequestObject {
+                key: build_abs_path(&self.root, &path),
+                version_id: None,
+            })
+            .collect();
+
+        self.s3_delete_objects_request(objects, bypass_governance)
+            .await
+    }
+
+    /// Delete given versions of the exact path in one request.
+    pub async fn s3_delete_object_versions(
+        &self,
+        path: &str,
+        version_ids: Vec<String>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let key = build_abs_path(&self.root, path);
+        let objects = version_ids
+            .into_iter()
+            .map(|version_id| DeleteObjectsRequestObject {
+                key: key.clone(),
+                version_id: Some(version_id),
+            })
+            .collect();
+
+        self.s3_delete_objects_request(objects, false).await
+    }
+
+    async fn s3_delete_objects_request(
+        &self,
+        objects: Vec<DeleteObjectsRequestObject>,
+        bypass_governance: bool,
     ) -> Result<Response<IncomingAsyncBody>> {
         let url = format!("{}/?delete", self.endpoint);
 
-        let req = Request::post(&url);
+        let mut req = Request::post(&url);
 
-        let content = quick_xml::se::to_string(&DeleteObjectsRequest {
-            object: paths
-                .into_iter()
-                .map(|path| DeleteObjectsRequestObject {
-                    key: build_abs_path(&self.root, &path),
-                })
-                .collect(),
-        })
-        .map_err(new_xml_deserialize_error)?;
+        if bypass_governance {
+            req = req.header(constants::X_AMZ_BYPASS_GOVERNANCE_RETENTION, "true");
+        }
+
+        let content = quick_xml::se::to_string(&DeleteObjectsRequest { object: objects })
+            .map_err(new_xml_deserialize_error)?;
 
         // Make sure content length has been set to avoid post with chunked encoding.
         let req = req.header(CONTENT_LENGTH, content.len());
@@ -667,8 +903,251 @@ impl S3Core {
 
         self.send(req).await
     }
+
+    pub async fn s3_put_object_retention(
+        &self,
+        path: &str,
+        retention: &ObjectRetention,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?retention", self.endpoint, percent_encode_path(&p));
+
+        let mode = match retention.mode() {
+            RetentionMode::Governance => "GOVERNANCE",
+            RetentionMode::Compliance => "COMPLIANCE",
+        };
+        let content = quick_xml::se::to_string(&ObjectRetentionConfiguration {
+            mode: mode.to_string(),
+            retain_until_date: retention
+                .retain_until()
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        self.s3_put_object_lock_subresource(url, content).await
+    }
+
+    pub async fn s3_get_object_retention(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?retention", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_put_object_legal_hold(
+        &self,
+        path: &str,
+        enabled: bool,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?legal-hold", self.endpoint, percent_encode_path(&p));
+
+        let content = quick_xml::se::to_string(&ObjectLegalHold {
+            status: if enabled { "ON" } else { "OFF" }.to_string(),
+        })
+        .map_err(new_xml_deserialize_error)?;
+
+        self.s3_put_object_lock_subresource(url, content).await
+    }
+
+    pub async fn s3_get_object_legal_hold(
+        &self,
+        path: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?legal-hold", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_get_object_tagging(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?tagging", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_get_object_acl(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}?acl", self.endpoint, percent_encode_path(&p));
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    async fn s3_put_object_lock_subresource(
+        &self,
+        url: String,
+        content: String,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let req = Request::put(&url);
+
+        let req = req.header(CONTENT_LENGTH, content.len());
+        let req = req.header(CONTENT_TYPE, "application/xml");
+        // Set content-md5 as required by API.
+        let req = req.header("CONTENT-MD5", format_content_md5(content.as_bytes()));
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+
+    pub async fn s3_select_object_content(
+        &self,
+        path: &str,
+        query: &OpSelect,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?select&select-type=2",
+            self.endpoint,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::post(&url);
+
+        let content = quick_xml::se::to_string(&SelectObjectContentRequest::new(query))
+            .map_err(new_xml_deserialize_error)?;
+
+        // Make sure content length has been set to avoid post with chunked encoding.
+        let req = req.header(CONTENT_LENGTH, content.len());
+        // Set content-type to `application/xml` to avoid mixed with form post.
+        let req = req.header(CONTENT_TYPE, "application/xml");
+
+        // Set SSE-C headers so that encrypted objects can be selected.
+        let req = self.insert_sse_headers(req, false);
+
+        let mut req = req
+            .body(AsyncBody::Bytes(Bytes::from(content)))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        self.send(req).await
+    }
+}
+
+/// Request of SelectObjectContent.
+#[derive(Default, Debug, Serialize)]
+#[serde(
+    default,
+    rename = "SelectObjectContentRequest",
+    rename_all = "PascalCase"
+)]
+pub struct SelectObjectContentRequest {
+    pub expression: String,
+    pub expression_type: String,
+    pub input_serialization: SelectInputSerialization,
+    pub output_serialization: SelectOutputSerialization,
 }
 
+impl SelectObjectContentRequest {
+    pub fn new(query: &OpSelect) -> Self {
+        let mut input = SelectInputSerialization::default();
+        match query.input() {
+            SelectInput::Csv { has_header } => {
+                input.csv = Some(SelectCsvInput {
+                    file_header_info: if has_header { "USE" } else { "NONE" }.to_string(),
+                })
+            }
+            SelectInput::Json { lines } => {
+                input.json = Some(SelectJsonInput {
+                    r#type: if lines { "LINES" } else { "DOCUMENT" }.to_string(),
+                })
+            }
+            SelectInput::Parquet => input.parquet = Some(SelectParquetInput {}),
+        }
+
+        let mut output = SelectOutputSerialization::default();
+        match query.output() {
+            SelectOutput::Csv => output.csv = Some(SelectCsvOutput {}),
+            SelectOutput::Json => output.json = Some(SelectJsonOutput {}),
+        }
+
+        Self {
+            expression: query.expression().to_string(),
+            expression_type: "SQL".to_string(),
+            input_serialization: input,
+            output_serialization: output,
+        }
+    }
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectInputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<SelectCsvInput>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<SelectJsonInput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parquet: Option<SelectParquetInput>,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectCsvInput {
+    pub file_header_info: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectJsonInput {
+    pub r#type: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectParquetInput {}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SelectOutputSerialization {
+    #[serde(rename = "CSV", skip_serializing_if = "Option::is_none")]
+    pub csv: Option<SelectCsvOutput>,
+    #[serde(rename = "JSON", skip_serializing_if = "Option::is_none")]
+    pub json: Option<SelectJsonOutput>,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectCsvOutput {}
+
+#[derive(Default, Debug, Serialize)]
+pub struct SelectJsonOutput {}
+
 /// Result of CreateMultipartUpload
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -676,6 +1155,141 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Build headers for write args, including the ones that are only
+/// supported by s3.
+pub fn build_s3_write_headers(op: &OpWrite) -> Result<HeaderMap> {
+    let mut headers = build_write_headers(op)?;
+    if let Some(v) = op.decompressed_size() {
+        headers.insert(
+            HeaderName::from_static(constants::X_AMZ_META_ORIGINAL_SIZE),
+            HeaderValue::from(v),
+        );
+    }
+    Ok(headers)
+}
+
+/// Parse decompressed size of object from user metadata in response headers.
+pub fn parse_decompressed_size(headers: &HeaderMap) -> Result<Option<u64>> {
+    match headers.get(constants::X_AMZ_META_ORIGINAL_SIZE) {
+        None => Ok(None),
+        Some(v) => Ok(Some(
+            v.to_str()
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "header value is not valid utf-8 string",
+                    )
+                    .with_operation("s3::parse_decompressed_size")
+                    .set_source(e)
+                })?
+                .parse::<u64>()
+                .map_err(|e| {
+                    Error::new(ErrorKind::Unexpected, "header value is not valid integer")
+                        .with_operation("s3::parse_decompressed_size")
+                        .set_source(e)
+                })?,
+        )),
+    }
+}
+
+/// Parse version id of object from response headers.
+///
+/// The header is only returned by buckets with versioning enabled.
+pub fn parse_version(headers: &HeaderMap) -> Result<Option<&str>> {
+    parse_header_to_str(headers, constants::X_AMZ_VERSION_ID)
+}
+
+/// Parse server side encryption of object from response headers.
+pub fn parse_encryption(headers: &HeaderMap) -> Result<Option<EncryptionInfo>> {
+    if parse_header_to_str(
+        headers,
+        constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+    )?
+    .is_some()
+    {
+        return Ok(Some(EncryptionInfo::SseC));
+    }
+
+    let info = match parse_header_to_str(headers, constants::X_AMZ_SERVER_SIDE_ENCRYPTION)? {
+        Some("AES256") => Some(EncryptionInfo::SseS3),
+        // `aws:kms:dsse` is dual-layer encryption with kms keys.
+        Some(v) if v.starts_with("aws:kms") => Some(EncryptionInfo::SseKms {
+            key_id: parse_header_to_str(
+                headers,
+                constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+            )?
+            .map(|v| v.to_string()),
+        }),
+        _ => None,
+    };
+    Ok(info)
+}
+
+/// Result of ListMultipartUploads
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResult {
+    pub upload: Vec<ListMultipartUploadsResultUpload>,
+    pub is_truncated: bool,
+    pub next_key_marker: String,
+    pub next_upload_id_marker: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListMultipartUploadsResultUpload {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: String,
+}
+
+/// Result of ListParts
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListPartsResult {
+    pub part: Vec<ListPartsResultPart>,
+    pub is_truncated: bool,
+    pub next_part_number_marker: usize,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListPartsResultPart {
+    pub part_number: usize,
+    #[serde(rename = "ETag")]
+    pub etag: String,
+    pub size: u64,
+}
+
+/// Result of ListObjectVersions
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListObjectVersionsResult {
+    pub is_truncated: bool,
+    pub next_key_marker: String,
+    pub next_version_id_marker: String,
+    pub version: Vec<ListObjectVersionsResultItem>,
+    pub delete_marker: Vec<ListObjectVersionsResultItem>,
+}
+
+/// Version or delete marker in ListObjectVersions.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ListObjectVersionsResultItem {
+    pub key: String,
+    pub version_id: String,
+    pub last_modified: String,
+    pub size: u64,
+}
+
+/// Result of UploadPartCopy
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CopyPartResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -731,6 +1345,8 @@ pub struct DeleteObjectsRequest {
 #[serde(rename_all = "PascalCase")]
 pub struct DeleteObjectsRequestObject {
     pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
 }
 
 /// Result of DeleteObjects.
@@ -755,6 +1371,141 @@ pub struct DeleteObjectsResultError {
     pub message: String,
 }
 
+/// Request and result of object retention.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Retention", rename_all = "PascalCase")]
+pub struct ObjectRetentionConfiguration {
+    pub mode: String,
+    pub retain_until_date: String,
+}
+
+/// Request and result of object legal hold.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "LegalHold", rename_all = "PascalCase")]
+pub struct ObjectLegalHold {
+    pub status: String,
+}
+
+/// Result of get object tagging.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ObjectTagging {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+impl ObjectTagging {
+    /// Encode tags as url query parameters used by `x-amz-tagging`.
+    pub fn to_header_value(&self) -> String {
+        self.tag_set
+            .tag
+            .iter()
+            .map(|t| {
+                format!(
+                    "{}={}",
+                    utf8_percent_encode(&t.key, NON_ALPHANUMERIC),
+                    utf8_percent_encode(&t.value, NON_ALPHANUMERIC)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+}
+
+/// The grantee uri of all users, including anonymous users.
+const ALL_USERS_URI: &str = "http://acs.amazonaws.com/groups/global/AllUsers";
+/// The grantee uri of all authenticated aws users.
+const AUTHENTICATED_USERS_URI: &str = "http://acs.amazonaws.com/groups/global/AuthenticatedUsers";
+
+/// Result of get object acl.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct AccessControlPolicy {
+    pub owner: AccessControlOwner,
+    pub access_control_list: AccessControlList,
+}
+
+impl AccessControlPolicy {
+    /// Get the owner of object, prefer display name over canonical id.
+    ///
+    /// Display name is not returned in most regions.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner
+            .display_name
+            .as_deref()
+            .or(self.owner.id.as_deref())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Summarize the grants to everyone.
+    ///
+    /// Grants to all authenticated users are treated as public too since
+    /// anyone could sign up an aws account.
+    pub fn summary(&self) -> AclSummary {
+        let (mut public_read, mut public_write) = (false, false);
+
+        for grant in &self.access_control_list.grants {
+            let public = matches!(
+                grant.grantee.uri.as_deref(),
+                Some(ALL_USERS_URI) | Some(AUTHENTICATED_USERS_URI)
+            );
+            if !public {
+                continue;
+            }
+
+            match grant.permission.as_str() {
+                "READ" => public_read = true,
+                "WRITE" => public_write = true,
+                "FULL_CONTROL" => (public_read, public_write) = (true, true),
+                _ => {}
+            }
+        }
+
+        AclSummary::new(public_read, public_write)
+    }
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct AccessControlOwner {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct AccessControlList {
+    #[serde(rename = "Grant")]
+    pub grants: Vec<AccessControlGrant>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct AccessControlGrant {
+    pub grantee: AccessControlGrantee,
+    pub permission: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct AccessControlGrantee {
+    #[serde(rename = "URI")]
+    pub uri: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -783,6 +1534,106 @@ mod tests {
         )
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListParts.html#API_ListParts_Examples
+    #[test]
+    fn test_deserialize_list_parts_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListPartsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Bucket>example-bucket</Bucket>
+              <Key>example-object</Key>
+              <UploadId>XXBsb2FkIElEIGZvciBlbHZpbmcncyVcdS1tb3ZpZS5tMnRzEEEwbG9hZA</UploadId>
+              <PartNumberMarker>1</PartNumberMarker>
+              <NextPartNumberMarker>3</NextPartNumberMarker>
+              <MaxParts>2</MaxParts>
+              <IsTruncated>true</IsTruncated>
+              <Part>
+                <PartNumber>2</PartNumber>
+                <LastModified>2010-11-10T20:48:34.000Z</LastModified>
+                <ETag>"7778aef83f66abc1fa1e8477f296d394"</ETag>
+                <Size>10485760</Size>
+              </Part>
+              <Part>
+                <PartNumber>3</PartNumber>
+                <LastModified>2010-11-10T20:48:33.000Z</LastModified>
+                <ETag>"aaaa18db4cc2f85cedef654fccc4a4x8"</ETag>
+                <Size>10485760</Size>
+              </Part>
+            </ListPartsResult>"#,
+        );
+
+        let out: ListPartsResult = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated);
+        assert_eq!(out.next_part_number_marker, 3);
+        assert_eq!(out.part.len(), 2);
+        assert_eq!(out.part[0].part_number, 2);
+        assert_eq!(out.part[0].etag, "\"7778aef83f66abc1fa1e8477f296d394\"");
+        assert_eq!(out.part[1].size, 10485760);
+    }
+
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectVersions.html#API_ListObjectVersions_Examples
+    #[test]
+    fn test_deserialize_list_object_versions_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Name>bucket</Name>
+              <Prefix>my</Prefix>
+              <KeyMarker/>
+              <VersionIdMarker/>
+              <NextKeyMarker>my-image.jpg</NextKeyMarker>
+              <NextVersionIdMarker>3/L4kqtJl40Nr8X8gdRQBpUMLUo</NextVersionIdMarker>
+              <MaxKeys>5</MaxKeys>
+              <IsTruncated>true</IsTruncated>
+              <Version>
+                <Key>my-image.jpg</Key>
+                <VersionId>3/L4kqtJl40Nr8X8gdRQBpUMLUo</VersionId>
+                <IsLatest>false</IsLatest>
+                <LastModified>2009-10-12T17:50:30.000Z</LastModified>
+                <ETag>"fba9dede5f27731c9771645a39863328"</ETag>
+                <Size>434234</Size>
+                <StorageClass>STANDARD</StorageClass>
+              </Version>
+              <DeleteMarker>
+                <Key>my-second-image.jpg</Key>
+                <VersionId>03jpff543dhffds434rfdsFDN943fdsFkdmqnh892</VersionId>
+                <IsLatest>true</IsLatest>
+                <LastModified>2009-11-12T17:50:30.000Z</LastModified>
+              </DeleteMarker>
+              <Version>
+                <Key>my-second-image.jpg</Key>
+                <VersionId>QUpfdndhfd8438MNFDN93jdnJFkdmqnh893</VersionId>
+                <IsLatest>false</IsLatest>
+                <LastModified>2009-10-10T17:50:30.000Z</LastModified>
+                <ETag>"9b2cf535f27731c974343645a3985328"</ETag>
+                <Size>166434</Size>
+                <StorageClass>STANDARD</StorageClass>
+              </Version>
+            </ListVersionsResult>"#,
+        );
+
+        let out: ListObjectVersionsResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated);
+        assert_eq!(out.next_key_marker, "my-image.jpg");
+        assert_eq!(out.next_version_id_marker, "3/L4kqtJl40Nr8X8gdRQBpUMLUo");
+        assert_eq!(out.version.len(), 2);
+        assert_eq!(out.version[1].key, "my-second-image.jpg");
+        assert_eq!(
+            out.version[1].version_id,
+            "QUpfdndhfd8438MNFDN93jdnJFkdmqnh893"
+        );
+        assert_eq!(out.version[1].last_modified, "2009-10-10T17:50:30.000Z");
+        assert_eq!(out.version[1].size, 166434);
+        assert_eq!(out.delete_marker.len(), 1);
+        assert_eq!(
+            out.delete_marker[0].version_id,
+            "03jpff543dhffds434rfdsFDN943fdsFkdmqnh892"
+        );
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Examples
     #[test]
     fn test_serialize_complete_multipart_upload_request() {
@@ -835,9 +1686,11 @@ mod tests {
             object: vec![
                 DeleteObjectsRequestObject {
                     key: "sample1.txt".to_string(),
+                    version_id: None,
                 },
                 DeleteObjectsRequestObject {
                     key: "sample2.txt".to_string(),
+                    version_id: None,
                 },
             ],
         };
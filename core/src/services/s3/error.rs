@@ -16,6 +16,8 @@
 // under the License.
 
 use bytes::Buf;
+use bytes::Bytes;
+use http::response::Parts;
 use http::Response;
 use http::StatusCode;
 use quick_xml::de;
@@ -26,6 +28,9 @@ use crate::Error;
 use crate::ErrorKind;
 use crate::Result;
 
+/// The error code returned if object doesn't have retention or legal hold.
+pub const NO_SUCH_OBJECT_LOCK_CONFIGURATION: &str = "NoSuchObjectLockConfiguration";
+
 /// S3Error is the error returned by s3 service.
 #[derive(Default, Debug, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
@@ -41,12 +46,25 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
+    Ok(parse_error_with_parts(parts, bs))
+}
+
+/// Parse the error code from error response body.
+pub fn parse_error_code(bs: &Bytes) -> Option<String> {
+    de::from_reader::<_, S3Error>(bs.clone().reader())
+        .ok()
+        .map(|s3_err| s3_err.code)
+}
+
+/// Parse error response which body has been consumed into Error.
+pub fn parse_error_with_parts(parts: Parts, bs: Bytes) -> Error {
     let (mut kind, mut retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
             (ErrorKind::ConditionNotMatch, false)
         }
+        StatusCode::RANGE_NOT_SATISFIABLE => (ErrorKind::RangeNotSatisfied, false),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -68,7 +86,23 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         err = err.set_temporary();
     }
 
-    Ok(err)
+    err
+}
+
+/// Returns whether the error means the source bucket of a cross bucket
+/// copy lives behind another endpoint (or region) than the target one.
+pub fn is_cross_endpoint_error(status: StatusCode, code: Option<&str>) -> bool {
+    matches!(
+        status,
+        StatusCode::MOVED_PERMANENTLY | StatusCode::TEMPORARY_REDIRECT
+    ) || matches!(
+        code,
+        Some(
+            "PermanentRedirect"
+                | "AuthorizationHeaderMalformed"
+                | "IllegalLocationConstraintException"
+        )
+    )
 }
 
 /// Returns the Errorkind of this code and whether the error is retryable.
@@ -95,6 +129,11 @@ pub fn parse_s3_error_code(code: &str) -> Option<(ErrorKind, bool)> {
         // indicates a temporary issue with the service or server, such as high load,
         // maintenance, or an internal problem.
         "ServiceUnavailable" => Some((ErrorKind::Unexpected, true)),
+        // > Access Denied
+        //
+        // Returned while deleting locked objects without the permission
+        // to bypass governance retention.
+        "AccessDenied" => Some((ErrorKind::PermissionDenied, false)),
         _ => None,
     }
 }
@@ -106,7 +145,7 @@ mod tests {
     /// Error response example is from https://docs.aws.amazon.com/AmazonS3/latest/API/ErrorResponses.html
     #[test]
     fn test_parse_error() {
-        let bs = bytes::Bytes::from(
+        let bs = Bytes::from(
             r#"
 <?xml version="1.0" encoding="UTF-8"?>
 <Error>
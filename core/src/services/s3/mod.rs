@@ -21,4 +21,5 @@ pub use backend::S3Builder as S3;
 mod core;
 mod error;
 mod pager;
+mod select;
 mod writer;
@@ -0,0 +1,347 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::raw::*;
+use crate::*;
+
+/// Length of prelude: total length, headers length and prelude crc.
+const PRELUDE_LEN: usize = 12;
+/// Length of the crc at the end of message.
+const MESSAGE_CRC_LEN: usize = 4;
+
+/// S3SelectReader decodes the event stream returned by `SelectObjectContent`
+/// and yields the payloads of `Records` events.
+///
+/// Both the prelude CRC and the message CRC are verified, messages that
+/// don't match will be returned as errors.
+///
+/// Reference: <https://docs.aws.amazon.com/AmazonS3/latest/API/RESTSelectObjectAppendix.html>
+pub struct S3SelectReader {
+    inner: IncomingAsyncBody,
+    buf: BytesMut,
+    chunk: Option<Bytes>,
+    done: bool,
+}
+
+impl S3SelectReader {
+    pub fn new(inner: IncomingAsyncBody) -> Self {
+        Self {
+            inner,
+            buf: BytesMut::new(),
+            chunk: None,
+            done: false,
+        }
+    }
+}
+
+/// A decoded message of event stream.
+#[derive(Debug, Default)]
+struct Message {
+    message_type: String,
+    event_type: String,
+    error_code: String,
+    error_message: String,
+    payload: Bytes,
+}
+
+/// Decode a message from the start of buf.
+///
+/// Returns `Ok(None)` if the buf doesn't contain a complete message yet.
+fn decode_message(buf: &mut BytesMut) -> Result<Option<Message>> {
+    if buf.len() < PRELUDE_LEN {
+        return Ok(None);
+    }
+
+    // Lengths can't be trusted before the prelude has been verified.
+    let prelude_crc = u32::from_be_bytes([buf[8], buf[9], buf[10], buf[11]]);
+    if crc32fast::hash(&buf[..8]) != prelude_crc {
+        return Err(new_event_stream_error("prelude crc mismatch"));
+    }
+
+    let total_len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    let headers_len = u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+    if total_len < PRELUDE_LEN + headers_len + MESSAGE_CRC_LEN {
+        return Err(new_event_stream_error("message length is invalid")
+            .with_context("total_length", total_len.to_string())
+            .with_context("headers_length", headers_len.to_string()));
+    }
+    if buf.len() < total_len {
+        return Ok(None);
+    }
+
+    let mut bs = buf.split_to(total_len).freeze();
+    let (content, crc) = bs.split_at(total_len - MESSAGE_CRC_LEN);
+    if crc32fast::hash(content) != u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]) {
+        return Err(new_event_stream_error("message crc mismatch"));
+    }
+    bs.advance(PRELUDE_LEN);
+    let mut headers = bs.split_to(headers_len);
+    let payload = bs.split_to(total_len - PRELUDE_LEN - headers_len - MESSAGE_CRC_LEN);
+
+    let mut msg = Message {
+        payload,
+        ..Default::default()
+    };
+    while headers.has_remaining() {
+        let (name, value) = decode_header(&mut headers)?;
+        let value = match value {
+            Some(v) => v,
+            None => continue,
+        };
+        match name.as_str() {
+            ":message-type" => msg.message_type = value,
+            ":event-type" => msg.event_type = value,
+            ":error-code" => msg.error_code = value,
+            ":error-message" => msg.error_message = value,
+            _ => {}
+        }
+    }
+
+    Ok(Some(msg))
+}
+
+/// Decode a header, only string values will be returned, others are skipped.
+fn decode_header(bs: &mut Bytes) -> Result<(String, Option<String>)> {
+    let name_len = take(bs, 1)?[0] as usize;
+    let name = String::from_utf8_lossy(&take(bs, name_len)?).to_string();
+
+    let value_type = take(bs, 1)?[0];
+    let value = match value_type {
+        // bool true and bool false
+        0 | 1 => None,
+        // byte
+        2 => take(bs, 1).map(|_| None)?,
+        // short
+        3 => take(bs, 2).map(|_| None)?,
+        // integer
+        4 => take(bs, 4).map(|_| None)?,
+        // long and timestamp
+        5 | 8 => take(bs, 8).map(|_| None)?,
+        // byte array
+        6 => {
+            let len = take(bs, 2)?.get_u16() as usize;
+            take(bs, len).map(|_| None)?
+        }
+        // string
+        7 => {
+            let len = take(bs, 2)?.get_u16() as usize;
+            Some(String::from_utf8_lossy(&take(bs, len)?).to_string())
+        }
+        // uuid
+        9 => take(bs, 16).map(|_| None)?,
+        v => {
+            return Err(new_event_stream_error("header value type is invalid")
+                .with_context("type", v.to_string()))
+        }
+    };
+
+    Ok((name, value))
+}
+
+fn take(bs: &mut Bytes, n: usize) -> Result<Bytes> {
+    if bs.remaining() < n {
+        return Err(new_event_stream_error("header is truncated"));
+    }
+    Ok(bs.split_to(n))
+}
+
+fn new_event_stream_error(msg: &str) -> Error {
+    Error::new(ErrorKind::Unexpected, msg).with_operation("S3SelectReader::poll_next")
+}
+
+impl oio::Read for S3SelectReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, mut buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let mut bs = match ready!(self.poll_next(cx)) {
+            Some(Ok(bs)) => bs,
+            Some(Err(err)) => return Poll::Ready(Err(err)),
+            None => return Poll::Ready(Ok(0)),
+        };
+
+        let amt = min(bs.len(), buf.len());
+        buf.put_slice(&bs[..amt]);
+        bs.advance(amt);
+        if !bs.is_empty() {
+            self.chunk = Some(bs);
+        }
+
+        Poll::Ready(Ok(amt))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (_, _) = (cx, pos);
+
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "output reader doesn't support seeking",
+        )))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if let Some(bs) = self.chunk.take() {
+            return Poll::Ready(Some(Ok(bs)));
+        }
+
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match decode_message(&mut self.buf)? {
+                Some(msg) if msg.message_type == "error" => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(Error::new(
+                        ErrorKind::Unexpected,
+                        &msg.error_message,
+                    )
+                    .with_operation("S3SelectReader::poll_next")
+                    .with_context("code", msg.error_code))));
+                }
+                Some(msg) => match msg.event_type.as_str() {
+                    "Records" if !msg.payload.is_empty() => {
+                        return Poll::Ready(Some(Ok(msg.payload)))
+                    }
+                    "End" => self.done = true,
+                    // Stats, Progress and Cont events are ignored.
+                    _ => {}
+                },
+                None => match ready!(oio::Read::poll_next(&mut self.inner, cx)) {
+                    Some(Ok(bs)) => self.buf.extend_from_slice(&bs),
+                    Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                    None => {
+                        self.done = true;
+                        return Poll::Ready(Some(Err(new_event_stream_error(
+                            "event stream ended without End event",
+                        ))));
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Encode a message of event stream with string headers.
+    pub fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut hs = Vec::new();
+        for (k, v) in headers {
+            hs.put_u8(k.len() as u8);
+            hs.put_slice(k.as_bytes());
+            hs.put_u8(7);
+            hs.put_u16(v.len() as u16);
+            hs.put_slice(v.as_bytes());
+        }
+
+        let total = PRELUDE_LEN + hs.len() + payload.len() + MESSAGE_CRC_LEN;
+        let mut bs = Vec::with_capacity(total);
+        bs.put_u32(total as u32);
+        bs.put_u32(hs.len() as u32);
+        bs.put_u32(crc32fast::hash(&bs));
+        bs.put_slice(&hs);
+        bs.put_slice(payload);
+        bs.put_u32(crc32fast::hash(&bs));
+        bs
+    }
+
+    pub fn records_event(payload: &[u8]) -> Vec<u8> {
+        encode_message(
+            &[
+                (":message-type", "event"),
+                (":event-type", "Records"),
+                (":content-type", "application/octet-stream"),
+            ],
+            payload,
+        )
+    }
+
+    pub fn end_event() -> Vec<u8> {
+        encode_message(&[(":message-type", "event"), (":event-type", "End")], &[])
+    }
+
+    #[test]
+    fn test_decode_message() {
+        let mut bs = BytesMut::new();
+        bs.extend_from_slice(&records_event(b"a,1\n"));
+        bs.extend_from_slice(&end_event());
+
+        // Incomplete message should wait for more data.
+        let mut partial = BytesMut::from(&bs[..10]);
+        assert!(decode_message(&mut partial).unwrap().is_none());
+
+        let msg = decode_message(&mut bs).unwrap().unwrap();
+        assert_eq!(msg.message_type, "event");
+        assert_eq!(msg.event_type, "Records");
+        assert_eq!(msg.payload.as_ref(), b"a,1\n");
+
+        let msg = decode_message(&mut bs).unwrap().unwrap();
+        assert_eq!(msg.event_type, "End");
+        assert!(msg.payload.is_empty());
+        assert!(bs.is_empty());
+    }
+
+    #[test]
+    fn test_decode_crc_mismatch() {
+        let bs = records_event(b"a,1\n");
+
+        let mut prelude = bs.clone();
+        prelude[2] ^= 1;
+        let err = decode_message(&mut BytesMut::from(&prelude[..])).unwrap_err();
+        assert!(err.to_string().contains("prelude crc mismatch"), "{err}");
+
+        let mut payload = bs;
+        let idx = payload.len() - MESSAGE_CRC_LEN - 1;
+        payload[idx] ^= 1;
+        let err = decode_message(&mut BytesMut::from(&payload[..])).unwrap_err();
+        assert!(err.to_string().contains("message crc mismatch"), "{err}");
+    }
+
+    #[test]
+    fn test_decode_error_message() {
+        let mut bs = BytesMut::from(
+            &encode_message(
+                &[
+                    (":message-type", "error"),
+                    (":error-code", "InvalidQuery"),
+                    (":error-message", "query is invalid"),
+                ],
+                &[],
+            )[..],
+        );
+
+        let msg = decode_message(&mut bs).unwrap().unwrap();
+        assert_eq!(msg.message_type, "error");
+        assert_eq!(msg.error_code, "InvalidQuery");
+        assert_eq!(msg.error_message, "query is invalid");
+    }
+}
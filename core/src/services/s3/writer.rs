@@ -28,6 +28,15 @@ use crate::ops::OpWrite;
 use crate::raw::*;
 use crate::*;
 
+/// AWS S3 requires part number must between [1..=10000]
+const MAX_PART_NUMBER: usize = 10000;
+/// AWS S3 allows parts up to 5 GiB.
+const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// AWS S3 requires parts except the last one to be at least 5 MiB.
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+/// Count of parts uploaded before the part size doubles in adaptive mode.
+const ADAPTIVE_PART_STEP: usize = 1000;
+
 pub struct S3Writer {
     core: Arc<S3Core>,
 
@@ -41,18 +50,20 @@ pub struct S3Writer {
 }
 
 impl S3Writer {
-    pub fn new(core: Arc<S3Core>, path: &str, op: OpWrite) -> Self {
+    pub fn new(core: Arc<S3Core>, path: &str, op: OpWrite) -> Result<Self> {
+        let (upload_id, parts) = resume_parts(op.resume_parts())?;
+
         let buffer_size = core.write_min_size;
-        S3Writer {
+        Ok(S3Writer {
             core,
             path: path.to_string(),
             op,
 
-            upload_id: None,
-            parts: vec![],
+            upload_id,
+            parts,
             buffer: oio::VectorCursor::new(),
             buffer_size,
-        }
+        })
     }
 
     async fn write_oneshot(&self, bs: Bytes) -> Result<()> {
@@ -64,6 +75,7 @@ impl S3Writer {
             self.op.cache_control(),
             AsyncBody::Bytes(bs),
         )?;
+        req.headers_mut().extend(build_s3_write_headers(&self.op)?);
 
         self.core.sign(&mut req).await?;
 
@@ -88,6 +100,7 @@ impl S3Writer {
                 self.op.content_type(),
                 self.op.content_disposition(),
                 self.op.cache_control(),
+                build_s3_write_headers(&self.op)?,
             )
             .await?;
 
@@ -106,6 +119,15 @@ impl S3Writer {
         }
     }
 
+    /// Size of the next part to upload.
+    fn part_size(&self) -> usize {
+        if self.op.adaptive_parts() {
+            adaptive_part_size(self.buffer_size, self.parts.len())
+        } else {
+            self.buffer_size
+        }
+    }
+
     async fn write_part(
         &self,
         upload_id: &str,
@@ -113,6 +135,7 @@ impl S3Writer {
     ) -> Result<CompleteMultipartUploadRequestPart> {
         // AWS S3 requires part number must between [1..=10000]
         let part_number = self.parts.len() + 1;
+        let size = bs.len() as u64;
 
         let mut req = self.core.s3_upload_part_request(
             &self.path,
@@ -141,6 +164,10 @@ impl S3Writer {
 
                 resp.into_body().consume().await?;
 
+                if let Some(callback) = self.op.part_callback() {
+                    callback(&PartInfo::new(upload_id, part_number, &etag, size));
+                }
+
                 Ok(CompleteMultipartUploadRequestPart { part_number, etag })
             }
             _ => Err(parse_error(resp).await?),
@@ -148,6 +175,50 @@ impl S3Writer {
     }
 }
 
+/// Calculate the part size after `uploaded` parts in adaptive mode.
+///
+/// Part size starts from `min_size` and doubles every `ADAPTIVE_PART_STEP`
+/// parts, so that `MAX_PART_NUMBER` parts could hold
+/// `min_size * 1023 * ADAPTIVE_PART_STEP` bytes.
+fn adaptive_part_size(min_size: usize, uploaded: usize) -> usize {
+    let steps = (uploaded / ADAPTIVE_PART_STEP).min(MAX_PART_NUMBER / ADAPTIVE_PART_STEP - 1);
+    let size = (min_size as u64)
+        .saturating_mul(1 << steps)
+        .min(MAX_PART_SIZE.max(min_size as u64));
+    usize::try_from(size).unwrap_or(usize::MAX)
+}
+
+/// Restore the upload id and completed parts of a resumed upload.
+///
+/// Parts must belong to the same upload and be numbered from 1 without
+/// gaps, so that following parts can be numbered after them.
+fn resume_parts(
+    parts: &[PartInfo],
+) -> Result<(Option<String>, Vec<CompleteMultipartUploadRequestPart>)> {
+    let upload_id = match parts.first() {
+        Some(part) => part.upload_id(),
+        None => return Ok((None, vec![])),
+    };
+
+    let mut completed = Vec::with_capacity(parts.len());
+    for (idx, part) in parts.iter().enumerate() {
+        if part.upload_id() != upload_id || part.part_number() != idx + 1 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "resume parts must belong to the same upload and be contiguous from 1",
+            )
+            .with_context("upload_id", part.upload_id())
+            .with_context("part_number", part.part_number().to_string()));
+        }
+        completed.push(CompleteMultipartUploadRequestPart {
+            part_number: part.part_number(),
+            etag: part.etag().to_string(),
+        });
+    }
+
+    Ok((Some(upload_id.to_string()), completed))
+}
+
 #[async_trait]
 impl oio::Write for S3Writer {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
@@ -170,12 +241,13 @@ impl oio::Write for S3Writer {
         }
 
         self.buffer.push(bs);
+        let part_size = self.part_size();
         // Return directly if the buffer is not full
-        if self.buffer.len() <= self.buffer_size {
+        if self.buffer.len() <= part_size {
             return Ok(());
         }
 
-        let bs = self.buffer.peak_at_least(self.buffer_size);
+        let bs = self.buffer.peak_at_least(part_size);
         let size = bs.len();
 
         match self.write_part(upload_id, bs).await {
@@ -214,6 +286,29 @@ impl oio::Write for S3Writer {
         }
     }
 
+    /// Upload all buffered data as a new part.
+    ///
+    /// Parts except the last one must be at least 5 MiB, so smaller data
+    /// is kept in buffer until following writes or `close`.
+    async fn flush(&mut self) -> Result<()> {
+        let upload_id = if let Some(upload_id) = &self.upload_id {
+            upload_id
+        } else {
+            return Ok(());
+        };
+
+        if self.buffer.len() < MIN_PART_SIZE {
+            return Ok(());
+        }
+
+        let bs = self.buffer.peak_exact(self.buffer.len());
+        let part = self.write_part(upload_id, bs).await?;
+        self.buffer.clear();
+        self.parts.push(part);
+
+        Ok(())
+    }
+
     async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
             upload_id
@@ -253,3 +348,257 @@ impl oio::Write for S3Writer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::adaptive_part_size;
+    use super::MAX_PART_NUMBER;
+    use super::MAX_PART_SIZE;
+    use super::MIN_PART_SIZE;
+    use crate::ops::OpWrite;
+    use crate::services::S3;
+    use crate::ErrorKind;
+    use crate::Operator;
+    use crate::PartInfo;
+    use crate::Result;
+
+    #[tokio::test]
+    async fn test_flush_keeps_small_part() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/hello"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/hello"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part\""))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let uploaded_parts = || async {
+            mock_server
+                .received_requests()
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|req| req.method == wiremock::http::Method::Put)
+                .map(|req| req.body.len())
+                .collect::<Vec<_>>()
+        };
+
+        let mut w = op.writer("hello").await?;
+        w.write("Hello, ").await?;
+        w.write("World!").await?;
+        assert!(uploaded_parts().await.is_empty());
+
+        // Data smaller than the minimum part size is kept in buffer.
+        w.flush().await?;
+        assert!(uploaded_parts().await.is_empty());
+
+        w.write(vec![0; MIN_PART_SIZE]).await?;
+        w.flush().await?;
+        assert_eq!(uploaded_parts().await, vec![MIN_PART_SIZE + 13]);
+
+        // Nothing is buffered, flush again should be a no-op.
+        w.flush().await?;
+        assert_eq!(uploaded_parts().await.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_resume_parts() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/hello"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/hello"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part\""))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/hello"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let checkpoint = Arc::new(Mutex::new(Vec::new()));
+        let cp = checkpoint.clone();
+        let mut w = op
+            .writer_with(
+                "hello",
+                OpWrite::new().with_part_callback(Arc::new(move |part: &PartInfo| {
+                    cp.lock().unwrap().push(part.clone())
+                })),
+            )
+            .await?;
+        w.write(vec![b'a'; MIN_PART_SIZE]).await?;
+        w.flush().await?;
+        w.write(vec![b'b'; MIN_PART_SIZE]).await?;
+        w.flush().await?;
+        // Simulate a crash: the writer is dropped without close.
+        drop(w);
+
+        let parts = checkpoint.lock().unwrap().clone();
+        let size = MIN_PART_SIZE as u64;
+        assert_eq!(
+            parts,
+            vec![
+                PartInfo::new("upload", 1, "\"part\"", size),
+                PartInfo::new("upload", 2, "\"part\"", size),
+            ]
+        );
+
+        let mut w = op
+            .writer_with("hello", OpWrite::new().with_resume_parts(parts))
+            .await?;
+        w.write("!").await?;
+        w.close().await?;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let initiated = requests
+            .iter()
+            .filter(|req| req.url.query_pairs().any(|(k, _)| k == "uploads"))
+            .count();
+        assert_eq!(initiated, 1, "resumed writer must not start a new upload");
+
+        let last_part = requests
+            .iter()
+            .rev()
+            .find(|req| req.method == wiremock::http::Method::Put)
+            .unwrap();
+        assert!(last_part.url.query().unwrap().contains("partNumber=3"));
+        assert_eq!(last_part.body, b"!".to_vec());
+
+        let complete = String::from_utf8(requests.last().unwrap().body.clone()).unwrap();
+        for n in 1..=3 {
+            assert!(complete.contains(&format!("<PartNumber>{n}</PartNumber>")));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_part_size() {
+        let min_size = 8 * 1024 * 1024;
+        assert_eq!(adaptive_part_size(min_size, 0), min_size);
+        assert_eq!(adaptive_part_size(min_size, 999), min_size);
+        assert_eq!(adaptive_part_size(min_size, 1000), 2 * min_size);
+
+        // Stream an unsized 5 TiB body, the max object size of s3.
+        let total: u64 = 5 * 1024 * 1024 * 1024 * 1024;
+        let (mut written, mut parts) = (0, 0);
+        while written < total {
+            let size = adaptive_part_size(min_size, parts) as u64;
+            assert!(size <= MAX_PART_SIZE);
+            written += size;
+            parts += 1;
+        }
+        assert!(parts <= MAX_PART_NUMBER, "{parts} parts used");
+
+        // Fixed min part size can't hold it.
+        assert!(total / min_size as u64 > MAX_PART_NUMBER as u64);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_content_language() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/hello"))
+            .and(header("content-language", "en-US"))
+            .and(header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/hello"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .insert_header("content-language", "en-US"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = S3::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        op.write_with(
+            "hello",
+            OpWrite::new()
+                .with_content_language("en-US")
+                .with_http_header("Content-Encoding", "gzip"),
+            "Hello, World!",
+        )
+        .await?;
+
+        let meta = op.stat("hello").await?;
+        assert_eq!(meta.content_language(), Some("en-US"));
+
+        let err = op
+            .write_with(
+                "hello",
+                OpWrite::new().with_http_header("Connection", "close"),
+                "Hello, World!",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        Ok(())
+    }
+}
@@ -190,6 +190,8 @@ impl SftpBuilder {
 impl Builder for SftpBuilder {
     const SCHEME: Scheme = Scheme::Sftp;
     type Accessor = SftpBackend;
+    const CONFIG_KEYS: &'static [&'static str] =
+        &["root", "endpoint", "user", "key", "known_hosts_strategy"];
 
     fn build(&mut self) -> Result<Self::Accessor> {
         debug!("sftp backend build started: {:?}", &self);
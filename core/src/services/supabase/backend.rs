@@ -159,6 +159,7 @@ impl SupabaseBuilder {
 impl Builder for SupabaseBuilder {
     const SCHEME: Scheme = Scheme::Supabase;
     type Accessor = SupabaseBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "bucket", "endpoint", "key"];
 
     fn from_map(map: std::collections::HashMap<String, String>) -> Self {
         let mut builder = SupabaseBuilder::default();
@@ -287,7 +288,7 @@ impl Accessor for SupabaseBackend {
         ))
     }
 
-    async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -298,8 +299,18 @@ impl Accessor for SupabaseBackend {
         // we then use get_object_info to get the actual error info
         let mut resp = self.core.supabase_head_object(path).await?;
 
+        // Users only asking for keys carried by head don't need the
+        // detailed error info, so we can save the get_object_info call.
+        let head_only = !args.metakey().contains(Metakey::Complete)
+            && (Metakey::Mode | Metakey::ContentLength | Metakey::ContentType)
+                .contains(args.metakey());
+
         match resp.status() {
             StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::NOT_FOUND if head_only && path.ends_with('/') => {
+                Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
+            }
+            _ if head_only => Err(parse_error(resp).await?),
             _ => {
                 resp = self.core.supabase_get_object_info(path).await?;
                 match resp.status() {
@@ -328,3 +339,77 @@ impl Accessor for SupabaseBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    async fn new_operator(mock_server: &MockServer) -> Result<Operator> {
+        let mut builder = SupabaseBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        Operator::new(builder).map(|op| op.finish())
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_minimal_metakey() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/storage/v1/object/public/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/object/info/public/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                r#"{"statusCode":"404","error":"not_found","message":"Object not found"}"#,
+            ))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let op = new_operator(&mock_server).await?;
+        let err = op
+            .stat_with(
+                "not_exist",
+                OpStat::new().with_metakey(Metakey::Mode | Metakey::ContentLength),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_complete_metakey() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/storage/v1/object/public/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/storage/v1/object/info/public/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                r#"{"statusCode":"404","error":"not_found","message":"Object not found"}"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let op = new_operator(&mock_server).await?;
+        let err = op.stat("not_exist").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+}
@@ -39,7 +39,9 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (mut kind, mut retryable) = (ErrorKind::Unexpected, false);
+    // Fallback to the status code if the body doesn't carry an error, for
+    // example the response of HEAD requests.
+    let (mut kind, mut retryable) = parse_status_code(parts.status);
     let (message, _) = from_slice::<SupabaseError>(&bs)
         .map(|sb_err| {
             (kind, retryable) = parse_supabase_error(&sb_err);
@@ -60,6 +62,10 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
 fn parse_supabase_error(err: &SupabaseError) -> (ErrorKind, bool) {
     let code = err.status_code.parse::<u16>().unwrap();
     let status_code = StatusCode::from_u16(code).unwrap();
+    parse_status_code(status_code)
+}
+
+fn parse_status_code(status_code: StatusCode) -> (ErrorKind, bool) {
     match status_code {
         StatusCode::CONFLICT => (ErrorKind::AlreadyExists, false),
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
@@ -701,6 +701,26 @@ impl WasabiBuilder {
 impl Builder for WasabiBuilder {
     const SCHEME: Scheme = Scheme::Wasabi;
     type Accessor = WasabiBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &[
+        "root",
+        "bucket",
+        "endpoint",
+        "region",
+        "access_key_id",
+        "secret_access_key",
+        "security_token",
+        "role_arn",
+        "external_id",
+        "server_side_encryption",
+        "server_side_encryption_aws_kms_key_id",
+        "server_side_encryption_customer_algorithm",
+        "server_side_encryption_customer_key",
+        "server_side_encryption_customer_key_md5",
+        "disable_config_load",
+        "disable_ec2_metadata",
+        "enable_virtual_host_style",
+        "default_storage_class",
+    ];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = WasabiBuilder::default();
@@ -179,6 +179,8 @@ impl WebdavBuilder {
 impl Builder for WebdavBuilder {
     const SCHEME: Scheme = Scheme::Webdav;
     type Accessor = WebdavBackend;
+    const CONFIG_KEYS: &'static [&'static str] =
+        &["root", "endpoint", "username", "password", "token"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = WebdavBuilder::default();
@@ -175,6 +175,7 @@ impl WebhdfsBuilder {
 impl Builder for WebhdfsBuilder {
     const SCHEME: Scheme = Scheme::Webhdfs;
     type Accessor = WebhdfsBackend;
+    const CONFIG_KEYS: &'static [&'static str] = &["root", "endpoint", "delegation"];
 
     fn from_map(map: HashMap<String, String>) -> Self {
         let mut builder = WebhdfsBuilder::default();
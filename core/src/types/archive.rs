@@ -0,0 +1,444 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use bytes::Bytes;
+
+use crate::*;
+
+/// ArchiveFormat is the format of archive used by
+/// [`Operator::read_archive_member`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ArchiveFormat {
+    /// Zip archive with a central directory.
+    ///
+    /// Members can be fetched by ranged reads without downloading the
+    /// whole archive. Only stored and deflated members are supported,
+    /// zip64 and encrypted archives are not. Deflated members require the
+    /// `archive-deflate` feature.
+    Zip,
+    /// Uncompressed tar archive.
+    ///
+    /// Tar doesn't have an index, the archive has to be read from the
+    /// beginning until the member is found.
+    Tar,
+}
+
+/// The max size of zip end of central directory record with comment.
+pub(crate) const ZIP_EOCD_MAX_SIZE: u64 = 22 + u16::MAX as u64;
+
+/// The size of zip local file header without name and extra field.
+pub(crate) const ZIP_LOCAL_HEADER_SIZE: u64 = 30;
+
+/// The size of tar header block.
+pub(crate) const TAR_BLOCK_SIZE: usize = 512;
+
+const ZIP_EOCD_SIGNATURE: u32 = 0x06054b50;
+const ZIP_CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const ZIP_LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+
+const ZIP_METHOD_STORED: u16 = 0;
+#[cfg(feature = "archive-deflate")]
+const ZIP_METHOD_DEFLATED: u16 = 8;
+
+/// The max size to pre-allocate for inflated zip member.
+#[cfg(feature = "archive-deflate")]
+const ZIP_INFLATE_PREALLOC_SIZE: u64 = 16 * 1024 * 1024;
+
+/// The max size of tar long name and pax header entries.
+pub(crate) const TAR_META_MAX_SIZE: u64 = 64 * 1024;
+
+fn new_archive_error(message: &'static str) -> Error {
+    Error::new(ErrorKind::Unexpected, message)
+}
+
+fn u16_at(bs: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([bs[pos], bs[pos + 1]])
+}
+
+fn u32_at(bs: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([bs[pos], bs[pos + 1], bs[pos + 2], bs[pos + 3]])
+}
+
+/// Find the range of central directory from the tail of zip archive.
+///
+/// Returns the offset and size of central directory.
+pub(crate) fn find_zip_central_directory(tail: &[u8]) -> Result<(u64, u64)> {
+    if tail.len() < 22 {
+        return Err(new_archive_error("zip archive is too small"));
+    }
+
+    let pos = (0..=tail.len() - 22)
+        .rev()
+        .find(|&pos| u32_at(tail, pos) == ZIP_EOCD_SIGNATURE)
+        .ok_or_else(|| new_archive_error("zip end of central directory not found"))?;
+
+    let entries = u16_at(tail, pos + 10);
+    let size = u32_at(tail, pos + 12);
+    let offset = u32_at(tail, pos + 16);
+    if entries == u16::MAX || size == u32::MAX || offset == u32::MAX {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "zip64 archive is not supported",
+        ));
+    }
+
+    Ok((offset as u64, size as u64))
+}
+
+/// ZipMember is a member of zip archive found in central directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ZipMember {
+    method: u16,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    local_header_offset: u64,
+}
+
+impl ZipMember {
+    /// Find the member with given name in central directory.
+    pub(crate) fn find(cd: &[u8], name: &str) -> Result<Option<ZipMember>> {
+        let mut pos = 0;
+        while pos + 46 <= cd.len() {
+            if u32_at(cd, pos) != ZIP_CENTRAL_HEADER_SIGNATURE {
+                return Err(new_archive_error("invalid zip central directory header"));
+            }
+
+            let name_len = u16_at(cd, pos + 28) as usize;
+            let extra_len = u16_at(cd, pos + 30) as usize;
+            let comment_len = u16_at(cd, pos + 32) as usize;
+            let next = pos + 46 + name_len + extra_len + comment_len;
+            if next > cd.len() {
+                return Err(new_archive_error("zip central directory is truncated"));
+            }
+
+            if &cd[pos + 46..pos + 46 + name_len] == name.as_bytes() {
+                if u16_at(cd, pos + 8) & 1 != 0 {
+                    return Err(Error::new(
+                        ErrorKind::Unsupported,
+                        "encrypted zip member is not supported",
+                    ));
+                }
+
+                return Ok(Some(ZipMember {
+                    method: u16_at(cd, pos + 10),
+                    compressed_size: u32_at(cd, pos + 20) as u64,
+                    uncompressed_size: u32_at(cd, pos + 24) as u64,
+                    local_header_offset: u32_at(cd, pos + 42) as u64,
+                }));
+            }
+
+            pos = next;
+        }
+
+        Ok(None)
+    }
+
+    /// The offset of local file header in archive.
+    pub(crate) fn local_header_offset(&self) -> u64 {
+        self.local_header_offset
+    }
+
+    /// The size of compressed data.
+    pub(crate) fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Get the offset of member data in archive from its local file header.
+    ///
+    /// The name and extra field in local file header could be different
+    /// from the ones in central directory, so we have to read it.
+    pub(crate) fn data_offset(&self, local_header: &[u8]) -> Result<u64> {
+        if local_header.len() < ZIP_LOCAL_HEADER_SIZE as usize
+            || u32_at(local_header, 0) != ZIP_LOCAL_HEADER_SIGNATURE
+        {
+            return Err(new_archive_error("invalid zip local file header"));
+        }
+
+        let name_len = u16_at(local_header, 26) as u64;
+        let extra_len = u16_at(local_header, 28) as u64;
+        Ok(self.local_header_offset + ZIP_LOCAL_HEADER_SIZE + name_len + extra_len)
+    }
+
+    /// Decompress the member data.
+    pub(crate) fn decompress(&self, data: Bytes) -> Result<Bytes> {
+        let bs = match self.method {
+            ZIP_METHOD_STORED => data,
+            #[cfg(feature = "archive-deflate")]
+            ZIP_METHOD_DEFLATED => inflate(&data, self.uncompressed_size)?.into(),
+            method => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "zip compression method is not supported",
+                )
+                .with_context("method", method.to_string()))
+            }
+        };
+
+        if bs.len() as u64 != self.uncompressed_size {
+            return Err(new_archive_error("zip member size mismatch")
+                .with_context("expect", self.uncompressed_size.to_string())
+                .with_context("actual", bs.len().to_string()));
+        }
+        Ok(bs)
+    }
+}
+
+/// TarEntryKind is the kind of tar entry that matters for finding members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TarEntryKind {
+    /// Regular file.
+    File,
+    /// GNU long name of the next entry.
+    LongName,
+    /// Pax extended header of the next entry.
+    Pax,
+    /// Other entries like dirs and links.
+    Other,
+}
+
+/// TarHeader is the parsed header block of tar entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TarHeader {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+    pub(crate) kind: TarEntryKind,
+}
+
+impl TarHeader {
+    /// Parse a tar header block, returns `None` for the zero block that
+    /// marks the end of archive.
+    pub(crate) fn parse(block: &[u8]) -> Result<Option<TarHeader>> {
+        if block.iter().all(|b| *b == 0) {
+            return Ok(None);
+        }
+
+        let checksum = parse_tar_number(&block[148..156])?;
+        let actual: u64 = block
+            .iter()
+            .enumerate()
+            .map(|(i, b)| if (148..156).contains(&i) { b' ' } else { *b } as u64)
+            .sum();
+        if checksum != actual {
+            return Err(new_archive_error("invalid tar header checksum"));
+        }
+
+        let mut name = tar_str(&block[0..100]);
+        // Ustar splits long names into prefix and name.
+        if &block[257..262] == b"ustar" {
+            let prefix = tar_str(&block[345..500]);
+            if !prefix.is_empty() {
+                name = format!("{prefix}/{name}");
+            }
+        }
+
+        let kind = match block[156] {
+            b'0' | 0 => TarEntryKind::File,
+            b'L' => TarEntryKind::LongName,
+            b'x' => TarEntryKind::Pax,
+            _ => TarEntryKind::Other,
+        };
+
+        Ok(Some(TarHeader {
+            name,
+            size: parse_tar_number(&block[124..136])?,
+            kind,
+        }))
+    }
+}
+
+/// The size of tar entry data with padding to block size.
+pub(crate) fn tar_padded_size(size: u64) -> Result<u64> {
+    let block = TAR_BLOCK_SIZE as u64;
+    size.checked_add(block - 1)
+        .map(|v| v / block * block)
+        .ok_or_else(|| {
+            new_archive_error("tar entry size is too large").with_context("size", size.to_string())
+        })
+}
+
+/// Parse pax extended header records, returns the path and size if set.
+pub(crate) fn parse_pax_records(bs: &[u8]) -> Result<(Option<String>, Option<u64>)> {
+    let (mut path, mut size) = (None, None);
+
+    let mut rest = bs;
+    while !rest.is_empty() && rest[0] != 0 {
+        // Every record is `<len> <key>=<value>\n`, len includes itself.
+        let space = rest
+            .iter()
+            .position(|b| *b == b' ')
+            .ok_or_else(|| new_archive_error("invalid pax record"))?;
+        let len: usize = std::str::from_utf8(&rest[..space])
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > space + 1 && *v <= rest.len())
+            .ok_or_else(|| new_archive_error("invalid pax record"))?;
+
+        let record = String::from_utf8_lossy(&rest[space + 1..len - 1]);
+        match record.split_once('=') {
+            Some(("path", v)) => path = Some(v.to_string()),
+            Some(("size", v)) => {
+                size = Some(
+                    v.parse()
+                        .map_err(|_| new_archive_error("invalid pax size"))?,
+                )
+            }
+            _ => {}
+        }
+        rest = &rest[len..];
+    }
+
+    Ok((path, size))
+}
+
+/// Normalize member names so that `./a.txt` matches `a.txt`.
+pub(crate) fn normalize_archive_member(name: &str) -> &str {
+    name.trim_start_matches("./").trim_start_matches('/')
+}
+
+/// Read a NUL terminated string in tar.
+pub(crate) fn tar_str(bs: &[u8]) -> String {
+    let end = bs.iter().position(|b| *b == 0).unwrap_or(bs.len());
+    String::from_utf8_lossy(&bs[..end]).into_owned()
+}
+
+fn parse_tar_number(bs: &[u8]) -> Result<u64> {
+    // GNU tar stores large numbers in base-256 with the highest bit set.
+    if bs[0] & 0x80 != 0 {
+        return Ok(bs[1..]
+            .iter()
+            .fold((bs[0] & 0x7f) as u64, |acc, b| (acc << 8) | *b as u64));
+    }
+
+    let s = std::str::from_utf8(bs)
+        .map_err(|_| new_archive_error("invalid tar number"))?
+        .trim_matches(|c| c == '\0' || c == ' ');
+    if s.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(s, 8).map_err(|_| new_archive_error("invalid tar number"))
+}
+
+/// Decompress raw deflate stream defined by [RFC 1951](https://www.rfc-editor.org/rfc/rfc1951).
+///
+/// `size` is the uncompressed size recorded in central directory, we stop
+/// as soon as the output exceeds it so that a zip bomb can't lead to OOM.
+#[cfg(feature = "archive-deflate")]
+fn inflate(data: &[u8], size: u64) -> Result<Vec<u8>> {
+    use flate2::Decompress;
+    use flate2::FlushDecompress;
+    use flate2::Status;
+
+    let mut de = Decompress::new(false);
+    let mut out = Vec::with_capacity(size.min(ZIP_INFLATE_PREALLOC_SIZE) as usize);
+    let mut buf = vec![0; 64 * 1024];
+
+    loop {
+        let (total_in, total_out) = (de.total_in(), de.total_out());
+        let status = de
+            .decompress(
+                &data[total_in as usize..],
+                &mut buf,
+                FlushDecompress::Finish,
+            )
+            .map_err(|err| new_archive_error("invalid deflate stream").set_source(err))?;
+
+        out.extend_from_slice(&buf[..(de.total_out() - total_out) as usize]);
+        if out.len() as u64 > size {
+            return Err(
+                new_archive_error("zip member is larger than its recorded size")
+                    .with_context("expect", size.to_string()),
+            );
+        }
+
+        match status {
+            Status::StreamEnd => return Ok(out),
+            _ if de.total_in() == total_in && de.total_out() == total_out => {
+                return Err(new_archive_error("deflate stream is truncated"))
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "archive-deflate")]
+    #[test]
+    fn test_inflate() {
+        let cases: Vec<(&str, &[u8], Vec<u8>)> = vec![
+            (
+                "stored",
+                &[0x01, 0x05, 0x00, 0xfa, 0xff, b'h', b'e', b'l', b'l', b'o'],
+                b"hello".to_vec(),
+            ),
+            (
+                "fixed huffman",
+                &[0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00],
+                b"hello hello hello".to_vec(),
+            ),
+            (
+                "dynamic huffman",
+                &[
+                    0x25, 0x8c, 0xb1, 0x0d, 0x80, 0x30, 0x0c, 0x04, 0x57, 0xf9, 0x12, 0x1a, 0x76,
+                    0xa0, 0xa1, 0xa2, 0xcb, 0x04, 0x26, 0x3c, 0x10, 0x89, 0xd8, 0xc8, 0x18, 0xb1,
+                    0x3e, 0x91, 0xa8, 0x5e, 0x77, 0x3a, 0xfd, 0x5c, 0x32, 0xf5, 0xe6, 0x8a, 0x30,
+                    0xc4, 0x41, 0x8c, 0x97, 0xe4, 0x36, 0xc9, 0xb6, 0x78, 0xc5, 0x89, 0xc9, 0x1e,
+                    0x5d, 0x25, 0x8a, 0x29, 0xba, 0x31, 0x4d, 0x3d, 0x1a, 0xd2, 0x61, 0x4a, 0x98,
+                    0xa3, 0x5a, 0x4b, 0xb2, 0x69, 0x78, 0x59, 0x9e, 0x68, 0xe2, 0xfc, 0xef, 0x20,
+                    0xbb, 0x93, 0x95, 0x1a, 0xf7, 0xf0, 0x01,
+                ],
+                b"Licensed to the Apache Software Foundation (ASF) under one or more \
+                  contributor license agreements."
+                    .to_vec(),
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(
+                inflate(input, expected.len() as u64).unwrap(),
+                expected,
+                "{name}"
+            );
+        }
+
+        assert!(inflate(&[0x07], 0).is_err(), "invalid block type");
+        assert!(inflate(&[0xcb, 0x48], 17).is_err(), "truncated stream");
+        // Output larger than the recorded size must be rejected.
+        let fixed = [0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00];
+        assert!(inflate(&fixed, 5).is_err(), "output exceeds size");
+    }
+
+    #[test]
+    fn test_tar_padded_size() {
+        assert_eq!(tar_padded_size(0).unwrap(), 0);
+        assert_eq!(tar_padded_size(1).unwrap(), 512);
+        assert_eq!(tar_padded_size(512).unwrap(), 512);
+        assert!(tar_padded_size(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_parse_pax_records() {
+        let bs = b"30 mtime=1686038400.123456789\n26 path=dir/long-name.txt\n13 size=1024\n";
+        assert_eq!(
+            parse_pax_records(bs).unwrap(),
+            (Some("dir/long-name.txt".to_string()), Some(1024))
+        );
+    }
+}
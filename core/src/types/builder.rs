@@ -40,6 +40,11 @@ pub trait Builder: Default {
     const SCHEME: Scheme;
     /// The accessor that built by this builder.
     type Accessor: Accessor;
+    /// Keys accepted by [`Builder::from_map`].
+    ///
+    /// [`Operator::from_env`] uses them to reject unknown variables. Builders
+    /// that leave it empty accept every key.
+    const CONFIG_KEYS: &'static [&'static str] = &[];
 
     /// Construct a builder from given map which contains several parameters needed by underlying service.
     fn from_map(map: HashMap<String, String>) -> Self;
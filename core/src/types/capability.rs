@@ -17,6 +17,8 @@
 
 use std::fmt::Debug;
 
+use crate::Scheme;
+
 /// Capability is used to describe what operations are supported
 /// by current Operator.
 ///
@@ -52,6 +54,8 @@ pub struct Capability {
     pub stat_with_if_match: bool,
     /// If operator supports stat with if none match natively, it will be true.
     pub stat_with_if_none_match: bool,
+    /// If operator supports stat with version natively, it will be true.
+    pub stat_with_version: bool,
 
     /// If operator supports read natively, it will be true.
     pub read: bool,
@@ -71,6 +75,8 @@ pub struct Capability {
     pub read_with_override_cache_control: bool,
     /// if operator supports read with override content disposition natively, it will be true.
     pub read_with_override_content_disposition: bool,
+    /// If operator supports read with version natively, it will be true.
+    pub read_with_version: bool,
 
     /// If operator supports write natively, it will be true.
     pub write: bool,
@@ -85,6 +91,32 @@ pub struct Capability {
     pub write_with_content_disposition: bool,
     /// If operator supports write with cache control natively, it will be true.
     pub write_with_cache_control: bool,
+    /// If operator supports write with content language natively, it will be true.
+    pub write_with_content_language: bool,
+    /// If operator supports write with extra http headers natively, it will be true.
+    pub write_with_http_header: bool,
+    /// If operator supports write with blob type natively, it will be true.
+    pub write_with_blob_type: bool,
+    /// If operator supports writing at an offset of existing file, it will
+    /// be true.
+    ///
+    /// Object storage services that can't update objects partially don't
+    /// support this.
+    pub write_can_positional: bool,
+    /// If operator supports write with if generation match natively, it
+    /// will be true.
+    pub write_with_if_generation_match: bool,
+    /// If operator supports write with expire natively, it will be true.
+    pub write_with_expire: bool,
+    /// If operator supports write with decompressed size natively, it will be true.
+    pub write_with_decompressed_size: bool,
+    /// If operator supports resuming multipart write from completed parts,
+    /// it will be true.
+    pub write_can_resume: bool,
+    /// The max size that operator supports to write in one request.
+    ///
+    /// Content larger than this must be written via multipart upload.
+    pub write_single_max_size: Option<usize>,
 
     /// If operator supports append natively, it will be true.
     pub append: bool,
@@ -100,9 +132,34 @@ pub struct Capability {
 
     /// If operator supports delete natively, it will be true.
     pub delete: bool,
+    /// If operator supports delete with bypassing governance retention,
+    /// it will be true.
+    pub delete_with_bypass_governance: bool,
+    /// If operator supports delete with if match, it will be true.
+    pub delete_with_if_match: bool,
+    /// If operator supports deleting all versions of an object natively,
+    /// it will be true.
+    pub delete_all_versions: bool,
 
     /// If operator supports copy natively, it will be true.
     pub copy: bool,
+    /// If operator supports copy from another bucket of the same service
+    /// natively, it will be true.
+    pub copy_cross_bucket: bool,
+    /// If operator supports copy with explicitly preserving or dropping
+    /// user metadata, it will be true.
+    pub copy_with_preserve_user_metadata: bool,
+    /// If operator supports copy with explicitly preserving or dropping
+    /// tags, it will be true.
+    pub copy_with_preserve_tags: bool,
+    /// If operator supports copy with if match on the source object,
+    /// it will be true.
+    pub copy_with_if_match: bool,
+    /// If operator reports progress of copy parts natively, it will be true.
+    pub copy_with_progress: bool,
+    /// If operator supports copy a byte range of the source natively, it
+    /// will be true.
+    pub copy_with_range: bool,
 
     /// If operator supports rename natively, it will be true.
     pub rename: bool,
@@ -117,6 +174,11 @@ pub struct Capability {
     pub list_with_delimiter_slash: bool,
     /// If backend supports list without delimiter.
     pub list_without_delimiter: bool,
+    /// If backend supports list with a key prefix natively.
+    pub list_with_prefix: bool,
+    /// If operator supports listing versions of objects natively, it will
+    /// be true.
+    pub list_versions: bool,
 
     /// If operator supports presign natively, it will be true.
     pub presign: bool,
@@ -134,8 +196,28 @@ pub struct Capability {
     /// The max operations that operator supports in batch.
     pub batch_max_operations: Option<usize>,
 
+    /// If operator supports select natively, it will be true.
+    pub select: bool,
+
+    /// If operator supports object retention natively, it will be true.
+    pub retention: bool,
+    /// If operator supports object legal hold natively, it will be true.
+    pub legal_hold: bool,
+    /// If operator supports fetching object acl natively, it will be true.
+    pub acl: bool,
+    /// If operator supports assembling object from ranges natively, it will be true.
+    pub write_range: bool,
+
     /// If operator supports blocking natively, it will be true.
     pub blocking: bool,
+
+    /// The max length of key in bytes, `None` means no limit.
+    pub key_max_len: Option<usize>,
+    /// The max length of each `/` separated segment of key in bytes,
+    /// `None` means no limit.
+    pub key_max_segment_len: Option<usize>,
+    /// If operator rejects keys that contain control characters, it will be true.
+    pub key_forbid_control_chars: bool,
 }
 
 impl Debug for Capability {
@@ -175,6 +257,18 @@ impl Debug for Capability {
         if self.batch {
             s.push("Batch");
         }
+        if self.select {
+            s.push("Select");
+        }
+        if self.retention {
+            s.push("Retention");
+        }
+        if self.legal_hold {
+            s.push("LegalHold");
+        }
+        if self.acl {
+            s.push("Acl");
+        }
         if self.blocking {
             s.push("Blocking");
         }
@@ -182,3 +276,220 @@ impl Debug for Capability {
         write!(f, "{{ {} }}", s.join(" | "))
     }
 }
+
+/// CapabilityValue is the value of one entry in [`CapabilityReport`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum CapabilityValue {
+    /// Whether this capability is supported natively.
+    Flag(bool),
+    /// The limit of this capability, `None` means no limit.
+    Limit(Option<usize>),
+}
+
+impl From<bool> for CapabilityValue {
+    fn from(v: bool) -> Self {
+        CapabilityValue::Flag(v)
+    }
+}
+
+impl From<Option<usize>> for CapabilityValue {
+    fn from(v: Option<usize>) -> Self {
+        CapabilityValue::Limit(v)
+    }
+}
+
+/// CapabilityReport is a snapshot of all capabilities of an operator.
+///
+/// Every field of [`Capability`] will be reported in declaration order with
+/// its field name, so users can render them without matching on
+/// [`Capability`] directly.
+///
+/// With feature `serde` enabled, report will be serialized like:
+///
+/// ```json
+/// {"scheme":"memory","name":"","capabilities":{"stat":true,"batch_max_operations":null}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityReport {
+    scheme: Scheme,
+    name: String,
+    entries: Vec<(&'static str, CapabilityValue)>,
+}
+
+macro_rules! capability_entries {
+    ($cap:expr, $($field:ident),* $(,)?) => {{
+        // Destruct without `..` so that new fields must be reported.
+        let Capability { $($field),* } = $cap;
+        vec![$((stringify!($field), CapabilityValue::from($field))),*]
+    }};
+}
+
+impl CapabilityReport {
+    pub(crate) fn new(scheme: Scheme, name: &str, cap: Capability) -> Self {
+        let entries = capability_entries!(
+            cap,
+            stat,
+            stat_with_if_match,
+            stat_with_if_none_match,
+            stat_with_version,
+            read,
+            read_can_seek,
+            read_can_next,
+            read_with_range,
+            read_with_if_match,
+            read_with_if_none_match,
+            read_with_override_cache_control,
+            read_with_override_content_disposition,
+            read_with_version,
+            write,
+            write_without_content_length,
+            write_with_content_type,
+            write_with_content_disposition,
+            write_with_cache_control,
+            write_with_content_language,
+            write_with_http_header,
+            write_with_blob_type,
+            write_can_positional,
+            write_with_if_generation_match,
+            write_with_expire,
+            write_with_decompressed_size,
+            write_can_resume,
+            write_single_max_size,
+            append,
+            append_with_content_type,
+            append_with_content_disposition,
+            append_with_cache_control,
+            create_dir,
+            delete,
+            delete_with_bypass_governance,
+            delete_with_if_match,
+            delete_all_versions,
+            copy,
+            copy_cross_bucket,
+            copy_with_preserve_user_metadata,
+            copy_with_preserve_tags,
+            copy_with_if_match,
+            copy_with_progress,
+            copy_with_range,
+            rename,
+            list,
+            list_with_limit,
+            list_with_start_after,
+            list_with_delimiter_slash,
+            list_without_delimiter,
+            list_with_prefix,
+            list_versions,
+            presign,
+            presign_read,
+            presign_stat,
+            presign_write,
+            batch,
+            batch_delete,
+            batch_max_operations,
+            select,
+            retention,
+            legal_hold,
+            acl,
+            write_range,
+            blocking,
+            key_max_len,
+            key_max_segment_len,
+            key_forbid_control_chars,
+        );
+
+        CapabilityReport {
+            scheme,
+            name: name.to_string(),
+            entries,
+        }
+    }
+
+    /// [`Scheme`] of the reported operator.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    /// Name of the reported operator.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Iterate all capabilities in the report.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, CapabilityValue)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Get the value of capability by its name like `read_with_range`.
+    pub fn get(&self, name: &str) -> Option<CapabilityValue> {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| *v)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CapabilityReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Entries<'a>(&'a [(&'static str, CapabilityValue)]);
+
+        impl serde::Serialize for Entries<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+            }
+        }
+
+        let mut s = serializer.serialize_struct("CapabilityReport", 3)?;
+        s.serialize_field("scheme", self.scheme.into_static())?;
+        s.serialize_field("name", &self.name)?;
+        s.serialize_field("capabilities", &Entries(&self.entries))?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capability_report() {
+        let cap = Capability {
+            read: true,
+            batch_max_operations: Some(1000),
+            ..Default::default()
+        };
+        let report = CapabilityReport::new(Scheme::Memory, "test", cap);
+
+        assert_eq!(report.get("read"), Some(CapabilityValue::Flag(true)));
+        assert_eq!(report.get("write"), Some(CapabilityValue::Flag(false)));
+        assert_eq!(
+            report.get("batch_max_operations"),
+            Some(CapabilityValue::Limit(Some(1000)))
+        );
+        assert_eq!(report.get("not_exist"), None);
+        assert_eq!(report.iter().next().map(|(k, _)| k), Some("stat"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_capability_report_serialize() {
+        let cap = Capability {
+            stat: true,
+            ..Default::default()
+        };
+        let report = CapabilityReport::new(Scheme::Memory, "test", cap);
+
+        let v = serde_json::to_value(report).expect("serialize must succeed");
+        assert_eq!(v["scheme"], "memory");
+        assert_eq!(v["name"], "test");
+        assert_eq!(v["capabilities"]["stat"], true);
+        assert_eq!(v["capabilities"]["read"], false);
+        assert_eq!(
+            v["capabilities"]["batch_max_operations"],
+            serde_json::Value::Null
+        );
+    }
+}
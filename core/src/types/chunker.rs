@@ -0,0 +1,226 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::*;
+
+/// ChunkerConfig is the config of content-defined chunking used by
+/// [`Operator::write_cdc`].
+///
+/// Chunk boundaries are decided by a rolling hash (FastCDC) over the
+/// content, so that an edit only changes the chunks near it.
+///
+/// - `min_size`: Chunks are never smaller than it except the last one.
+///   Larger value makes chunking faster but dedup less effective.
+/// - `avg_size`: The expected chunk size, rounded down to a power of two.
+/// - `max_size`: Chunks are never larger than it. It bounds the memory
+///   used for buffering content.
+///
+/// By default, chunks are between 1 MiB and 16 MiB with 4 MiB on average.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkerConfig {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        Self::new(4 * 1024 * 1024)
+    }
+}
+
+impl ChunkerConfig {
+    /// Create a new config with given average chunk size.
+    ///
+    /// `min_size` is set to `avg_size / 4` and `max_size` is set to
+    /// `avg_size * 4`.
+    pub fn new(avg_size: usize) -> Self {
+        Self {
+            min_size: avg_size / 4,
+            avg_size,
+            max_size: avg_size.saturating_mul(4),
+        }
+    }
+
+    /// Set the min chunk size.
+    pub fn with_min_size(mut self, size: usize) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Set the max chunk size.
+    pub fn with_max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Get the min chunk size.
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Get the average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    /// Get the max chunk size.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Check if the config is valid.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.min_size == 0 || self.min_size > self.avg_size || self.avg_size > self.max_size {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "chunker config must satisfy 0 < min_size <= avg_size <= max_size",
+            )
+            .with_context("min_size", self.min_size.to_string())
+            .with_context("avg_size", self.avg_size.to_string())
+            .with_context("max_size", self.max_size.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Find the boundary of the first chunk in data.
+    ///
+    /// The returned position is only final if data is at least `max_size`
+    /// long or the end of content has been reached.
+    pub(crate) fn cut(&self, data: &[u8]) -> usize {
+        if data.len() <= self.min_size {
+            return data.len();
+        }
+
+        let bits = usize::BITS - 1 - self.avg_size.leading_zeros();
+        // Use a harder mask before the average size and an easier one
+        // after it to normalize chunk sizes.
+        let mask_s = high_bits_mask(bits + 1);
+        let mask_l = high_bits_mask(bits.saturating_sub(1));
+
+        let end = data.len().min(self.max_size);
+        let normal = end.min(self.avg_size);
+
+        let mut hash = 0u64;
+        for (i, b) in data.iter().enumerate().take(end).skip(self.min_size) {
+            hash = (hash << 1).wrapping_add(GEAR[*b as usize]);
+            let mask = if i < normal { mask_s } else { mask_l };
+            if hash & mask == 0 {
+                return i + 1;
+            }
+        }
+        end
+    }
+}
+
+/// ChunkRef is the reference of a chunk written by [`Operator::write_cdc`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunkRef {
+    path: String,
+    hash: String,
+    offset: u64,
+    size: u64,
+}
+
+impl ChunkRef {
+    pub(crate) fn new(path: String, hash: String, offset: u64, size: u64) -> Self {
+        Self {
+            path,
+            hash,
+            offset,
+            size,
+        }
+    }
+
+    /// Path of the chunk object.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Hex encoded SHA-256 of the chunk content.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Offset of the chunk in the whole content.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Size of the chunk.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Build a mask with the highest `n` bits set.
+///
+/// High bits of gear hash are affected by more recent bytes.
+const fn high_bits_mask(n: u32) -> u64 {
+    if n == 0 {
+        0
+    } else {
+        u64::MAX << (64 - n)
+    }
+}
+
+/// GEAR is the random table used by gear hash.
+///
+/// It's generated by splitmix64 so that chunk boundaries are stable
+/// across versions.
+static GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunker_config_validate() {
+        assert!(ChunkerConfig::default().validate().is_ok());
+        assert!(ChunkerConfig::new(0).validate().is_err());
+        assert!(ChunkerConfig::new(1024)
+            .with_max_size(512)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_chunker_cut() {
+        let cfg = ChunkerConfig::new(1024);
+        let data: Vec<u8> = (0..16 * 1024).map(|v| (v * 31 % 251) as u8).collect();
+
+        // Small content is one chunk.
+        assert_eq!(cfg.cut(&data[..100]), 100);
+
+        let n = cfg.cut(&data);
+        assert!(n >= cfg.min_size() && n <= cfg.max_size(), "cut at {n}");
+    }
+}
@@ -26,6 +26,10 @@ pub struct Entry {
 
     /// Optional cached metadata
     metadata: Option<Metadata>,
+
+    /// Operator this entry is listed from if it's not the one used to
+    /// list, see `Operator::merge_list`.
+    source: Option<Operator>,
 }
 
 impl Entry {
@@ -38,10 +42,11 @@ impl Entry {
     ///
     /// The only way to get an entry with associated cached metadata
     /// is `Operator::list` or `Operator::scan`.
-    pub(crate) fn new_with(path: String, metadata: Metadata) -> Self {
+    pub(crate) fn new_with(path: String, metadata: Metadata, source: Option<Operator>) -> Self {
         Self {
             path,
             metadata: Some(metadata),
+            source,
         }
     }
 
@@ -50,6 +55,7 @@ impl Entry {
         Self {
             path: normalize_path(path),
             metadata: None,
+            source: None,
         }
     }
 
@@ -77,4 +83,11 @@ impl Entry {
     pub(crate) fn metadata(&self) -> &Option<Metadata> {
         &self.metadata
     }
+
+    /// Get the operator this entry is listed from.
+    ///
+    /// Returns `None` if the entry belongs to the operator used to list it.
+    pub(crate) fn source(&self) -> Option<&Operator> {
+        self.source.as_ref()
+    }
 }
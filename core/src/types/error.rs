@@ -99,6 +99,21 @@ pub enum ErrorKind {
     /// - Users expected to read 1024 bytes, but service returned less bytes.
     /// - Service expected to write 1024 bytes, but users write less bytes.
     ContentIncomplete,
+    /// The storage doesn't have enough space for this operation.
+    ///
+    /// For examples:
+    ///
+    /// - Writing to fs will leave less free space than the configured `min_free_space`.
+    InsufficientStorage,
+    /// The requested range is invalid and can't be satisfied.
+    ///
+    /// For examples:
+    ///
+    /// - Users request a range whose start is larger than its end.
+    /// - Users request an empty range.
+    /// - Service returns `416 Range Not Satisfiable` since the range starts
+    ///   beyond the end of object.
+    RangeNotSatisfied,
 }
 
 impl ErrorKind {
@@ -130,6 +145,8 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::ConditionNotMatch => "ConditionNotMatch",
             ErrorKind::ContentTruncated => "ContentTruncated",
             ErrorKind::ContentIncomplete => "ContentIncomplete",
+            ErrorKind::InsufficientStorage => "InsufficientStorage",
+            ErrorKind::RangeNotSatisfied => "RangeNotSatisfied",
         }
     }
 }
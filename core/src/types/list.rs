@@ -15,13 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::mem;
 use std::pin::Pin;
-use std::task::ready;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
+use async_trait::async_trait;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use futures::Stream;
@@ -29,6 +31,70 @@ use futures::Stream;
 use crate::raw::*;
 use crate::*;
 
+/// ListSummary is the summary of a finished list.
+///
+/// It's collected while entries are returned by [`Lister`] or
+/// [`BlockingLister`], no extra requests will be sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListSummary {
+    entries: usize,
+    pages: usize,
+    bytes: u64,
+    bytes_complete: bool,
+}
+
+impl ListSummary {
+    fn new() -> Self {
+        Self {
+            bytes_complete: true,
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, entries: &[oio::Entry]) {
+        self.pages += 1;
+        self.entries += entries.len();
+
+        for oe in entries {
+            let meta = oe.metadata();
+            if !meta.is_file() {
+                continue;
+            }
+            match meta.content_length_raw() {
+                Some(v)
+                    if meta.bit().contains(Metakey::ContentLength)
+                        || meta.bit().contains(Metakey::Complete) =>
+                {
+                    self.bytes += v
+                }
+                _ => self.bytes_complete = false,
+            }
+        }
+    }
+
+    /// Total count of entries, including dirs.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Total count of pages returned by service.
+    pub fn pages(&self) -> usize {
+        self.pages
+    }
+
+    /// Total content length of all file entries.
+    ///
+    /// Returns `None` if content length of any file entry is not returned
+    /// by list, use [`Operator::metadata`] to fetch them instead.
+    pub fn bytes(&self) -> Option<u64> {
+        if self.bytes_complete {
+            Some(self.bytes)
+        } else {
+            None
+        }
+    }
+}
+
 /// Lister is designed to list entries at given path in an asynchronous
 /// manner.
 ///
@@ -36,8 +102,14 @@ use crate::*;
 ///
 /// User can use lister as `Stream<Item = Result<Entry>>` or
 /// call `next_page` directly.
+///
+/// While used as `Stream`, lister will fetch the next page in advance when
+/// entries of current page are returned. At most one page will be fetched
+/// in advance.
 pub struct Lister {
     pager: Option<oio::Pager>,
+    summary: ListSummary,
+    done: bool,
 
     buf: VecDeque<oio::Entry>,
     /// We will move `pager` inside future and return it back while future is ready.
@@ -45,6 +117,8 @@ pub struct Lister {
     /// a future.
     #[allow(clippy::type_complexity)]
     fut: Option<BoxFuture<'static, (oio::Pager, Result<Option<Vec<oio::Entry>>>)>>,
+    /// The next page which has been fetched but not consumed yet.
+    prefetched: Option<Result<Option<Vec<oio::Entry>>>>,
 }
 
 /// # Safety
@@ -57,11 +131,38 @@ impl Lister {
     pub(crate) fn new(pager: oio::Pager) -> Self {
         Self {
             pager: Some(pager),
+            summary: ListSummary::new(),
+            done: false,
             buf: VecDeque::default(),
             fut: None,
+            prefetched: None,
         }
     }
 
+    /// Get the summary of this list.
+    ///
+    /// Returns `None` until all entries have been returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut lister = op.scan("path/to/dir/").await?;
+    /// while let Some(_) = lister.try_next().await? {}
+    ///
+    /// let summary = lister.summary().expect("list has finished");
+    /// println!("listed {} objects totaling {:?} bytes", summary.entries(), summary.bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn summary(&self) -> Option<ListSummary> {
+        self.done.then_some(self.summary)
+    }
+
     /// has_next can be used to check if there are more pages.
     pub async fn has_next(&mut self) -> Result<bool> {
         debug_assert!(
@@ -83,8 +184,14 @@ impl Lister {
             // Ideally, the convert from `Vec` to `VecDeque` will not do reallocation.
             //
             // However, this could be changed as described in [impl<T, A> From<Vec<T, A>> for VecDeque<T, A>](https://doc.rust-lang.org/std/collections/struct.VecDeque.html#impl-From%3CVec%3CT%2C%20A%3E%3E-for-VecDeque%3CT%2C%20A%3E)
-            Some(entries) => entries.into(),
-            None => return Ok(false),
+            Some(entries) => {
+                self.summary.record(&entries);
+                entries.into()
+            }
+            None => {
+                self.done = true;
+                return Ok(false);
+            }
         };
         // Push fetched entries into buffer.
         self.buf = entries;
@@ -117,8 +224,14 @@ impl Lister {
                 // Ideally, the convert from `Vec` to `VecDeque` will not do reallocation.
                 //
                 // However, this could be changed as described in [impl<T, A> From<Vec<T, A>> for VecDeque<T, A>](https://doc.rust-lang.org/std/collections/struct.VecDeque.html#impl-From%3CVec%3CT%2C%20A%3E%3E-for-VecDeque%3CT%2C%20A%3E)
-                Some(entries) => entries.into(),
-                None => return Ok(None),
+                Some(entries) => {
+                    self.summary.record(&entries);
+                    entries.into()
+                }
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
             }
         };
 
@@ -130,35 +243,48 @@ impl Stream for Lister {
     type Item = Result<Entry>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        if let Some(oe) = self.buf.pop_front() {
-            return Poll::Ready(Some(Ok(oe.into_entry())));
+        if self.done {
+            return Poll::Ready(None);
         }
 
-        if let Some(fut) = self.fut.as_mut() {
-            let (op, res) = ready!(fut.poll_unpin(cx));
-            self.pager = Some(op);
+        // Fetch the next page while entries of current page are consumed.
+        if self.prefetched.is_none() {
+            let this = &mut *self;
+            let fut = this.fut.get_or_insert_with(|| {
+                let mut pager = this.pager.take().expect("pager must be valid");
+                Box::pin(async move {
+                    let res = pager.next().await;
 
-            return match res? {
-                Some(oes) => {
-                    self.fut = None;
-                    self.buf = oes.into();
-                    self.poll_next(cx)
-                }
-                None => {
-                    self.fut = None;
-                    Poll::Ready(None)
-                }
-            };
+                    (pager, res)
+                })
+            });
+
+            if let Poll::Ready((op, res)) = fut.poll_unpin(cx) {
+                self.fut = None;
+                self.pager = Some(op);
+                self.prefetched = Some(res);
+            }
         }
 
-        let mut pager = self.pager.take().expect("pager must be valid");
-        let fut = async move {
-            let res = pager.next().await;
+        if let Some(oe) = self.buf.pop_front() {
+            return Poll::Ready(Some(Ok(oe.into_entry())));
+        }
 
-            (pager, res)
-        };
-        self.fut = Some(Box::pin(fut));
-        self.poll_next(cx)
+        // Errors of the next page will be returned after all entries of
+        // current page have been consumed.
+        match self.prefetched.take() {
+            None => Poll::Pending,
+            Some(Err(err)) => Poll::Ready(Some(Err(err))),
+            Some(Ok(Some(oes))) => {
+                self.summary.record(&oes);
+                self.buf = oes.into();
+                self.poll_next(cx)
+            }
+            Some(Ok(None)) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+        }
     }
 }
 
@@ -168,6 +294,8 @@ impl Stream for Lister {
 /// Users can construct Lister by `blocking_list` or `blocking_scan`.
 pub struct BlockingLister {
     pager: oio::BlockingPager,
+    summary: ListSummary,
+    done: bool,
     buf: VecDeque<oio::Entry>,
 }
 
@@ -181,10 +309,19 @@ impl BlockingLister {
     pub(crate) fn new(pager: oio::BlockingPager) -> Self {
         Self {
             pager,
+            summary: ListSummary::new(),
+            done: false,
             buf: VecDeque::default(),
         }
     }
 
+    /// Get the summary of this list.
+    ///
+    /// Returns `None` until all entries have been returned.
+    pub fn summary(&self) -> Option<ListSummary> {
+        self.done.then_some(self.summary)
+    }
+
     /// next_page can be used to fetch a new page.
     pub fn next_page(&mut self) -> Result<Option<Vec<Entry>>> {
         let entries = if !self.buf.is_empty() {
@@ -194,8 +331,14 @@ impl BlockingLister {
                 // Ideally, the convert from `Vec` to `VecDeque` will not do reallocation.
                 //
                 // However, this could be changed as described in [impl<T, A> From<Vec<T, A>> for VecDeque<T, A>](https://doc.rust-lang.org/std/collections/struct.VecDeque.html#impl-From%3CVec%3CT%2C%20A%3E%3E-for-VecDeque%3CT%2C%20A%3E)
-                Some(entries) => entries.into(),
-                None => return Ok(None),
+                Some(entries) => {
+                    self.summary.record(&entries);
+                    entries.into()
+                }
+                None => {
+                    self.done = true;
+                    return Ok(None);
+                }
             }
         };
 
@@ -216,11 +359,435 @@ impl Iterator for BlockingLister {
             // Ideally, the convert from `Vec` to `VecDeque` will not do reallocation.
             //
             // However, this could be changed as described in [impl<T, A> From<Vec<T, A>> for VecDeque<T, A>](https://doc.rust-lang.org/std/collections/struct.VecDeque.html#impl-From%3CVec%3CT%2C%20A%3E%3E-for-VecDeque%3CT%2C%20A%3E)
-            Ok(Some(entries)) => entries.into(),
-            Ok(None) => return None,
+            Ok(Some(entries)) => {
+                self.summary.record(&entries);
+                entries.into()
+            }
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
             Err(err) => return Some(Err(err)),
         };
 
         self.next()
     }
 }
+
+/// ListConsistency decides how to handle keys reappeared in list.
+///
+/// Keys repeated right after themselves, like the last key of a page
+/// returned again as the first key of next page, are always dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListConsistency {
+    /// Drop keys that have been returned.
+    Dedup,
+    /// Return an `Unexpected` error if a key has been returned before.
+    Strict,
+}
+
+/// ConsistencyCheckPager tracks keys returned by inner pager within a
+/// rolling window to detect keys reappeared across pages.
+pub(crate) struct ConsistencyCheckPager<P> {
+    inner: P,
+    mode: ListConsistency,
+    window: usize,
+
+    seen: HashSet<Arc<str>>,
+    order: VecDeque<Arc<str>>,
+}
+
+impl<P> ConsistencyCheckPager<P> {
+    pub(crate) fn new(inner: P, mode: ListConsistency, window: usize) -> Self {
+        Self {
+            inner,
+            mode,
+            window,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn check(&mut self, entries: Vec<oio::Entry>) -> Result<Vec<oio::Entry>> {
+        let mut checked = Vec::with_capacity(entries.len());
+        for oe in entries {
+            let path = oe.path();
+            if self.order.back().map(|v| v.as_ref()) == Some(path) {
+                continue;
+            }
+            if self.seen.contains(path) {
+                match self.mode {
+                    ListConsistency::Dedup => continue,
+                    ListConsistency::Strict => {
+                        return Err(Error::new(
+                            ErrorKind::Unexpected,
+                            "list returned a key more than once, the listing is inconsistent",
+                        )
+                        .with_context("path", path))
+                    }
+                }
+            }
+
+            let path: Arc<str> = Arc::from(path);
+            self.seen.insert(path.clone());
+            self.order.push_back(path);
+            if self.order.len() > self.window {
+                if let Some(v) = self.order.pop_front() {
+                    self.seen.remove(&v);
+                }
+            }
+
+            checked.push(oe);
+        }
+        Ok(checked)
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for ConsistencyCheckPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        match self.inner.next().await? {
+            Some(entries) => Ok(Some(self.check(entries)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// MergePager merges pagers listing in sorted order into one sorted pager.
+///
+/// Keys listed by more than one pager are only returned once, the entry
+/// from the pager with the smallest index wins. Entries are tagged with the
+/// source operator of their pager if there is one.
+pub(crate) struct MergePager {
+    sources: Vec<MergeSource>,
+}
+
+struct MergeSource {
+    pager: oio::Pager,
+    source: Option<Operator>,
+    buf: VecDeque<oio::Entry>,
+    done: bool,
+}
+
+impl MergeSource {
+    fn is_pending(&self) -> bool {
+        self.buf.is_empty() && !self.done
+    }
+
+    async fn fill(&mut self) -> Result<()> {
+        while self.is_pending() {
+            match self.pager.next().await? {
+                Some(entries) => self.buf.extend(entries),
+                None => self.done = true,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MergePager {
+    pub(crate) fn new(pagers: Vec<(oio::Pager, Option<Operator>)>) -> Self {
+        Self {
+            sources: pagers
+                .into_iter()
+                .map(|(pager, source)| MergeSource {
+                    pager,
+                    source,
+                    buf: VecDeque::new(),
+                    done: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// Take the smallest key out of all sources.
+    ///
+    /// Returns `None` if any source could have a smaller key in its next
+    /// page which must be fetched first.
+    fn pop(&mut self) -> Option<oio::Entry> {
+        if self.sources.iter().any(|s| s.is_pending()) {
+            return None;
+        }
+
+        let (idx, _) = self
+            .sources
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, s)| s.buf.front().map(|e| (idx, e.path())))
+            .min_by(|(ia, a), (ib, b)| a.cmp(b).then(ia.cmp(ib)))?;
+        let mut entry = self.sources[idx].buf.pop_front()?;
+        if let Some(op) = &self.sources[idx].source {
+            entry.set_source(op.clone());
+        }
+        for s in self.sources.iter_mut().skip(idx + 1) {
+            if s.buf.front().map(|e| e.path()) == Some(entry.path()) {
+                s.buf.pop_front();
+            }
+        }
+        Some(entry)
+    }
+}
+
+#[async_trait]
+impl oio::Page for MergePager {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        futures::future::try_join_all(
+            self.sources
+                .iter_mut()
+                .filter(|s| s.is_pending())
+                .map(|s| s.fill()),
+        )
+        .await?;
+
+        let mut entries = Vec::new();
+        while let Some(entry) = self.pop() {
+            entries.push(entry);
+        }
+
+        if entries.is_empty() {
+            // All sources have been drained.
+            Ok(None)
+        } else {
+            Ok(Some(entries))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::StreamExt;
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    struct MockPager(VecDeque<Vec<oio::Entry>>);
+
+    impl MockPager {
+        fn new(pages: Vec<Vec<(&str, Option<u64>)>>) -> Self {
+            let pages = pages
+                .into_iter()
+                .map(|page| {
+                    page.into_iter()
+                        .map(|(path, size)| {
+                            let mode = if path.ends_with('/') {
+                                EntryMode::DIR
+                            } else {
+                                EntryMode::FILE
+                            };
+                            let mut meta = Metadata::new(mode);
+                            if let Some(size) = size {
+                                meta.set_content_length(size);
+                            }
+                            oio::Entry::new(path, meta)
+                        })
+                        .collect()
+                })
+                .collect();
+            Self(pages)
+        }
+    }
+
+    #[async_trait]
+    impl oio::Page for MockPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    impl oio::BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    /// CountingPager records how many pages have been fetched and fails
+    /// after all pages are returned if `fail` is set.
+    struct CountingPager {
+        pages: VecDeque<Vec<oio::Entry>>,
+        fetched: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl oio::Page for CountingPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            self.fetched.fetch_add(1, Ordering::SeqCst);
+            match self.pages.pop_front() {
+                Some(page) => Ok(Some(page)),
+                None if self.fail => Err(Error::new(ErrorKind::Unexpected, "list failed")),
+                None => Ok(None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lister_prefetch_next_page() -> Result<()> {
+        let pages = vec![
+            vec![("dir/a", None), ("dir/b", None)],
+            vec![("dir/c", None)],
+        ];
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let mut lister = Lister::new(Box::new(CountingPager {
+            pages: MockPager::new(pages.clone()).0,
+            fetched: fetched.clone(),
+            fail: false,
+        }));
+
+        let entry = lister.try_next().await?.expect("entry must exist");
+        assert_eq!(entry.path(), "dir/a");
+        assert_eq!(
+            fetched.load(Ordering::SeqCst),
+            2,
+            "next page must be fetched before current page is drained"
+        );
+
+        let rest: Vec<_> = (&mut lister).try_collect().await?;
+        let paths: Vec<_> = rest.iter().map(|v| v.path()).collect();
+        assert_eq!(paths, vec!["dir/b", "dir/c"]);
+
+        // Errors of prefetched page are returned in order.
+        let mut lister = Lister::new(Box::new(CountingPager {
+            pages: MockPager::new(pages).0,
+            fetched: Arc::new(AtomicUsize::new(0)),
+            fail: true,
+        }));
+        let mut paths = Vec::new();
+        while let Some(res) = lister.next().await {
+            match res {
+                Ok(entry) => paths.push(entry.path().to_string()),
+                Err(err) => {
+                    assert_eq!(err.kind(), ErrorKind::Unexpected);
+                    break;
+                }
+            }
+        }
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lister_summary() -> Result<()> {
+        let pages = vec![
+            vec![("dir/a", Some(3)), ("dir/b", Some(5)), ("dir/sub/", None)],
+            vec![("dir/c", Some(7))],
+        ];
+
+        let mut lister = Lister::new(Box::new(MockPager::new(pages.clone())));
+        assert_eq!(lister.summary(), None);
+        let first = lister.try_next().await?;
+        assert!(first.is_some());
+        assert_eq!(lister.summary(), None, "list is not finished");
+
+        let rest: Vec<Entry> = (&mut lister).try_collect().await?;
+        assert_eq!(rest.len(), 3);
+        let summary = lister.summary().expect("summary must exist");
+        assert_eq!(summary.entries(), 4);
+        assert_eq!(summary.pages(), 2);
+        assert_eq!(summary.bytes(), Some(15));
+
+        let mut lister = BlockingLister::new(Box::new(MockPager::new(pages)));
+        let entries = lister.by_ref().collect::<Result<Vec<_>>>()?;
+        assert_eq!(entries.len(), 4);
+        assert_eq!(lister.summary(), Some(summary));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lister_summary_without_size() -> Result<()> {
+        let pages = vec![vec![("dir/a", Some(3)), ("dir/b", None)]];
+
+        let mut lister = Lister::new(Box::new(MockPager::new(pages)));
+        while lister.next_page().await?.is_some() {}
+
+        let summary = lister.summary().expect("summary must exist");
+        assert_eq!(summary.entries(), 2);
+        assert_eq!(summary.pages(), 1);
+        assert_eq!(summary.bytes(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lister_consistency_check() -> Result<()> {
+        let pages = vec![
+            vec![("dir/a", None), ("dir/b", None)],
+            // `dir/b` repeated at page boundary is dropped.
+            vec![("dir/b", None), ("dir/c", None)],
+            // `dir/a` reappears across pages.
+            vec![("dir/a", None), ("dir/d", None)],
+        ];
+        let new_lister = |mode, window| {
+            Lister::new(Box::new(ConsistencyCheckPager::new(
+                MockPager::new(pages.clone()),
+                mode,
+                window,
+            )))
+        };
+
+        let entries: Vec<Entry> = new_lister(ListConsistency::Dedup, 16).try_collect().await?;
+        let paths: Vec<_> = entries.iter().map(|v| v.path()).collect();
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c", "dir/d"]);
+
+        let mut lister = new_lister(ListConsistency::Strict, 16);
+        let mut paths = Vec::new();
+        let err = loop {
+            match lister.try_next().await {
+                Ok(Some(entry)) => paths.push(entry.path().to_string()),
+                Ok(None) => panic!("duplicated key must be detected"),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c"]);
+
+        // `dir/a` has been out of the window.
+        let entries: Vec<Entry> = new_lister(ListConsistency::Strict, 2).try_collect().await?;
+        assert_eq!(entries.len(), 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_pager() -> Result<()> {
+        let high = MockPager::new(vec![
+            vec![("dir/a", Some(1)), ("dir/c", Some(1))],
+            vec![],
+            vec![("dir/e", Some(1))],
+        ]);
+        let low = MockPager::new(vec![
+            vec![("dir/b", Some(2))],
+            vec![("dir/c", Some(2)), ("dir/d", Some(2))],
+            vec![("dir/e", Some(2)), ("dir/f", Some(2))],
+        ]);
+
+        let lister = Lister::new(Box::new(MergePager::new(vec![
+            (Box::new(high), None),
+            (Box::new(low), None),
+        ])));
+        let entries: Vec<Entry> = lister.try_collect().await?;
+        let got: Vec<_> = entries
+            .iter()
+            .map(|v| (v.path(), v.metadata().as_ref().unwrap().content_length()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("dir/a", 1),
+                ("dir/b", 2),
+                ("dir/c", 1),
+                ("dir/d", 2),
+                ("dir/e", 1),
+                ("dir/f", 2),
+            ]
+        );
+
+        Ok(())
+    }
+}
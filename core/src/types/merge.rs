@@ -0,0 +1,100 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use serde_json::Value;
+
+/// MergeStrategy decides how [`Operator::merge_json`] merges the given
+/// value into the existing JSON object.
+///
+/// [`Operator::merge_json`]: crate::Operator::merge_json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergeStrategy {
+    /// Only merge the top-level fields: fields of the given value replace
+    /// the existing ones as a whole.
+    Shallow,
+    /// Merge objects recursively, all other values (including arrays) in
+    /// the given value replace the existing ones.
+    Deep,
+    /// Like [`MergeStrategy::Deep`], but arrays are appended to the
+    /// existing arrays instead of replacing them.
+    ArrayAppend,
+}
+
+impl MergeStrategy {
+    /// Merge `patch` into `base`.
+    ///
+    /// If either of them is not an object, `patch` replaces `base` except
+    /// that two arrays are concatenated by [`MergeStrategy::ArrayAppend`].
+    pub(crate) fn merge(self, base: &mut Value, patch: Value) {
+        match (base, patch) {
+            (Value::Object(base), Value::Object(patch)) => {
+                for (k, v) in patch {
+                    match base.get_mut(&k) {
+                        Some(bv) if self != MergeStrategy::Shallow => self.merge(bv, v),
+                        _ => {
+                            base.insert(k, v);
+                        }
+                    }
+                }
+            }
+            (Value::Array(base), Value::Array(patch)) if self == MergeStrategy::ArrayAppend => {
+                base.extend(patch)
+            }
+            (base, patch) => *base = patch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let base = json!({"a": {"x": 1, "y": [1]}, "b": 1});
+        let patch = json!({"a": {"y": [2], "z": 3}, "c": 2});
+
+        let cases = vec![
+            (
+                MergeStrategy::Shallow,
+                json!({"a": {"y": [2], "z": 3}, "b": 1, "c": 2}),
+            ),
+            (
+                MergeStrategy::Deep,
+                json!({"a": {"x": 1, "y": [2], "z": 3}, "b": 1, "c": 2}),
+            ),
+            (
+                MergeStrategy::ArrayAppend,
+                json!({"a": {"x": 1, "y": [1, 2], "z": 3}, "b": 1, "c": 2}),
+            ),
+        ];
+
+        for (strategy, expected) in cases {
+            let mut v = base.clone();
+            strategy.merge(&mut v, patch.clone());
+            assert_eq!(v, expected, "{strategy:?}");
+        }
+
+        // Non-object values are replaced.
+        let mut v = json!([1]);
+        MergeStrategy::Deep.merge(&mut v, json!({"a": 1}));
+        assert_eq!(v, json!({"a": 1}));
+    }
+}
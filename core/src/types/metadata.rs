@@ -42,8 +42,25 @@ pub struct Metadata {
     content_md5: Option<String>,
     content_range: Option<BytesContentRange>,
     content_type: Option<String>,
+    created: Option<DateTime<Utc>>,
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
+
+    /// Rarely returned metadata, boxed to keep `Metadata` and `Entry` small.
+    ext: Option<Box<MetadataExt>>,
+}
+
+/// Metadata that only returned by few services or by extra requests.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct MetadataExt {
+    acl_summary: Option<AclSummary>,
+    content_language: Option<String>,
+    decompressed_size: Option<u64>,
+    encryption: Option<EncryptionInfo>,
+    expires_at: Option<DateTime<Utc>>,
+    generation: Option<i64>,
+    owner: Option<String>,
+    version: Option<String>,
 }
 
 impl Metadata {
@@ -66,12 +83,22 @@ impl Metadata {
             content_md5: None,
             content_type: None,
             content_range: None,
+            created: None,
             last_modified: None,
             etag: None,
+            ext: None,
             content_disposition: None,
         }
     }
 
+    fn ext(&self) -> Option<&MetadataExt> {
+        self.ext.as_deref()
+    }
+
+    fn ext_mut(&mut self) -> &mut MetadataExt {
+        self.ext.get_or_insert_with(Default::default)
+    }
+
     /// Get the bit from metadata.
     pub(crate) fn bit(&self) -> FlagSet<Metakey> {
         self.bit
@@ -311,6 +338,38 @@ impl Metadata {
         self
     }
 
+    /// Creation time of this entry.
+    ///
+    /// Only services that keep the creation time separately return it,
+    /// like `fs` (birth time, if supported by the platform and filesystem)
+    /// and `azblob` (`x-ms-creation-time`). It's `None` on other services
+    /// like `s3`, which only have [`Metadata::last_modified`].
+    ///
+    /// Overwriting an object keeps its creation time on `azblob`, but
+    /// services that replace the file on write may reset it.
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        debug_assert!(
+            self.bit.contains(Metakey::Created) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: created, maybe a bug"
+        );
+
+        self.created
+    }
+
+    /// Set creation time of this entry.
+    pub fn set_created(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.created = Some(v);
+        self.bit |= Metakey::Created;
+        self
+    }
+
+    /// Set creation time of this entry.
+    pub fn with_created(mut self, v: DateTime<Utc>) -> Self {
+        self.created = Some(v);
+        self.bit |= Metakey::Created;
+        self
+    }
+
     /// ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -365,6 +424,318 @@ impl Metadata {
         self
     }
 
+    /// The time after which this entry expires.
+    ///
+    /// Only returned by services that support [`OpWrite::with_expire`] for
+    /// entries written with it.
+    ///
+    /// [`OpWrite::with_expire`]: crate::ops::OpWrite::with_expire
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        debug_assert!(
+            self.bit.contains(Metakey::ExpiresAt) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: expires_at, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.expires_at)
+    }
+
+    /// Set the time after which this entry expires.
+    pub fn set_expires_at(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.ext_mut().expires_at = Some(v);
+        self.bit |= Metakey::ExpiresAt;
+        self
+    }
+
+    /// Set the time after which this entry expires.
+    pub fn with_expires_at(mut self, v: DateTime<Utc>) -> Self {
+        self.ext_mut().expires_at = Some(v);
+        self.bit |= Metakey::ExpiresAt;
+        self
+    }
+
+    /// Generation of this entry.
+    ///
+    /// Generation is a number that changes every time the object is
+    /// overwritten, it's returned by services like `gcs`. Use it with
+    /// [`OpWrite::with_if_generation_match`] to implement compare-and-swap.
+    ///
+    /// [`OpWrite::with_if_generation_match`]: crate::ops::OpWrite::with_if_generation_match
+    pub fn generation(&self) -> Option<i64> {
+        debug_assert!(
+            self.bit.contains(Metakey::Generation) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: generation, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.generation)
+    }
+
+    /// Set generation of this entry.
+    pub fn set_generation(&mut self, v: i64) -> &mut Self {
+        self.ext_mut().generation = Some(v);
+        self.bit |= Metakey::Generation;
+        self
+    }
+
+    /// Set generation of this entry.
+    pub fn with_generation(mut self, v: i64) -> Self {
+        self.ext_mut().generation = Some(v);
+        self.bit |= Metakey::Generation;
+        self
+    }
+
+    /// Decompressed size of this entry.
+    ///
+    /// Decompressed size is the size of content before compression, it's
+    /// stored with objects written by `CompressionLayer` on services that
+    /// support [`OpWrite::with_decompressed_size`].
+    ///
+    /// `None` means the object is not compressed or the size is unknown.
+    ///
+    /// [`OpWrite::with_decompressed_size`]: crate::ops::OpWrite::with_decompressed_size
+    pub fn decompressed_size(&self) -> Option<u64> {
+        debug_assert!(
+            self.bit.contains(Metakey::DecompressedSize) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: decompressed_size, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.decompressed_size)
+    }
+
+    /// Set decompressed size of this entry.
+    pub fn set_decompressed_size(&mut self, v: u64) -> &mut Self {
+        self.ext_mut().decompressed_size = Some(v);
+        self.bit |= Metakey::DecompressedSize;
+        self
+    }
+
+    /// Set decompressed size of this entry.
+    pub fn with_decompressed_size(mut self, v: u64) -> Self {
+        self.ext_mut().decompressed_size = Some(v);
+        self.bit |= Metakey::DecompressedSize;
+        self
+    }
+
+    /// Version of this entry.
+    ///
+    /// Version is the id of the object version that this metadata
+    /// belongs to, it's returned by services with versioning enabled
+    /// like `s3`. Use it with [`OpRead::with_version`] to read exactly
+    /// this version.
+    ///
+    /// [`OpRead::with_version`]: crate::ops::OpRead::with_version
+    pub fn version(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::Version) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: version, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.version.as_deref())
+    }
+
+    /// Set version of this entry.
+    pub fn set_version(&mut self, v: &str) -> &mut Self {
+        self.ext_mut().version = Some(v.to_string());
+        self.bit |= Metakey::Version;
+        self
+    }
+
+    /// Set version of this entry.
+    pub fn with_version(mut self, v: String) -> Self {
+        self.ext_mut().version = Some(v);
+        self.bit |= Metakey::Version;
+        self
+    }
+
+    /// Server side encryption of this entry.
+    ///
+    /// `None` means the entry is not encrypted by service or the service
+    /// doesn't support server side encryption.
+    pub fn encryption(&self) -> Option<&EncryptionInfo> {
+        debug_assert!(
+            self.bit.contains(Metakey::Encryption) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: encryption, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.encryption.as_ref())
+    }
+
+    /// Set server side encryption of this entry.
+    pub fn set_encryption(&mut self, v: EncryptionInfo) -> &mut Self {
+        self.ext_mut().encryption = Some(v);
+        self.bit |= Metakey::Encryption;
+        self
+    }
+
+    /// Set server side encryption of this entry.
+    pub fn with_encryption(mut self, v: EncryptionInfo) -> Self {
+        self.ext_mut().encryption = Some(v);
+        self.bit |= Metakey::Encryption;
+        self
+    }
+
+    /// Summary of the ACL of this entry.
+    ///
+    /// ACL is not returned by `stat` since fetching it requires an extra
+    /// request, use [`Operator::get_object_acl`] or query
+    /// [`Metakey::AclSummary`] via [`Operator::metadata`] instead.
+    pub fn acl_summary(&self) -> Option<AclSummary> {
+        debug_assert!(
+            self.bit.contains(Metakey::AclSummary),
+            "visiting not set metadata: acl_summary, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.acl_summary)
+    }
+
+    /// Set summary of the ACL of this entry.
+    pub fn set_acl_summary(&mut self, v: AclSummary) -> &mut Self {
+        self.ext_mut().acl_summary = Some(v);
+        self.bit |= Metakey::AclSummary;
+        self
+    }
+
+    /// Set summary of the ACL of this entry.
+    pub fn with_acl_summary(mut self, v: AclSummary) -> Self {
+        self.ext_mut().acl_summary = Some(v);
+        self.bit |= Metakey::AclSummary;
+        self
+    }
+
+    /// Owner of this entry.
+    ///
+    /// The format of owner depends on services:
+    ///
+    /// - s3: display name of the owner, or canonical user id if display
+    ///   name is not returned.
+    /// - gcs: entity of the owner like `user-liam@example.com`.
+    ///
+    /// Owner is not returned by `stat` since fetching it requires an extra
+    /// request, use [`Operator::get_object_owner`] or query
+    /// [`Metakey::Owner`] via [`Operator::metadata`] instead.
+    pub fn owner(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::Owner),
+            "visiting not set metadata: owner, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.owner.as_deref())
+    }
+
+    /// Set owner of this entry.
+    pub fn set_owner(&mut self, v: &str) -> &mut Self {
+        self.ext_mut().owner = Some(v.to_string());
+        self.bit |= Metakey::Owner;
+        self
+    }
+
+    /// Set owner of this entry.
+    pub fn with_owner(mut self, v: String) -> Self {
+        self.ext_mut().owner = Some(v);
+        self.bit |= Metakey::Owner;
+        self
+    }
+
+    /// Check whether this entry was uploaded via multipart upload, derived
+    /// from the shape of its [`ETag`][Metadata::etag].
+    ///
+    /// S3 and S3 compatible services (like `oss`, `cos`, `obs`, `wasabi`)
+    /// return the hex MD5 of content as etag for single part uploads, and
+    /// `<hex>-<parts>` for multipart uploads. The latter is not the MD5 of
+    /// content and MUST NOT be used for content verification.
+    ///
+    /// - `Some(true)`: etag looks like `"<32 hex>-<N>"`.
+    /// - `Some(false)`: etag looks like `"<32 hex>"`.
+    /// - `None`: etag is not set or its shape is unknown, for example the
+    ///   etags returned by `azblob` or `gcs`.
+    ///
+    /// # Notes
+    ///
+    /// This is a heuristic and has its limits:
+    ///
+    /// - Only meaningful for S3 compatible services, other services may
+    ///   return etags that happen to have the same shape.
+    /// - `Some(false)` doesn't mean etag is the MD5 of content. For example,
+    ///   S3 objects encrypted by SSE-C or SSE-KMS have non-MD5 etags too.
+    pub fn is_multipart(&self) -> Option<bool> {
+        let etag = self.etag()?;
+        let etag = etag.strip_prefix("W/").unwrap_or(etag).trim_matches('"');
+
+        let (hash, parts) = match etag.split_once('-') {
+            Some((hash, parts)) => (hash, Some(parts)),
+            None => (etag, None),
+        };
+        if hash.len() != 32 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        match parts {
+            None => Some(false),
+            Some(parts) if !parts.is_empty() && parts.chars().all(|c| c.is_ascii_digit()) => {
+                Some(true)
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Get the backend neutral identity of this entry's content.
+    ///
+    /// The identity is picked in the following order:
+    ///
+    /// - [`ContentIdentity::ContentHash`] if `Content-MD5` is available.
+    /// - [`ContentIdentity::StrongEtag`] if etag is available and neither
+    ///   weak (`W/"..."`) nor a multipart etag (see [`Metadata::is_multipart`]).
+    /// - [`ContentIdentity::WeakEtag`] if etag is available.
+    /// - [`ContentIdentity::SizeTime`] with content length and last modified.
+    ///
+    /// Please read the docs of [`ContentIdentity`] for the guarantee of every
+    /// variant. Only metadata that has been fetched will be used, call
+    /// `stat` to get the complete metadata if possible.
+    pub fn content_identity(&self) -> ContentIdentity {
+        if let Some(md5) = &self.content_md5 {
+            return ContentIdentity::ContentHash(md5.clone());
+        }
+
+        if let Some(etag) = &self.etag {
+            return if etag.starts_with("W/") || self.is_multipart() == Some(true) {
+                ContentIdentity::WeakEtag(etag.clone())
+            } else {
+                ContentIdentity::StrongEtag(etag.clone())
+            };
+        }
+
+        ContentIdentity::SizeTime {
+            content_length: self.content_length,
+            last_modified: self.last_modified,
+        }
+    }
+
+    /// Content-Language of this entry.
+    ///
+    /// `Content-Language` is defined by [RFC 7231](https://httpwg.org/specs/rfc7231.html#header.content-language)
+    /// Refer to [MDN Content-Language](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Language) for more information.
+    pub fn content_language(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ContentLanguage) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: content_language, maybe a bug"
+        );
+
+        self.ext().and_then(|v| v.content_language.as_deref())
+    }
+
+    /// Set Content-Language of this entry.
+    pub fn with_content_language(mut self, v: String) -> Self {
+        self.ext_mut().content_language = Some(v);
+        self.bit |= Metakey::ContentLanguage;
+        self
+    }
+
+    /// Set Content-Language of this entry.
+    pub fn set_content_language(&mut self, v: &str) -> &mut Self {
+        self.ext_mut().content_language = Some(v.to_string());
+        self.bit |= Metakey::ContentLanguage;
+        self
+    }
+
     /// Content-Disposition of this entry
     ///
     /// `Content-Disposition` is defined by [RFC 2616](https://www.rfc-editor/rfcs/2616) and
@@ -420,6 +791,103 @@ impl Metadata {
     }
 }
 
+/// AclSummary is a backend neutral summary of an object's ACL, returned by
+/// [`Metadata::acl_summary`].
+///
+/// Only grants to everyone are taken into account. An object that is
+/// neither public readable nor public writable is owner only, or shared
+/// with some specific users.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AclSummary {
+    public_read: bool,
+    public_write: bool,
+}
+
+impl AclSummary {
+    /// Create a new acl summary.
+    pub fn new(public_read: bool, public_write: bool) -> Self {
+        Self {
+            public_read,
+            public_write,
+        }
+    }
+
+    /// Whether everyone can read the object.
+    pub fn public_read(&self) -> bool {
+        self.public_read
+    }
+
+    /// Whether everyone can write the object.
+    pub fn public_write(&self) -> bool {
+        self.public_write
+    }
+
+    /// Whether the object is accessible by everyone.
+    pub fn is_public(&self) -> bool {
+        self.public_read || self.public_write
+    }
+}
+
+/// EncryptionInfo is the server side encryption of an entry, returned by
+/// [`Metadata::encryption`].
+///
+/// Variants are named after s3, other services are mapped to the closest one:
+///
+/// - azblob: `x-ms-server-encrypted` is `SseS3`, `x-ms-encryption-key-sha256`
+///   is `SseC`.
+/// - gcs: google-managed keys is `SseS3`, `kmsKeyName` is `SseKms` and
+///   `customerEncryption` is `SseC`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EncryptionInfo {
+    /// Encrypted with keys managed by service.
+    SseS3,
+    /// Encrypted with keys managed by a key management service.
+    SseKms {
+        /// The id of the key, could be `None` if service doesn't return it.
+        key_id: Option<String>,
+    },
+    /// Encrypted with keys provided by customer.
+    SseC,
+}
+
+/// ContentIdentity is a backend neutral identity of an entry's content,
+/// returned by [`Metadata::content_identity`].
+///
+/// Variants are listed from the highest confidence to the lowest. Identities
+/// are only comparable with the same variant, and only from the same service
+/// for etags.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentIdentity {
+    /// The MD5 of content returned by service as `Content-MD5`.
+    ///
+    /// Equal hashes mean equal content, even across services.
+    ContentHash(String),
+    /// A strong etag returned by service.
+    ///
+    /// Equal etags mean equal content in the same service. Some services
+    /// like `s3` return the MD5 of content as strong etag, but that's not
+    /// guaranteed, for example `azblob` and `gcs` return opaque versions.
+    StrongEtag(String),
+    /// A weak etag (`W/"..."`) or multipart etag (`"<hex>-<parts>"`).
+    ///
+    /// Equal etags usually mean equal content in the same service, but
+    /// the same content could have different etags. For example, uploading
+    /// the same content with different part sizes leads to different
+    /// multipart etags.
+    WeakEtag(String),
+    /// Content length and last modified time.
+    ///
+    /// The weakest identity, changed content may keep the same identity if
+    /// it's written within the precision of last modified with the same
+    /// size. Both could be `None` if service doesn't return them.
+    SizeTime {
+        /// Content length of the entry.
+        content_length: Option<u64>,
+        /// Last modified time of the entry.
+        last_modified: Option<DateTime<Utc>>,
+    },
+}
+
 flags! {
     /// Metakey describes the metadata keys that can be stored
     /// or queried.
@@ -441,10 +909,17 @@ flags! {
 
         /// Key for mode.
         Mode,
+        /// Key for acl summary.
+        ///
+        /// ACL is never returned by `stat`, so [`Metakey::Complete`]
+        /// doesn't cover it.
+        AclSummary,
         /// Key for cache control.
         CacheControl,
         /// Key for content disposition.
         ContentDisposition,
+        /// Key for content language.
+        ContentLanguage,
         /// Key for content length.
         ContentLength,
         /// Key for content md5.
@@ -453,9 +928,114 @@ flags! {
         ContentRange,
         /// Key for content type.
         ContentType,
+        /// Key for decompressed size.
+        DecompressedSize,
+        /// Key for creation time.
+        Created,
+        /// Key for encryption.
+        Encryption,
         /// Key for etag.
         Etag,
+        /// Key for expires at.
+        ExpiresAt,
+        /// Key for generation.
+        Generation,
         /// Key for last last modified.
         LastModified,
+        /// Key for owner.
+        ///
+        /// Owner is never returned by `stat`, so [`Metakey::Complete`]
+        /// doesn't cover it.
+        Owner,
+        /// Key for version.
+        Version,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_multipart() {
+        let cases = vec![
+            ("not set", None, None),
+            (
+                "single part",
+                Some(r#""d41d8cd98f00b204e9800998ecf8427e""#),
+                Some(false),
+            ),
+            (
+                "multipart",
+                Some(r#""9b2cf535f27731c974343645a3985328-2""#),
+                Some(true),
+            ),
+            (
+                "weak multipart",
+                Some(r#"W/"9b2cf535f27731c974343645a3985328-12""#),
+                Some(true),
+            ),
+            ("azblob", Some(r#""0x8D91A1A2B1C2D3E""#), None),
+            (
+                "invalid parts",
+                Some(r#""9b2cf535f27731c974343645a3985328-x""#),
+                None,
+            ),
+        ];
+
+        for (name, etag, expected) in cases {
+            let mut meta = Metadata::new(EntryMode::FILE).with_bit(Metakey::Etag);
+            if let Some(etag) = etag {
+                meta.set_etag(etag);
+            }
+
+            assert_eq!(meta.is_multipart(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_content_identity() {
+        let md5 = "1B2M2Y8AsgTpgAmY7PhCfg==";
+        let multipart = r#""9b2cf535f27731c974343645a3985328-2""#;
+        let single = r#""d41d8cd98f00b204e9800998ecf8427e""#;
+        let last_modified = Utc.timestamp_opt(1_680_000_000, 0).unwrap();
+
+        // Content-MD5 is preferred even if a multipart etag exists.
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_etag(multipart.to_string())
+            .with_content_md5(md5.to_string());
+        assert_eq!(
+            meta.content_identity(),
+            ContentIdentity::ContentHash(md5.to_string())
+        );
+
+        let meta = Metadata::new(EntryMode::FILE).with_etag(multipart.to_string());
+        assert_eq!(
+            meta.content_identity(),
+            ContentIdentity::WeakEtag(multipart.to_string())
+        );
+
+        let meta = Metadata::new(EntryMode::FILE).with_etag(format!("W/{single}"));
+        assert_eq!(
+            meta.content_identity(),
+            ContentIdentity::WeakEtag(format!("W/{single}"))
+        );
+
+        let meta = Metadata::new(EntryMode::FILE).with_etag(single.to_string());
+        assert_eq!(
+            meta.content_identity(),
+            ContentIdentity::StrongEtag(single.to_string())
+        );
+
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(1024)
+            .with_last_modified(last_modified);
+        assert_eq!(
+            meta.content_identity(),
+            ContentIdentity::SizeTime {
+                content_length: Some(1024),
+                last_modified: Some(last_modified),
+            }
+        );
     }
 }
@@ -22,23 +22,49 @@ mod entry;
 pub use entry::Entry;
 
 mod metadata;
+pub use metadata::AclSummary;
+pub use metadata::ContentIdentity;
+pub use metadata::EncryptionInfo;
 pub use metadata::Metadata;
 pub use metadata::Metakey;
 
 mod reader;
+pub(crate) use reader::check_read_size;
+pub(crate) use reader::read_buffer_capacity;
+pub(crate) use reader::read_with_clamp_range;
 pub use reader::BlockingReader;
 pub use reader::Reader;
 
 mod writer;
 pub use writer::BlockingWriter;
+pub use writer::PartInfo;
 pub use writer::Writer;
 
+#[cfg(feature = "spill")]
+mod spill;
+#[cfg(feature = "spill")]
+pub(crate) use spill::SpillWriter;
+
 mod appender;
 pub use appender::Appender;
 
+mod archive;
+pub use archive::ArchiveFormat;
+
+mod chunker;
+pub use chunker::ChunkRef;
+pub use chunker::ChunkerConfig;
+
+mod merge;
+pub use merge::MergeStrategy;
+
 mod list;
 pub use list::BlockingLister;
+pub(crate) use list::ConsistencyCheckPager;
+pub use list::ListConsistency;
+pub use list::ListSummary;
 pub use list::Lister;
+pub(crate) use list::MergePager;
 
 mod operator;
 pub use operator::BlockingOperator;
@@ -59,5 +85,7 @@ pub use scheme::Scheme;
 
 mod capability;
 pub use capability::Capability;
+pub use capability::CapabilityReport;
+pub use capability::CapabilityValue;
 
 pub mod ops;
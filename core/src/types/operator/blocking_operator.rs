@@ -363,19 +363,29 @@ impl BlockingOperator {
         }
 
         let br = BytesRange::from(range);
+        br.validate()
+            .map_err(|err| err.with_context("path", &path))?;
+
         let (rp, mut s) = self
             .inner()
             .blocking_read(&path, OpRead::new().with_range(br))?;
 
-        let mut buffer = Vec::with_capacity(rp.into_metadata().content_length() as usize);
+        let content_length = rp.into_metadata().content_length_raw();
+        let mut buffer = Vec::with_capacity(read_buffer_capacity(None, content_length));
         s.read_to_end(&mut buffer).map_err(|err| {
             Error::new(ErrorKind::Unexpected, "blocking range read failed")
                 .with_operation("BlockingOperator::range_read")
                 .with_context("service", self.info().scheme().into_static())
-                .with_context("path", path)
+                .with_context("path", &path)
                 .with_context("range", br.to_string())
                 .set_source(err)
         })?;
+        check_read_size(content_length, buffer.len()).map_err(|err| {
+            err.with_operation("BlockingOperator::range_read")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", path)
+                .with_context("range", br.to_string())
+        })?;
 
         Ok(buffer)
     }
@@ -422,7 +432,11 @@ impl BlockingOperator {
             );
         }
 
-        let op = OpRead::new().with_range(range.into());
+        let br = BytesRange::from(range);
+        br.validate()
+            .map_err(|err| err.with_context("path", &path))?;
+
+        let op = OpRead::new().with_range(br);
 
         BlockingReader::create(self.inner().clone(), &path, op)
     }
@@ -596,24 +610,62 @@ impl BlockingOperator {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "write path is a directory")
-                    .with_operation("BlockingOperator::write_with")
-                    .with_context("service", self.info().scheme().into_static())
-                    .with_context("path", &path),
-            );
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("BlockingOperator::write_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        if args.require_parent() {
+            self.check_parent(&path, "BlockingOperator::write_with")?;
         }
 
         let bs = bs.into();
-        let (_, mut w) = self
-            .inner()
-            .blocking_write(&path, args.with_content_length(bs.len() as u64))?;
+        // The whole content is owned here and written in one shot, it's safe to replay.
+        let args = args
+            .with_content_length(bs.len() as u64)
+            .with_idempotent(true);
+        let (_, mut w) = self.inner().blocking_write(&path, args)?;
         w.write(bs)?;
         w.close()?;
 
         Ok(())
     }
 
+    /// Make sure the parent directory of path exists.
+    fn check_parent(&self, path: &str, operation: &'static str) -> Result<()> {
+        let parent = get_parent(path);
+        if parent == "/" {
+            return Ok(());
+        }
+
+        match self
+            .inner()
+            .blocking_stat(parent, OpStat::new())
+            .map(RpStat::into_metadata)
+        {
+            Ok(meta) if meta.is_dir() => Ok(()),
+            Ok(_) => Err(Error::new(
+                ErrorKind::NotADirectory,
+                "parent directory is not a directory",
+            )),
+            Err(err) if err.kind() == ErrorKind::NotFound => Err(Error::new(
+                ErrorKind::NotFound,
+                "parent directory doesn't exist",
+            )),
+            Err(err) => Err(err),
+        }
+        .map_err(|err| {
+            err.with_operation(operation)
+                .with_context("service", self.info().scheme())
+                .with_context("path", path)
+                .with_context("parent", parent)
+        })
+    }
+
     /// Write multiple bytes into given path.
     ///
     /// # Notes
@@ -641,12 +693,13 @@ impl BlockingOperator {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "write path is a directory")
-                    .with_operation("BlockingOperator::writer")
-                    .with_context("service", self.info().scheme().into_static())
-                    .with_context("path", &path),
-            );
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("BlockingOperator::writer")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
         }
 
         let op = OpWrite::default();
@@ -671,9 +724,26 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path, OpDelete::new())
+    }
+
+    /// Delete given path with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # use opendal::ops::OpDelete;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.delete_with("path/to/file", OpDelete::new().with_bypass_governance(true))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_with(&self, path: &str, args: OpDelete) -> Result<()> {
         let path = normalize_path(path);
 
-        let _ = self.inner().blocking_delete(&path, OpDelete::new())?;
+        let _ = self.inner().blocking_delete(&path, args)?;
 
         Ok(())
     }
@@ -16,6 +16,8 @@
 // under the License.
 
 use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
 use std::sync::Arc;
 
 use crate::layers::*;
@@ -223,6 +225,164 @@ impl Operator {
         Ok(op)
     }
 
+    /// Create a new operator from given scheme and environment variables.
+    ///
+    /// # Notes
+    ///
+    /// All environment variables named `OPENDAL_<SCHEME>_<KEY>` will be
+    /// collected into a map with `<key>` in lower case and passed to
+    /// [`Operator::via_map`]. For example:
+    ///
+    /// - `OPENDAL_S3_BUCKET=test` => `bucket=test` for [`Scheme::S3`].
+    /// - `OPENDAL_FS_ROOT=/tmp` => `root=/tmp` for [`Scheme::Fs`].
+    /// - `OPENDAL_VERCEL_ARTIFACTS_ACCESS_TOKEN=xxx` =>
+    ///   `access_token=xxx` for [`Scheme::VercelArtifacts`].
+    ///
+    /// Names are matched case-insensitively, and variables that are not
+    /// valid unicode are ignored. Unlike `via_map`, keys not known by the
+    /// service are rejected with an [`ErrorKind::ConfigInvalid`] naming
+    /// the unknown variables.
+    ///
+    /// Values from environment variables are set on the builder directly,
+    /// so they take precedence over the configs loaded by services
+    /// themselves, like `AWS_*` environment variables loaded by s3.
+    ///
+    /// If the service can't be built, for example a required field is
+    /// missing, an [`ErrorKind::ConfigInvalid`] will be returned with the
+    /// environment variables that have been found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// use opendal::Scheme;
+    ///
+    /// # fn main() -> Result<()> {
+    /// // OPENDAL_S3_BUCKET=test OPENDAL_S3_REGION=us-east-1
+    /// let op: Operator = Operator::from_env(Scheme::S3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env(scheme: Scheme) -> Result<Operator> {
+        Self::from_vars(scheme, env::vars_os())
+    }
+
+    /// Create a new operator from given scheme and variables, which are
+    /// handled the same as environment variables in [`Operator::from_env`].
+    fn from_vars(
+        scheme: Scheme,
+        vars: impl IntoIterator<Item = (OsString, OsString)>,
+    ) -> Result<Operator> {
+        let prefix = format!("opendal_{scheme}_");
+
+        let map = vars
+            .into_iter()
+            .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+            .filter_map(|(k, v)| {
+                k.to_lowercase()
+                    .strip_prefix(&prefix)
+                    .map(|k| (k.to_string(), v))
+            })
+            .collect::<HashMap<String, String>>();
+
+        let keys = Self::config_keys(scheme);
+        let mut unknown = map
+            .keys()
+            .filter(|k| !keys.is_empty() && !keys.contains(&k.as_str()))
+            .map(|k| format!("{prefix}{k}").to_uppercase())
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "environment variables contain unknown keys",
+            )
+            .with_operation("Operator::from_env")
+            .with_context("scheme", scheme)
+            .with_context("unknown", format!("[{}]", unknown.join(", "))));
+        }
+
+        let mut names = map
+            .keys()
+            .map(|k| format!("{prefix}{k}").to_uppercase())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        Self::via_map(scheme, map).map_err(|err| match err.kind() {
+            ErrorKind::ConfigInvalid => err
+                .with_operation("Operator::from_env")
+                .with_context("prefix", prefix.to_uppercase())
+                .with_context("found", format!("[{}]", names.join(", "))),
+            _ => err,
+        })
+    }
+
+    /// Get the keys accepted by the builder of given scheme.
+    fn config_keys(scheme: Scheme) -> &'static [&'static str] {
+        match scheme {
+            #[cfg(feature = "services-azblob")]
+            Scheme::Azblob => services::Azblob::CONFIG_KEYS,
+            #[cfg(feature = "services-azdfs")]
+            Scheme::Azdfs => services::Azdfs::CONFIG_KEYS,
+            #[cfg(feature = "services-cos")]
+            Scheme::Cos => services::Cos::CONFIG_KEYS,
+            #[cfg(feature = "services-dashmap")]
+            Scheme::Dashmap => services::Dashmap::CONFIG_KEYS,
+            #[cfg(feature = "services-fs")]
+            Scheme::Fs => services::Fs::CONFIG_KEYS,
+            #[cfg(feature = "services-ftp")]
+            Scheme::Ftp => services::Ftp::CONFIG_KEYS,
+            #[cfg(feature = "services-gcs")]
+            Scheme::Gcs => services::Gcs::CONFIG_KEYS,
+            #[cfg(feature = "services-ghac")]
+            Scheme::Ghac => services::Ghac::CONFIG_KEYS,
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs => services::Hdfs::CONFIG_KEYS,
+            #[cfg(feature = "services-http")]
+            Scheme::Http => services::Http::CONFIG_KEYS,
+            #[cfg(feature = "services-ipfs")]
+            Scheme::Ipfs => services::Ipfs::CONFIG_KEYS,
+            #[cfg(feature = "services-ipmfs")]
+            Scheme::Ipmfs => services::Ipmfs::CONFIG_KEYS,
+            #[cfg(feature = "services-memcached")]
+            Scheme::Memcached => services::Memcached::CONFIG_KEYS,
+            #[cfg(feature = "services-memory")]
+            Scheme::Memory => services::Memory::CONFIG_KEYS,
+            #[cfg(feature = "services-moka")]
+            Scheme::Moka => services::Moka::CONFIG_KEYS,
+            #[cfg(feature = "services-obs")]
+            Scheme::Obs => services::Obs::CONFIG_KEYS,
+            #[cfg(feature = "services-onedrive")]
+            Scheme::Onedrive => services::Onedrive::CONFIG_KEYS,
+            #[cfg(feature = "services-gdrive")]
+            Scheme::Gdrive => services::Gdrive::CONFIG_KEYS,
+            #[cfg(feature = "services-oss")]
+            Scheme::Oss => services::Oss::CONFIG_KEYS,
+            #[cfg(feature = "services-redis")]
+            Scheme::Redis => services::Redis::CONFIG_KEYS,
+            #[cfg(feature = "services-rocksdb")]
+            Scheme::Rocksdb => services::Rocksdb::CONFIG_KEYS,
+            #[cfg(feature = "services-s3")]
+            Scheme::S3 => services::S3::CONFIG_KEYS,
+            #[cfg(feature = "services-sftp")]
+            Scheme::Sftp => services::Sftp::CONFIG_KEYS,
+            #[cfg(feature = "services-sled")]
+            Scheme::Sled => services::Sled::CONFIG_KEYS,
+            #[cfg(feature = "services-supabase")]
+            Scheme::Supabase => services::Supabase::CONFIG_KEYS,
+            #[cfg(feature = "services-vercel-artifacts")]
+            Scheme::VercelArtifacts => services::VercelArtifacts::CONFIG_KEYS,
+            #[cfg(feature = "services-wasabi")]
+            Scheme::Wasabi => services::Wasabi::CONFIG_KEYS,
+            #[cfg(feature = "services-webdav")]
+            Scheme::Webdav => services::Webdav::CONFIG_KEYS,
+            #[cfg(feature = "services-webhdfs")]
+            Scheme::Webhdfs => services::Webhdfs::CONFIG_KEYS,
+            _ => &[],
+        }
+    }
+
     /// Create a new layer with dynamic dispatch.
     ///
     /// # Notes
@@ -362,3 +522,59 @@ impl<A: Accessor> OperatorBuilder<A> {
         Operator::from_inner(Arc::new(ob.accessor) as FusedAccessor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vs: &[(&str, &str)]) -> Vec<(OsString, OsString)> {
+        vs.iter()
+            .map(|(k, v)| (OsString::from(k), OsString::from(v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_env() -> Result<()> {
+        let op = Operator::from_vars(
+            Scheme::Memory,
+            vars(&[
+                ("OPENDAL_MEMORY_ROOT", "/from_env/"),
+                ("OPENDAL_FS_ROOT", "/ignored/"),
+            ]),
+        )?;
+
+        assert_eq!(op.info().root(), "/from_env/");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_env_unknown_key() {
+        let err = Operator::from_vars(
+            Scheme::Memory,
+            vars(&[
+                ("OPENDAL_MEMORY_ROOT", "/from_env/"),
+                ("opendal_memory_bucket", "test"),
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(
+            err.to_string().contains("OPENDAL_MEMORY_BUCKET"),
+            "unknown variables must be reported: {err}"
+        );
+    }
+
+    #[cfg(feature = "services-s3")]
+    #[test]
+    fn test_from_env_config_invalid() {
+        let err = Operator::from_vars(Scheme::S3, vars(&[("OPENDAL_S3_REGION", "us-east-1")]))
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(
+            err.to_string().contains("OPENDAL_S3_REGION"),
+            "found variables must be reported: {err}"
+        );
+    }
+}
@@ -47,11 +47,23 @@ impl OperatorInfo {
         self.0.name()
     }
 
+    /// Endpoint of backend, could be empty if underlying backend doesn't
+    /// talk to a remote endpoint.
+    pub fn endpoint(&self) -> &str {
+        self.0.endpoint()
+    }
+
     /// Get [`Capability`] of operator.
     pub fn capability(&self) -> Capability {
         self.0.capability()
     }
 
+    /// Get a [`CapabilityReport`] of operator which contains every
+    /// capability flag and limit in a serializable shape.
+    pub fn capability_report(&self) -> CapabilityReport {
+        CapabilityReport::new(self.scheme(), self.name(), self.capability())
+    }
+
     /// Check if current backend supports [`Accessor::read`] or not.
     pub fn can_read(&self) -> bool {
         self.0.capability().read
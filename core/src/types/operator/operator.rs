@@ -15,23 +15,52 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io;
+use std::ops::Range;
 use std::ops::RangeBounds;
 use std::time::Duration;
 
+use backon::BackoffBuilder;
+use backon::ExponentialBuilder;
 use bytes::Bytes;
+use bytes::BytesMut;
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
+use futures::future;
 use futures::stream;
+use futures::AsyncRead;
 use futures::AsyncReadExt;
+use futures::AsyncSeek;
+use futures::AsyncSeekExt;
+use futures::AsyncWrite;
+use futures::AsyncWriteExt;
+use futures::Future;
+use futures::FutureExt;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
-use tokio::io::ReadBuf;
+use log::warn;
+use sha2::Digest;
+use sha2::Sha256;
 
 use super::BlockingOperator;
+use crate::layers::TimeTravelLayer;
 use crate::ops::*;
 use crate::raw::*;
+use crate::types::archive::*;
 use crate::*;
 
+/// The suffix of the dir that stores ranges on services without native
+/// ranged write.
+const RANGED_WRITE_SUFFIX: &str = ".ranges/";
+
+/// The max attempts of [`Operator::merge_json`] on conflicts.
+const MERGE_JSON_MAX_ATTEMPTS: usize = 10;
+
 /// Operator is the entry for all public async APIs.
 /// Developer should manipulate the data from storage service through Operator only by right.
 ///
@@ -99,12 +128,35 @@ impl Operator {
     /// Specify the batch limit.
     ///
     /// Default: 1000
+    ///
+    /// # Notes
+    ///
+    /// Batch operations will be split by backend's `batch_max_operations`
+    /// if the limit is larger than it.
     pub fn with_limit(&self, limit: usize) -> Self {
         let mut op = self.clone();
         op.limit = limit;
         op
     }
 
+    /// Get the size of every batch request, which is the limit clamped to
+    /// backend's `batch_max_operations`.
+    fn batch_limit(&self) -> usize {
+        match self.info().capability().batch_max_operations {
+            Some(max) if self.limit > max => {
+                warn!(
+                    target: "opendal::operator",
+                    "limit {} is larger than batch max operations {} of service {}, clamped",
+                    self.limit,
+                    max,
+                    self.info().scheme()
+                );
+                max
+            }
+            _ => self.limit,
+        }
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -130,6 +182,40 @@ impl Operator {
     pub fn blocking(&self) -> BlockingOperator {
         BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
     }
+
+    /// Create a read-only operator pinned at given time.
+    ///
+    /// All `read`, `stat` and `list` through the returned operator resolve
+    /// the version of objects that was current at `at`. Operations that
+    /// change storage like `write` and `delete` will be rejected with
+    /// [`ErrorKind::PermissionDenied`].
+    ///
+    /// Services must support [`Capability::list_versions`] and
+    /// [`Capability::read_with_version`], see [`TimeTravelLayer`] for
+    /// more details.
+    ///
+    /// [`TimeTravelLayer`]: crate::layers::TimeTravelLayer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use chrono::Duration;
+    /// use chrono::Utc;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let snapshot = op.snapshot_at(Utc::now() - Duration::hours(1));
+    /// let bs = snapshot.read("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot_at(&self, at: DateTime<Utc>) -> Operator {
+        self.clone()
+            .layer(TimeTravelLayer::new(at))
+            .with_limit(self.limit)
+    }
 }
 
 /// Operator async API.
@@ -255,6 +341,10 @@ impl Operator {
     /// Visiting not fetched metadata will lead to panic in debug build.
     /// It must be a bug, please fix it instead.
     ///
+    /// [`Metakey::AclSummary`] and [`Metakey::Owner`] are opt-in and not
+    /// covered by `Complete`. Querying them costs an extra request, see
+    /// [`Operator::get_object_acl`] and [`Operator::get_object_owner`].
+    ///
     /// # Examples
     ///
     /// ## Query already cached metadata
@@ -323,15 +413,29 @@ impl Operator {
         entry: &Entry,
         flags: impl Into<FlagSet<Metakey>>,
     ) -> Result<Metadata> {
+        let flags = flags.into();
+        // ACL and owner are not covered by `Complete` since stat never
+        // returns them.
+        let with_acl = !(flags & (Metakey::AclSummary | Metakey::Owner)).is_empty();
+
         // Check if cached metadata saticifies the query.
         if let Some(meta) = entry.metadata() {
-            if meta.bit().contains(flags) || meta.bit().contains(Metakey::Complete) {
+            if meta.bit().contains(flags) || (meta.bit().contains(Metakey::Complete) && !with_acl) {
                 return Ok(meta.clone());
             }
         }
 
-        // Else request from backend..
-        let meta = self.stat(entry.path()).await?;
+        // Else request from backend, entries returned by `merge_list`
+        // must be queried from the operator they are listed from.
+        let op = entry.source().unwrap_or(self);
+        let mut meta = op.stat(entry.path()).await?;
+        if with_acl && meta.mode().is_file() {
+            let rp = op
+                .inner()
+                .acl(&normalize_path(entry.path()), OpAcl::new())
+                .await?;
+            meta = Self::with_owner(meta.with_acl_summary(rp.summary()), rp);
+        }
         Ok(meta)
     }
 
@@ -516,20 +620,18 @@ impl Operator {
         }
 
         let br = BytesRange::from(range);
+        br.validate()
+            .map_err(|err| err.with_context("path", &path))?;
 
-        let (rp, mut s) = self.inner().read(&path, args.with_range(br)).await?;
-
-        let length = rp.into_metadata().content_length() as usize;
-        let mut buffer = Vec::with_capacity(length);
-
-        let dst = buffer.spare_capacity_mut();
-        let mut buf = ReadBuf::uninit(dst);
+        let size_hint = args.size_hint();
+        let (rp, mut s) = read_with_clamp_range(self.inner(), &path, args.with_range(br)).await?;
 
-        // Safety: the input buffer is created with_capacity(length).
-        unsafe { buf.assume_init(length) };
+        let content_length = rp.into_metadata().content_length_raw();
+        let capacity = read_buffer_capacity(size_hint, content_length);
+        let mut buffer = Vec::with_capacity(capacity);
 
         // TODO: use native read api
-        s.read_exact(buf.initialized_mut()).await.map_err(|err| {
+        s.read_to_end(&mut buffer).await.map_err(|err| {
             Error::new(ErrorKind::Unexpected, "read from storage")
                 .with_operation("range_read")
                 .with_context("service", self.inner().info().scheme().into_static())
@@ -537,1012 +639,5768 @@ impl Operator {
                 .with_context("range", br.to_string())
                 .set_source(err)
         })?;
-
-        // Safety: read_exact makes sure this buffer has been filled.
-        unsafe { buffer.set_len(length) }
+        check_read_size(content_length, buffer.len()).map_err(|err| {
+            err.with_operation("range_read")
+                .with_context("service", self.inner().info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("range", br.to_string())
+        })?;
 
         Ok(buffer)
     }
 
-    /// Create a new reader which can read the whole path.
-    ///
-    /// # Examples
+    /// Read at most `max_bytes` from the start of the given path.
     ///
-    /// ```no_run
-    /// # use std::io::Result;
-    /// # use opendal::Operator;
-    /// # use futures::TryStreamExt;
-    /// # use opendal::Scheme;
-    /// # #[tokio::main]
-    /// # async fn test(op: Operator) -> Result<()> {
-    /// let r = op.reader("path/to/file").await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn reader(&self, path: &str) -> Result<Reader> {
-        self.reader_with(path, OpRead::default()).await
-    }
-
-    /// Create a new reader which can read the specified range.
+    /// Unlike [`Operator::range_read`], this function doesn't need to know
+    /// the size of the object: only the first `max_bytes` will be requested,
+    /// so the rest of the object won't be fetched.
     ///
     /// # Notes
     ///
-    /// - The returning content's length may be smaller than the range specified.
+    /// - The returning content's length may be smaller than `max_bytes` if
+    ///   the object is smaller, and no error will be returned for that.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// # use futures::TryStreamExt;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let r = op.range_reader("path/to/file", 1024..2048).await?;
+    /// let header = op.read_prefix("path/to/file", 512).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn range_reader(&self, path: &str, range: impl RangeBounds<u64>) -> Result<Reader> {
-        self.reader_with(path, OpRead::new().with_range(range.into()))
-            .await
+    pub async fn read_prefix(&self, path: &str, max_bytes: usize) -> Result<Bytes> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("read_prefix")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        if max_bytes == 0 {
+            return Ok(Bytes::new());
+        }
+
+        // Objects smaller than `max_bytes` (including empty ones) will be
+        // read entirely instead of failing with range not satisfied.
+        let op = OpRead::new()
+            .with_range(BytesRange::new(Some(0), Some(max_bytes as u64)))
+            .with_clamp_range(true);
+        let (rp, mut s) = read_with_clamp_range(self.inner(), &path, op).await?;
+
+        let capacity = read_buffer_capacity(None, rp.into_metadata().content_length_raw());
+        let mut buffer = Vec::with_capacity(capacity.min(max_bytes));
+        s.read_to_end(&mut buffer).await.map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "read from storage")
+                .with_operation("read_prefix")
+                .with_context("service", self.inner().info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("max_bytes", max_bytes.to_string())
+                .set_source(err)
+        })?;
+
+        Ok(buffer.into())
     }
 
-    /// Create a new reader with extra options
+    /// Read the whole path into `w` by fetching parts concurrently.
+    ///
+    /// The file will be split into parts of `part_size`, at most `concurrency`
+    /// parts will be fetched at the same time. Parts are written into `w` in
+    /// order as soon as all their previous parts have been written, parts
+    /// completed out of order are held until then. So memory usage is bounded
+    /// to `part_size * concurrency` instead of the whole file.
+    ///
+    /// Returns the total bytes written.
+    ///
+    /// Returns [`ErrorKind::ConfigInvalid`] if `part_size` or `concurrency`
+    /// is `0`.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// # use futures::TryStreamExt;
-    /// # use opendal::Scheme;
-    /// # use opendal::ops::OpRead;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let r = op
-    ///     .reader_with("path/to/file", OpRead::default().with_range((0..10).into()))
+    /// let mut w = futures::io::Cursor::new(Vec::new());
+    /// let n = op
+    ///     .parallel_read_to("path/to/file", &mut w, 8 * 1024 * 1024, 4)
     ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn reader_with(&self, path: &str, args: OpRead) -> Result<Reader> {
+    pub async fn parallel_read_to<W>(
+        &self,
+        path: &str,
+        w: &mut W,
+        part_size: u64,
+        concurrency: usize,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
         let path = normalize_path(path);
 
+        if part_size == 0 || concurrency == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "part_size and concurrency must be larger than 0",
+            )
+            .with_operation("Operator::parallel_read_to")
+            .with_context("service", self.info().scheme())
+            .with_context("path", &path)
+            .with_context("part_size", part_size.to_string())
+            .with_context("concurrency", concurrency.to_string()));
+        }
+
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "read path is a directory")
-                    .with_operation("Operator::range_reader")
+                    .with_operation("Operator::parallel_read_to")
                     .with_context("service", self.info().scheme())
-                    .with_context("path", path),
+                    .with_context("path", &path),
             );
         }
 
-        Reader::create_dir(self.inner().clone(), &path, args).await
-    }
+        let size = self.stat(&path).await?.content_length();
 
-    /// Write bytes into path.
-    ///
-    /// # Notes
-    ///
-    /// - Write will make sure all bytes has been written, or an error will be returned.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # use std::io::Result;
-    /// # use opendal::Operator;
-    /// # use futures::StreamExt;
-    /// # use futures::SinkExt;
-    /// use bytes::Bytes;
-    ///
-    /// # #[tokio::main]
-    /// # async fn test(op: Operator) -> Result<()> {
-    /// op.write("path/to/file", vec![0; 4096]).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn write(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
-        let bs = bs.into();
-        self.write_with(
-            path,
-            OpWrite::new().with_content_length(bs.len() as u64),
-            bs,
-        )
-        .await
+        let path = path.as_str();
+        // Compute offsets in u64 so that `part_size` will not be truncated
+        // on 32-bit targets.
+        let offsets = std::iter::successors(Some(0), |offset: &u64| offset.checked_add(part_size))
+            .take_while(|offset| *offset < size);
+        let mut parts = stream::iter(offsets)
+            .map(|offset| self.range_read(path, offset..size.min(offset.saturating_add(part_size))))
+            // `buffered` yields results in order and will not start new
+            // parts until the head part has been taken.
+            .buffered(concurrency);
+
+        let new_write_error = |err: io::Error| {
+            Error::new(ErrorKind::Unexpected, "write into output")
+                .with_operation("Operator::parallel_read_to")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path)
+                .set_source(err)
+        };
+
+        while let Some(bs) = parts.try_next().await? {
+            w.write_all(&bs).await.map_err(new_write_error)?;
+        }
+        w.flush().await.map_err(new_write_error)?;
+
+        Ok(size)
     }
 
-    /// Append bytes into path.
+    /// Read multiple ranges of path into bytes.
     ///
-    /// # Notes
-    ///
-    /// - Append will make sure all bytes has been written, or an error will be returned.
-    /// - Append will create the file if it does not exist.
-    /// - Append always write bytes to the end of the file.
+    /// This is the same as [`read_ranges_with_gap`](Self::read_ranges_with_gap)
+    /// with gap of 1 MiB and concurrency of 4.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// use bytes::Bytes;
-    ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.append("path/to/file", vec![0; 4096]).await?;
+    /// let bss = op
+    ///     .read_ranges("path/to/file", vec![0..4, 8..16, 4096..8192])
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn append(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
-        let bs = bs.into();
-        self.append_with(path, OpAppend::new(), bs).await
+    pub async fn read_ranges(&self, path: &str, ranges: Vec<Range<u64>>) -> Result<Vec<Bytes>> {
+        self.read_ranges_with_gap(path, ranges, 1024 * 1024, 4)
+            .await
     }
 
-    /// Copy a file from `from` to `to`.
+    /// Read multiple ranges of path into bytes, ranges whose distance is
+    /// not larger than `gap` will be coalesced into one request.
+    ///
+    /// At most `concurrency` coalesced ranges are fetched at the same time
+    /// and split back into the requested ranges. The returning bytes are in
+    /// the same order as `ranges`.
     ///
     /// # Notes
     ///
-    /// - `from` and `to` must be a file.
-    /// - `to` will be overwritten if it exists.
-    /// - If `from` and `to` are the same,  an `IsSameFile` error will occur.
-    /// - `copy` is idempotent. For same `from` and `to` input, the result will be the same.
+    /// - Returns [`ErrorKind::RangeNotSatisfied`] if any range is empty or
+    ///   inverted, no request will be sent in this case.
+    /// - Bytes in the gaps will be read and dropped, a larger gap means
+    ///   fewer requests but more wasted bandwidth.
+    /// - The returning content's length may be smaller than the range
+    ///   specified if the range exceeds the end of file.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.copy("path/to/file", "path/to/file2").await?;
+    /// let bss = op
+    ///     .read_ranges_with_gap("path/to/file", vec![0..4, 8..16], 64 * 1024, 4)
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+    pub async fn read_ranges_with_gap(
+        &self,
+        path: &str,
+        ranges: Vec<Range<u64>>,
+        gap: u64,
+        concurrency: usize,
+    ) -> Result<Vec<Bytes>> {
+        let path = normalize_path(path);
 
-        if !validate_path(&from, EntryMode::FILE) {
+        if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::read_ranges")
                     .with_context("service", self.info().scheme())
-                    .with_context("from", from),
+                    .with_context("path", &path),
             );
         }
 
-        let to = normalize_path(to);
-
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
+        if let Some(r) = ranges.iter().find(|r| r.start >= r.end) {
+            return Err(Error::new(
+                ErrorKind::RangeNotSatisfied,
+                "range is empty or its start is larger than its end",
+            )
+            .with_operation("Operator::read_ranges")
+            .with_context("service", self.info().scheme())
+            .with_context("path", &path)
+            .with_context("range", format!("{r:?}")));
         }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
+        let mut sorted: Vec<&Range<u64>> = ranges.iter().collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<u64>> = Vec::new();
+        for r in sorted {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end.saturating_add(gap) => {
+                    last.end = last.end.max(r.end)
+                }
+                _ => merged.push(r.clone()),
+            }
         }
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
+        let path = path.as_str();
+        let bss: Vec<Bytes> = stream::iter(merged.iter().cloned())
+            .map(|r| async move { self.range_read(path, r).await.map(Bytes::from) })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
 
-        Ok(())
+        Ok(ranges
+            .iter()
+            .map(|r| {
+                // Merged ranges are sorted and disjoint, the last one starts
+                // before `r` must contain it.
+                let idx = merged.partition_point(|m| m.start <= r.start) - 1;
+                let bs = &bss[idx];
+                let start = ((r.start - merged[idx].start) as usize).min(bs.len());
+                let end = ((r.end - merged[idx].start) as usize).min(bs.len());
+                bs.slice(start..end)
+            })
+            .collect())
     }
 
-    /// Rename a file from `from` to `to`.
+    /// Read a member of the archive at path.
+    ///
+    /// `member` is the path of member inside archive like `dir/file.txt`.
+    /// Returns [`ErrorKind::NotFound`] if the member doesn't exist.
     ///
     /// # Notes
     ///
-    /// - `from` and `to` must be a file.
-    /// - `to` will be overwritten if it exists.
-    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    /// How much of the archive will be fetched depends on the format:
+    ///
+    /// - [`ArchiveFormat::Zip`]: The member is located via the central
+    ///   directory at the end of archive, then only its bytes are fetched.
+    ///   It takes at most four ranged reads no matter how large the
+    ///   archive is.
+    /// - [`ArchiveFormat::Tar`]: Tar doesn't have an index, the archive is
+    ///   streamed from the beginning until the member is found. Reading a
+    ///   member near the end costs almost the same as downloading the
+    ///   whole archive.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use std::io::Result;
+    /// # use anyhow::Result;
     /// # use opendal::Operator;
+    /// use opendal::ArchiveFormat;
     ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// let bs = op
+    ///     .read_archive_member("path/to/archive.zip", "dir/file.txt", ArchiveFormat::Zip)
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
-        let from = normalize_path(from);
+    pub async fn read_archive_member(
+        &self,
+        path: &str,
+        member: &str,
+        format: ArchiveFormat,
+    ) -> Result<Bytes> {
+        let path = normalize_path(path);
 
-        if !validate_path(&from, EntryMode::FILE) {
+        if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::move_")
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::read_archive_member")
                     .with_context("service", self.info().scheme())
-                    .with_context("from", from),
+                    .with_context("path", &path),
             );
         }
 
-        let to = normalize_path(to);
+        let member = normalize_archive_member(member);
+        let res = match format {
+            ArchiveFormat::Zip => self.read_zip_member(&path, member).await,
+            ArchiveFormat::Tar => self.read_tar_member(&path, member).await,
+        };
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
-        }
+        res?.ok_or_else(|| {
+            Error::new(ErrorKind::NotFound, "archive member not found")
+                .with_operation("Operator::read_archive_member")
+                .with_context("service", self.info().scheme())
+                .with_context("path", &path)
+                .with_context("member", member)
+        })
+    }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
-                    .with_operation("Operator::move_")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
-        }
+    async fn read_zip_member(&self, path: &str, member: &str) -> Result<Option<Bytes>> {
+        let size = self.stat(path).await?.content_length();
 
-        self.inner().rename(&from, &to, OpRename::new()).await?;
+        // The central directory is usually small and fetched along with the
+        // end of central directory record.
+        let tail_offset = size.saturating_sub(ZIP_EOCD_MAX_SIZE);
+        let tail = self.range_read(path, tail_offset..size).await?;
+        let (cd_offset, cd_size) = find_zip_central_directory(&tail)?;
 
-        Ok(())
+        let zm = if cd_offset >= tail_offset && cd_offset + cd_size <= size {
+            let start = (cd_offset - tail_offset) as usize;
+            ZipMember::find(&tail[start..start + cd_size as usize], member)?
+        } else {
+            let cd = self
+                .range_read(path, cd_offset..cd_offset + cd_size)
+                .await?;
+            ZipMember::find(&cd, member)?
+        };
+        let zm = match zm {
+            Some(zm) => zm,
+            None => return Ok(None),
+        };
+
+        let offset = zm.local_header_offset();
+        let header = self
+            .range_read(path, offset..offset + ZIP_LOCAL_HEADER_SIZE)
+            .await?;
+        let offset = zm.data_offset(&header)?;
+        let data = self
+            .range_read(path, offset..offset + zm.compressed_size())
+            .await?;
+
+        zm.decompress(data.into()).map(Some)
     }
 
-    /// Write multiple bytes into path.
-    ///
-    /// Refer to [`Writer`] for more details.
-    ///
+    async fn read_tar_member(&self, path: &str, member: &str) -> Result<Option<Bytes>> {
+        let new_read_error = |err: io::Error| {
+            Error::new(ErrorKind::Unexpected, "read from storage")
+                .with_operation("Operator::read_archive_member")
+                .with_context("service", self.info().scheme())
+                .with_context("path", path)
+                .set_source(err)
+        };
+
+        let mut r = self.reader(path).await?;
+        let mut block = [0; TAR_BLOCK_SIZE];
+        let (mut long_name, mut pax_path, mut pax_size) = (None, None, None);
+
+        loop {
+            match r.read_exact(&mut block).await {
+                Ok(()) => {}
+                // Archive ends without the end of archive blocks.
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(err) => return Err(new_read_error(err)),
+            }
+            let header = match TarHeader::parse(&block)? {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+
+            // Data of long name and pax header entries describes the next
+            // entry, it's usually small.
+            let size = match header.kind {
+                TarEntryKind::LongName | TarEntryKind::Pax => header.size,
+                _ => pax_size.take().unwrap_or(header.size),
+            };
+            let is_member = header.kind == TarEntryKind::File && {
+                let name = pax_path
+                    .take()
+                    .or_else(|| long_name.take())
+                    .unwrap_or(header.name);
+                normalize_archive_member(&name) == member
+            };
+
+            let is_meta = matches!(header.kind, TarEntryKind::LongName | TarEntryKind::Pax);
+            if is_meta && size > TAR_META_MAX_SIZE {
+                return Err(
+                    Error::new(ErrorKind::Unexpected, "tar metadata entry is too large")
+                        .with_operation("Operator::read_archive_member")
+                        .with_context("service", self.info().scheme())
+                        .with_context("path", path)
+                        .with_context("size", size.to_string()),
+                );
+            }
+            let skip = if is_meta || is_member {
+                let mut buf = Vec::with_capacity(read_buffer_capacity(None, Some(size)));
+                AsyncReadExt::take(&mut r, size)
+                    .read_to_end(&mut buf)
+                    .await
+                    .map_err(new_read_error)?;
+                if buf.len() as u64 != size {
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "tar archive is truncated")
+                            .with_operation("Operator::read_archive_member")
+                            .with_context("service", self.info().scheme())
+                            .with_context("path", path),
+                    );
+                }
+
+                match header.kind {
+                    TarEntryKind::LongName => long_name = Some(tar_str(&buf)),
+                    TarEntryKind::Pax => (pax_path, pax_size) = parse_pax_records(&buf)?,
+                    _ => return Ok(Some(buf.into())),
+                }
+                tar_padded_size(size)? - size
+            } else {
+                long_name = None;
+                pax_path = None;
+                tar_padded_size(size)?
+            };
+
+            futures::io::copy(AsyncReadExt::take(&mut r, skip), &mut futures::io::sink())
+                .await
+                .map_err(new_read_error)?;
+        }
+    }
+
+    /// Create a new reader which can read the whole path.
+    ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// # use futures::StreamExt;
-    /// # use futures::SinkExt;
-    /// use bytes::Bytes;
-    ///
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Scheme;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let mut w = op.writer("path/to/file").await?;
-    /// w.write(vec![0; 4096]).await?;
-    /// w.write(vec![1; 4096]).await?;
-    /// w.close().await?;
+    /// let r = op.reader("path/to/file").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn writer(&self, path: &str) -> Result<Writer> {
-        self.writer_with(path, OpWrite::default()).await
+    pub async fn reader(&self, path: &str) -> Result<Reader> {
+        self.reader_with(path, OpRead::default()).await
     }
 
-    /// Write multiple bytes into path with extra options.
+    /// Create a new reader which can read the specified range.
     ///
-    /// Refer to [`Writer`] for more details.
+    /// # Notes
+    ///
+    /// - The returning content's length may be smaller than the range specified.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// # use futures::StreamExt;
-    /// # use futures::SinkExt;
-    /// use bytes::Bytes;
-    /// use opendal::ops::OpWrite;
-    ///
+    /// # use futures::TryStreamExt;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let args = OpWrite::new().with_content_type("application/octet-stream");
-    /// let mut w = op.writer_with("path/to/file", args).await?;
-    /// w.write(vec![0; 4096]).await?;
-    /// w.write(vec![1; 4096]).await?;
-    /// w.close().await?;
+    /// let r = op.range_reader("path/to/file", 1024..2048).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn writer_with(&self, path: &str, args: OpWrite) -> Result<Writer> {
-        let path = normalize_path(path);
-
-        if !validate_path(&path, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "write path is a directory")
-                    .with_operation("Operator::writer")
-                    .with_context("service", self.inner().info().scheme().into_static())
-                    .with_context("path", &path),
-            );
-        }
-
-        Writer::create(self.inner().clone(), &path, args).await
+    pub async fn range_reader(&self, path: &str, range: impl RangeBounds<u64>) -> Result<Reader> {
+        self.reader_with(path, OpRead::new().with_range(range.into()))
+            .await
     }
 
-    /// Write data with extra options.
-    ///
-    /// # Notes
-    ///
-    /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// Create a new reader with extra options
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use std::io::Result;
     /// # use opendal::Operator;
-    /// use bytes::Bytes;
-    /// use opendal::ops::OpWrite;
-    ///
+    /// # use futures::TryStreamExt;
+    /// # use opendal::Scheme;
+    /// # use opendal::ops::OpRead;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let bs = b"hello, world!".to_vec();
-    /// let args = OpWrite::new().with_content_type("text/plain");
-    /// let _ = op.write_with("path/to/file", args, bs).await?;
+    /// let r = op
+    ///     .reader_with("path/to/file", OpRead::default().with_range((0..10).into()))
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn write_with(&self, path: &str, args: OpWrite, bs: impl Into<Bytes>) -> Result<()> {
+    pub async fn reader_with(&self, path: &str, args: OpRead) -> Result<Reader> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsADirectory, "write path is a directory")
-                    .with_operation("Operator::write_with")
-                    .with_context("service", self.info().scheme().into_static())
-                    .with_context("path", &path),
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::range_reader")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
             );
         }
 
-        let bs = bs.into();
-        let (_, mut w) = self
-            .inner()
-            .write(&path, args.with_content_length(bs.len() as u64))
-            .await?;
-        w.write(bs).await?;
-        w.close().await?;
+        args.range()
+            .validate()
+            .map_err(|err| err.with_context("path", &path))?;
 
-        Ok(())
+        Reader::create_dir(self.inner().clone(), &path, args).await
     }
 
-    /// Append multiple bytes into path.
-    ///
-    /// Refer to [`Appender`] for more details.
+    /// Create a reader racing this operator with replicas in `others`.
     ///
-    /// # Examples
-    ///
-    /// ```
-    /// # use std::io::Result;
-    /// # use opendal::Operator;
-    /// use bytes::Bytes;
+    /// The read is sent to this operator first. If it doesn't respond in
+    /// `hedge_delay`, the read will be sent to the next replica as well, and
+    /// so on. The first reader whose content starts arriving is returned,
+    /// and all other pending reads are cancelled.
     ///
-    /// # #[tokio::main]
-    /// # async fn test(op: Operator) -> Result<()> {
-    /// let mut a = op.appender("path/to/file").await?;
-    /// a.append(vec![0; 4096]).await?;
-    /// a.append(vec![1; 4096]).await?;
-    /// a.close().await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn appender(&self, path: &str) -> Result<Appender> {
-        self.appender_with(path, OpAppend::default()).await
-    }
-
-    /// Append multiple bytes into path with extra options.
+    /// # Notes
     ///
-    /// Refer to [`Appender`] for more details.
+    /// - Replicas are only read after the hedge fires, so a fast primary
+    ///   costs exactly one request.
+    /// - A failed read will fire the next one immediately. The error of the
+    ///   last read is returned if all of them failed.
+    /// - A read responds when the first chunk of its content arrives, a
+    ///   replica returning the response quickly but stalling on the body
+    ///   won't win the race.
     ///
     /// # Examples
     ///
-    /// ```
-    /// # use std::io::Result;
-    /// # use opendal::Operator;
-    /// use bytes::Bytes;
-    /// use opendal::ops::OpAppend;
-    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use anyhow::Result;
+    /// use opendal::ops::OpRead;
+    /// use opendal::Operator;
     /// # #[tokio::main]
-    /// # async fn test(op: Operator) -> Result<()> {
-    /// let args = OpAppend::new().with_content_type("application/octet-stream");
-    /// let mut a = op.appender_with("path/to/file", args).await?;
-    /// a.append(vec![0; 4096]).await?;
-    /// a.append(vec![1; 4096]).await?;
-    /// a.close().await?;
+    /// # async fn test(primary: Operator, replica: Operator) -> Result<()> {
+    /// let r = primary
+    ///     .hedged_read(
+    ///         &[replica],
+    ///         "path/to/file",
+    ///         OpRead::new(),
+    ///         Duration::from_millis(50),
+    ///     )
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn appender_with(&self, path: &str, args: OpAppend) -> Result<Appender> {
+    pub async fn hedged_read(
+        &self,
+        others: &[Operator],
+        path: &str,
+        args: OpRead,
+        hedge_delay: Duration,
+    ) -> Result<Reader> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsADirectory, "append path is a directory")
-                    .with_operation("Operator::appender")
-                    .with_context("service", self.inner().info().scheme().into_static())
-                    .with_context("path", &path),
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::hedged_read")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
             );
         }
 
-        Appender::create(self.inner().clone(), &path, args).await
+        args.range()
+            .validate()
+            .map_err(|err| err.with_context("path", &path))?;
+
+        let mut candidates = std::iter::once(self).chain(others).peekable();
+        let mut inflight = stream::FuturesUnordered::new();
+        let mut last_err = None;
+        // Every round ends with either the hedge fired or a read failed,
+        // both of which send the next read immediately.
+        loop {
+            if let Some(op) = candidates.next() {
+                inflight.push(Reader::create_peeked(
+                    op.inner().clone(),
+                    &path,
+                    args.clone(),
+                ));
+            }
+            if inflight.is_empty() {
+                return Err(last_err.expect("at least one read must be sent"));
+            }
+
+            let res = if candidates.peek().is_some() {
+                let hedge = Box::pin(tokio::time::sleep(hedge_delay));
+                match future::select(inflight.next(), hedge).await {
+                    future::Either::Left((res, _)) => res,
+                    future::Either::Right(_) => continue,
+                }
+            } else {
+                inflight.next().await
+            };
+
+            match res {
+                Some(Ok(r)) => return Ok(r),
+                Some(Err(err)) => last_err = Some(err),
+                None => unreachable!("inflight reads must not be empty"),
+            }
+        }
     }
 
-    /// Append bytes with extra options.
+    /// Select records from the object at path with given query.
+    ///
+    /// The query will be evaluated by services natively, only matched
+    /// records will be returned in the output format of query.
     ///
     /// # Notes
     ///
-    /// - Append will make sure all bytes has been written, or an error will be returned.
-    /// - Append will create the file if it does not exist.
-    /// - Append always write bytes to the end of the file.
+    /// Only services with [`Capability::select`] support this operation,
+    /// `Unsupported` error will be returned for others.
     ///
     /// # Examples
     ///
-    /// ```
-    /// # use std::io::Result;
+    /// ```no_run
+    /// # use anyhow::Result;
     /// # use opendal::Operator;
-    /// use bytes::Bytes;
-    /// use opendal::ops::OpAppend;
+    /// # use futures::TryStreamExt;
+    /// use opendal::ops::OpSelect;
+    /// use opendal::ops::SelectInput;
+    /// use opendal::ops::SelectOutput;
     ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let bs = b"hello, world!".to_vec();
-    /// let args = OpAppend::new().with_content_type("text/plain");
-    /// let _ = op.append_with("path/to/file", args, bs).await?;
+    /// let query = OpSelect::new(
+    ///     "SELECT * FROM S3Object s WHERE s.age > '18'",
+    ///     SelectInput::Csv { has_header: true },
+    ///     SelectOutput::Csv,
+    /// );
+    /// let bs: Vec<_> = op.select("path/to/file.csv", query).await?.try_collect().await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn append_with(
+    pub async fn select(
         &self,
         path: &str,
-        args: OpAppend,
-        bs: impl Into<Bytes>,
-    ) -> Result<()> {
+        query: OpSelect,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Unpin> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::FILE) {
             return Err(
-                Error::new(ErrorKind::IsADirectory, "append path is a directory")
-                    .with_operation("Operator::append_with")
-                    .with_context("service", self.info().scheme().into_static())
-                    .with_context("path", &path),
+                Error::new(ErrorKind::IsADirectory, "select path is a directory")
+                    .with_operation("Operator::select")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
             );
         }
 
-        let bs = bs.into();
-        let (_, mut a) = self.inner().append(&path, args).await?;
-        a.append(bs).await?;
-        a.close().await?;
+        let (_, mut r) = self.inner().select(&path, query).await?;
 
-        Ok(())
+        Ok(stream::poll_fn(move |cx| r.poll_next(cx)))
     }
 
-    /// Delete the given path.
-    ///
-    /// # Notes
+    /// Set the retention of the object at path.
     ///
-    /// - Deleting a file that does not exist won't return errors.
+    /// The object can't be deleted or overwritten until `retain_until`.
+    /// Require object lock to be enabled on the bucket.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
+    /// use chrono::Duration;
+    /// use chrono::Utc;
+    /// use opendal::ops::RetentionMode;
+    ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.delete("test").await?;
+    /// op.set_object_retention(
+    ///     "path/to/file",
+    ///     RetentionMode::Governance,
+    ///     Utc::now() + Duration::days(30),
+    /// )
+    /// .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, path: &str) -> Result<()> {
-        let path = normalize_path(path);
+    pub async fn set_object_retention(
+        &self,
+        path: &str,
+        mode: RetentionMode,
+        retain_until: DateTime<Utc>,
+    ) -> Result<()> {
+        let path = self.normalize_object_lock_path(path, "Operator::set_object_retention")?;
 
-        let _ = self.inner().delete(&path, OpDelete::new()).await?;
+        let args = OpRetention::Set(ObjectRetention::new(mode, retain_until));
+        let _ = self.inner().retention(&path, args).await?;
 
         Ok(())
     }
 
+    /// Get the retention of the object at path.
     ///
-    /// # Notes
-    ///
-    /// If underlying services support delete in batch, we will use batch
-    /// delete instead.
+    /// Returns `None` if the object doesn't have retention.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
-    /// #
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.remove(vec!["abc".to_string(), "def".to_string()])
-    ///     .await?;
+    /// if let Some(retention) = op.get_object_retention("path/to/file").await? {
+    ///     println!("retain until {}", retention.retain_until());
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove(&self, paths: Vec<String>) -> Result<()> {
-        self.remove_via(stream::iter(paths)).await
+    pub async fn get_object_retention(&self, path: &str) -> Result<Option<ObjectRetention>> {
+        let path = self.normalize_object_lock_path(path, "Operator::get_object_retention")?;
+
+        let rp = self.inner().retention(&path, OpRetention::Get).await?;
+
+        Ok(rp.into_retention())
     }
 
-    /// remove will remove files via the given paths.
-    ///
-    /// remove_via will remove files via the given stream.
+    /// Turn on or off the legal hold of the object at path.
     ///
-    /// We will delete by chunks with given batch limit on the stream.
-    ///
-    /// # Notes
-    ///
-    /// If underlying services support delete in batch, we will use batch
-    /// delete instead.
+    /// The object can't be deleted or overwritten while legal hold is on.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
-    /// use futures::stream;
-    /// #
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let stream = stream::iter(vec!["abc".to_string(), "def".to_string()]);
-    /// op.remove_via(stream).await?;
+    /// op.set_legal_hold("path/to/file", true).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
-        if self.info().can_batch() {
-            let mut input = input
-                .map(|v| (v, OpDelete::default().into()))
-                .chunks(self.limit());
-
-            while let Some(batches) = input.next().await {
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
+    pub async fn set_legal_hold(&self, path: &str, enabled: bool) -> Result<()> {
+        let path = self.normalize_object_lock_path(path, "Operator::set_legal_hold")?;
 
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
-            }
-        } else {
-            input
-                .map(Ok)
-                .try_for_each_concurrent(self.limit, |path| async move {
-                    let _ = self.inner().delete(&path, OpDelete::default()).await?;
-                    Ok::<(), Error>(())
-                })
-                .await?;
-        }
+        let _ = self
+            .inner()
+            .legal_hold(&path, OpLegalHold::Set(enabled))
+            .await?;
 
         Ok(())
     }
 
-    /// Remove the path and all nested dirs and files recursively.
-    ///
-    /// # Notes
-    ///
-    /// If underlying services support delete in batch, we will use batch
-    /// delete instead.
+    /// Get whether the legal hold of the object at path is on.
     ///
     /// # Examples
     ///
     /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
     /// # use opendal::Operator;
-    /// #
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// op.remove_all("path/to/dir").await?;
+    /// let on = op.get_legal_hold("path/to/file").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove_all(&self, path: &str) -> Result<()> {
-        let meta = match self.stat(path).await {
-            // If object exists.
-            Ok(metadata) => metadata,
-
-            // If object not found, return success.
-            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
-
-            // Pass on any other error.
-            Err(e) => return Err(e),
-        };
-
-        if meta.mode() != EntryMode::DIR {
-            return self.delete(path).await;
-        }
+    pub async fn get_legal_hold(&self, path: &str) -> Result<bool> {
+        let path = self.normalize_object_lock_path(path, "Operator::get_legal_hold")?;
 
-        let obs = self.scan(path).await?;
+        let rp = self.inner().legal_hold(&path, OpLegalHold::Get).await?;
 
-        if self.info().can_batch() {
-            let mut obs = obs.try_chunks(self.limit());
-
-            while let Some(batches) = obs.next().await {
-                let batches = batches
-                    .map_err(|err| err.1)?
-                    .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
-                    .collect();
+        Ok(rp.enabled())
+    }
 
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
+    /// Fetch the ACL of the object at path.
+    ///
+    /// The returned metadata only carries [`Metadata::acl_summary`], use
+    /// [`Operator::stat`] for other fields since fetching ACL is an extra
+    /// request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op.get_object_acl("path/to/file").await?;
+    /// if meta.acl_summary().map_or(false, |v| v.is_public()) {
+    ///     println!("path/to/file is public");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_acl(&self, path: &str) -> Result<Metadata> {
+        let path = normalize_path(path);
 
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
-            }
-        } else {
-            obs.try_for_each(|v| async move { self.delete(v.path()).await })
-                .await?;
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "acl path is a directory")
+                    .with_operation("Operator::get_object_acl")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
         }
 
-        // Remove the directory itself.
-        self.delete(path).await?;
+        let rp = self.inner().acl(&path, OpAcl::new()).await?;
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE).with_acl_summary(rp.summary()))
     }
 
-    /// List given path.
-    ///
-    /// This function will create a new handle to list entries.
+    /// Fetch the owner of the object at path.
     ///
-    /// An error will be returned if given path doesn't end with `/`.
+    /// The returned metadata only carries [`Metadata::owner`]. Owner is
+    /// returned along with ACL, so this costs an extra request just like
+    /// [`Operator::get_object_acl`]. Services that don't expose ownership
+    /// return `Unsupported`.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// # use anyhow::Result;
-    /// # use futures::io;
-    /// use futures::TryStreamExt;
-    /// use opendal::EntryMode;
-    /// use opendal::Metakey;
-    /// use opendal::Operator;
+    /// # use opendal::Operator;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let mut ds = op.list("path/to/dir/").await?;
-    /// while let Some(mut de) = ds.try_next().await? {
-    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
-    ///     match meta.mode() {
-    ///         EntryMode::FILE => {
-    ///             println!("Handling file")
-    ///         }
-    ///         EntryMode::DIR => {
-    ///             println!("Handling dir like start a new list via meta.path()")
-    ///         }
-    ///         EntryMode::Unknown => continue,
-    ///     }
+    /// let meta = op.get_object_owner("path/to/file").await?;
+    /// if let Some(owner) = meta.owner() {
+    ///     println!("path/to/file is uploaded by {owner}");
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, path: &str) -> Result<Lister> {
-        self.list_with(path, OpList::new()).await
+    pub async fn get_object_owner(&self, path: &str) -> Result<Metadata> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "owner path is a directory")
+                    .with_operation("Operator::get_object_owner")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        let rp = self.inner().acl(&path, OpAcl::new()).await?;
+
+        Ok(Self::with_owner(Metadata::new(EntryMode::FILE), rp))
     }
 
-    /// List given path with OpList.
+    /// Fill owner of metadata by `acl` reply.
+    ///
+    /// Owner bit is always set so that callers can tell an object without
+    /// owner from an owner that is not fetched yet.
+    fn with_owner(meta: Metadata, rp: RpAcl) -> Metadata {
+        let bit = meta.bit() | Metakey::Owner;
+        let meta = match rp.owner() {
+            Some(owner) => meta.with_owner(owner.to_string()),
+            None => meta,
+        };
+        meta.with_bit(bit)
+    }
+
+    /// Object lock only applies to files.
+    fn normalize_object_lock_path(&self, path: &str, operation: &'static str) -> Result<String> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "object lock path is a directory")
+                    .with_operation(operation)
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        Ok(path)
+    }
+
+    /// Write bytes into path.
     ///
-    /// This function will create a new handle to list entries.
+    /// # Notes
     ///
-    /// An error will be returned if given path doesn't end with `/`.
+    /// - Write will make sure all bytes has been written, or an error will be returned.
     ///
     /// # Examples
     ///
-    /// ## List current dir
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use futures::StreamExt;
+    /// # use futures::SinkExt;
+    /// use bytes::Bytes;
     ///
-    /// ```no_run
-    /// # use anyhow::Result;
-    /// # use futures::io;
-    /// use futures::TryStreamExt;
-    /// use opendal::ops::OpList;
-    /// use opendal::EntryMode;
-    /// use opendal::Metakey;
-    /// use opendal::Operator;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let option = OpList::new().with_limit(10).with_start_after("start");
-    /// let mut ds = op.list_with("path/to/dir/", option).await?;
-    /// while let Some(mut de) = ds.try_next().await? {
-    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
-    ///     match meta.mode() {
-    ///         EntryMode::FILE => {
-    ///             println!("Handling file")
-    ///         }
-    ///         EntryMode::DIR => {
-    ///             println!("Handling dir like start a new list via meta.path()")
-    ///         }
-    ///         EntryMode::Unknown => continue,
-    ///     }
-    /// }
+    /// op.write("path/to/file", vec![0; 4096]).await?;
     /// # Ok(())
     /// # }
     /// ```
+    pub async fn write(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
+        let bs = bs.into();
+        self.write_with(
+            path,
+            OpWrite::new().with_content_length(bs.len() as u64),
+            bs,
+        )
+        .await
+    }
+
+    /// Append bytes into path.
     ///
-    /// ## List all files recursively
+    /// # Notes
     ///
-    /// We can use `op.scan()` as a shorter alias.
+    /// - Append will make sure all bytes has been written, or an error will be returned.
+    /// - Append will create the file if it does not exist.
+    /// - Append always write bytes to the end of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
     ///
-    /// ```no_run
-    /// # use anyhow::Result;
-    /// # use futures::io;
-    /// use futures::TryStreamExt;
-    /// use opendal::ops::OpList;
-    /// use opendal::EntryMode;
-    /// use opendal::Metakey;
-    /// use opendal::Operator;
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let option = OpList::new().with_delimiter("");
-    /// let mut ds = op.list_with("path/to/dir/", option).await?;
-    /// while let Some(mut de) = ds.try_next().await? {
-    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
-    ///     match meta.mode() {
-    ///         EntryMode::FILE => {
-    ///             println!("Handling file")
-    ///         }
-    ///         EntryMode::DIR => {
-    ///             println!("Handling dir like start a new list via meta.path()")
-    ///         }
-    ///         EntryMode::Unknown => continue,
-    ///     }
-    /// }
+    /// op.append("path/to/file", vec![0; 4096]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list_with(&self, path: &str, op: OpList) -> Result<Lister> {
-        let path = normalize_path(path);
-
-        if !validate_path(&path, EntryMode::DIR) {
-            return Err(Error::new(
-                ErrorKind::NotADirectory,
-                "the path trying to list should end with `/`",
-            )
-            .with_operation("Operator::list")
-            .with_context("service", self.info().scheme().into_static())
-            .with_context("path", &path));
-        }
-
-        let (_, pager) = self.inner().list(&path, op).await?;
-
-        Ok(Lister::new(pager))
+    pub async fn append(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
+        let bs = bs.into();
+        self.append_with(path, OpAppend::new(), bs).await
     }
 
-    /// List dir in flat way.
-    ///
-    /// Also, this function can be used to list a prefix.
-    ///
-    /// An error will be returned if given path doesn't end with `/`.
+    /// Copy a file from `from` to `to`.
     ///
     /// # Notes
     ///
-    /// - `scan` will not return the prefix itself.
-    /// - `scan` is an alias of `list_with(OpList::new().with_delimiter(""))`
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If `from` and `to` are the same,  an `IsSameFile` error will occur.
+    /// - `copy` is idempotent. For same `from` and `to` input, the result will be the same.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// # use anyhow::Result;
-    /// # use futures::io;
-    /// use futures::TryStreamExt;
-    /// use opendal::EntryMode;
-    /// use opendal::Metakey;
-    /// use opendal::Operator;
-    /// #
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let mut ds = op.scan("/path/to/dir/").await?;
-    /// while let Some(mut de) = ds.try_next().await? {
-    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
-    ///     match meta.mode() {
-    ///         EntryMode::FILE => {
-    ///             println!("Handling file")
-    ///         }
-    ///         EntryMode::DIR => {
-    ///             println!("Handling dir like start a new list via meta.path()")
-    ///         }
-    ///         EntryMode::Unknown => continue,
-    ///     }
-    /// }
+    /// op.copy("path/to/file", "path/to/file2").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn scan(&self, path: &str) -> Result<Lister> {
-        let path = normalize_path(path);
+    pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.copy_with(from, to, OpCopy::new()).await
+    }
 
-        if !validate_path(&path, EntryMode::DIR) {
-            return Err(Error::new(
-                ErrorKind::NotADirectory,
-                "the path trying to scan should end with `/`",
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// # Copy with range
+    ///
+    /// If [`OpCopy::with_range`] is set but the service doesn't support
+    /// [`Capability::copy_with_range`], the range of `from` will be read
+    /// and written to `to` instead. The slice is streamed through the
+    /// client, which costs the bandwidth of downloading and uploading it.
+    /// User metadata and tags of `from` are not copied in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::ops::OpCopy;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpCopy::new()
+    ///     .with_preserve_user_metadata(true)
+    ///     .with_preserve_tags(false);
+    /// op.copy_with("path/to/file", "path/to/file2", args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_with(&self, from: &str, to: &str, args: OpCopy) -> Result<()> {
+        let from = normalize_path(from);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::copy_with")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        let to = normalize_path(to);
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::copy_with")
+                    .with_context("service", self.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::copy_with")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .with_context("to", to),
+            );
+        }
+
+        let range = args.range();
+        if !range.is_full() {
+            range.validate()?;
+            if !self.info().capability().copy_with_range {
+                return self.copy_range_by_read(&from, &to, args).await;
+            }
+        }
+
+        let progress = args.progress().cloned();
+        self.inner().copy(&from, &to, args).await?;
+
+        // Services without native progress report only once after copy.
+        if let Some(progress) = progress {
+            if !self.info().capability().copy_with_progress {
+                let size = self
+                    .inner()
+                    .stat(&to, OpStat::new())
+                    .await?
+                    .into_metadata()
+                    .content_length();
+                progress(size, size);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy the range of `from` to `to` by reading and writing it.
+    async fn copy_range_by_read(&self, from: &str, to: &str, args: OpCopy) -> Result<()> {
+        if args.source_bucket().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with range from another bucket is not supported",
             )
-            .with_operation("list")
-            .with_context("service", self.info().scheme().into_static())
-            .with_context("path", &path));
+            .with_operation("Operator::copy_with")
+            .with_context("service", self.info().scheme())
+            .with_context("from", from));
         }
 
-        let (_, pager) = self
-            .inner()
-            .list(&path, OpList::new().with_delimiter(""))
-            .await?;
+        let mut op = OpRead::new().with_range(args.range());
+        if let Some(v) = args.if_match() {
+            op = op.with_if_match(v);
+        }
+        let (_, mut r) = self.inner().read(from, op).await?;
 
-        Ok(Lister::new(pager))
+        let mut w = self.writer(to).await?;
+        let mut size = 0;
+        while let Some(bs) = oio::ReadExt::next(&mut r).await {
+            let bs = match bs {
+                Ok(bs) => bs,
+                Err(err) => {
+                    // The read error will be returned anyway.
+                    let _ = w.abort().await;
+                    return Err(err);
+                }
+            };
+            size += bs.len() as u64;
+            if let Err(err) = w.write(bs).await {
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+        if let Err(err) = w.close().await {
+            let _ = w.abort().await;
+            return Err(err);
+        }
+
+        if let Some(progress) = args.progress() {
+            progress(size, size);
+        }
+        Ok(())
     }
-}
 
-/// Operator presign API.
-impl Operator {
-    /// Presign an operation for stat(head).
+    /// Rename a file from `from` to `to`.
     ///
-    /// # Example
+    /// # Notes
     ///
-    /// ```no_run
-    /// use anyhow::Result;
-    /// use futures::io;
-    /// use opendal::Operator;
-    /// use std::time::Duration;
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
     ///
-    /// #[tokio::main]
-    /// async fn test(op: Operator) -> Result<()> {
-    ///     let signed_req = op.presign_stat("test",Duration::from_secs(3600)).await?;
-    ///     let req = http::Request::builder()
-    ///         .method(signed_req.method())
-    ///         .uri(signed_req.uri())
-    ///         .body(())?;
+    /// # Examples
     ///
-    /// #    Ok(())
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.rename("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
     /// # }
     /// ```
-    pub async fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
-
-        let op = OpPresign::new(OpStat::new(), expire);
-
-        let rp = self.inner().presign(&path, op).await?;
-        Ok(rp.into_presigned_request())
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        self.rename_with(from, to, OpRename::new()).await
     }
 
-    /// Presign an operation for read.
+    /// Rename a file from `from` to `to` with extra options.
     ///
-    /// # Example
+    /// # Emulation
     ///
-    /// ```no_run
-    /// use anyhow::Result;
-    /// use futures::io;
-    /// use opendal::Operator;
-    /// use std::time::Duration;
+    /// Object stores like s3 don't support rename natively. If
+    /// [`OpRename::with_conditional_copy`] is enabled and the service
+    /// supports copy and delete with if match, rename will be emulated by:
     ///
-    /// #[tokio::main]
-    /// async fn test(op: Operator) -> Result<()> {
-    ///     let signed_req = op.presign_read("test.txt", Duration::from_secs(3600)).await?;
-    /// #    Ok(())
-    /// # }
-    /// ```
+    /// - stat `from` to get its etag.
+    /// - copy `from` to `to` only if `from` still matches the etag.
+    /// - delete `from` only if it still matches the etag.
     ///
-    /// - `signed_req.method()`: `GET`
-    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>`
-    /// - `signed_req.headers()`: `{ "host": "s3.amazonaws.com" }`
+    /// If `from` is rewritten by others during rename, rename will be
+    /// aborted with [`ErrorKind::ConditionNotMatch`] instead of losing the
+    /// new content.
     ///
-    /// We can download this file via `curl` or other tools without credentials:
+    /// The emulation is still not atomic:
+    ///
+    /// - `to` is visible before `from` is deleted, readers could see both.
+    /// - If the race is detected at delete, `to` has been written and
+    ///   `from` is kept with the new content. Users should decide whether
+    ///   to remove `to` or retry.
+    ///
+    /// # Examples
     ///
-    /// ```shell
-    /// curl "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -O /tmp/test.txt
     /// ```
-    pub async fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::ops::OpRename;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpRename::new().with_conditional_copy(true);
+    /// op.rename_with("path/to/file", "path/to/file2", args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_with(&self, from: &str, to: &str, args: OpRename) -> Result<()> {
+        let from = normalize_path(from);
 
-        let op = OpPresign::new(OpRead::new(), expire);
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::move_")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
 
-        let rp = self.inner().presign(&path, op).await?;
-        Ok(rp.into_presigned_request())
+        let to = normalize_path(to);
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::move_")
+                    .with_context("service", self.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::move_")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .with_context("to", to),
+            );
+        }
+
+        let cap = self.info().capability();
+        if !cap.rename
+            && args.conditional_copy()
+            && cap.copy_with_if_match
+            && cap.delete_with_if_match
+            && cap.stat
+        {
+            return self.rename_via_conditional_copy(&from, &to).await;
+        }
+
+        self.inner().rename(&from, &to, args).await?;
+
+        Ok(())
     }
 
-    /// Presign an operation for read option described in OpenDAL [rfc-1735](../../docs/rfcs/1735_operation_extension.md).
+    /// Emulate rename by conditional copy and delete.
+    async fn rename_via_conditional_copy(&self, from: &str, to: &str) -> Result<()> {
+        let meta = self
+            .inner()
+            .stat(from, OpStat::new())
+            .await?
+            .into_metadata();
+        let etag = meta.etag().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "rename requires etag of source but service doesn't return it",
+            )
+            .with_operation("Operator::rename")
+            .with_context("service", self.info().scheme())
+            .with_context("from", from)
+        })?;
+
+        self.inner()
+            .copy(from, to, OpCopy::new().with_if_match(etag))
+            .await?;
+
+        self.inner()
+            .delete(from, OpDelete::new().with_if_match(etag))
+            .await
+            .map_err(|err| match err.kind() {
+                ErrorKind::ConditionNotMatch => Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "source has been changed during rename, target is written but source is kept",
+                )
+                .with_operation("Operator::rename")
+                .with_context("service", self.info().scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+                .set_source(err),
+                _ => err,
+            })?;
+
+        Ok(())
+    }
+
+    /// Copy a file from `from` of this operator to `to` of `target` operator.
     ///
-    /// You can pass `OpRead` to this method to specify the content disposition.
+    /// # Notes
     ///
-    /// # Example
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If both operators point to the same storage, this is the same as [`Operator::copy`].
+    /// - If both operators are in different buckets of the same service and
+    ///   [`Capability::copy_cross_bucket`] is supported by both, copy will be
+    ///   done by service natively. If the service reports the copy as
+    ///   [`ErrorKind::Unsupported`], for example the buckets live behind
+    ///   different endpoints, copy falls back to streaming. Other errors
+    ///   are returned directly.
+    /// - Otherwise, content will be read from `from` and written into `to`.
     ///
-    /// ```no_run
-    /// use anyhow::Result;
-    /// use futures::io;
-    /// use opendal::Operator;
-    /// use std::time::Duration;
-    /// use opendal::ops::OpRead;
+    /// # Examples
     ///
-    /// #[tokio::main]
-    /// async fn test(op: Operator) -> Result<()> {
-    ///     let args = OpRead::new()
-    ///         .with_override_content_disposition("attachment; filename=\"othertext.txt\"");
-    ///     let signed_req = op.presign_read_with("test.txt", args, Duration::from_secs(3600)).await?;
-    /// #    Ok(())
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// op.copy_to("path/to/file", &target, "path/to/file2").await?;
+    /// # Ok(())
     /// # }
     /// ```
-    pub async fn presign_read_with(
-        &self,
-        path: &str,
-        op: OpRead,
-        expire: Duration,
-    ) -> Result<PresignedRequest> {
-        let path = normalize_path(path);
+    pub async fn copy_to(&self, from: &str, target: &Operator, to: &str) -> Result<()> {
+        let (from, to) = self.validate_cross_paths("Operator::copy_to", from, target, to)?;
 
-        let op = OpPresign::new(op, expire);
+        let (src, dst) = (self.info(), target.info());
+        if self.is_same_storage(target) {
+            self.inner().copy(&from, &to, OpCopy::new()).await?;
+        } else if src.scheme() == dst.scheme()
+            && src.capability().copy_cross_bucket
+            && dst.capability().copy_cross_bucket
+        {
+            let abs_from = build_abs_path(src.root(), &from);
+            let op = OpCopy::new().with_source_bucket(src.name());
+            // Native copy is rejected with `Unsupported` if the buckets
+            // live behind different endpoints, fall back to streaming in
+            // that case. Other errors are returned as is.
+            match target.inner().copy(&abs_from, &to, op).await {
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::Unsupported => {
+                    self.copy_via_stream(&from, target, &to).await?;
+                }
+                Err(err) => return Err(err),
+            }
+        } else {
+            self.copy_via_stream(&from, target, &to).await?;
+        }
 
-        let rp = self.inner().presign(&path, op).await?;
-        Ok(rp.into_presigned_request())
+        Ok(())
     }
 
-    /// Presign an operation for write.
+    /// Rename a file from `from` of this operator to `to` of `target` operator.
     ///
-    /// # Example
+    /// # Notes
     ///
-    /// ```no_run
-    /// use anyhow::Result;
-    /// use futures::io;
-    /// use opendal::Operator;
-    /// use std::time::Duration;
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - If both operators point to the same storage, this is the same as [`Operator::rename`].
+    /// - Otherwise, `from` will be copied by [`Operator::copy_to`] and then deleted.
+    ///
+    /// # Examples
     ///
-    /// #[tokio::main]
-    /// async fn test(op: Operator) -> Result<()> {
-    ///     let signed_req = op.presign_write("test.txt", Duration::from_secs(3600)).await?;
-    /// #    Ok(())
-    /// # }
     /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
     ///
-    /// - `signed_req.method()`: `PUT`
-    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>`
-    /// - `signed_req.headers()`: `{ "host": "s3.amazonaws.com" }`
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator, target: Operator) -> Result<()> {
+    /// op.rename_to("path/to/file", &target, "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_to(&self, from: &str, target: &Operator, to: &str) -> Result<()> {
+        if self.is_same_storage(target) {
+            return self.rename(from, to).await;
+        }
+
+        self.copy_to(from, target, to).await?;
+        self.delete(from).await
+    }
+
+    fn validate_cross_paths(
+        &self,
+        operation: &'static str,
+        from: &str,
+        target: &Operator,
+        to: &str,
+    ) -> Result<(String, String)> {
+        let from = normalize_path(from);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation(operation)
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        let to = normalize_path(to);
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation(operation)
+                    .with_context("service", target.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if self.is_same_storage(target) && from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation(operation)
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .with_context("to", to),
+            );
+        }
+
+        Ok((from, to))
+    }
+
+    /// Check if target operator points to the same endpoint, bucket and root.
+    fn is_same_storage(&self, target: &Operator) -> bool {
+        let (src, dst) = (self.info(), target.info());
+        src.scheme() == dst.scheme()
+            && src.endpoint() == dst.endpoint()
+            && src.name() == dst.name()
+            && src.root() == dst.root()
+    }
+
+    /// Copy by reading from `from` and writing into `to` of target.
+    async fn copy_via_stream(&self, from: &str, target: &Operator, to: &str) -> Result<()> {
+        self.copy_via_stream_with("Operator::copy_to", from, target, to, OpWrite::new(), Ok)
+            .await
+    }
+
+    /// Copy by reading from `from` and writing every transformed chunk
+    /// into `to` of target.
+    async fn copy_via_stream_with(
+        &self,
+        operation: &'static str,
+        from: &str,
+        target: &Operator,
+        to: &str,
+        args: OpWrite,
+        mut transform: impl FnMut(Bytes) -> Result<Bytes>,
+    ) -> Result<()> {
+        let (_, mut r) = self.inner().read(from, OpRead::new()).await?;
+        let mut w = target.writer_with(to, args).await?;
+
+        while let Some(bs) = future::poll_fn(|cx| r.poll_next(cx)).await {
+            let res = match bs.and_then(&mut transform) {
+                Ok(bs) => w.write(bs).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                // Make sure the partial content will not be left behind.
+                let _ = w.abort().await;
+                return Err(err.with_operation(operation));
+            }
+        }
+
+        w.close().await
+    }
+
+    /// Copy a file from `from` to `to` while applying `transform` to the content.
     ///
-    /// We can upload file as this file via `curl` or other tools without credential:
+    /// Content of `from` will be read as a stream, every chunk will be passed
+    /// to `transform` and the result will be written into `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - Content type of `from` will be preserved. Use [`Operator::reader`]
+    ///   and [`Operator::writer_with`] instead if the transform changes it.
+    /// - Chunk boundaries are decided by the service and not aligned to any
+    ///   record structure like lines. `transform` must be safe to apply on
+    ///   arbitrary chunks or buffer the incomplete records itself.
+    /// - If `transform` returns an error, the copy will be aborted and the
+    ///   error will be returned.
+    ///
+    /// # Examples
     ///
-    /// ```shell
-    /// curl -X PUT "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -d "Hello, World!"
     /// ```
-    pub async fn presign_write(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
-        self.presign_write_with(path, OpWrite::new(), expire).await
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.transform_copy("path/to/file", "path/to/file2", |bs| {
+    ///     Ok(Bytes::from(bs.to_ascii_uppercase()))
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transform_copy(
+        &self,
+        from: &str,
+        to: &str,
+        transform: impl FnMut(Bytes) -> Result<Bytes>,
+    ) -> Result<()> {
+        let (from, to) = self.validate_cross_paths("Operator::transform_copy", from, self, to)?;
+
+        let meta = self
+            .stat_with(&from, OpStat::new().with_metakey(Metakey::ContentType))
+            .await?;
+        let mut args = OpWrite::new();
+        if let Some(content_type) = meta.content_type() {
+            args = args.with_content_type(content_type);
+        }
+
+        self.copy_via_stream_with(
+            "Operator::transform_copy",
+            &from,
+            self,
+            &to,
+            args,
+            transform,
+        )
+        .await
     }
 
-    /// Presign an operation for write with option described in OpenDAL [rfc-0661](../../docs/rfcs/0661-path-in-accessor.md)
+    /// Write multiple bytes into path.
     ///
-    /// You can pass `OpWrite` to this method to specify the content length and content type.
+    /// Refer to [`Writer`] for more details.
     ///
-    /// # Example
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use futures::StreamExt;
+    /// # use futures::SinkExt;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op.writer("path/to/file").await?;
+    /// w.write(vec![0; 4096]).await?;
+    /// w.write(vec![1; 4096]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn writer(&self, path: &str) -> Result<Writer> {
+        self.writer_with(path, OpWrite::default()).await
+    }
+
+    /// Write multiple bytes into path with extra options.
+    ///
+    /// Refer to [`Writer`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use futures::StreamExt;
+    /// # use futures::SinkExt;
+    /// use bytes::Bytes;
+    /// use opendal::ops::OpWrite;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpWrite::new().with_content_type("application/octet-stream");
+    /// let mut w = op.writer_with("path/to/file", args).await?;
+    /// w.write(vec![0; 4096]).await?;
+    /// w.write(vec![1; 4096]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn writer_with(&self, path: &str, args: OpWrite) -> Result<Writer> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::writer")
+            .with_context("service", self.inner().info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        if args.require_parent() {
+            self.check_parent(&path, "Operator::writer").await?;
+        }
+
+        Writer::create(self.inner().clone(), &path, args).await
+    }
+
+    /// Write data at the given offset of path.
+    ///
+    /// # Notes
+    ///
+    /// - Only the region `[offset, offset + bs.len())` will be updated,
+    ///   other content of file will be kept.
+    /// - File will be created if not exists, and extended if `offset` is
+    ///   beyond the end of file.
+    /// - Only services with [`Capability::write_can_positional`] support
+    ///   this, others will return [`ErrorKind::Unsupported`].
+    ///
+    /// # Examples
     ///
     /// ```no_run
-    /// use anyhow::Result;
-    /// use futures::io;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.write_at("path/to/file", 4096, vec![0; 4096]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_at(&self, path: &str, offset: u64, bs: impl Into<Bytes>) -> Result<()> {
+        self.write_with(path, OpWrite::new().with_offset(offset), bs)
+            .await
+    }
+
+    /// Write data with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - Content larger than [`Capability::write_single_max_size`] will be
+    ///   written via multipart upload, unless disabled by
+    ///   [`OpWrite::with_auto_multipart`]. Multipart writes are not
+    ///   idempotent and won't be retried by [`RetryLayer`] by default.
+    ///
+    /// [`RetryLayer`]: crate::layers::RetryLayer
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
     /// use opendal::ops::OpWrite;
-    /// use opendal::Operator;
-    /// use std::time::Duration;
     ///
-    /// #[tokio::main]
-    /// async fn test(op: Operator) -> Result<()> {
-    ///     let args = OpWrite::new().with_content_type("text/csv");
-    ///     let signed_req = op.presign_write_with("test", args, Duration::from_secs(3600)).await?;
-    ///     let req = http::Request::builder()
-    ///         .method(signed_req.method())
-    ///         .uri(signed_req.uri())
-    ///         .body(())?;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = b"hello, world!".to_vec();
+    /// let args = OpWrite::new().with_content_type("text/plain");
+    /// let _ = op.write_with("path/to/file", args, bs).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_with(&self, path: &str, args: OpWrite, bs: impl Into<Bytes>) -> Result<()> {
+        self.write_with_skipped(path, args, bs).await?;
+
+        Ok(())
+    }
+
+    /// Write data with extra options and return whether the write is
+    /// skipped.
     ///
-    /// #    Ok(())
+    /// The write could only be skipped with
+    /// [`OpWrite::with_skip_if_identical`] enabled, when the target already
+    /// has identical content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpWrite;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpWrite::new().with_skip_if_identical(true);
+    /// let skipped = op
+    ///     .write_with_skipped("path/to/file", args, "Hello, World!")
+    ///     .await?;
+    /// # Ok(())
     /// # }
     /// ```
-    pub async fn presign_write_with(
+    pub async fn write_with_skipped(
         &self,
         path: &str,
-        op: OpWrite,
-        expire: Duration,
-    ) -> Result<PresignedRequest> {
+        args: OpWrite,
+        bs: impl Into<Bytes>,
+    ) -> Result<bool> {
         let path = normalize_path(path);
 
-        let op = OpPresign::new(op, expire);
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::write_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
 
-        let rp = self.inner().presign(&path, op).await?;
-        Ok(rp.into_presigned_request())
+        if args.require_parent() {
+            self.check_parent(&path, "Operator::write_with").await?;
+        }
+
+        let bs = bs.into();
+
+        if args.skip_if_identical() && self.is_identical(&path, &bs).await? {
+            return Ok(true);
+        }
+
+        match self.info().capability().write_single_max_size {
+            Some(max) if bs.len() > max && args.auto_multipart() => {
+                self.write_multipart(&path, args, bs, max).await?;
+                return Ok(false);
+            }
+            _ => {}
+        }
+
+        // The whole content is owned here and written in one shot, it's safe to replay.
+        let args = args
+            .with_content_length(bs.len() as u64)
+            .with_idempotent(true);
+        let (_, mut w) = self.inner().write(&path, args).await?;
+        w.write(bs).await?;
+        w.close().await?;
+
+        Ok(false)
+    }
+
+    /// Check whether the object at path has the same content with bs.
+    ///
+    /// Returns false if the object doesn't exist or its MD5 is unknown.
+    async fn is_identical(&self, path: &str, bs: &[u8]) -> Result<bool> {
+        let meta = match self.inner().stat(path, OpStat::new()).await {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if !meta.is_file() || meta.content_length() != bs.len() as u64 {
+            return Ok(false);
+        }
+
+        let digest = md5::Md5::digest(bs);
+        let hex_md5: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+
+        // Some services return content md5 in hex instead of base64.
+        if let Some(md5) = meta.content_md5() {
+            return Ok(md5 == format_content_md5(bs) || md5.eq_ignore_ascii_case(&hex_md5));
+        }
+        // Weak or multipart etags are not the MD5 of content.
+        match meta.etag() {
+            Some(etag) if meta.is_multipart() == Some(false) && !etag.starts_with("W/") => {
+                Ok(etag.trim_matches('"').eq_ignore_ascii_case(&hex_md5))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Write content too large for a single request via multipart upload.
+    ///
+    /// Content will be fed to writer in small chunks so that services will
+    /// upload it by their own part size instead of buffering it all.
+    async fn write_multipart(
+        &self,
+        path: &str,
+        args: OpWrite,
+        bs: Bytes,
+        max_size: usize,
+    ) -> Result<()> {
+        // The first chunk must be smaller than the whole content, otherwise
+        // services will still write it in one request.
+        let chunk_size = max_size.clamp(1, 4 * 1024 * 1024);
+
+        let args = args.with_content_length(bs.len() as u64);
+        let (_, mut w) = self.inner().write(path, args).await?;
+        for offset in (0..bs.len()).step_by(chunk_size) {
+            let chunk = bs.slice(offset..bs.len().min(offset + chunk_size));
+            if let Err(err) = w.write(chunk).await {
+                // Don't leave the incomplete upload behind.
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+        w.close().await?;
+
+        Ok(())
+    }
+
+    /// Write the content of a seekable reader into path.
+    ///
+    /// The content length will be detected by seeking to the end of reader,
+    /// so that services can check the written size and use the single
+    /// request fast path for small content.
+    ///
+    /// # Notes
+    ///
+    /// - Content from the current position of reader to the end will be written.
+    /// - Content is read and written in chunks of at most 8 MiB, content not
+    ///   larger than that will be written in one request.
+    /// - The reader must not be modified concurrently, otherwise an error
+    ///   will be returned on close since the written size mismatches.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use futures::io::AllowStdIo;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let f = std::fs::File::open("path/to/local/file")?;
+    /// op.write_from("path/to/file", AllowStdIo::new(f)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_from(
+        &self,
+        path: &str,
+        mut r: impl AsyncRead + AsyncSeek + Unpin,
+    ) -> Result<u64> {
+        /// Chunk size of reading, it's larger than the minimal part size
+        /// of multipart upload in most services.
+        const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::write_from")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let new_io_error = |err: io::Error| {
+            Error::new(ErrorKind::Unexpected, "read from source")
+                .with_operation("Operator::write_from")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", &path)
+                .set_source(err)
+        };
+
+        let start = r.stream_position().await.map_err(new_io_error)?;
+        let end = r.seek(io::SeekFrom::End(0)).await.map_err(new_io_error)?;
+        r.seek(io::SeekFrom::Start(start))
+            .await
+            .map_err(new_io_error)?;
+        let size = end.saturating_sub(start);
+
+        let args = OpWrite::new().with_content_length(size);
+        let (_, mut w) = self.inner().write(&path, args).await?;
+
+        let mut remaining = size;
+        while remaining > 0 {
+            let n = remaining.min(CHUNK_SIZE);
+            let mut buf = Vec::with_capacity(n as usize);
+            let res = match (&mut r).take(n).read_to_end(&mut buf).await {
+                Ok(0) => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "source reached EOF before detected content length",
+                )
+                .with_operation("Operator::write_from")
+                .with_context("path", &path)
+                .with_context("remaining", remaining.to_string())),
+                Ok(n) => {
+                    remaining -= n as u64;
+                    w.write(buf.into()).await
+                }
+                Err(err) => Err(new_io_error(err)),
+            };
+            if let Err(err) = res {
+                // Make sure the partial content will not be left behind.
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+
+        w.close().await?;
+
+        Ok(size)
+    }
+
+    /// Split the content of stream into content-defined chunks and write
+    /// each chunk as a content-addressed object under prefix.
+    ///
+    /// Every chunk is stored at `{prefix}{hash}` where `hash` is the hex
+    /// encoded SHA-256 of its content. The returned manifest lists chunks
+    /// in content order, which can be used to reassemble the content.
+    ///
+    /// # Notes
+    ///
+    /// - Chunk boundaries are decided by a rolling hash, so identical content
+    ///   always produces identical manifests and a small edit only changes
+    ///   the chunks near it. See [`ChunkerConfig`] for the chunk sizes.
+    /// - Chunks that already exist will not be written again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use opendal::ChunkerConfig;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let input = stream::iter(vec![Ok(Bytes::from(vec![0; 4096]))]);
+    /// let chunks = op
+    ///     .write_cdc("chunks/", input, ChunkerConfig::default())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_cdc(
+        &self,
+        prefix: &str,
+        mut input: impl Stream<Item = Result<Bytes>> + Unpin,
+        config: ChunkerConfig,
+    ) -> Result<Vec<ChunkRef>> {
+        config.validate()?;
+
+        let mut chunks = Vec::new();
+        let mut buf = BytesMut::new();
+        let mut offset = 0;
+        let mut eof = false;
+        while !eof {
+            match input.next().await {
+                Some(bs) => buf.extend_from_slice(&bs?),
+                None => eof = true,
+            }
+
+            // Cut points are only stable once enough content is buffered.
+            while !buf.is_empty() && (eof || buf.len() >= config.max_size()) {
+                let chunk = buf.split_to(config.cut(&buf)).freeze();
+                let size = chunk.len() as u64;
+
+                let hash = format!("{:x}", Sha256::digest(&chunk));
+                let path = format!("{prefix}{hash}");
+                if !self.is_exist(&path).await? {
+                    self.write(&path, chunk).await?;
+                }
+
+                chunks.push(ChunkRef::new(normalize_path(&path), hash, offset, size));
+                offset += size;
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    /// Write a range of an object which will be assembled by
+    /// [`Operator::finalize_ranged_write`].
+    ///
+    /// Ranges use [`WRITE_RANGE_DEFAULT_PART_SIZE`] as part size, use
+    /// [`Operator::write_range_with`] to specify another one.
+    ///
+    /// # Notes
+    ///
+    /// - Ranges of the same object could be written by independent workers
+    ///   concurrently and in any order.
+    /// - Ranges must be aligned with part size, see [`OpWriteRange`] for
+    ///   details.
+    /// - Services with [`Capability::write_range`] upload ranges as parts of
+    ///   a multipart upload of path, so path must not be written by other
+    ///   writers at the same time. Others store ranges as temporary objects
+    ///   under `path.ranges/` until finalized.
+    ///
+    /// [`WRITE_RANGE_DEFAULT_PART_SIZE`]: crate::ops::WRITE_RANGE_DEFAULT_PART_SIZE
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::WRITE_RANGE_DEFAULT_PART_SIZE;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let part_size = WRITE_RANGE_DEFAULT_PART_SIZE as usize;
+    /// // Ranges could be written by different workers in any order.
+    /// op.write_range("path/to/file", part_size as u64, vec![1; 1024])
+    ///     .await?;
+    /// op.write_range("path/to/file", 0, vec![0; part_size]).await?;
+    /// op.finalize_ranged_write("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_range(&self, path: &str, offset: u64, bs: impl Into<Bytes>) -> Result<()> {
+        self.write_range_with(path, OpWriteRange::new(offset), bs)
+            .await
+    }
+
+    /// Write a range of an object with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpWriteRange;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpWriteRange::new(16 * 1024 * 1024).with_part_size(16 * 1024 * 1024);
+    /// op.write_range_with("path/to/file", args, vec![0; 1024])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_range_with(
+        &self,
+        path: &str,
+        args: OpWriteRange,
+        bs: impl Into<Bytes>,
+    ) -> Result<()> {
+        let path = normalize_path(path);
+        let bs = bs.into();
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "write path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::write_range_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let invalid = |msg: &str| {
+            Error::new(ErrorKind::ConfigInvalid, msg)
+                .with_operation("Operator::write_range_with")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("offset", args.offset().to_string())
+                .with_context("part_size", args.part_size().to_string())
+        };
+        if args.part_size() == 0 {
+            return Err(invalid("part size must be positive"));
+        }
+        if args.offset() % args.part_size() != 0 {
+            return Err(invalid("range offset must be aligned with part size"));
+        }
+        if bs.is_empty() || bs.len() as u64 > args.part_size() {
+            return Err(invalid("range size must be in (0, part_size]"));
+        }
+
+        if self.info().capability().write_range {
+            self.inner().write_range(&path, args, bs).await?;
+        } else {
+            let part = format!("{path}{RANGED_WRITE_SUFFIX}{:020}", args.offset());
+            self.write(&part, bs).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Assemble all ranges written by [`Operator::write_range`] into the
+    /// object at path.
+    ///
+    /// # Notes
+    ///
+    /// - This function should only be called once after all ranges have
+    ///   been written.
+    /// - Ranges must cover the object without gaps, otherwise
+    ///   [`ErrorKind::ConfigInvalid`] will be returned.
+    /// - [`ErrorKind::NotFound`] will be returned if no range is written.
+    /// - Services with [`Capability::write_range`] will fail without
+    ///   committing if other multipart uploads of path have uploaded parts.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.finalize_ranged_write("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn finalize_ranged_write(&self, path: &str) -> Result<()> {
+        let path = normalize_path(path);
+
+        if self.info().capability().write_range {
+            self.inner()
+                .finalize_ranged_write(&path, OpFinalizeRangedWrite::new())
+                .await?;
+            return Ok(());
+        }
+
+        let dir = format!("{path}{RANGED_WRITE_SUFFIX}");
+        let mut parts: Vec<(u64, String)> = self
+            .list(&dir)
+            .await?
+            .try_filter_map(|e| async move {
+                let offset = e.name().parse::<u64>().ok();
+                Ok(offset.map(|v| (v, e.path().to_string())))
+            })
+            .try_collect()
+            .await?;
+        if parts.is_empty() {
+            return Err(Error::new(ErrorKind::NotFound, "no range has been written")
+                .with_operation("Operator::finalize_ranged_write")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", &path));
+        }
+        parts.sort_unstable();
+
+        // Make sure ranges are contiguous before touching the target.
+        let mut total = 0;
+        for (offset, part) in &parts {
+            if *offset != total {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "ranges have gap")
+                    .with_operation("Operator::finalize_ranged_write")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path)
+                    .with_context("expected_offset", total.to_string())
+                    .with_context("actual_offset", offset.to_string()));
+            }
+            total += self.stat(part).await?.content_length();
+        }
+
+        let mut w = self
+            .writer_with(&path, OpWrite::new().with_content_length(total))
+            .await?;
+        for (_, part) in &parts {
+            w.write(self.read(part).await?).await?;
+        }
+        w.close().await?;
+
+        self.remove_all(&dir).await
+    }
+
+    /// Make sure the parent directory of path exists.
+    async fn check_parent(&self, path: &str, operation: &'static str) -> Result<()> {
+        let parent = get_parent(path);
+        if parent == "/" {
+            return Ok(());
+        }
+
+        match self
+            .inner()
+            .stat(parent, OpStat::new())
+            .await
+            .map(RpStat::into_metadata)
+        {
+            Ok(meta) if meta.is_dir() => Ok(()),
+            Ok(_) => Err(Error::new(
+                ErrorKind::NotADirectory,
+                "parent directory is not a directory",
+            )),
+            Err(err) if err.kind() == ErrorKind::NotFound => Err(Error::new(
+                ErrorKind::NotFound,
+                "parent directory doesn't exist",
+            )),
+            Err(err) => Err(err),
+        }
+        .map_err(|err| {
+            err.with_operation(operation)
+                .with_context("service", self.info().scheme())
+                .with_context("path", path)
+                .with_context("parent", parent)
+        })
+    }
+
+    /// Append multiple bytes into path.
+    ///
+    /// Refer to [`Appender`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut a = op.appender("path/to/file").await?;
+    /// a.append(vec![0; 4096]).await?;
+    /// a.append(vec![1; 4096]).await?;
+    /// a.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn appender(&self, path: &str) -> Result<Appender> {
+        self.appender_with(path, OpAppend::default()).await
+    }
+
+    /// Append multiple bytes into path with extra options.
+    ///
+    /// Refer to [`Appender`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use opendal::ops::OpAppend;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpAppend::new().with_content_type("application/octet-stream");
+    /// let mut a = op.appender_with("path/to/file", args).await?;
+    /// a.append(vec![0; 4096]).await?;
+    /// a.append(vec![1; 4096]).await?;
+    /// a.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn appender_with(&self, path: &str, args: OpAppend) -> Result<Appender> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "append path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::appender")
+            .with_context("service", self.inner().info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        Appender::create(self.inner().clone(), &path, args).await
+    }
+
+    /// Append bytes with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Append will make sure all bytes has been written, or an error will be returned.
+    /// - Append will create the file if it does not exist.
+    /// - Append always write bytes to the end of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use opendal::ops::OpAppend;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = b"hello, world!".to_vec();
+    /// let args = OpAppend::new().with_content_type("text/plain");
+    /// let _ = op.append_with("path/to/file", args, bs).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn append_with(
+        &self,
+        path: &str,
+        args: OpAppend,
+        bs: impl Into<Bytes>,
+    ) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "append path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::append_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let bs = bs.into();
+        let (_, mut a) = self.inner().append(&path, args).await?;
+        a.append(bs).await?;
+        a.close().await?;
+
+        Ok(())
+    }
+
+    /// Merge the JSON `value` into the object at path, or create it with
+    /// `value` if it doesn't exist.
+    ///
+    /// The existing object is read with its generation, merged with
+    /// `value` by `strategy`, and written back only if the generation is
+    /// not changed. The whole process will be retried with backoff on
+    /// conflicts, so concurrent merges won't lose updates.
+    ///
+    /// # Notes
+    ///
+    /// - Only JSON content is supported, an `Unexpected` error will be
+    ///   returned if the existing object is not valid JSON.
+    /// - Only services with [`Capability::write_with_if_generation_match`]
+    ///   (like `gcs`) support this, others will return `Unsupported`.
+    /// - At most 10 attempts will be made, `ConditionNotMatch` will be
+    ///   returned if the object is still changed by others after that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::MergeStrategy;
+    /// use serde_json::json;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.merge_json(
+    ///     "path/to/config.json",
+    ///     json!({"features": {"dark_mode": true}}),
+    ///     MergeStrategy::Deep,
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Capability::write_with_if_generation_match`]: crate::Capability::write_with_if_generation_match
+    pub async fn merge_json(
+        &self,
+        path: &str,
+        value: serde_json::Value,
+        strategy: MergeStrategy,
+    ) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "merge path is a directory")
+                    .with_operation("Operator::merge_json")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        if !self.info().capability().write_with_if_generation_match {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "merge json requires write with if generation match",
+            )
+            .with_operation("Operator::merge_json")
+            .with_context("service", self.info().scheme())
+            .with_context("path", &path));
+        }
+
+        let mut backoff = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(10))
+            .with_max_delay(Duration::from_secs(1))
+            .with_max_times(MERGE_JSON_MAX_ATTEMPTS - 1)
+            .with_jitter()
+            .build();
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+
+            let err = match self.merge_json_once(&path, &value, strategy).await {
+                Ok(()) => return Ok(()),
+                Err(err) if err.kind() == ErrorKind::ConditionNotMatch => err,
+                Err(err) => return Err(err),
+            };
+            match backoff.next() {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => {
+                    return Err(err
+                        .with_operation("Operator::merge_json")
+                        .with_context("attempts", attempts.to_string()))
+                }
+            }
+        }
+    }
+
+    /// Read, merge and conditionally write the object at path once.
+    ///
+    /// Returns `ConditionNotMatch` if the object has been changed.
+    async fn merge_json_once(
+        &self,
+        path: &str,
+        value: &serde_json::Value,
+        strategy: MergeStrategy,
+    ) -> Result<()> {
+        let (generation, mut merged) = match self.inner().stat(path, OpStat::new()).await {
+            Ok(rp) => {
+                let generation = rp.into_metadata().generation().ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unsupported,
+                        "merge json requires generation but service doesn't return it",
+                    )
+                    .with_operation("Operator::merge_json")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path)
+                })?;
+
+                let bs = match self.read(path).await {
+                    Ok(bs) => bs,
+                    // The object has been deleted after stat.
+                    Err(err) if err.kind() == ErrorKind::NotFound => {
+                        return Err(Error::new(
+                            ErrorKind::ConditionNotMatch,
+                            "object has been deleted during merge",
+                        )
+                        .set_source(err))
+                    }
+                    Err(err) => return Err(err),
+                };
+                let existing = serde_json::from_slice(&bs).map_err(|err| {
+                    Error::new(ErrorKind::Unexpected, "existing content is not valid json")
+                        .with_operation("Operator::merge_json")
+                        .with_context("service", self.info().scheme())
+                        .with_context("path", path)
+                        .set_source(err)
+                })?;
+                (generation, existing)
+            }
+            // Generation `0` means the object must not exist.
+            Err(err) if err.kind() == ErrorKind::NotFound => (0, serde_json::Value::Null),
+            Err(err) => return Err(err),
+        };
+
+        strategy.merge(&mut merged, value.clone());
+        let bs = serde_json::to_vec(&merged).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "serialize merged json")
+                .with_operation("Operator::merge_json")
+                .set_source(err)
+        })?;
+
+        self.write_with(
+            path,
+            OpWrite::new().with_if_generation_match(generation),
+            bs,
+        )
+        .await
+    }
+
+    /// Append bytes into path if it exists, or create it with bytes.
+    ///
+    /// Returns the content length of the file after append.
+    ///
+    /// # Notes
+    ///
+    /// - If the service supports append natively, bytes will be appended
+    ///   directly and an extra `stat` is sent to fetch the final length.
+    /// - Else, the existing content will be read and written back with
+    ///   bytes. This is not atomic: concurrent writes between read and
+    ///   write will be lost. It's only suitable for small files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let len = op.upsert_append("path/to/file", "hello").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upsert_append(&self, path: &str, bs: impl Into<Bytes>) -> Result<u64> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "append path must not end with `/`, use `create_dir` for directories",
+            )
+            .with_operation("Operator::upsert_append")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let bs = bs.into();
+        if self.info().capability().append {
+            let (_, mut a) = self.inner().append(&path, OpAppend::new()).await?;
+            a.append(bs).await?;
+            a.close().await?;
+
+            let meta = self.stat(&path).await?;
+            return Ok(meta.content_length());
+        }
+
+        let mut buf = match self.read(&path).await {
+            Ok(v) => v,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.with_operation("Operator::upsert_append")),
+        };
+        buf.extend_from_slice(&bs);
+
+        let size = buf.len() as u64;
+        self.write(&path, buf).await?;
+
+        Ok(size)
+    }
+
+    /// Delete the given path.
+    ///
+    /// # Notes
+    ///
+    /// - Deleting a file that does not exist won't return errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.delete("test").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path, OpDelete::new()).await
+    }
+
+    /// Delete given path with extra options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::ops::OpDelete;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.delete_with("test", OpDelete::new().with_bypass_governance(true))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_with(&self, path: &str, args: OpDelete) -> Result<()> {
+        let path = normalize_path(path);
+
+        let confirm = args.confirm();
+        let _ = self.inner().delete(&path, args).await?;
+
+        if confirm {
+            self.confirm_deleted(&path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Poll `is_exist` with bounded backoff until the path is gone.
+    async fn confirm_deleted(&self, path: &str) -> Result<()> {
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(20))
+            .with_max_delay(Duration::from_secs(1))
+            .with_max_times(8)
+            .build();
+
+        let mut polls = 1;
+        for delay in backoff {
+            if !self.is_exist(path).await? {
+                return Ok(());
+            }
+            tokio::time::sleep(delay).await;
+            polls += 1;
+        }
+        if !self.is_exist(path).await? {
+            return Ok(());
+        }
+
+        Err(
+            Error::new(ErrorKind::Unexpected, "path still exists after delete")
+                .with_operation("Operator::delete_with")
+                .with_context("service", self.inner().info().scheme())
+                .with_context("path", path)
+                .with_context("polls", polls.to_string()),
+        )
+    }
+
+    /// Delete every version of the given path, including delete markers.
+    ///
+    /// On versioned buckets `delete` only creates a delete marker, use this
+    /// to purge the object entirely. Returns the count of deleted versions.
+    ///
+    /// # Notes
+    ///
+    /// - Services with [`Capability::delete_all_versions`] (like `s3`) will
+    ///   list all versions and delete them in batch.
+    /// - Other services don't keep versions, the object will be deleted
+    ///   directly and `1` is returned if it existed.
+    /// - Purging a path that does not exist returns `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let purged = op.delete_all_versions("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Capability::delete_all_versions`]: crate::Capability::delete_all_versions
+    pub async fn delete_all_versions(&self, path: &str) -> Result<usize> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "delete path is a directory")
+                    .with_operation("Operator::delete_all_versions")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        if self.info().capability().delete_all_versions {
+            let rp = self
+                .inner()
+                .delete_all_versions(&path, OpDeleteAllVersions::new())
+                .await?;
+            return Ok(rp.deleted());
+        }
+
+        match self.inner().stat(&path, OpStat::new()).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        }
+        self.inner().delete(&path, OpDelete::new()).await?;
+
+        Ok(1)
+    }
+
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support delete in batch, we will use batch
+    /// delete instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.remove(vec!["abc".to_string(), "def".to_string()])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove(&self, paths: Vec<String>) -> Result<()> {
+        self.remove_via(stream::iter(paths)).await
+    }
+
+    /// remove will remove files via the given paths with extra options.
+    ///
+    /// The same args will be used for every path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::ops::OpDelete;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.remove_with(
+    ///     vec!["abc".to_string(), "def".to_string()],
+    ///     OpDelete::new().with_bypass_governance(true),
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_with(&self, paths: Vec<String>, args: OpDelete) -> Result<()> {
+        self.remove_via_with(stream::iter(paths), args).await
+    }
+
+    /// remove will remove files via the given paths.
+    ///
+    /// remove_via will remove files via the given stream.
+    ///
+    /// We will delete by chunks with given batch limit on the stream.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support delete in batch, we will use batch
+    /// delete instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// use futures::stream;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let stream = stream::iter(vec!["abc".to_string(), "def".to_string()]);
+    /// op.remove_via(stream).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
+        self.remove_via_with(input, OpDelete::default()).await
+    }
+
+    async fn remove_via_with(
+        &self,
+        input: impl Stream<Item = String> + Unpin,
+        args: OpDelete,
+    ) -> Result<()> {
+        if self.info().can_batch() {
+            let mut input = input
+                .map(|v| (v, args.clone().into()))
+                .chunks(self.batch_limit());
+
+            while let Some(batches) = input.next().await {
+                let results = self
+                    .inner()
+                    .batch(OpBatch::new(batches))
+                    .await?
+                    .into_results();
+
+                // TODO: return error here directly seems not a good idea?
+                for (_, result) in results {
+                    let _ = result?;
+                }
+            }
+        } else {
+            input
+                .map(Ok)
+                .try_for_each_concurrent(self.limit, |path| {
+                    let args = args.clone();
+                    async move {
+                        let _ = self.inner().delete(&path, args).await?;
+                        Ok::<(), Error>(())
+                    }
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the path and all nested dirs and files recursively.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support delete in batch, we will use batch
+    /// delete instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.remove_all("path/to/dir").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_all(&self, path: &str) -> Result<()> {
+        let obs = match self.remove_all_scan(path).await? {
+            RemoveAllTarget::NotExist => return Ok(()),
+            RemoveAllTarget::File => return self.delete(path).await,
+            RemoveAllTarget::Dir(obs) => obs,
+        };
+
+        if self.info().can_batch() {
+            let mut obs = obs.try_chunks(self.batch_limit());
+
+            while let Some(batches) = obs.next().await {
+                let batches = batches
+                    .map_err(|err| err.1)?
+                    .into_iter()
+                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
+                    .collect();
+
+                let results = self
+                    .inner()
+                    .batch(OpBatch::new(batches))
+                    .await?
+                    .into_results();
+
+                // TODO: return error here directly seems not a good idea?
+                for (_, result) in results {
+                    let _ = result?;
+                }
+            }
+        } else {
+            obs.try_for_each(|v| async move { self.delete(v.path()).await })
+                .await?;
+        }
+
+        // Remove the directory itself.
+        self.delete(path).await?;
+
+        Ok(())
+    }
+
+    /// Preview the paths that [`Operator::remove_all`] would remove without
+    /// deleting anything.
+    ///
+    /// Paths are returned in the same order as `remove_all` visiting them,
+    /// including directory markers and the given path itself.
+    ///
+    /// # Notes
+    ///
+    /// The preview is only accurate if no changes happen between preview and
+    /// the real `remove_all`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let paths = op.remove_all_preview("path/to/dir/").await?;
+    /// for path in paths {
+    ///     println!("{path} will be removed");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_all_preview(&self, path: &str) -> Result<Vec<String>> {
+        let mut paths: Vec<String> = match self.remove_all_scan(path).await? {
+            RemoveAllTarget::NotExist => return Ok(vec![]),
+            RemoveAllTarget::File => vec![],
+            RemoveAllTarget::Dir(obs) => obs.map_ok(|v| v.path().to_string()).try_collect().await?,
+        };
+        paths.push(normalize_path(path));
+
+        Ok(paths)
+    }
+
+    /// Stat and scan path in the same way for `remove_all` and its preview.
+    async fn remove_all_scan(&self, path: &str) -> Result<RemoveAllTarget> {
+        let meta = match self.stat(path).await {
+            // If object exists.
+            Ok(metadata) => metadata,
+
+            // If object not found, return success.
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(RemoveAllTarget::NotExist),
+
+            // Pass on any other error.
+            Err(e) => return Err(e),
+        };
+
+        if meta.mode() != EntryMode::DIR {
+            return Ok(RemoveAllTarget::File);
+        }
+
+        Ok(RemoveAllTarget::Dir(self.scan(path).await?))
+    }
+
+    /// List given path.
+    ///
+    /// This function will create a new handle to list entries.
+    ///
+    /// An error will be returned if given path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// use futures::TryStreamExt;
+    /// use opendal::EntryMode;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op.list("path/to/dir/").await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
+    ///     match meta.mode() {
+    ///         EntryMode::FILE => {
+    ///             println!("Handling file")
+    ///         }
+    ///         EntryMode::DIR => {
+    ///             println!("Handling dir like start a new list via meta.path()")
+    ///         }
+    ///         EntryMode::Unknown => continue,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(&self, path: &str) -> Result<Lister> {
+        self.list_with(path, OpList::new()).await
+    }
+
+    /// List given path with OpList.
+    ///
+    /// This function will create a new handle to list entries.
+    ///
+    /// An error will be returned if given path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ## List current dir
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::EntryMode;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let option = OpList::new().with_limit(10).with_start_after("start");
+    /// let mut ds = op.list_with("path/to/dir/", option).await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
+    ///     match meta.mode() {
+    ///         EntryMode::FILE => {
+    ///             println!("Handling file")
+    ///         }
+    ///         EntryMode::DIR => {
+    ///             println!("Handling dir like start a new list via meta.path()")
+    ///         }
+    ///         EntryMode::Unknown => continue,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## List all files recursively
+    ///
+    /// We can use `op.scan()` as a shorter alias.
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::EntryMode;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let option = OpList::new().with_delimiter("");
+    /// let mut ds = op.list_with("path/to/dir/", option).await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
+    ///     match meta.mode() {
+    ///         EntryMode::FILE => {
+    ///             println!("Handling file")
+    ///         }
+    ///         EntryMode::DIR => {
+    ///             println!("Handling dir like start a new list via meta.path()")
+    ///         }
+    ///         EntryMode::Unknown => continue,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_with(&self, path: &str, op: OpList) -> Result<Lister> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path trying to list should end with `/`",
+            )
+            .with_operation("Operator::list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let check = op.consistency_check();
+        let (_, pager) = self.inner().list(&path, op).await?;
+
+        let pager: oio::Pager = match check {
+            Some((mode, window)) => Box::new(ConsistencyCheckPager::new(pager, mode, window)),
+            None => pager,
+        };
+        Ok(Lister::new(pager))
+    }
+
+    /// List given path with OpList, and yield entries in batches of up to
+    /// `batch_size`.
+    ///
+    /// All batches are full except the last one, which holds the remaining
+    /// entries. The stream stops after the first error.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `batch_size` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut batches = op
+    ///     .list_batched("path/to/dir/", OpList::new().with_delimiter(""), 500)
+    ///     .await?;
+    /// while let Some(entries) = batches.try_next().await? {
+    ///     println!("submit job with {} keys", entries.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_batched(
+        &self,
+        path: &str,
+        op: OpList,
+        batch_size: usize,
+    ) -> Result<impl Stream<Item = Result<Vec<Entry>>> + Unpin> {
+        assert!(batch_size > 0, "batch_size must be larger than 0");
+
+        let lister = self.list_with(path, op).await?;
+        Ok(lister.try_chunks(batch_size).map_err(|err| err.1))
+    }
+
+    /// List dir in flat way.
+    ///
+    /// Also, this function can be used to list a prefix.
+    ///
+    /// An error will be returned if given path doesn't end with `/`.
+    ///
+    /// # Notes
+    ///
+    /// - `scan` will not return the prefix itself.
+    /// - `scan` is an alias of `list_with(OpList::new().with_delimiter(""))`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// use futures::TryStreamExt;
+    /// use opendal::EntryMode;
+    /// use opendal::Metakey;
+    /// use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op.scan("/path/to/dir/").await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::Mode).await?;
+    ///     match meta.mode() {
+    ///         EntryMode::FILE => {
+    ///             println!("Handling file")
+    ///         }
+    ///         EntryMode::DIR => {
+    ///             println!("Handling dir like start a new list via meta.path()")
+    ///         }
+    ///         EntryMode::Unknown => continue,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan(&self, path: &str) -> Result<Lister> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path trying to scan should end with `/`",
+            )
+            .with_operation("list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let (_, pager) = self
+            .inner()
+            .list(&path, OpList::new().with_delimiter(""))
+            .await?;
+
+        Ok(Lister::new(pager))
+    }
+
+    /// List dir in flat way with extra options.
+    ///
+    /// The delimiter of `op` will be ignored. Use [`OpList::with_prune`]
+    /// to skip subtrees that are irrelevant.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::Operator;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op
+    ///     .scan_with(
+    ///         "path/to/dir/",
+    ///         OpList::new().with_prune(|dir| dir.ends_with("/archived/")),
+    ///     )
+    ///     .await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan_with(&self, path: &str, op: OpList) -> Result<Lister> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path trying to scan should end with `/`",
+            )
+            .with_operation("list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        self.list_with(&path, op.with_delimiter("")).await
+    }
+
+    /// List the same dir across this operator and `others`, merging the
+    /// results into one lister sorted by key.
+    ///
+    /// All operators are listed concurrently. If a key is listed by more
+    /// than one operator, only the entry from the operator with the highest
+    /// priority is returned: this operator first, then `others` in order.
+    ///
+    /// # Notes
+    ///
+    /// Merge assumes every operator lists keys in ascending lexicographic
+    /// order, like most object storage services do. Results are not sorted
+    /// and may contain duplicated keys otherwise.
+    ///
+    /// Entries remember the operator they are listed from:
+    /// [`Operator::metadata`] on an entry listed from `others` queries that
+    /// operator instead of this one. Other calls taking the entry's path,
+    /// like `stat` or `read`, must be sent to that operator by users.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(hot: Operator, cold: Operator) -> Result<()> {
+    /// let mut ds = hot
+    ///     .merge_list(&[cold], "path/to/dir/", OpList::new())
+    ///     .await?;
+    /// while let Some(de) = ds.try_next().await? {
+    ///     println!("{}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn merge_list(&self, others: &[Operator], path: &str, op: OpList) -> Result<Lister> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path trying to list should end with `/`",
+            )
+            .with_operation("Operator::merge_list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let check = op.consistency_check();
+        let pagers = future::try_join_all(
+            std::iter::once(self)
+                .chain(others)
+                .map(|o| o.inner().list(&path, op.clone())),
+        )
+        .await?;
+
+        let sources = std::iter::once(None).chain(others.iter().cloned().map(Some));
+        let pagers = pagers
+            .into_iter()
+            .zip(sources)
+            .map(|((_, pager), source)| {
+                let pager = match check {
+                    Some((mode, window)) => {
+                        Box::new(ConsistencyCheckPager::new(pager, mode, window)) as oio::Pager
+                    }
+                    None => pager,
+                };
+                (pager, source)
+            })
+            .collect();
+        Ok(Lister::new(Box::new(MergePager::new(pagers))))
+    }
+
+    /// List dir and return the most recently modified entries.
+    ///
+    /// This is the same as
+    /// [`list_recent_with`](Self::list_recent_with) with default `OpList`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let entries = op.list_recent("path/to/dir/", 10).await?;
+    /// for de in entries {
+    ///     println!("recently modified: {}", de.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_recent(&self, path: &str, limit: usize) -> Result<Vec<Entry>> {
+        self.list_recent_with(path, OpList::new(), limit).await
+    }
+
+    /// List dir with OpList and return at most `limit` entries sorted by
+    /// last modified time in descending order.
+    ///
+    /// # Notes
+    ///
+    /// - No service returns entries sorted by last modified time, so all
+    ///   entries will be listed and up to `limit` of them are buffered in
+    ///   memory. This is not a streaming sorted operation, the first entry
+    ///   is only available after the whole listing has been visited.
+    /// - `LastModified` is read from the metadata returned by listing if
+    ///   possible, otherwise a `stat` will be sent for the entry.
+    /// - Entries without last modified time (like dirs on most services)
+    ///   are treated as the oldest.
+    /// - Use `OpList::new().with_delimiter("")` to visit the dir recursively.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::ops::OpList;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let entries = op
+    ///     .list_recent_with("path/to/dir/", OpList::new().with_delimiter(""), 10)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_recent_with(
+        &self,
+        path: &str,
+        op: OpList,
+        limit: usize,
+    ) -> Result<Vec<Entry>> {
+        let mut lister = self.list_with(path, op).await?;
+
+        // A min heap keeps the `limit` most recent entries.
+        let mut heap = BinaryHeap::with_capacity(limit.saturating_add(1));
+        while let Some(de) = lister.try_next().await? {
+            let last_modified = match de.metadata() {
+                Some(meta)
+                    if meta.bit().contains(Metakey::LastModified)
+                        || meta.bit().contains(Metakey::Complete) =>
+                {
+                    meta.last_modified()
+                }
+                _ => self
+                    .stat_with(de.path(), OpStat::new().with_metakey(Metakey::LastModified))
+                    .await?
+                    .last_modified(),
+            };
+
+            heap.push(Reverse(RecentEntry {
+                last_modified,
+                entry: de,
+            }));
+            if heap.len() > limit {
+                heap.pop();
+            }
+        }
+
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(v)| v.entry)
+            .collect())
+    }
+
+    /// Compute a digest of all files under the prefix recursively.
+    ///
+    /// Every file is folded into the digest with its path relative to the
+    /// prefix, content length and content identity (see
+    /// [`Metadata::content_identity`]) in sorted order. So two prefixes
+    /// with the same files yield the same digest no matter where they are
+    /// located or how the listing is paginated, which makes it a cheap
+    /// check of whether they are in sync.
+    ///
+    /// # Notes
+    ///
+    /// - Dirs are skipped since not all services have them.
+    /// - Only content hash and etags are folded as identity. Etags are only
+    ///   comparable in the same service, and multipart etags depend on the
+    ///   part size, so identical content could lead to different digests.
+    ///   Services without either are only compared by path and size.
+    /// - Metadata is read from listing if possible, otherwise a `stat` will
+    ///   be sent for the entry.
+    /// - Paths of all files are buffered in memory for sorting.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(src: Operator, dst: Operator) -> Result<()> {
+    /// let in_sync = src.prefix_digest("dataset/").await? == dst.prefix_digest("dataset/").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Metadata::content_identity`]: crate::Metadata::content_identity
+    pub async fn prefix_digest(&self, path: &str) -> Result<String> {
+        let path = normalize_path(path);
+
+        let mut lister = self
+            .list_with(&path, OpList::new().with_delimiter(""))
+            .await?;
+
+        let mut files = Vec::new();
+        while let Some(de) = lister.try_next().await? {
+            if de.path().ends_with('/') {
+                continue;
+            }
+
+            let meta = self
+                .metadata(
+                    &de,
+                    Metakey::Mode | Metakey::ContentLength | Metakey::Etag | Metakey::ContentMd5,
+                )
+                .await?;
+            if !meta.is_file() {
+                continue;
+            }
+            let identity = match meta.content_identity() {
+                ContentIdentity::ContentHash(v) => format!("hash:{v}"),
+                ContentIdentity::StrongEtag(v) | ContentIdentity::WeakEtag(v) => {
+                    format!("etag:{v}")
+                }
+                ContentIdentity::SizeTime { .. } => String::new(),
+            };
+            let rel = de.path().strip_prefix(path.as_str()).unwrap_or(de.path());
+            files.push((rel.to_string(), meta.content_length(), identity));
+        }
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        for (rel, size, identity) in files {
+            // Fields are length prefixed to avoid ambiguity.
+            for field in [rel.as_bytes(), identity.as_bytes()] {
+                hasher.update((field.len() as u64).to_le_bytes());
+                hasher.update(field);
+            }
+            hasher.update(size.to_le_bytes());
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Scan every prefix and process its entries with `f`, up to
+    /// `concurrency` prefixes at the same time.
+    ///
+    /// # Notes
+    ///
+    /// - Every prefix should be a dir path ending with `/`, it's visited
+    ///   recursively like [`Operator::scan`].
+    /// - At most `concurrency` listers are alive at the same time, memory
+    ///   usage is bounded as long as `f` consumes the lister in streaming.
+    /// - Failure of one prefix doesn't abort others. Errors returned by
+    ///   either listing or `f` are collected along with their prefix in the
+    ///   order they happened. An empty result means all prefixes succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use futures::TryStreamExt;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let prefixes = vec!["logs/a/".to_string(), "logs/b/".to_string()];
+    /// let errors = op
+    ///     .for_each_prefix(prefixes, 8, |lister| async move {
+    ///         let entries: Vec<_> = lister.try_collect().await?;
+    ///         println!("visited {} entries", entries.len());
+    ///         Ok(())
+    ///     })
+    ///     .await;
+    /// for (prefix, err) in errors {
+    ///     println!("failed to process {prefix}: {err}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn for_each_prefix<F, Fut>(
+        &self,
+        prefixes: Vec<String>,
+        concurrency: usize,
+        f: F,
+    ) -> Vec<(String, Error)>
+    where
+        F: Fn(Lister) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let f = &f;
+
+        stream::iter(prefixes)
+            .map(|prefix| async move {
+                let res = match self.scan(&prefix).await {
+                    Ok(lister) => f(lister).await,
+                    Err(err) => Err(err),
+                };
+                res.err().map(|err| (prefix, err))
+            })
+            .buffer_unordered(concurrency.max(1))
+            .filter_map(future::ready)
+            .collect()
+            .await
+    }
+
+    /// Scan dir in flat way and call `visitor` for every entry, returns the
+    /// count of visited entries.
+    ///
+    /// # Notes
+    ///
+    /// - The delimiter of `op` will be ignored like [`Operator::scan_with`].
+    /// - At most `concurrency` visitors are running at the same time.
+    /// - Failure of one entry doesn't abort others. If any visitor failed,
+    ///   the first error will be returned with the count of failures, after
+    ///   all entries have been visited. Errors of listing abort the walk
+    ///   directly.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// use opendal::ops::OpList;
+    /// use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let count = op
+    ///     .walk("path/to/dir/", OpList::new(), 8, |de| async move {
+    ///         println!("visit {}", de.path());
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// println!("visited {count} entries");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn walk<F, Fut>(
+        &self,
+        path: &str,
+        op: OpList,
+        concurrency: usize,
+        mut visitor: F,
+    ) -> Result<usize>
+    where
+        F: FnMut(Entry) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let (visited, failed, first_err) = self
+            .scan_with(path, op)
+            .await?
+            .map_ok(|de| {
+                let path = de.path().to_string();
+                visitor(de).map(|res| Ok(res.map_err(|err| err.with_context("entry", path))))
+            })
+            .try_buffer_unordered(concurrency.max(1))
+            .try_fold(
+                (0, 0, None),
+                |(visited, failed, first_err), res| match res {
+                    Ok(()) => future::ok((visited + 1, failed, first_err)),
+                    Err(err) => future::ok((visited, failed + 1, first_err.or(Some(err)))),
+                },
+            )
+            .await?;
+
+        match first_err {
+            None => Ok(visited),
+            Some(err) => Err(err
+                .with_operation("Operator::walk")
+                .with_context("visited", visited.to_string())
+                .with_context("failed", failed.to_string())),
+        }
+    }
+}
+
+/// Entry ordered by last modified time and then path.
+struct RecentEntry {
+    last_modified: Option<DateTime<Utc>>,
+    entry: Entry,
+}
+
+impl RecentEntry {
+    fn key(&self) -> (Option<DateTime<Utc>>, &str) {
+        (self.last_modified, self.entry.path())
+    }
+}
+
+impl PartialEq for RecentEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for RecentEntry {}
+
+impl PartialOrd for RecentEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RecentEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Operator presign API.
+impl Operator {
+    /// Presign an operation for stat(head).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op.presign_stat("test",Duration::from_secs(3600)).await?;
+    ///     let req = http::Request::builder()
+    ///         .method(signed_req.method())
+    ///         .uri(signed_req.uri())
+    ///         .body(())?;
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpStat::new(), expire);
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign an operation for read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op.presign_read("test.txt", Duration::from_secs(3600)).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// - `signed_req.method()`: `GET`
+    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>`
+    /// - `signed_req.headers()`: `{ "host": "s3.amazonaws.com" }`
+    ///
+    /// We can download this file via `curl` or other tools without credentials:
+    ///
+    /// ```shell
+    /// curl "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -O /tmp/test.txt
+    /// ```
+    pub async fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpRead::new(), expire);
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign an operation for read option described in OpenDAL [rfc-1735](../../docs/rfcs/1735_operation_extension.md).
+    ///
+    /// You can pass `OpRead` to this method to specify the content disposition.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    /// use opendal::ops::OpRead;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let args = OpRead::new()
+    ///         .with_override_content_disposition("attachment; filename=\"othertext.txt\"");
+    ///     let signed_req = op.presign_read_with("test.txt", args, Duration::from_secs(3600)).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn presign_read_with(
+        &self,
+        path: &str,
+        op: OpRead,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(op, expire);
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Presign an operation for write.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op.presign_write("test.txt", Duration::from_secs(3600)).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// - `signed_req.method()`: `PUT`
+    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>`
+    /// - `signed_req.headers()`: `{ "host": "s3.amazonaws.com" }`
+    ///
+    /// We can upload file as this file via `curl` or other tools without credential:
+    ///
+    /// ```shell
+    /// curl -X PUT "https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>" -d "Hello, World!"
+    /// ```
+    pub async fn presign_write(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
+        self.presign_write_with(path, OpWrite::new(), expire).await
+    }
+
+    /// Presign an operation for write with option described in OpenDAL [rfc-0661](../../docs/rfcs/0661-path-in-accessor.md)
+    ///
+    /// You can pass `OpWrite` to this method to specify the content length and content type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::ops::OpWrite;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let args = OpWrite::new().with_content_type("text/csv");
+    ///     let signed_req = op.presign_write_with("test", args, Duration::from_secs(3600)).await?;
+    ///     let req = http::Request::builder()
+    ///         .method(signed_req.method())
+    ///         .uri(signed_req.uri())
+    ///         .body(())?;
+    ///
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn presign_write_with(
+        &self,
+        path: &str,
+        op: OpWrite,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(op, expire);
+
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Read the content by executing a presigned request.
+    ///
+    /// The request is usually generated by [`Operator::presign_read`] in
+    /// another place, so no operator or credential is required here.
+    ///
+    /// # Notes
+    ///
+    /// - Only the method, uri and headers of request will be used.
+    /// - Non-2xx responses will be converted into errors, for example
+    ///   `404` as [`ErrorKind::NotFound`] and `403` (usually caused by
+    ///   expired requests) as [`ErrorKind::PermissionDenied`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op.presign_read("test.txt", Duration::from_secs(3600)).await?;
+    ///     // Send signed_req to other services...
+    ///     let bs = Operator::read_presigned(signed_req).await?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn read_presigned(req: PresignedRequest) -> Result<Bytes> {
+        let client = HttpClient::new()?;
+
+        let uri = req.uri().to_string();
+        let new_error = |err: Error| {
+            err.with_operation("Operator::read_presigned")
+                .with_context("uri", &uri)
+        };
+
+        let resp = client.send(req.into()).await.map_err(new_error)?;
+        let (parts, body) = resp.into_parts();
+        let bs = body.bytes().await.map_err(new_error)?;
+
+        if parts.status.is_success() {
+            return Ok(bs);
+        }
+
+        let (kind, retryable) = match parts.status {
+            http::StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
+            http::StatusCode::FORBIDDEN | http::StatusCode::UNAUTHORIZED => {
+                (ErrorKind::PermissionDenied, false)
+            }
+            http::StatusCode::PRECONDITION_FAILED | http::StatusCode::NOT_MODIFIED => {
+                (ErrorKind::ConditionNotMatch, false)
+            }
+            http::StatusCode::INTERNAL_SERVER_ERROR
+            | http::StatusCode::BAD_GATEWAY
+            | http::StatusCode::SERVICE_UNAVAILABLE
+            | http::StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+            _ => (ErrorKind::Unexpected, false),
+        };
+
+        let mut err = Error::new(kind, &String::from_utf8_lossy(&bs))
+            .with_context("response", format!("{parts:?}"));
+        if retryable {
+            err = err.set_temporary();
+        }
+
+        Err(new_error(err))
+    }
+}
+
+/// Targets to remove by `remove_all`.
+enum RemoveAllTarget {
+    NotExist,
+    File,
+    Dir(Lister),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::fmt::Formatter;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use async_trait::async_trait;
+    use futures::future::BoxFuture;
+    use futures::TryFutureExt;
+    use rand::rngs::StdRng;
+    use rand::RngCore;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    type MockHandler<A, R> = Arc<dyn Fn(A) -> BoxFuture<'static, Result<R>> + Send + Sync>;
+
+    /// MockService is an accessor whose operations are served by handlers
+    /// given by tests.
+    ///
+    /// Operations without a handler return `Unsupported`.
+    #[derive(Clone, Default)]
+    struct MockService {
+        capability: Capability,
+        stat: Option<MockHandler<(String, OpStat), RpStat>>,
+        read: Option<MockHandler<(String, OpRead), (RpRead, oio::Reader)>>,
+        write: Option<MockHandler<(String, OpWrite), (RpWrite, oio::Writer)>>,
+        copy: Option<MockHandler<(String, String, OpCopy), RpCopy>>,
+        delete: Option<MockHandler<(String, OpDelete), RpDelete>>,
+        list: Option<MockHandler<(String, OpList), (RpList, oio::Pager)>>,
+        batch: Option<MockHandler<OpBatch, RpBatch>>,
+    }
+
+    impl MockService {
+        fn new(capability: Capability) -> Self {
+            Self {
+                capability,
+                ..Default::default()
+            }
+        }
+
+        fn with_stat<F, Fut>(mut self, f: F) -> Self
+        where
+            F: Fn(String, OpStat) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<RpStat>> + Send + 'static,
+        {
+            self.stat = Some(Arc::new(move |(path, args)| f(path, args).boxed()));
+            self
+        }
+
+        fn with_read<F, Fut, R>(mut self, f: F) -> Self
+        where
+            F: Fn(String, OpRead) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<(RpRead, R)>> + Send + 'static,
+            R: oio::Read + 'static,
+        {
+            self.read = Some(Arc::new(move |(path, args)| {
+                f(path, args)
+                    .map_ok(|(rp, r)| (rp, Box::new(r) as oio::Reader))
+                    .boxed()
+            }));
+            self
+        }
+
+        fn with_write<F, Fut, W>(mut self, f: F) -> Self
+        where
+            F: Fn(String, OpWrite) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<(RpWrite, W)>> + Send + 'static,
+            W: oio::Write + 'static,
+        {
+            self.write = Some(Arc::new(move |(path, args)| {
+                f(path, args)
+                    .map_ok(|(rp, w)| (rp, Box::new(w) as oio::Writer))
+                    .boxed()
+            }));
+            self
+        }
+
+        fn with_copy<F, Fut>(mut self, f: F) -> Self
+        where
+            F: Fn(String, String, OpCopy) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<RpCopy>> + Send + 'static,
+        {
+            self.copy = Some(Arc::new(move |(from, to, args)| f(from, to, args).boxed()));
+            self
+        }
+
+        fn with_delete<F, Fut>(mut self, f: F) -> Self
+        where
+            F: Fn(String, OpDelete) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<RpDelete>> + Send + 'static,
+        {
+            self.delete = Some(Arc::new(move |(path, args)| f(path, args).boxed()));
+            self
+        }
+
+        fn with_list<F, Fut, P>(mut self, f: F) -> Self
+        where
+            F: Fn(String, OpList) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<(RpList, P)>> + Send + 'static,
+            P: oio::Page + 'static,
+        {
+            self.list = Some(Arc::new(move |(path, args)| {
+                f(path, args)
+                    .map_ok(|(rp, p)| (rp, Box::new(p) as oio::Pager))
+                    .boxed()
+            }));
+            self
+        }
+
+        fn with_batch<F, Fut>(mut self, f: F) -> Self
+        where
+            F: Fn(OpBatch) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = Result<RpBatch>> + Send + 'static,
+        {
+            self.batch = Some(Arc::new(move |args| f(args).boxed()));
+            self
+        }
+
+        async fn call<A, R>(handler: &Option<MockHandler<A, R>>, args: A) -> Result<R> {
+            match handler {
+                Some(f) => f(args).await,
+                None => Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "operation is not mocked",
+                )),
+            }
+        }
+    }
+
+    impl Debug for MockService {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MockService").finish_non_exhaustive()
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Reader;
+        type BlockingReader = ();
+        type Writer = oio::Writer;
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = oio::Pager;
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_scheme(Scheme::Custom("mock"));
+            am.set_capability(self.capability);
+            am
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            Self::call(&self.stat, (path.to_string(), args)).await
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            Self::call(&self.read, (path.to_string(), args)).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Self::call(&self.write, (path.to_string(), args)).await
+        }
+
+        async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+            Self::call(&self.copy, (from.to_string(), to.to_string(), args)).await
+        }
+
+        async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+            Self::call(&self.delete, (path.to_string(), args)).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            Self::call(&self.list, (path.to_string(), args)).await
+        }
+
+        async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+            Self::call(&self.batch, args).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_via_clamp_limit() -> Result<()> {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let srv = MockService::new(Capability {
+            batch: true,
+            batch_delete: true,
+            batch_max_operations: Some(2),
+            ..Default::default()
+        })
+        .with_batch({
+            let batches = batches.clone();
+            move |args| {
+                let ops = args.into_operation();
+                let res = if ops.len() > 2 {
+                    Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "batch exceeds max operations",
+                    ))
+                } else {
+                    batches.lock().unwrap().push(ops.len());
+                    Ok(RpBatch::new(
+                        ops.into_iter()
+                            .map(|(path, _)| (path, Ok(RpDelete::default().into())))
+                            .collect(),
+                    ))
+                };
+                future::ready(res)
+            }
+        });
+        let op = OperatorBuilder::new(srv).finish().with_limit(1000);
+
+        let paths = (0..5).map(|i| format!("file-{i}")).collect::<Vec<_>>();
+        op.remove_via(stream::iter(paths)).await?;
+
+        assert_eq!(*batches.lock().unwrap(), vec![2, 2, 1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_to_across_storages() -> Result<()> {
+        let src = Operator::new(services::Memory::default())?.finish();
+        let dst = Operator::new(services::Memory::default())?.finish();
+
+        src.write("a.txt", "Hello, World!").await?;
+        src.copy_to("a.txt", &dst, "b.txt").await?;
+        assert_eq!(dst.read("b.txt").await?, b"Hello, World!");
+        assert!(src.is_exist("a.txt").await?);
+
+        src.rename_to("a.txt", &dst, "c.txt").await?;
+        assert_eq!(dst.read("c.txt").await?, b"Hello, World!");
+        assert!(!src.is_exist("a.txt").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_to_same_storage() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        op.write("a.txt", "Hello, World!").await?;
+        let err = op.copy_to("a.txt", &op, "a.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IsSameFile);
+
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockReadState {
+        /// Bytes have been read but not written into output yet.
+        outstanding: usize,
+        peak: usize,
+        completed: Vec<u64>,
+    }
+
+    /// Serve ranged reads of `data`, parts of 4 bytes in every window of 4
+    /// complete in reverse order.
+    fn mock_read_service(data: Bytes, state: Arc<Mutex<MockReadState>>) -> MockService {
+        let len = data.len() as u64;
+        MockService::new(Capability {
+            stat: true,
+            read: true,
+            read_can_seek: true,
+            read_can_next: true,
+            read_with_range: true,
+            ..Default::default()
+        })
+        .with_stat(move |_, _| {
+            future::ok(RpStat::new(
+                Metadata::new(EntryMode::FILE).with_content_length(len),
+            ))
+        })
+        .with_read(move |_, args| {
+            let (data, state) = (data.clone(), state.clone());
+            async move {
+                let offset = args.range().offset().unwrap();
+                let size = args.range().size().unwrap();
+
+                let delay = 10 * (3 - (offset / 4) % 4);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+
+                let mut state = state.lock().unwrap();
+                state.outstanding += size as usize;
+                state.peak = state.peak.max(state.outstanding);
+                state.completed.push(offset);
+
+                let bs = data.slice(offset as usize..(offset + size) as usize);
+                Ok((RpRead::new(size), oio::Cursor::from(bs)))
+            }
+        })
+    }
+
+    struct MockOutput {
+        buf: Vec<u8>,
+        state: Arc<Mutex<MockReadState>>,
+    }
+
+    impl AsyncWrite for MockOutput {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.state.lock().unwrap().outstanding -= buf.len();
+            self.buf.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_read_to() -> Result<()> {
+        let data: Bytes = (0..30u8).collect::<Vec<_>>().into();
+        let state = Arc::new(Mutex::new(MockReadState::default()));
+        let op = OperatorBuilder::new(mock_read_service(data.clone(), state.clone())).finish();
+
+        let mut output = MockOutput {
+            buf: Vec::new(),
+            state: state.clone(),
+        };
+        let n = op.parallel_read_to("file", &mut output, 4, 4).await?;
+        assert_eq!(n, 30);
+        assert_eq!(output.buf, data);
+
+        {
+            let state = state.lock().unwrap();
+            let mut sorted = state.completed.clone();
+            sorted.sort();
+            assert_ne!(state.completed, sorted, "parts must complete out of order");
+            assert!(
+                state.peak <= 4 * 4,
+                "peak memory {} exceeds window",
+                state.peak
+            );
+            assert_eq!(state.outstanding, 0);
+        }
+
+        for (part_size, concurrency) in [(0, 4), (4, 0)] {
+            let err = op
+                .parallel_read_to("file", &mut output, part_size, concurrency)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_ranges() -> Result<()> {
+        let data: Bytes = (0..64u8).collect::<Vec<_>>().into();
+        let state = Arc::new(Mutex::new(MockReadState::default()));
+        let op = OperatorBuilder::new(mock_read_service(data.clone(), state.clone())).finish();
+
+        let ranges = vec![12..14, 0..4, 40..44, 6..10];
+        let bss = op
+            .read_ranges_with_gap("file", ranges.clone(), 4, 2)
+            .await?;
+        assert_eq!(bss.len(), ranges.len());
+        for (r, bs) in ranges.iter().zip(bss) {
+            assert_eq!(bs, data.slice(r.start as usize..r.end as usize));
+        }
+
+        // Nearby ranges are coalesced into one request, the distant one is not.
+        let mut completed = state.lock().unwrap().completed.clone();
+        completed.sort();
+        assert_eq!(completed, vec![0, 40]);
+
+        // Empty or inverted ranges are rejected.
+        #[allow(clippy::reversed_empty_ranges)]
+        for ranges in [vec![0..4, 5..5], vec![0..4, 10..6]] {
+            let err = op.read_ranges("file", ranges).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+        }
+
+        Ok(())
+    }
+
+    /// MockCountingReader yields at most 16 bytes per read and counts the
+    /// bytes have been pulled from it.
+    struct MockCountingReader {
+        inner: oio::Cursor,
+        pulled: Arc<Mutex<usize>>,
+    }
+
+    impl oio::Read for MockCountingReader {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            let size = buf.len().min(16);
+            let n = futures::ready!(self.inner.poll_read(cx, &mut buf[..size]))?;
+            *self.pulled.lock().unwrap() += n;
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: io::SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "seek is not supported",
+            )))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            Poll::Ready(Some(Err(Error::new(
+                ErrorKind::Unsupported,
+                "next is not supported",
+            ))))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_prefix() -> Result<()> {
+        let data: Bytes = (0..=255u8).cycle().take(4096).collect::<Vec<_>>().into();
+        let pulled = Arc::new(Mutex::new(0));
+        let srv = MockService::new(Capability {
+            read: true,
+            read_can_seek: true,
+            read_can_next: true,
+            read_with_range: true,
+            ..Default::default()
+        })
+        .with_read({
+            let (data, pulled) = (data.clone(), pulled.clone());
+            move |_, args| {
+                let data = args.range().apply_on_bytes(data.clone());
+                let size = data.len() as u64;
+                let r = MockCountingReader {
+                    inner: oio::Cursor::from(data),
+                    pulled: pulled.clone(),
+                };
+                future::ok((RpRead::new(size), r))
+            }
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        let bs = op.read_prefix("file", 100).await?;
+        assert_eq!(bs, data.slice(..100));
+        assert_eq!(*pulled.lock().unwrap(), 100);
+
+        // Objects smaller than max_bytes are read entirely.
+        let bs = op.read_prefix("file", 8192).await?;
+        assert_eq!(bs, data);
+
+        assert!(op.read_prefix("file", 0).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_bogus_content_length() -> Result<()> {
+        // The content length returned by `bogus` and `short` doesn't match
+        // the real content.
+        let srv = MockService::new(Capability {
+            read: true,
+            read_can_seek: true,
+            read_can_next: true,
+            ..Default::default()
+        })
+        .with_read(|path, _| {
+            let size = match path.as_str() {
+                "bogus" => 1 << 50,
+                "short" => 5,
+                _ => 13,
+            };
+            future::ok((
+                RpRead::new(size),
+                oio::Cursor::from(Bytes::from_static(b"Hello, World!")),
+            ))
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        let err = op.range_read("bogus", ..).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ContentIncomplete);
+        let err = op.range_read("short", ..).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ContentTruncated);
+
+        // Size hint is capped as well.
+        let bs = op
+            .range_read_with("file", .., OpRead::new().with_size_hint(1 << 50))
+            .await?;
+        assert_eq!(bs, b"Hello, World!");
+        assert!(
+            bs.capacity() <= 16 * 1024 * 1024,
+            "capacity {} is not bounded",
+            bs.capacity()
+        );
+
+        let bs = op
+            .range_read_with("file", .., OpRead::new().with_size_hint(64))
+            .await?;
+        assert_eq!(bs, b"Hello, World!");
+        assert_eq!(bs.capacity(), 64);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transform_copy() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        op.write("from", "hello, world").await?;
+
+        op.transform_copy("from", "to", |bs| Ok(Bytes::from(bs.to_ascii_uppercase())))
+            .await?;
+        assert_eq!(op.read("to").await?, b"HELLO, WORLD");
+
+        let err = op
+            .transform_copy("from", "failed", |_| {
+                Err(Error::new(ErrorKind::Unexpected, "transform failed"))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!op.is_exist("failed").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_from() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let content: Vec<u8> = (0..4096).map(|v| v as u8).collect();
+        std::fs::write(&path, &content)
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "prepare file").set_source(err))?;
+        let f = std::fs::File::open(&path)
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "open file").set_source(err))?;
+
+        let mut r = futures::io::AllowStdIo::new(f);
+        r.seek(io::SeekFrom::Start(96)).await.unwrap();
+        let n = op.write_from("file", r).await?;
+        assert_eq!(n, 4000);
+
+        assert_eq!(op.stat("file").await?.content_length(), 4000);
+        assert_eq!(op.read("file").await?, content[96..]);
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[cfg(feature = "services-s3")]
+    #[tokio::test]
+    async fn test_read_presigned() -> Result<()> {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/file"))
+            .and(query_param("X-Amz-Expires", "3600"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/not_exist"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = services::S3::default();
+        builder.endpoint(&mock_server.uri());
+        builder.bucket("bucket");
+        builder.region("us-east-1");
+        builder.access_key_id("access_key_id");
+        builder.secret_access_key("secret_access_key");
+        builder.disable_config_load();
+        let op = Operator::new(builder)?.finish();
+
+        let req = op.presign_read("file", Duration::from_secs(3600)).await?;
+        let bs = Operator::read_presigned(req).await?;
+        assert_eq!(bs, "Hello, World!");
+
+        let req = op
+            .presign_read("not_exist", Duration::from_secs(3600))
+            .await?;
+        let err = Operator::read_presigned(req).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockWriteState {
+        content: Vec<u8>,
+        /// Size of every write call.
+        writes: Vec<usize>,
+    }
+
+    /// MockWriter rejects single writes larger than 4 bytes.
+    struct MockWriter {
+        content_length: Option<u64>,
+        buf: Vec<u8>,
+        state: Arc<Mutex<MockWriteState>>,
+    }
+
+    #[async_trait]
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, bs: Bytes) -> Result<()> {
+            // Like most services, write the whole content in one request
+            // if it's given at once.
+            if self.buf.is_empty() && Some(bs.len() as u64) == self.content_length && bs.len() > 4 {
+                return Err(Error::new(ErrorKind::Unexpected, "entity too large"));
+            }
+            self.state.lock().unwrap().writes.push(bs.len());
+            self.buf.extend_from_slice(&bs);
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            self.state.lock().unwrap().content = std::mem::take(&mut self.buf);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_with_auto_multipart() -> Result<()> {
+        let state = Arc::new(Mutex::new(MockWriteState::default()));
+        let srv = MockService::new(Capability {
+            write: true,
+            write_single_max_size: Some(4),
+            ..Default::default()
+        })
+        .with_write({
+            let state = state.clone();
+            move |_, args| {
+                future::ok((
+                    RpWrite::default(),
+                    MockWriter {
+                        content_length: args.content_length(),
+                        buf: Vec::new(),
+                        state: state.clone(),
+                    },
+                ))
+            }
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        op.write("small", "abcd").await?;
+        assert_eq!(state.lock().unwrap().writes, vec![4]);
+
+        state.lock().unwrap().writes.clear();
+        op.write("large", "Hello, World!").await?;
+        {
+            let state = state.lock().unwrap();
+            assert_eq!(state.content, b"Hello, World!");
+            assert_eq!(state.writes, vec![4, 4, 4, 1]);
+        }
+
+        let err = op
+            .write_with(
+                "large",
+                OpWrite::new().with_auto_multipart(false),
+                "Hello, World!",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "spill")]
+    #[derive(Debug, Default)]
+    struct MockFlakyWriteState {
+        failed_uploads: usize,
+        failed_closes: usize,
+        uploads: usize,
+        aborts: usize,
+        content: Vec<u8>,
+    }
+
+    /// Build a service that fails the first `failed_uploads` uploads with
+    /// a temporary error once more than `4 * upload` bytes have been written,
+    /// and fails the first `failed_closes` closes.
+    #[cfg(feature = "spill")]
+    fn mock_flaky_write_service(
+        failed_uploads: usize,
+        failed_closes: usize,
+    ) -> (MockService, Arc<Mutex<MockFlakyWriteState>>) {
+        let state = Arc::new(Mutex::new(MockFlakyWriteState {
+            failed_uploads,
+            failed_closes,
+            ..Default::default()
+        }));
+        let srv = MockService::new(Capability {
+            write: true,
+            write_without_content_length: true,
+            ..Default::default()
+        })
+        .with_write({
+            let state = state.clone();
+            move |_, _| {
+                let mut guard = state.lock().unwrap();
+                guard.uploads += 1;
+                future::ok((
+                    RpWrite::default(),
+                    MockFlakyWriter {
+                        upload: guard.uploads,
+                        fail: guard.uploads <= guard.failed_uploads,
+                        buf: Vec::new(),
+                        state: state.clone(),
+                    },
+                ))
+            }
+        });
+        (srv, state)
+    }
+
+    #[cfg(feature = "spill")]
+    struct MockFlakyWriter {
+        upload: usize,
+        fail: bool,
+        buf: Vec<u8>,
+        state: Arc<Mutex<MockFlakyWriteState>>,
+    }
+
+    #[cfg(feature = "spill")]
+    #[async_trait]
+    impl oio::Write for MockFlakyWriter {
+        async fn write(&mut self, bs: Bytes) -> Result<()> {
+            self.buf.extend_from_slice(&bs);
+            if self.fail && self.buf.len() > 4 * self.upload {
+                return Err(Error::new(ErrorKind::Unexpected, "connection reset").set_temporary());
+            }
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            self.state.lock().unwrap().aborts += 1;
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            let mut state = self.state.lock().unwrap();
+            if state.failed_closes > 0 {
+                state.failed_closes -= 1;
+                return Err(Error::new(ErrorKind::Unexpected, "connection reset").set_temporary());
+            }
+            state.content = std::mem::take(&mut self.buf);
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "spill")]
+    #[tokio::test]
+    async fn test_writer_with_spill() -> Result<()> {
+        let (srv, state) = mock_flaky_write_service(1, 0);
+        let op = OperatorBuilder::new(srv).finish();
+        let spill = std::env::temp_dir().join(format!("opendal-spill-{}", uuid::Uuid::new_v4()));
+
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_spill(&spill))
+            .await?;
+        w.write("abc").await?;
+        // The first upload fails here and is retried from spill.
+        w.write("def").await?;
+        w.write("ghi").await?;
+        w.close().await?;
+
+        let (content, uploads, aborts) = {
+            let state = state.lock().unwrap();
+            (state.content.clone(), state.uploads, state.aborts)
+        };
+        assert_eq!(content, b"abcdefghi");
+        assert_eq!(uploads, 2);
+        assert_eq!(aborts, 1);
+        assert!(!spill.exists(), "spill file must be removed");
+
+        // Every failure is retried from spill with the full retry times.
+        let (srv, state) = mock_flaky_write_service(4, 0);
+        let op = OperatorBuilder::new(srv).finish();
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_spill(&spill))
+            .await?;
+        for _ in 0..6 {
+            w.write("abc").await?;
+        }
+        w.close().await?;
+        let uploads = state.lock().unwrap().uploads;
+        assert_eq!(uploads, 5);
+
+        // Spill file is removed if writer is dropped without close.
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_spill(&spill))
+            .await?;
+        w.write("abc").await?;
+        assert!(spill.exists());
+        drop(w);
+        assert!(!spill.exists(), "spill file must be removed after drop");
+
+        // Close keeps failing after replays, the retry times are shared.
+        let (srv, state) = mock_flaky_write_service(0, usize::MAX);
+        let op = OperatorBuilder::new(srv).finish();
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_spill(&spill))
+            .await?;
+        w.write("abc").await?;
+        let err = w.close().await.unwrap_err();
+        assert!(err.is_temporary());
+        assert_eq!(state.lock().unwrap().uploads, 4);
+        w.abort().await?;
+        assert!(!spill.exists());
+
+        // Without spill, the error is returned.
+        let (srv, _) = mock_flaky_write_service(1, 0);
+        let op = OperatorBuilder::new(srv).finish();
+        let mut w = op.writer("file").await?;
+        w.write("abc").await?;
+        let err = w.write("def").await.unwrap_err();
+        assert!(err.is_temporary());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_with_dir_path() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        let errs = [
+            op.write("foo/bar/", "content").await.unwrap_err(),
+            op.writer("foo/bar/").await.err().unwrap(),
+            op.append("foo/bar/", "content").await.unwrap_err(),
+        ];
+        for err in errs {
+            assert_eq!(err.kind(), ErrorKind::IsADirectory);
+            assert!(
+                err.to_string().contains("must not end with `/`"),
+                "trailing slash must be explained: {err}"
+            );
+        }
+
+        Ok(())
+    }
+
+    struct MockPager(Option<Vec<oio::Entry>>);
+
+    #[async_trait]
+    impl oio::Page for MockPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.0.take())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_recent() -> Result<()> {
+        // Entries with last modified time in seconds.
+        let entries = [
+            ("dir/a", Some(30)),
+            ("dir/b", Some(10)),
+            ("dir/sub/", None),
+            ("dir/c", Some(50)),
+            ("dir/d", Some(20)),
+        ];
+        let srv = MockService::new(Capability {
+            list: true,
+            list_with_delimiter_slash: true,
+            ..Default::default()
+        })
+        .with_list(move |_, _| {
+            let entries = entries
+                .iter()
+                .map(|(path, secs)| {
+                    let mode = if path.ends_with('/') {
+                        EntryMode::DIR
+                    } else {
+                        EntryMode::FILE
+                    };
+                    let mut meta = Metadata::new(mode);
+                    if let Some(secs) = secs {
+                        let t = DateTime::<Utc>::from_utc(
+                            chrono::NaiveDateTime::from_timestamp_opt(*secs, 0).unwrap(),
+                            Utc,
+                        );
+                        meta = meta.with_last_modified(t);
+                    } else {
+                        let bit = meta.bit();
+                        meta = meta.with_bit(bit | Metakey::LastModified);
+                    }
+                    oio::Entry::new(path, meta)
+                })
+                .collect();
+            future::ok((RpList::default(), MockPager(Some(entries))))
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        let paths = |entries: Vec<Entry>| {
+            entries
+                .iter()
+                .map(|de| de.path().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let entries = op.list_recent("dir/", 3).await?;
+        assert_eq!(paths(entries), vec!["dir/c", "dir/a", "dir/d"]);
+
+        let entries = op.list_recent("dir/", 10).await?;
+        assert_eq!(
+            paths(entries),
+            vec!["dir/c", "dir/a", "dir/d", "dir/b", "dir/sub/"]
+        );
+
+        assert!(op.list_recent("dir/", 0).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_with_case_insensitive_prefix() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        for path in [
+            "reports/report-1.csv",
+            "reports/REPORT-2.csv",
+            "reports/Report-3.csv",
+            "reports/summary.csv",
+            "reports/rep.csv",
+        ] {
+            op.write(path, "data").await?;
+        }
+
+        let mut paths: Vec<String> = op
+            .list_with(
+                "reports/",
+                OpList::new()
+                    .with_prefix("Report")
+                    .with_case_insensitive_prefix(true),
+            )
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "reports/REPORT-2.csv",
+                "reports/Report-3.csv",
+                "reports/report-1.csv",
+            ]
+        );
+
+        // Case-sensitive by default.
+        let paths: Vec<String> = op
+            .list_with("reports/", OpList::new().with_prefix("Report"))
+            .await?
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await?;
+        assert_eq!(paths, vec!["reports/Report-3.csv"]);
+
+        Ok(())
+    }
+
+    /// SlowRead responds after `delay` and returns its content after
+    /// `body_delay`, or fails after `delay` if `fail` is set.
+    #[derive(Debug, Clone, Default)]
+    struct SlowRead {
+        delay: Duration,
+        body_delay: Duration,
+        fail: bool,
+        content: &'static str,
+    }
+
+    impl SlowRead {
+        /// Build an operator serving reads like this, with the count of
+        /// reads have been sent.
+        fn into_operator(self) -> (Operator, Arc<Mutex<usize>>) {
+            let reads = Arc::new(Mutex::new(0));
+            let srv = MockService::new(Capability {
+                read: true,
+                read_can_next: true,
+                ..Default::default()
+            })
+            .with_read({
+                let reads = reads.clone();
+                move |_, _| {
+                    *reads.lock().unwrap() += 1;
+                    let cfg = self.clone();
+                    async move {
+                        tokio::time::sleep(cfg.delay).await;
+                        if cfg.fail {
+                            return Err(Error::new(ErrorKind::Unexpected, "read failed"));
+                        }
+                        Ok((
+                            RpRead::new(cfg.content.len() as u64),
+                            SlowCursor {
+                                sleep: Box::pin(tokio::time::sleep(cfg.body_delay)),
+                                inner: oio::Cursor::from(Bytes::from(cfg.content)),
+                            },
+                        ))
+                    }
+                }
+            });
+            (OperatorBuilder::new(srv).finish(), reads)
+        }
+    }
+
+    struct SlowCursor {
+        sleep: Pin<Box<tokio::time::Sleep>>,
+        inner: oio::Cursor,
+    }
+
+    impl oio::Read for SlowCursor {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            futures::ready!(self.sleep.poll_unpin(cx));
+            self.inner.poll_read(cx, buf)
+        }
+
+        fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+            self.inner.poll_seek(cx, pos)
+        }
+
+        fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            futures::ready!(self.sleep.poll_unpin(cx));
+            self.inner.poll_next(cx)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hedged_read() -> Result<()> {
+        let read_to_end = |mut r: Reader| async move {
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs)
+                .await
+                .map_err(|err| Error::new(ErrorKind::Unexpected, "read failed").set_source(err))?;
+            Result::Ok(bs)
+        };
+
+        let (primary, primary_reads) = SlowRead {
+            delay: Duration::from_secs(10),
+            content: "primary",
+            ..Default::default()
+        }
+        .into_operator();
+        let (replica, replica_reads) = SlowRead {
+            delay: Duration::from_millis(10),
+            content: "replica",
+            ..Default::default()
+        }
+        .into_operator();
+
+        let r = primary
+            .hedged_read(
+                std::slice::from_ref(&replica),
+                "file",
+                OpRead::new(),
+                Duration::from_millis(50),
+            )
+            .await?;
+        assert_eq!(read_to_end(r).await?, b"replica");
+        assert_eq!(*primary_reads.lock().unwrap(), 1);
+        assert_eq!(*replica_reads.lock().unwrap(), 1);
+
+        // The hedge doesn't fire if primary responds in time.
+        let (fast, fast_reads) = SlowRead {
+            delay: Duration::from_millis(1),
+            content: "fast",
+            ..Default::default()
+        }
+        .into_operator();
+        let r = fast
+            .hedged_read(
+                std::slice::from_ref(&replica),
+                "file",
+                OpRead::new(),
+                Duration::from_secs(10),
+            )
+            .await?;
+        assert_eq!(read_to_end(r).await?, b"fast");
+        assert_eq!(*fast_reads.lock().unwrap(), 1);
+        assert_eq!(*replica_reads.lock().unwrap(), 1);
+
+        // Reads race on the first chunk of content instead of the response.
+        let (stalled, _) = SlowRead {
+            delay: Duration::from_millis(1),
+            body_delay: Duration::from_secs(10),
+            content: "stalled",
+            ..Default::default()
+        }
+        .into_operator();
+        let r = stalled
+            .hedged_read(
+                std::slice::from_ref(&replica),
+                "file",
+                OpRead::new(),
+                Duration::from_millis(50),
+            )
+            .await?;
+        assert_eq!(read_to_end(r).await?, b"replica");
+
+        // The next replica is sent immediately if a read failed while the
+        // hedge is in flight.
+        let (failing, _) = SlowRead {
+            delay: Duration::from_millis(1500),
+            fail: true,
+            ..Default::default()
+        }
+        .into_operator();
+        let (hanging, _) = SlowRead {
+            delay: Duration::from_secs(10),
+            ..Default::default()
+        }
+        .into_operator();
+        let start = tokio::time::Instant::now();
+        let r = failing
+            .hedged_read(
+                &[hanging, replica],
+                "file",
+                OpRead::new(),
+                Duration::from_secs(1),
+            )
+            .await?;
+        assert_eq!(read_to_end(r).await?, b"replica");
+        assert!(
+            start.elapsed() < Duration::from_millis(2300),
+            "next replica must not wait for another hedge: {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_list() -> Result<()> {
+        let hot = Operator::new(services::Memory::default())?.finish();
+        let cold = Operator::new(services::Memory::default())?.finish();
+
+        for p in ["dir/a", "dir/c", "dir/sub/x"] {
+            hot.write(p, "hot").await?;
+        }
+        for p in ["dir/b", "dir/c", "dir/d", "dir/sub/y"] {
+            cold.write(p, "cold").await?;
+        }
+
+        let entries: Vec<_> = hot
+            .merge_list(std::slice::from_ref(&cold), "dir/", OpList::new())
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c", "dir/d", "dir/sub/"]);
+
+        // Metadata not returned by list is queried from the operator the
+        // entry is listed from.
+        let mut lengths = Vec::new();
+        for e in entries.iter().filter(|e| e.path() != "dir/sub/") {
+            let meta = hot.metadata(e, Metakey::Complete).await?;
+            lengths.push((e.path(), meta.content_length()));
+        }
+        assert_eq!(
+            lengths,
+            vec![("dir/a", 3), ("dir/b", 4), ("dir/c", 3), ("dir/d", 4)]
+        );
+
+        let entries: Vec<_> = hot
+            .merge_list(&[cold], "dir/", OpList::new().with_delimiter(""))
+            .await?
+            .try_collect()
+            .await?;
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(
+            paths,
+            vec!["dir/a", "dir/b", "dir/c", "dir/d", "dir/sub/x", "dir/sub/y"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_walk() -> Result<()> {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+
+        let op = Operator::new(services::Memory::default())?.finish();
+        for i in 0..20 {
+            op.write(&format!("dir/{i}/file"), "data").await?;
+        }
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let visitor = |de: Entry| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            async move {
+                let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(n, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+
+                if de.path().ends_with("/7/file") {
+                    return Err(Error::new(ErrorKind::Unexpected, "visit failed"));
+                }
+                Ok(())
+            }
+        };
+
+        let err = op
+            .walk("dir/", OpList::new(), 4, visitor)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let max = max_running.load(Ordering::SeqCst);
+        assert!(max > 1 && max <= 4, "max running visitors: {max}");
+
+        let count = op
+            .walk("dir/", OpList::new(), 4, |_| async { Ok(()) })
+            .await?;
+        assert_eq!(count, 20);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_range_by_read() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        assert!(!op.info().capability().copy_with_range);
+
+        let content: Vec<u8> = (0..=255).collect();
+        op.write("src", content.clone()).await?;
+
+        op.copy_with(
+            "src",
+            "slice",
+            OpCopy::new().with_range(BytesRange::from(16..48)),
+        )
+        .await?;
+        assert_eq!(op.read("slice").await?, content[16..48]);
+
+        op.copy_with(
+            "src",
+            "suffix",
+            OpCopy::new().with_range(BytesRange::new(None, Some(10))),
+        )
+        .await?;
+        assert_eq!(op.read("suffix").await?, content[246..]);
+        assert_eq!(op.read("src").await?, content);
+
+        Ok(())
+    }
+
+    /// MockFailingWriter fails on write or close and counts aborts.
+    struct MockFailingWriter {
+        fail_close: bool,
+        aborts: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl oio::Write for MockFailingWriter {
+        async fn write(&mut self, _: Bytes) -> Result<()> {
+            if self.fail_close {
+                Ok(())
+            } else {
+                Err(Error::new(ErrorKind::Unexpected, "write failed"))
+            }
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            *self.aborts.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Err(Error::new(ErrorKind::Unexpected, "close failed"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_range_by_read_aborts_writer() -> Result<()> {
+        let aborts = Arc::new(Mutex::new(0));
+        let srv = MockService::new(Capability {
+            read: true,
+            read_can_seek: true,
+            read_can_next: true,
+            read_with_range: true,
+            write: true,
+            ..Default::default()
+        })
+        .with_read(|_, _| future::ok((RpRead::new(4), oio::Cursor::from(Bytes::from("data")))))
+        .with_write({
+            let aborts = aborts.clone();
+            move |path, _| {
+                future::ok((
+                    RpWrite::default(),
+                    MockFailingWriter {
+                        fail_close: path == "close_fail",
+                        aborts: aborts.clone(),
+                    },
+                ))
+            }
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        // Both failed write and close must abort the writer.
+        for to in ["write_fail", "close_fail"] {
+            let err = op
+                .copy_with("src", to, OpCopy::new().with_range(BytesRange::from(0..4)))
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{to}");
+        }
+        assert_eq!(*aborts.lock().unwrap(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_with_prune() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        for p in ["dir/a", "dir/skip/b", "dir/skip/deep/c", "dir/keep/d"] {
+            op.write(p, "data").await?;
+        }
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let v = visited.clone();
+        let entries: Vec<_> = op
+            .scan_with(
+                "dir/",
+                OpList::new().with_prune(move |dir| {
+                    v.lock().unwrap().push(dir.to_string());
+                    dir == "dir/skip/"
+                }),
+            )
+            .await?
+            .try_collect()
+            .await?;
+        let mut paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["dir/a", "dir/keep/d"]);
+        assert!(!visited
+            .lock()
+            .unwrap()
+            .contains(&"dir/skip/deep/".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_confirm() -> Result<()> {
+        // The deleted path keeps being reported as existing for `lag` stats.
+        let new_op = |lag: usize| {
+            let stats = Arc::new(Mutex::new(0));
+            let srv = MockService::new(Capability {
+                stat: true,
+                delete: true,
+                ..Default::default()
+            })
+            .with_stat({
+                let stats = stats.clone();
+                move |_, _| {
+                    let mut stats = stats.lock().unwrap();
+                    *stats += 1;
+                    future::ready(if *stats <= lag {
+                        Ok(RpStat::new(Metadata::new(EntryMode::FILE)))
+                    } else {
+                        Err(Error::new(ErrorKind::NotFound, "not found"))
+                    })
+                }
+            })
+            .with_delete(|_, _| future::ok(RpDelete::default()));
+            (OperatorBuilder::new(srv).finish(), stats)
+        };
+
+        let (op, stats) = new_op(3);
+        op.delete_with("file", OpDelete::new().with_confirm(true))
+            .await?;
+        assert_eq!(*stats.lock().unwrap(), 4);
+
+        // Deletes without confirm won't poll.
+        op.delete("file").await?;
+        assert_eq!(*stats.lock().unwrap(), 4);
+
+        let (op, _) = new_op(usize::MAX);
+        let err = op
+            .delete_with("file", OpDelete::new().with_confirm(true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_batched() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        for i in 0..7 {
+            op.write(&format!("dir/file-{i}"), "x").await?;
+        }
+
+        let batches: Vec<_> = op
+            .list_batched("dir/", OpList::new().with_delimiter(""), 3)
+            .await?
+            .try_collect()
+            .await?;
+        let sizes: Vec<_> = batches.iter().map(|v| v.len()).collect();
+        assert_eq!(sizes, vec![3, 3, 1]);
+
+        let mut paths: Vec<_> = batches
+            .into_iter()
+            .flatten()
+            .map(|e| e.path().to_string())
+            .collect();
+        paths.sort();
+        let expected: Vec<_> = (0..7).map(|i| format!("dir/file-{i}")).collect();
+        assert_eq!(paths, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prefix_digest() -> Result<()> {
+        let src = Operator::new(services::Memory::default())?.finish();
+        let dst = Operator::new(services::Memory::default())?.finish();
+        for (path, content) in [("a", "1"), ("dir/b", "22"), ("dir/c", "333")] {
+            src.write(&format!("data/{path}"), content).await?;
+            dst.write(&format!("backup/{path}"), content).await?;
+        }
+        // Files out of prefix are not included.
+        src.write("other", "x").await?;
+
+        let digest = src.prefix_digest("data/").await?;
+        assert_eq!(digest, dst.prefix_digest("backup/").await?);
+        assert_eq!(digest, src.prefix_digest("data/").await?);
+
+        dst.write("backup/dir/c", "4444").await?;
+        assert_ne!(digest, dst.prefix_digest("backup/").await?);
+
+        // Same content under another path.
+        dst.delete("backup/dir/c").await?;
+        dst.write("backup/dir/d", "333").await?;
+        assert_ne!(digest, dst.prefix_digest("backup/").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_versions_without_versioning() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        op.write("file", "v1").await?;
+        op.write("file", "v2").await?;
+        assert_eq!(op.delete_all_versions("file").await?, 1);
+        assert!(!op.is_exist("file").await?);
+        assert_eq!(op.delete_all_versions("file").await?, 0);
+
+        let err = op.delete_all_versions("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_range() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        let range = |offset| OpWriteRange::new(offset).with_part_size(4);
+
+        // Ranges are written out of order.
+        op.write_range_with("file", range(8), "ij").await?;
+        op.write_range_with("file", range(0), "abcd").await?;
+        op.write_range_with("file", range(4), "efgh").await?;
+        op.finalize_ranged_write("file").await?;
+        assert_eq!(op.read("file").await?, b"abcdefghij");
+        let parts: Vec<_> = op.list("file.ranges/").await?.try_collect().await?;
+        assert!(parts.is_empty());
+
+        let err = op
+            .write_range_with("file", range(2), "cd")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op
+            .write_range_with("file", range(0), "abcde")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        // Ranges must cover the object without gaps.
+        op.write_range_with("gap", range(0), "abcd").await?;
+        op.write_range_with("gap", range(8), "ij").await?;
+        let err = op.finalize_ranged_write("gap").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert!(!op.is_exist("gap").await?);
+
+        let err = op.finalize_ranged_write("empty").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    /// Build a zip archive with `(name, method, content, data)` members.
+    fn build_zip(members: &[(&str, u16, &[u8], &[u8])]) -> Vec<u8> {
+        let (mut buf, mut cd) = (Vec::new(), Vec::new());
+        for (name, method, content, data) in members {
+            let offset = buf.len() as u32;
+            let mut header = Vec::new();
+            header.extend_from_slice(&method.to_le_bytes());
+            header.extend_from_slice(&[0; 8]);
+            header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            header.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            header.extend_from_slice(&0u16.to_le_bytes());
+
+            buf.extend_from_slice(&0x04034b50u32.to_le_bytes());
+            buf.extend_from_slice(&[20, 0, 0, 0]);
+            buf.extend_from_slice(&header);
+            buf.extend_from_slice(name.as_bytes());
+            buf.extend_from_slice(data);
+
+            cd.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            cd.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            cd.extend_from_slice(&header);
+            cd.extend_from_slice(&[0; 10]);
+            cd.extend_from_slice(&offset.to_le_bytes());
+            cd.extend_from_slice(name.as_bytes());
+        }
+
+        let cd_offset = buf.len() as u32;
+        let entries = (members.len() as u16).to_le_bytes();
+        buf.extend_from_slice(&cd);
+        buf.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        buf.extend_from_slice(&[0; 4]);
+        buf.extend_from_slice(&entries);
+        buf.extend_from_slice(&entries);
+        buf.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&cd_offset.to_le_bytes());
+        buf.extend_from_slice(&[0; 2]);
+        buf
+    }
+
+    /// Append a tar entry with given type flag.
+    fn append_tar_entry(buf: &mut Vec<u8>, name: &str, typeflag: u8, data: &[u8]) {
+        let mut header = [0u8; 512];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+        header[136..147].copy_from_slice(b"00000000000");
+        header[156] = typeflag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|b| *b as u32).sum();
+        header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+
+        buf.extend_from_slice(&header);
+        buf.extend_from_slice(data);
+        buf.resize((buf.len() + 511) / 512 * 512, 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_archive_member() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        // Deflated `hello hello hello`.
+        let deflated = [0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00];
+        let zip = build_zip(&[
+            ("a.txt", 0, b"Hello, World!", b"Hello, World!"),
+            ("dir/b.txt", 8, b"hello hello hello", &deflated),
+        ]);
+        op.write("archive.zip", zip).await?;
+
+        let bs = op
+            .read_archive_member("archive.zip", "a.txt", ArchiveFormat::Zip)
+            .await?;
+        assert_eq!(bs, "Hello, World!");
+        let res = op
+            .read_archive_member("archive.zip", "dir/b.txt", ArchiveFormat::Zip)
+            .await;
+        #[cfg(feature = "archive-deflate")]
+        assert_eq!(res?, "hello hello hello");
+        #[cfg(not(feature = "archive-deflate"))]
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let long_name = format!("dir/{}.txt", "x".repeat(120));
+        let mut tar = Vec::new();
+        append_tar_entry(&mut tar, "./a.txt", b'0', b"Hello, World!");
+        append_tar_entry(&mut tar, "dir/", b'5', b"");
+        append_tar_entry(&mut tar, "././@LongLink", b'L', long_name.as_bytes());
+        append_tar_entry(&mut tar, "dir/xxx", b'0', b"long name");
+        append_tar_entry(&mut tar, "dir/b.txt", b'0', &[b'b'; 1000]);
+        tar.resize(tar.len() + 1024, 0);
+        op.write("archive.tar", tar).await?;
+
+        let bs = op
+            .read_archive_member("archive.tar", "a.txt", ArchiveFormat::Tar)
+            .await?;
+        assert_eq!(bs, "Hello, World!");
+        let bs = op
+            .read_archive_member("archive.tar", &long_name, ArchiveFormat::Tar)
+            .await?;
+        assert_eq!(bs, "long name");
+        let bs = op
+            .read_archive_member("archive.tar", "dir/b.txt", ArchiveFormat::Tar)
+            .await?;
+        assert_eq!(bs, vec![b'b'; 1000]);
+
+        // Metadata entries larger than the limit are rejected before reading.
+        let mut tar = Vec::new();
+        let huge_name = vec![b'x'; TAR_META_MAX_SIZE as usize + 1];
+        append_tar_entry(&mut tar, "././@LongLink", b'L', &huge_name);
+        append_tar_entry(&mut tar, "a.txt", b'0', b"Hello, World!");
+        op.write("huge_meta.tar", tar).await?;
+        let err = op
+            .read_archive_member("huge_meta.tar", "a.txt", ArchiveFormat::Tar)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        for (path, format) in [
+            ("archive.zip", ArchiveFormat::Zip),
+            ("archive.tar", ArchiveFormat::Tar),
+        ] {
+            let err = op
+                .read_archive_member(path, "not_exist", format)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::NotFound, "{path}");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_for_each_prefix() -> Result<()> {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+
+        let op = Operator::new(services::Memory::default())?.finish();
+        for path in ["a/1", "a/2", "b/1", "c/1", "d/1", "d/2", "e/1"] {
+            op.write(path, "data").await?;
+        }
+
+        let (running, peak, visited) = (
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+        );
+        let prefixes = ["a/", "b/", "c/", "d/", "e/", "file"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let mut errors = op
+            .for_each_prefix(prefixes, 2, |lister| async {
+                let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+
+                let entries: Vec<Entry> = lister.try_collect().await?;
+                visited.fetch_add(entries.len(), Ordering::SeqCst);
+                running.fetch_sub(1, Ordering::SeqCst);
+
+                if entries.iter().any(|e| e.path().starts_with("b/")) {
+                    return Err(Error::new(ErrorKind::Unexpected, "process failed"));
+                }
+                Ok(())
+            })
+            .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(visited.load(Ordering::SeqCst), 7);
+
+        errors.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, "b/");
+        assert_eq!(errors[0].1.kind(), ErrorKind::Unexpected);
+        assert_eq!(errors[1].0, "file");
+        assert_eq!(errors[1].1.kind(), ErrorKind::NotADirectory);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_cdc() -> Result<()> {
+        let op = Operator::new(services::Memory::default())?.finish();
+        let config = ChunkerConfig::new(16 * 1024);
+
+        let mut content = vec![0; 1024 * 1024];
+        StdRng::seed_from_u64(42).fill_bytes(&mut content);
+        let input = |bs: &[u8]| {
+            // Feed content in small pieces to make sure chunking doesn't
+            // depend on how the input is split.
+            let pieces: Vec<_> = bs
+                .chunks(10000)
+                .map(|v| Ok(Bytes::copy_from_slice(v)))
+                .collect();
+            stream::iter(pieces)
+        };
+
+        let chunks = op.write_cdc("chunks/", input(&content), config).await?;
+        assert!(chunks.len() > 1);
+        let mut restored = Vec::new();
+        for chunk in &chunks {
+            assert!(chunk.size() <= config.max_size() as u64);
+            restored.extend(op.read(chunk.path()).await?);
+        }
+        assert_eq!(restored, content);
+
+        // Identical content produces identical manifest.
+        let again = op.write_cdc("chunks/", input(&content), config).await?;
+        assert_eq!(again, chunks);
+
+        // A small edit only changes nearby chunks.
+        content[512 * 1024] ^= 0xff;
+        let edited = op.write_cdc("chunks/", input(&content), config).await?;
+        let changed = edited.iter().filter(|c| !chunks.contains(c)).count();
+        assert!(
+            (1..=2).contains(&changed),
+            "{changed} of {} chunks changed",
+            edited.len()
+        );
+
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockRenameState {
+        /// Etag of objects.
+        objects: std::collections::HashMap<String, String>,
+        /// Rewrite the source right before this operation.
+        rewrite_before: Option<Operation>,
+    }
+
+    impl MockRenameState {
+        fn check_source(
+            &mut self,
+            path: &str,
+            if_match: Option<&str>,
+            op: Operation,
+        ) -> Result<()> {
+            if self.rewrite_before == Some(op) {
+                self.objects
+                    .insert(path.to_string(), "rewritten".to_string());
+            }
+            match self.objects.get(path) {
+                None => Err(Error::new(ErrorKind::NotFound, "not found")),
+                Some(etag) if if_match.is_some() && if_match != Some(etag.as_str()) => {
+                    Err(Error::new(ErrorKind::ConditionNotMatch, "etag not match"))
+                }
+                Some(_) => Ok(()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_via_conditional_copy() -> Result<()> {
+        let new_op = |rewrite_before: Option<Operation>| {
+            let state = Arc::new(Mutex::new(MockRenameState {
+                objects: [("from".to_string(), "v1".to_string())].into(),
+                rewrite_before,
+            }));
+            let srv = MockService::new(Capability {
+                stat: true,
+                copy: true,
+                copy_with_if_match: true,
+                delete: true,
+                delete_with_if_match: true,
+                ..Default::default()
+            })
+            .with_stat({
+                let state = state.clone();
+                move |path, _| {
+                    let state = state.lock().unwrap();
+                    future::ready(
+                        state
+                            .objects
+                            .get(&path)
+                            .map(|etag| {
+                                RpStat::new(Metadata::new(EntryMode::FILE).with_etag(etag.clone()))
+                            })
+                            .ok_or_else(|| Error::new(ErrorKind::NotFound, "not found")),
+                    )
+                }
+            })
+            .with_copy({
+                let state = state.clone();
+                move |from, to, args| {
+                    let mut state = state.lock().unwrap();
+                    let res = state
+                        .check_source(&from, args.if_match(), Operation::Copy)
+                        .map(|_| {
+                            let etag = state.objects[&from].clone();
+                            state.objects.insert(to, etag);
+                            RpCopy::default()
+                        });
+                    future::ready(res)
+                }
+            })
+            .with_delete({
+                let state = state.clone();
+                move |path, args| {
+                    let mut state = state.lock().unwrap();
+                    let res = state
+                        .check_source(&path, args.if_match(), Operation::Delete)
+                        .map(|_| {
+                            state.objects.remove(&path);
+                            RpDelete::default()
+                        });
+                    future::ready(res)
+                }
+            });
+            (state, OperatorBuilder::new(srv).finish())
+        };
+
+        let args = || OpRename::new().with_conditional_copy(true);
+
+        // Emulation is not enabled by default.
+        let (mock, op) = new_op(None);
+        let err = op.rename("from", "to").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(mock.lock().unwrap().objects.len(), 1);
+
+        let (mock, op) = new_op(None);
+        op.rename_with("from", "to", args()).await?;
+        let objects = mock.lock().unwrap().objects.clone();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects["to"], "v1");
+
+        // Source rewritten before copy: nothing is changed except the source.
+        let (mock, op) = new_op(Some(Operation::Copy));
+        let err = op.rename_with("from", "to", args()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let objects = mock.lock().unwrap().objects.clone();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects["from"], "rewritten");
+
+        // Source rewritten before delete: the new content must be kept.
+        let (mock, op) = new_op(Some(Operation::Delete));
+        let err = op.rename_with("from", "to", args()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        let objects = mock.lock().unwrap().objects.clone();
+        assert_eq!(objects["from"], "rewritten");
+        assert_eq!(objects["to"], "v1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_append() -> Result<()> {
+        // Memory doesn't support append natively.
+        let op = Operator::new(services::Memory::default())?.finish();
+
+        op.write("exist", "Hello").await?;
+        assert_eq!(op.upsert_append("exist", ", World!").await?, 13);
+        assert_eq!(op.read("exist").await?, b"Hello, World!");
+
+        assert_eq!(op.upsert_append("not_exist", "Hello").await?, 5);
+        assert_eq!(op.upsert_append("not_exist", "Hello").await?, 10);
+        assert_eq!(op.read("not_exist").await?, b"HelloHello");
+
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockCasState {
+        /// Generation and content of objects.
+        objects: std::collections::HashMap<String, (i64, Bytes)>,
+        next_generation: i64,
+        conflicts: usize,
+    }
+
+    struct MockCasWriter {
+        path: String,
+        generation: Option<i64>,
+        buf: Vec<u8>,
+        state: Arc<Mutex<MockCasState>>,
+    }
+
+    #[async_trait]
+    impl oio::Write for MockCasWriter {
+        async fn write(&mut self, bs: Bytes) -> Result<()> {
+            self.buf.extend_from_slice(&bs);
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            let mut state = self.state.lock().unwrap();
+            let current = state.objects.get(&self.path).map_or(0, |(g, _)| *g);
+            if self.generation.is_some() && self.generation != Some(current) {
+                state.conflicts += 1;
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "generation not match",
+                ));
+            }
+
+            state.next_generation += 1;
+            let generation = state.next_generation;
+            let bs = Bytes::from(std::mem::take(&mut self.buf));
+            state.objects.insert(self.path.clone(), (generation, bs));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_json() -> Result<()> {
+        // Only write with if generation match is supported.
+        let state = Arc::new(Mutex::new(MockCasState::default()));
+        let srv = MockService::new(Capability {
+            stat: true,
+            read: true,
+            read_can_seek: true,
+            read_can_next: true,
+            write: true,
+            write_with_if_generation_match: true,
+            ..Default::default()
+        })
+        .with_stat({
+            let state = state.clone();
+            move |path, _| {
+                let state = state.lock().unwrap();
+                future::ready(
+                    state
+                        .objects
+                        .get(&path)
+                        .map(|(generation, bs)| {
+                            RpStat::new(
+                                Metadata::new(EntryMode::FILE)
+                                    .with_content_length(bs.len() as u64)
+                                    .with_generation(*generation),
+                            )
+                        })
+                        .ok_or_else(|| Error::new(ErrorKind::NotFound, "not found")),
+                )
+            }
+        })
+        .with_read({
+            let state = state.clone();
+            move |path, _| {
+                let state = state.clone();
+                async move {
+                    // Let other merges run between stat and write.
+                    tokio::task::yield_now().await;
+
+                    let state = state.lock().unwrap();
+                    let (_, bs) = state
+                        .objects
+                        .get(&path)
+                        .ok_or_else(|| Error::new(ErrorKind::NotFound, "not found"))?;
+                    Ok((RpRead::new(bs.len() as u64), oio::Cursor::from(bs.clone())))
+                }
+            }
+        })
+        .with_write({
+            let state = state.clone();
+            move |path, args| {
+                future::ok((
+                    RpWrite::default(),
+                    MockCasWriter {
+                        path,
+                        generation: args.if_generation_match(),
+                        buf: Vec::new(),
+                        state: state.clone(),
+                    },
+                ))
+            }
+        });
+        let op = OperatorBuilder::new(srv).finish();
+
+        let tasks = (0..5).map(|i| {
+            let op = op.clone();
+            async move {
+                op.merge_json(
+                    "config.json",
+                    serde_json::json!({"features": {format!("f{i}"): true}, "log": [i]}),
+                    MergeStrategy::ArrayAppend,
+                )
+                .await
+            }
+        });
+        for res in future::join_all(tasks).await {
+            res?;
+        }
+        assert!(state.lock().unwrap().conflicts > 0);
+
+        // No updates are lost.
+        let merged: serde_json::Value =
+            serde_json::from_slice(&op.read("config.json").await?).expect("must be valid json");
+        for i in 0..5 {
+            assert_eq!(merged["features"][format!("f{i}")], true);
+        }
+        let mut log: Vec<_> = merged["log"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_i64().unwrap())
+            .collect();
+        log.sort();
+        assert_eq!(log, vec![0, 1, 2, 3, 4]);
+
+        Ok(())
     }
 }
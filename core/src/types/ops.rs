@@ -19,9 +19,21 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::fmt::Debug;
+use std::fmt::Formatter;
+#[cfg(feature = "spill")]
+use std::path::Path;
+#[cfg(feature = "spill")]
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
+use flagset::FlagSet;
+
 use crate::raw::*;
+use crate::*;
 
 /// Args for `create` operation.
 ///
@@ -40,17 +52,71 @@ impl OpCreateDir {
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
-pub struct OpDelete {}
+pub struct OpDelete {
+    bypass_governance: bool,
+    if_match: Option<String>,
+    confirm: bool,
+}
 
 impl OpDelete {
     /// Create a new `OpDelete`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set whether to bypass the governance-mode retention of the object.
+    ///
+    /// This allows privileged callers to delete objects locked in
+    /// governance mode. Services without object lock return `Unsupported`.
+    pub fn with_bypass_governance(mut self, bypass: bool) -> Self {
+        self.bypass_governance = bypass;
+        self
+    }
+
+    /// Get whether to bypass the governance-mode retention of the object.
+    pub fn bypass_governance(&self) -> bool {
+        self.bypass_governance
+    }
+
+    /// Only delete the object if its etag matches the given one.
+    ///
+    /// Services return [`ErrorKind::ConditionNotMatch`] if the object
+    /// has been changed.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`]: crate::ErrorKind::ConditionNotMatch
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get the if_match of delete.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Wait until the deleted path is confirmed gone.
+    ///
+    /// With this option enabled, `delete` will poll the path with bounded
+    /// backoff until it doesn't exist anymore, and returns
+    /// [`ErrorKind::Unexpected`] if it's still there after all retries.
+    ///
+    /// This adds latency to every delete and only makes sense on services
+    /// that don't guarantee read-after-delete consistency.
+    ///
+    /// [`ErrorKind::Unexpected`]: crate::ErrorKind::Unexpected
+    pub fn with_confirm(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
+    /// Get whether to confirm the path is gone after delete.
+    pub fn confirm(&self) -> bool {
+        self.confirm
     }
 }
 
 /// Args for `list` operation.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OpList {
     /// The limit passed to underlying service to specify the max results
     /// that could return.
@@ -62,6 +128,40 @@ pub struct OpList {
 
     /// The delimiter used to for the list operation. Default to be `/`
     delimiter: String,
+
+    /// The key prefix relative to the listing path.
+    prefix: Option<String>,
+
+    /// Whether to match the prefix case-insensitively.
+    case_insensitive_prefix: bool,
+
+    /// Whether to yield the entry of listing path itself.
+    include_self: bool,
+
+    /// How to handle keys reappeared in list, and the window of tracked keys.
+    consistency_check: Option<(ListConsistency, usize)>,
+
+    /// The predicate to decide whether to skip the contents of a dir.
+    prune: Option<ListPrune>,
+}
+
+/// ListPrune is the predicate to decide whether to skip the contents of a
+/// dir while listing.
+pub type ListPrune = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+impl Debug for OpList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpList")
+            .field("limit", &self.limit)
+            .field("start_after", &self.start_after)
+            .field("delimiter", &self.delimiter)
+            .field("prefix", &self.prefix)
+            .field("case_insensitive_prefix", &self.case_insensitive_prefix)
+            .field("include_self", &self.include_self)
+            .field("consistency_check", &self.consistency_check)
+            .field("prune", &self.prune.is_some())
+            .finish()
+    }
 }
 
 impl Default for OpList {
@@ -70,6 +170,11 @@ impl Default for OpList {
             limit: None,
             start_after: None,
             delimiter: "/".to_string(),
+            prefix: None,
+            case_insensitive_prefix: false,
+            include_self: false,
+            consistency_check: None,
+            prune: None,
         }
     }
 }
@@ -92,6 +197,13 @@ impl OpList {
     }
 
     /// Change the start_after of this list operation.
+    ///
+    /// Only entries whose path is greater than `start_after` will be
+    /// returned. `start_after` is a full path like `dir/to/file`, so a
+    /// listing can be resumed from a known entry without the original
+    /// continuation token.
+    ///
+    /// Services without native support will have entries filtered instead.
     pub fn with_start_after(mut self, start_after: &str) -> Self {
         self.start_after = Some(start_after.into());
         self
@@ -112,6 +224,128 @@ impl OpList {
     pub fn delimiter(&self) -> &str {
         &self.delimiter
     }
+
+    /// Change the prefix of this list operation.
+    ///
+    /// The prefix is relative to the listing path and doesn't need to be a
+    /// dir. For example, listing `reports/` with prefix `2024-01` will only
+    /// return entries whose path starts with `reports/2024-01`.
+    ///
+    /// # Notes
+    ///
+    /// The delimiter will be applied after the prefix:
+    ///
+    /// - With delimiter `/`, `reports/2024-01-01.csv` and `reports/2024-01/`
+    ///   will be returned, but not `reports/2024-01/01.csv`.
+    /// - With empty delimiter, all files starting with the prefix will be
+    ///   returned, including `reports/2024-01/01.csv`.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Get the prefix of list operation.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Change whether to match the prefix case-insensitively.
+    ///
+    /// With it enabled, listing `reports/` with prefix `Report` will return
+    /// both `reports/report-1.csv` and `reports/REPORT-2.csv`.
+    ///
+    /// # Notes
+    ///
+    /// Services can't filter entries case-insensitively. The prefix is
+    /// matched at client side, only the leading part of it without any
+    /// cased characters (like `2024-` of `2024-Report`) will be sent to
+    /// services. So the list could fetch far more entries than it returns.
+    pub fn with_case_insensitive_prefix(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive_prefix = case_insensitive;
+        self
+    }
+
+    /// Get whether to match the prefix case-insensitively.
+    pub fn case_insensitive_prefix(&self) -> bool {
+        self.case_insensitive_prefix
+    }
+
+    /// Cut the prefix before the first cased character so that it can be
+    /// sent to services for case-insensitive listing.
+    pub(crate) fn with_caseless_prefix(mut self) -> Self {
+        if let Some(prefix) = &mut self.prefix {
+            let idx = prefix
+                .find(|c: char| c.is_lowercase() || c.is_uppercase())
+                .unwrap_or(prefix.len());
+            prefix.truncate(idx);
+            if prefix.is_empty() {
+                self.prefix = None;
+            }
+        }
+        self
+    }
+
+    /// Change whether to include the listing path itself.
+    ///
+    /// If enabled, the listing path will be yielded as a dir entry exactly
+    /// once as the first entry, no matter whether the directory marker
+    /// exists in storage. The dir must exist, either as a marker or by
+    /// containing entries, otherwise nothing is yielded for it. For
+    /// listing without delimiter, only the listing path itself is added,
+    /// sub directories are returned as before.
+    pub fn with_include_self(mut self, include_self: bool) -> Self {
+        self.include_self = include_self;
+        self
+    }
+
+    /// Get whether to include the listing path itself.
+    pub fn include_self(&self) -> bool {
+        self.include_self
+    }
+
+    /// Check keys returned by list for pagination inconsistencies.
+    ///
+    /// On eventually consistent services, a key could be returned twice
+    /// across page boundaries. The last `window` keys are tracked, keys
+    /// that reappear within the window will be handled by `mode`, see
+    /// [`ListConsistency`] for details.
+    ///
+    /// # Notes
+    ///
+    /// - Tracking costs about `window * (key_len + 48)` bytes of memory.
+    /// - Keys reappeared out of the window or skipped keys can't be detected.
+    /// - Only applied by [`Operator::list_with`].
+    pub fn with_consistency_check(mut self, mode: ListConsistency, window: usize) -> Self {
+        self.consistency_check = Some((mode, window.max(1)));
+        self
+    }
+
+    /// Get the consistency check mode and window of list.
+    pub fn consistency_check(&self) -> Option<(ListConsistency, usize)> {
+        self.consistency_check
+    }
+
+    /// Prune dirs that the predicate returns `true` for while listing.
+    ///
+    /// The predicate is called with the full path of dirs like `dir/to/`.
+    /// A pruned dir will still be returned, but nothing under it will be.
+    ///
+    /// # Notes
+    ///
+    /// - Only makes difference for list without delimiter, dirs are never
+    ///   expanded while listing with delimiter `/`.
+    /// - Services that list without delimiter natively will still fetch
+    ///   keys under pruned dirs, they are filtered at client side. The
+    ///   pruned dirs will not be listed at all on other services.
+    pub fn with_prune(mut self, prune: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.prune = Some(Arc::new(prune));
+        self
+    }
+
+    /// Get the prune predicate of list.
+    pub fn prune(&self) -> Option<&ListPrune> {
+        self.prune.as_ref()
+    }
 }
 
 /// Args for `presign` operation.
@@ -174,6 +408,257 @@ impl From<OpWrite> for PresignOperation {
     }
 }
 
+/// Args for `select` operation.
+///
+/// OpSelect carries a SQL expression which will be evaluated by
+/// services against the content of the object, only the selected
+/// records will be returned.
+#[derive(Debug, Clone)]
+pub struct OpSelect {
+    expression: String,
+    input: SelectInput,
+    output: SelectOutput,
+}
+
+impl OpSelect {
+    /// Create a new `OpSelect`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::ops::OpSelect;
+    /// use opendal::ops::SelectInput;
+    /// use opendal::ops::SelectOutput;
+    ///
+    /// let _ = OpSelect::new(
+    ///     "SELECT * FROM S3Object s WHERE s.age > '18'",
+    ///     SelectInput::Csv { has_header: true },
+    ///     SelectOutput::Csv,
+    /// );
+    /// ```
+    pub fn new(expression: &str, input: SelectInput, output: SelectOutput) -> Self {
+        Self {
+            expression: expression.to_string(),
+            input,
+            output,
+        }
+    }
+
+    /// Get the SQL expression of select query.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Get the input format of select query.
+    pub fn input(&self) -> SelectInput {
+        self.input
+    }
+
+    /// Get the output format of select query.
+    pub fn output(&self) -> SelectOutput {
+        self.output
+    }
+}
+
+/// The format of object content used in select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectInput {
+    /// Content is in CSV format.
+    Csv {
+        /// Whether the first line is the header which can be used in
+        /// expression as column names.
+        has_header: bool,
+    },
+    /// Content is in JSON format.
+    Json {
+        /// Whether the content is JSON lines instead of one JSON document.
+        lines: bool,
+    },
+    /// Content is in Parquet format.
+    Parquet,
+}
+
+/// The format of selected records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectOutput {
+    /// Return records in CSV format.
+    Csv,
+    /// Return records in JSON lines format.
+    Json,
+}
+
+/// Args for `retention` operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OpRetention {
+    /// Get the retention of the object.
+    Get,
+    /// Set the retention of the object.
+    Set(ObjectRetention),
+}
+
+/// Args for `legal_hold` operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OpLegalHold {
+    /// Get the legal hold status of the object.
+    Get,
+    /// Turn on or off the legal hold of the object.
+    Set(bool),
+}
+
+/// The mode of object retention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RetentionMode {
+    /// Object can't be deleted or overwritten before retain until date
+    /// unless users have the permission to bypass governance.
+    Governance,
+    /// Object can't be deleted or overwritten by any user before retain
+    /// until date.
+    Compliance,
+}
+
+/// ObjectRetention is the retention setting of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectRetention {
+    mode: RetentionMode,
+    retain_until: DateTime<Utc>,
+}
+
+impl ObjectRetention {
+    /// Create a new object retention.
+    pub fn new(mode: RetentionMode, retain_until: DateTime<Utc>) -> Self {
+        Self { mode, retain_until }
+    }
+
+    /// Get the mode of retention.
+    pub fn mode(&self) -> RetentionMode {
+        self.mode
+    }
+
+    /// Get the date until which the object is retained.
+    pub fn retain_until(&self) -> DateTime<Utc> {
+        self.retain_until
+    }
+}
+
+/// Args for `acl` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpAcl {}
+
+impl OpAcl {
+    /// Create a new `OpAcl`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The default part size of ranged write.
+///
+/// With at most 10000 parts, the final object could be up to 80 GiB.
+pub const WRITE_RANGE_DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Args for `write_range` operation.
+///
+/// Every range is uploaded as a part of the final object, so ranges must
+/// be aligned with part size:
+///
+/// - `offset` must be a multiple of `part_size`.
+/// - Every range MUST be exactly `part_size` long except the last one.
+///
+/// All ranges of the same object must use the same `part_size`.
+#[derive(Debug, Clone)]
+pub struct OpWriteRange {
+    offset: u64,
+    part_size: u64,
+}
+
+impl OpWriteRange {
+    /// Create a new `OpWriteRange` for range starts at `offset`.
+    pub fn new(offset: u64) -> Self {
+        Self {
+            offset,
+            part_size: WRITE_RANGE_DEFAULT_PART_SIZE,
+        }
+    }
+
+    /// Set the part size of this ranged write.
+    ///
+    /// Services could have a minimum part size, for example, s3 requires
+    /// parts except the last one to be at least 5 MiB.
+    pub fn with_part_size(mut self, part_size: u64) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    /// Get the offset of this range.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Get the part size of this ranged write.
+    pub fn part_size(&self) -> u64 {
+        self.part_size
+    }
+
+    /// Get the index of the part this range belongs to, starts from 0.
+    pub fn part_index(&self) -> u64 {
+        self.offset / self.part_size
+    }
+}
+
+/// Args for `finalize_ranged_write` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpFinalizeRangedWrite {}
+
+impl OpFinalizeRangedWrite {
+    /// Create a new `OpFinalizeRangedWrite`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `delete_all_versions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpDeleteAllVersions {}
+
+impl OpDeleteAllVersions {
+    /// Create a new `OpDeleteAllVersions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `list_versions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListVersions {
+    exact: bool,
+}
+
+impl OpListVersions {
+    /// Create a new `OpListVersions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only list versions of the exact path instead of all paths under it.
+    ///
+    /// Services can stop listing as soon as all versions of the path
+    /// have been returned.
+    pub fn with_exact(mut self, exact: bool) -> Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Get whether to list versions of the exact path only.
+    pub fn exact(&self) -> bool {
+        self.exact
+    }
+}
+
 /// Args for `batch` operation.
 #[derive(Debug, Clone)]
 pub struct OpBatch {
@@ -229,6 +714,10 @@ pub struct OpRead {
     if_none_match: Option<String>,
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
+    first_byte_timeout: Option<Duration>,
+    size_hint: Option<u64>,
+    clamp_range: bool,
+    version: Option<String>,
 }
 
 impl OpRead {
@@ -283,6 +772,14 @@ impl OpRead {
     }
 
     /// Set the If-None-Match of the option
+    ///
+    /// If the etag of object matches, read returns
+    /// [`ErrorKind::ConditionNotMatch`] without transferring the content,
+    /// which is useful to revalidate cached content. Services without
+    /// [`Capability::read_with_if_none_match`] return `Unsupported`.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`]: crate::ErrorKind::ConditionNotMatch
+    /// [`Capability::read_with_if_none_match`]: crate::Capability::read_with_if_none_match
     pub fn with_if_none_match(mut self, if_none_match: &str) -> Self {
         self.if_none_match = Some(if_none_match.to_string());
         self
@@ -292,13 +789,105 @@ impl OpRead {
     pub fn if_none_match(&self) -> Option<&str> {
         self.if_none_match.as_deref()
     }
+
+    /// Set the timeout of waiting for the first byte.
+    ///
+    /// The timeout starts when the read request is sent. If no data has
+    /// been returned by the reader in time, read will fail with a temporary
+    /// [`ErrorKind::Unexpected`] error. Once the first byte arrives, the
+    /// reader will never time out no matter how slow the rest are.
+    ///
+    /// This timeout only applies to async reads.
+    ///
+    /// [`ErrorKind::Unexpected`]: crate::ErrorKind::Unexpected
+    pub fn with_first_byte_timeout(mut self, timeout: Duration) -> Self {
+        self.first_byte_timeout = Some(timeout);
+        self
+    }
+
+    /// Get the timeout of waiting for the first byte.
+    pub fn first_byte_timeout(&self) -> Option<Duration> {
+        self.first_byte_timeout
+    }
+
+    /// Set the size hint of the content to read.
+    ///
+    /// Reading the whole content into memory will allocate the buffer with
+    /// this capacity. Without a hint, the content length returned by the
+    /// service is used. Both are capped to 16 MiB to avoid a huge allocation
+    /// caused by a bogus value, the buffer will grow while reading if needed.
+    pub fn with_size_hint(mut self, size: u64) -> Self {
+        self.size_hint = Some(size);
+        self
+    }
+
+    /// Get the size hint of the content to read.
+    pub fn size_hint(&self) -> Option<u64> {
+        self.size_hint
+    }
+
+    /// Set whether to clamp the range to the current size of object.
+    ///
+    /// Object could shrink after the range is computed, for example, logs
+    /// get truncated or rotated. By default, reading such a range fails with
+    /// [`ErrorKind::RangeNotSatisfied`]. With this option enabled, the range
+    /// will be clamped to the current size of object learned by `stat`, and
+    /// read returns whatever bytes exist in the range instead:
+    ///
+    /// - `offset..offset + size` reads `offset..min(offset + size, len)`, and
+    ///   nothing if `offset >= len`.
+    /// - Suffix range `-size` reads the last `min(size, len)` bytes, and
+    ///   nothing if object is empty.
+    ///
+    /// This option only applies to async reads.
+    ///
+    /// [`ErrorKind::RangeNotSatisfied`]: crate::ErrorKind::RangeNotSatisfied
+    pub fn with_clamp_range(mut self, clamp: bool) -> Self {
+        self.clamp_range = clamp;
+        self
+    }
+
+    /// Get whether to clamp the range to the current size of object.
+    pub fn clamp_range(&self) -> bool {
+        self.clamp_range
+    }
+
+    /// Set the version of object to read.
+    ///
+    /// Versions are returned by [`Accessor::list_versions`]. Services
+    /// without [`Capability::read_with_version`] return `Unsupported`.
+    ///
+    /// [`Accessor::list_versions`]: crate::raw::Accessor::list_versions
+    /// [`Capability::read_with_version`]: crate::Capability::read_with_version
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get the version of object to read.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
 }
 
 /// Args for `stat` operation.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct OpStat {
     if_match: Option<String>,
     if_none_match: Option<String>,
+    metakey: FlagSet<Metakey>,
+    version: Option<String>,
+}
+
+impl Default for OpStat {
+    fn default() -> Self {
+        Self {
+            if_match: None,
+            if_none_match: None,
+            metakey: Metakey::Complete.into(),
+            version: None,
+        }
+    }
 }
 
 impl OpStat {
@@ -307,6 +896,23 @@ impl OpStat {
         Self::default()
     }
 
+    /// Set the metakey of the option.
+    ///
+    /// Services could pick the cheapest request that satisfies the
+    /// requested keys. Other metadata may still be returned, but users
+    /// should not rely on it.
+    ///
+    /// By default, [`Metakey::Complete`] is requested.
+    pub fn with_metakey(mut self, metakey: impl Into<FlagSet<Metakey>>) -> Self {
+        self.metakey = metakey.into();
+        self
+    }
+
+    /// Get metakey from option.
+    pub fn metakey(&self) -> FlagSet<Metakey> {
+        self.metakey
+    }
+
     /// Set the If-Match of the option
     pub fn with_if_match(mut self, if_match: &str) -> Self {
         self.if_match = Some(if_match.to_string());
@@ -328,15 +934,104 @@ impl OpStat {
     pub fn if_none_match(&self) -> Option<&str> {
         self.if_none_match.as_deref()
     }
+
+    /// Set the version of object to stat.
+    ///
+    /// Services without [`Capability::stat_with_version`] return
+    /// `Unsupported`.
+    ///
+    /// [`Capability::stat_with_version`]: crate::Capability::stat_with_version
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get the version of object to stat.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
 }
 
 /// Args for `write` operation.
-#[derive(Debug, Clone, Default)]
+#[derive(Clone)]
 pub struct OpWrite {
     content_length: Option<u64>,
     content_type: Option<String>,
     content_disposition: Option<String>,
     cache_control: Option<String>,
+    content_language: Option<String>,
+    http_headers: Vec<(String, String)>,
+    idempotent: bool,
+    require_parent: bool,
+    offset: Option<u64>,
+    if_generation_match: Option<i64>,
+    auto_multipart: bool,
+    expire: Option<Duration>,
+    decompressed_size: Option<u64>,
+    skip_if_identical: bool,
+    resume_parts: Vec<PartInfo>,
+    part_callback: Option<PartCallback>,
+    adaptive_parts: bool,
+    blob_type: Option<BlobType>,
+    #[cfg(feature = "spill")]
+    spill: Option<PathBuf>,
+}
+
+/// PartCallback is the callback invoked after a multipart part has been
+/// uploaded.
+pub type PartCallback = Arc<dyn Fn(&PartInfo) + Send + Sync>;
+
+impl Debug for OpWrite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("OpWrite");
+        f.field("content_length", &self.content_length)
+            .field("content_type", &self.content_type)
+            .field("content_disposition", &self.content_disposition)
+            .field("cache_control", &self.cache_control)
+            .field("content_language", &self.content_language)
+            .field("http_headers", &self.http_headers)
+            .field("idempotent", &self.idempotent)
+            .field("require_parent", &self.require_parent)
+            .field("offset", &self.offset)
+            .field("if_generation_match", &self.if_generation_match)
+            .field("auto_multipart", &self.auto_multipart)
+            .field("expire", &self.expire)
+            .field("skip_if_identical", &self.skip_if_identical)
+            .field("resume_parts", &self.resume_parts)
+            .field("part_callback", &self.part_callback.is_some())
+            .field("adaptive_parts", &self.adaptive_parts)
+            .field("blob_type", &self.blob_type);
+        #[cfg(feature = "spill")]
+        f.field("spill", &self.spill);
+        f.finish()
+    }
+}
+
+impl Default for OpWrite {
+    fn default() -> Self {
+        Self {
+            content_length: None,
+            content_type: None,
+            content_disposition: None,
+            cache_control: None,
+            content_language: None,
+            http_headers: vec![],
+            idempotent: false,
+            require_parent: false,
+            offset: None,
+            if_generation_match: None,
+            auto_multipart: true,
+            expire: None,
+            decompressed_size: None,
+            skip_if_identical: false,
+            resume_parts: vec![],
+            part_callback: None,
+            adaptive_parts: false,
+            blob_type: None,
+            #[cfg(feature = "spill")]
+            spill: None,
+        }
+    }
 }
 
 impl OpWrite {
@@ -363,6 +1058,33 @@ impl OpWrite {
         self
     }
 
+    /// Clear the content length of op.
+    ///
+    /// Used by layers that change the content, whose size can't be known
+    /// before all content has been written.
+    #[cfg(feature = "layers-compression")]
+    pub(crate) fn without_content_length(mut self) -> Self {
+        self.content_length = None;
+        self
+    }
+
+    /// Check if this write is idempotent.
+    pub fn idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    /// Mark this write as idempotent.
+    ///
+    /// An idempotent write can be replayed without duplicating data, for
+    /// example, writing the whole content in one shot. [`RetryLayer`] will
+    /// only retry writes which are idempotent by default.
+    ///
+    /// [`RetryLayer`]: crate::layers::RetryLayer
+    pub fn with_idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
     /// Get the content type from option
     pub fn content_type(&self) -> Option<&str> {
         self.content_type.as_deref()
@@ -395,6 +1117,327 @@ impl OpWrite {
         self.cache_control = Some(cache_control.to_string());
         self
     }
+
+    /// Get the content language from option
+    pub fn content_language(&self) -> Option<&str> {
+        self.content_language.as_deref()
+    }
+
+    /// Set the content language of option
+    ///
+    /// Only services with [`Capability::write_with_content_language`]
+    /// support this, others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Capability::write_with_content_language`]: crate::Capability::write_with_content_language
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_content_language(mut self, content_language: &str) -> Self {
+        self.content_language = Some(content_language.to_string());
+        self
+    }
+
+    /// Get the extra http headers from option
+    pub fn http_headers(&self) -> &[(String, String)] {
+        &self.http_headers
+    }
+
+    /// Store a standard http header that the typed args don't cover with
+    /// the object.
+    ///
+    /// Only headers that services store with objects are allowed:
+    /// `Cache-Control`, `Content-Disposition`, `Content-Encoding`,
+    /// `Content-Language`, `Content-Type` and `Expires`. They override
+    /// the typed args if both are set. Other headers, like hop-by-hop
+    /// headers (`Connection`, `Transfer-Encoding`...) or headers controlling
+    /// the request (`Content-Length`, `Authorization`...), will be rejected
+    /// with [`ErrorKind::ConfigInvalid`].
+    ///
+    /// Only services with [`Capability::write_with_http_header`] support
+    /// this, others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Capability::write_with_http_header`]: crate::Capability::write_with_http_header
+    /// [`ErrorKind::ConfigInvalid`]: crate::ErrorKind::ConfigInvalid
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_http_header(mut self, name: &str, value: &str) -> Self {
+        self.http_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Check if the parent directory is required to exist.
+    pub fn require_parent(&self) -> bool {
+        self.require_parent
+    }
+
+    /// Require the parent directory to exist before writing.
+    ///
+    /// If enabled, operator will `stat` the parent directory first and return
+    /// [`ErrorKind::NotFound`] if it doesn't exist. The check relies on the
+    /// service's `stat` of the parent dir path: s3 reports a dir path without
+    /// directory marker (`HEAD` returns 404) as an existing dir, and kv services
+    /// treat every dir as existing, so they always pass the check.
+    ///
+    /// Default to `false` which allows writing to any path.
+    ///
+    /// [`ErrorKind::NotFound`]: crate::ErrorKind::NotFound
+    pub fn with_require_parent(mut self, require_parent: bool) -> Self {
+        self.require_parent = require_parent;
+        self
+    }
+
+    /// Get the offset to write at.
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Write at the given offset of an existing file instead of replacing it.
+    ///
+    /// Content outside the written region will be kept. Writing beyond the
+    /// end of file will extend it, the gap may be filled with zeros.
+    ///
+    /// Only services with [`Capability::write_can_positional`] support it,
+    /// others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Capability::write_can_positional`]: crate::Capability::write_can_positional
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Check if write could switch to multipart automatically.
+    pub fn auto_multipart(&self) -> bool {
+        self.auto_multipart
+    }
+
+    /// Allow [`Operator::write_with`] to switch to multipart upload if the
+    /// content is larger than [`Capability::write_single_max_size`].
+    ///
+    /// Default to `true`. Disable it to send the content in one request
+    /// anyway and let the service reject it.
+    ///
+    /// [`Operator::write_with`]: crate::Operator::write_with
+    /// [`Capability::write_single_max_size`]: crate::Capability::write_single_max_size
+    pub fn with_auto_multipart(mut self, auto_multipart: bool) -> Self {
+        self.auto_multipart = auto_multipart;
+        self
+    }
+
+    /// Get the generation that the existing object must match.
+    pub fn if_generation_match(&self) -> Option<i64> {
+        self.if_generation_match
+    }
+
+    /// Only write if the existing object's generation matches the given one.
+    ///
+    /// The generation is returned by [`Metadata::generation`]. Use `0` to
+    /// write only if the object doesn't exist. Write will fail with
+    /// [`ErrorKind::ConditionNotMatch`] if the object has been changed.
+    ///
+    /// Only services with [`Capability::write_with_if_generation_match`]
+    /// (like `gcs`) support this, others will return
+    /// [`ErrorKind::Unsupported`].
+    ///
+    /// [`Metadata::generation`]: crate::Metadata::generation
+    /// [`Capability::write_with_if_generation_match`]: crate::Capability::write_with_if_generation_match
+    /// [`ErrorKind::ConditionNotMatch`]: crate::ErrorKind::ConditionNotMatch
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_if_generation_match(mut self, generation: i64) -> Self {
+        self.if_generation_match = Some(generation);
+        self
+    }
+
+    /// Get the duration after which the written object expires.
+    pub fn expire(&self) -> Option<Duration> {
+        self.expire
+    }
+
+    /// Make the written object expire after the given duration.
+    ///
+    /// The expiry time is decided by services when the write completes,
+    /// and can be fetched via [`Metadata::expires_at`].
+    ///
+    /// Expiration is best-effort, don't rely on it for access control.
+    /// Services like `memory`, `dashmap` and `moka` remove expired objects
+    /// lazily with millisecond precision: an expired object can't be read
+    /// or stat, but it could still show up in list until it's accessed.
+    ///
+    /// Only services with [`Capability::write_with_expire`] support this,
+    /// others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Metadata::expires_at`]: crate::Metadata::expires_at
+    /// [`Capability::write_with_expire`]: crate::Capability::write_with_expire
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_expire(mut self, expire: Duration) -> Self {
+        self.expire = Some(expire);
+        self
+    }
+
+    /// Get the decompressed size from option
+    pub fn decompressed_size(&self) -> Option<u64> {
+        self.decompressed_size
+    }
+
+    /// Store the decompressed size of compressed content with the object,
+    /// so readers can get it via [`Metadata::decompressed_size`].
+    ///
+    /// Only services with [`Capability::write_with_decompressed_size`]
+    /// support this, others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Metadata::decompressed_size`]: crate::Metadata::decompressed_size
+    /// [`Capability::write_with_decompressed_size`]: crate::Capability::write_with_decompressed_size
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_decompressed_size(mut self, decompressed_size: u64) -> Self {
+        self.decompressed_size = Some(decompressed_size);
+        self
+    }
+
+    /// Get whether to skip the write if an identical object exists.
+    pub fn skip_if_identical(&self) -> bool {
+        self.skip_if_identical
+    }
+
+    /// Skip the write if the target already has identical content.
+    ///
+    /// The target will be stat before write, and the write is skipped only
+    /// if its content length and MD5 match the new content. MD5 is taken
+    /// from `Content-MD5`, or from etag if it's not a weak or multipart
+    /// etag. The write always happens if none of them is comparable.
+    ///
+    /// Use [`Operator::write_with_skipped`] to know whether the write
+    /// is skipped.
+    ///
+    /// [`Operator::write_with_skipped`]: crate::Operator::write_with_skipped
+    pub fn with_skip_if_identical(mut self, skip: bool) -> Self {
+        self.skip_if_identical = skip;
+        self
+    }
+
+    /// Get the completed parts to resume from.
+    pub fn resume_parts(&self) -> &[PartInfo] {
+        &self.resume_parts
+    }
+
+    /// Resume an interrupted multipart upload with its completed parts.
+    ///
+    /// Parts are reported by [`OpWrite::with_part_callback`] of the previous
+    /// writer and must belong to the same upload. The new writer will keep
+    /// numbering parts after them, so callers should skip the first
+    /// `parts.iter().map(|p| p.size()).sum()` bytes of content and write
+    /// the rest.
+    ///
+    /// Only services with [`Capability::write_can_resume`] support this,
+    /// others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Capability::write_can_resume`]: crate::Capability::write_can_resume
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_resume_parts(mut self, parts: Vec<PartInfo>) -> Self {
+        self.resume_parts = parts;
+        self
+    }
+
+    /// Get the callback of completed parts.
+    pub fn part_callback(&self) -> Option<&PartCallback> {
+        self.part_callback.as_ref()
+    }
+
+    /// Call `callback` every time a multipart part has been uploaded.
+    ///
+    /// Callers can checkpoint the reported [`PartInfo`] and use
+    /// [`OpWrite::with_resume_parts`] to continue the upload after restart.
+    /// Services without multipart upload will never call it.
+    pub fn with_part_callback(mut self, callback: PartCallback) -> Self {
+        self.part_callback = Some(callback);
+        self
+    }
+
+    /// Check if the part size of multipart write grows adaptively.
+    pub fn adaptive_parts(&self) -> bool {
+        self.adaptive_parts
+    }
+
+    /// Grow the part size of multipart write as more data is written.
+    ///
+    /// The first 1000 parts use the service's min part size, and the part
+    /// size doubles every 1000 parts after that, bounded by the service's
+    /// max part size. For `s3` with the default 8 MiB min part size, parts
+    /// are 8 MiB, 16 MiB, ..., 4 GiB, so objects up to about 7.8 TiB can be
+    /// written within the limit of 10000 parts while only one part is
+    /// buffered in memory.
+    ///
+    /// Default to `false` which uses the min part size for all parts.
+    /// Services without multipart upload will ignore it.
+    pub fn with_adaptive_parts(mut self, adaptive: bool) -> Self {
+        self.adaptive_parts = adaptive;
+        self
+    }
+
+    /// Get the blob type of write.
+    pub fn blob_type(&self) -> Option<BlobType> {
+        self.blob_type
+    }
+
+    /// Set the type of blob to write, see [`BlobType`] for details.
+    ///
+    /// Only services with [`Capability::write_with_blob_type`] support
+    /// this, others will return [`ErrorKind::Unsupported`].
+    ///
+    /// [`Capability::write_with_blob_type`]: crate::Capability::write_with_blob_type
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_blob_type(mut self, blob_type: BlobType) -> Self {
+        self.blob_type = Some(blob_type);
+        self
+    }
+
+    /// Get the path of local spill file.
+    #[cfg(feature = "spill")]
+    pub fn spill(&self) -> Option<&Path> {
+        self.spill.as_deref()
+    }
+
+    /// Tee written bytes to a local spill file so that the upload can be
+    /// retried from the start.
+    ///
+    /// On a temporary failure in the middle of writing, [`Writer`] will
+    /// abort the upload, re-read the spill file and upload it again from
+    /// the start, at most 3 times. The spill file is removed after the
+    /// writer is closed, aborted or dropped.
+    ///
+    /// This is useful for streaming writes from non-replayable sources
+    /// like network, but the whole content will be kept on local disk,
+    /// and every retry re-uploads all written bytes.
+    ///
+    /// Only takes effect for [`Operator::writer_with`], and requires the
+    /// `spill` feature.
+    ///
+    /// [`Writer`]: crate::Writer
+    /// [`Operator::writer_with`]: crate::Operator::writer_with
+    #[cfg(feature = "spill")]
+    pub fn with_spill(mut self, path: impl AsRef<Path>) -> Self {
+        self.spill = Some(path.as_ref().to_path_buf());
+        self
+    }
+}
+
+/// The type of blob to write on services like `azblob`.
+///
+/// Blob types other than [`BlobType::Block`] don't support multipart args
+/// like [`OpWrite::with_adaptive_parts`], services will return
+/// [`ErrorKind::ConfigInvalid`] for them.
+///
+/// [`ErrorKind::ConfigInvalid`]: crate::ErrorKind::ConfigInvalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlobType {
+    /// Block blob is the general purpose blob, it's the default blob type.
+    Block,
+    /// Append blob is optimized for appending like logs, every write will
+    /// be appended to the end of the blob.
+    Append,
+    /// Page blob is optimized for random read and write like VHDs.
+    ///
+    /// The content length must be known before writing, and both the
+    /// content length and every write must be aligned to 512 bytes.
+    Page,
 }
 
 /// Args for `append` operation.
@@ -446,23 +1489,184 @@ impl OpAppend {
 }
 
 /// Args for `copy` operation.
-#[derive(Debug, Clone, Default)]
-pub struct OpCopy {}
+///
+/// # User metadata and tags
+///
+/// Whether user metadata and tags are copied depends on services if not
+/// specified:
+///
+/// - `s3`: both user metadata and tags are copied.
+/// - Other services: not supported to be specified.
+#[derive(Clone, Default)]
+pub struct OpCopy {
+    source_bucket: Option<String>,
+    preserve_user_metadata: Option<bool>,
+    preserve_tags: Option<bool>,
+    if_match: Option<String>,
+    progress: Option<CopyProgress>,
+    range: BytesRange,
+}
+
+/// CopyProgress is the callback of copy progress with `(bytes_done, total)`.
+pub type CopyProgress = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+impl Debug for OpCopy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpCopy")
+            .field("source_bucket", &self.source_bucket)
+            .field("preserve_user_metadata", &self.preserve_user_metadata)
+            .field("preserve_tags", &self.preserve_tags)
+            .field("if_match", &self.if_match)
+            .field("progress", &self.progress.is_some())
+            .field("range", &self.range)
+            .finish()
+    }
+}
 
 impl OpCopy {
     /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Copy from another bucket of the same service.
+    ///
+    /// # Notes
+    ///
+    /// - `from` will be treated as an absolute path inside the source
+    ///   bucket instead of a path relative to root.
+    /// - Only services with [`Capability::copy_cross_bucket`] support this.
+    ///
+    /// [`Capability::copy_cross_bucket`]: crate::Capability::copy_cross_bucket
+    pub fn with_source_bucket(mut self, bucket: &str) -> Self {
+        self.source_bucket = Some(bucket.to_string());
+        self
+    }
+
+    /// Get the source bucket of copy.
+    pub fn source_bucket(&self) -> Option<&str> {
+        self.source_bucket.as_deref()
+    }
+
+    /// Set whether to copy user metadata to the target object.
+    ///
+    /// If false, the target object will have no user metadata.
+    ///
+    /// # Notes
+    ///
+    /// On s3, dropping user metadata also resets system metadata like
+    /// `Content-Type` of the target object.
+    pub fn with_preserve_user_metadata(mut self, preserve: bool) -> Self {
+        self.preserve_user_metadata = Some(preserve);
+        self
+    }
+
+    /// Get whether to copy user metadata to the target object.
+    pub fn preserve_user_metadata(&self) -> Option<bool> {
+        self.preserve_user_metadata
+    }
+
+    /// Set whether to copy tags to the target object.
+    ///
+    /// If false, the target object will have no tags.
+    pub fn with_preserve_tags(mut self, preserve: bool) -> Self {
+        self.preserve_tags = Some(preserve);
+        self
+    }
+
+    /// Get whether to copy tags to the target object.
+    pub fn preserve_tags(&self) -> Option<bool> {
+        self.preserve_tags
+    }
+
+    /// Only copy if the etag of the source object matches the given one.
+    ///
+    /// Services return [`ErrorKind::ConditionNotMatch`] if the source
+    /// has been changed.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`]: crate::ErrorKind::ConditionNotMatch
+    pub fn with_if_match(mut self, if_match: &str) -> Self {
+        self.if_match = Some(if_match.to_string());
+        self
+    }
+
+    /// Get the if_match of copy source.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Report progress of copy with `(bytes_done, total)`.
+    ///
+    /// For services with [`Capability::copy_with_progress`], the callback
+    /// is called once a copy part is finished, so the granularity equals
+    /// the copy part size. For single-shot copies or other services, it's
+    /// called once after copy finished, with the size fetched by `stat`.
+    ///
+    /// [`Capability::copy_with_progress`]: crate::Capability::copy_with_progress
+    pub fn with_progress(mut self, progress: CopyProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Get the progress callback of copy.
+    pub fn progress(&self) -> Option<&CopyProgress> {
+        self.progress.as_ref()
+    }
+
+    /// Only copy the given byte range of the source object, the target
+    /// object will contain exactly that slice.
+    ///
+    /// # Notes
+    ///
+    /// Services with [`Capability::copy_with_range`] copy the range at
+    /// server side. Otherwise, [`Operator::copy_with`] falls back to
+    /// reading the range and writing it to the target, so the slice will
+    /// be transferred through the client twice.
+    ///
+    /// [`Capability::copy_with_range`]: crate::Capability::copy_with_range
+    /// [`Operator::copy_with`]: crate::Operator::copy_with
+    pub fn with_range(mut self, range: BytesRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Get the range of copy source.
+    pub fn range(&self) -> BytesRange {
+        self.range
+    }
 }
 
 /// Args for `rename` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpRename {}
+pub struct OpRename {
+    conditional_copy: bool,
+}
 
 impl OpRename {
     /// Create a new `OpMove`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Allow emulating rename by conditional copy and delete on services
+    /// that don't support rename natively.
+    ///
+    /// The emulation relies on the service honouring `If-Match` on delete.
+    /// Many s3 compatible services ignore it, and a concurrent overwrite of
+    /// `from` will be lost on them. Only enable this if the service is
+    /// known to support it.
+    ///
+    /// Default to `false`, rename returns [`ErrorKind::Unsupported`] on
+    /// services without native rename.
+    ///
+    /// [`ErrorKind::Unsupported`]: crate::ErrorKind::Unsupported
+    pub fn with_conditional_copy(mut self, conditional_copy: bool) -> Self {
+        self.conditional_copy = conditional_copy;
+        self
+    }
+
+    /// Check if rename could be emulated by conditional copy and delete.
+    pub fn conditional_copy(&self) -> bool {
+        self.conditional_copy
+    }
 }
@@ -15,18 +15,24 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::Ordering;
 use std::io;
+use std::io::IoSliceMut;
 use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use bytes::Bytes;
 use futures::AsyncRead;
 use futures::AsyncSeek;
+use futures::FutureExt;
 use futures::Stream;
 
 use crate::ops::OpRead;
+use crate::ops::OpStat;
+use crate::raw::oio::ReadExt;
 use crate::raw::*;
 use crate::*;
 
@@ -41,10 +47,16 @@ use crate::*;
 /// - `AsyncSeek`
 /// - `Stream<Item = <io::Result<Bytes>>>`
 ///
+/// Both `futures` and `tokio` versions of `AsyncRead` and `AsyncSeek` are
+/// implemented. Use `Reader::into_tokio` with the `tokio-buf-read` feature
+/// if `tokio::io::AsyncBufRead` is required too.
+///
 /// For reading data, we can use `AsyncRead` and `Stream`. The mainly
 /// different is where the `copy` happens.
 ///
 /// `AsyncRead` requires user to prepare a buffer for `Reader` to fill.
+/// It also supports `read_vectored` to fill multiple buffers (for example,
+/// the header and payload of a frame) in one call.
 /// And `Stream` will stream out a `Bytes` for user to decide when to copy
 /// it's content.
 ///
@@ -68,13 +80,53 @@ impl Reader {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create_dir(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
-        let (_, r) = acc.read(path, op).await?;
+        let (_, r) = read_with_clamp_range(&acc, path, op).await?;
 
         Ok(Reader {
             inner: r,
             seek_state: SeekState::Init,
         })
     }
+
+    /// Create a new reader and wait for the first chunk of its content.
+    ///
+    /// The chunk will be yielded first by the returning reader, so callers
+    /// could race readers on when their content starts arriving.
+    pub(crate) async fn create_peeked(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
+        let (_, mut r) = read_with_clamp_range(&acc, path, op).await?;
+        let first = r.next().await.transpose()?.unwrap_or_default();
+
+        Ok(Reader {
+            inner: Box::new(PeekedReader { first, inner: r }),
+            seek_state: SeekState::Init,
+        })
+    }
+
+    /// Convert into a reader implementing tokio's `AsyncRead`, `AsyncBufRead`
+    /// and `AsyncSeek`.
+    ///
+    /// The returned reader is buffered, seeking on it will discard the
+    /// buffered data and seek the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use tokio::io::AsyncBufReadExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut r = op.reader("path/to/file").await?.into_tokio();
+    /// let mut line = String::new();
+    /// r.read_line(&mut line).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio-buf-read")]
+    pub fn into_tokio(self) -> impl tokio::io::AsyncBufRead + tokio::io::AsyncSeek + Unpin + Send {
+        tokio::io::BufReader::new(self)
+    }
 }
 
 impl oio::Read for Reader {
@@ -82,6 +134,14 @@ impl oio::Read for Reader {
         self.inner.poll_read(cx, buf)
     }
 
+    fn poll_read_vectored(
+        &mut self,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize>> {
+        self.inner.poll_read_vectored(cx, bufs)
+    }
+
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
         self.inner.poll_seek(cx, pos)
     }
@@ -99,6 +159,14 @@ impl AsyncRead for Reader {
     ) -> Poll<io::Result<usize>> {
         Pin::new(&mut self.inner).poll_read(cx, buf)
     }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read_vectored(cx, bufs)
+    }
 }
 
 impl AsyncSeek for Reader {
@@ -178,8 +246,233 @@ impl Stream for Reader {
     }
 }
 
+/// The max capacity to pre-allocate while reading content into memory.
+pub(crate) const MAX_READ_PREALLOC_SIZE: usize = 16 * 1024 * 1024;
+
+/// Decide the initial buffer capacity for reading the whole content.
+///
+/// Users' size hint is preferred over the content length returned by
+/// services. Both are capped by [`MAX_READ_PREALLOC_SIZE`] so that a bogus
+/// value can't lead to OOM. The buffer will still grow while reading if needed.
+pub(crate) fn read_buffer_capacity(size_hint: Option<u64>, content_length: Option<u64>) -> usize {
+    size_hint
+        .or(content_length)
+        .map_or(0, |v| v.min(MAX_READ_PREALLOC_SIZE as u64) as usize)
+}
+
+/// Check the size of content have been read against the content length
+/// returned by services.
+///
+/// Returns [`ErrorKind::ContentIncomplete`] if the content is shorter, or
+/// [`ErrorKind::ContentTruncated`] if it's longer.
+pub(crate) fn check_read_size(content_length: Option<u64>, actual: usize) -> Result<()> {
+    let expect = match content_length {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let actual = actual as u64;
+    let kind = match actual.cmp(&expect) {
+        Ordering::Equal => return Ok(()),
+        Ordering::Less => ErrorKind::ContentIncomplete,
+        Ordering::Greater => ErrorKind::ContentTruncated,
+    };
+    Err(
+        Error::new(kind, "content length doesn't match the content read")
+            .with_context("expect", expect.to_string())
+            .with_context("actual", actual.to_string()),
+    )
+}
+
+/// Read with [`OpRead::with_clamp_range`] applied.
+///
+/// The range will be clamped to the current size of object and read again
+/// if service returns [`ErrorKind::RangeNotSatisfied`].
+pub(crate) async fn read_with_clamp_range(
+    acc: &FusedAccessor,
+    path: &str,
+    op: OpRead,
+) -> Result<(RpRead, oio::Reader)> {
+    if !op.clamp_range() || op.range().is_full() {
+        return read_with_first_byte_timeout(acc, path, op).await;
+    }
+
+    let br = op.range();
+    match read_with_first_byte_timeout(acc, path, op.clone()).await {
+        Err(err) if err.kind() == ErrorKind::RangeNotSatisfied => {}
+        res => return res,
+    }
+
+    let len = acc
+        .stat(path, OpStat::new())
+        .await?
+        .into_metadata()
+        .content_length();
+    match br.clamp(len) {
+        Some(br) => read_with_first_byte_timeout(acc, path, op.with_range(br)).await,
+        None => Ok((RpRead::new(0), Box::new(oio::Cursor::from(Bytes::new())))),
+    }
+}
+
+/// Send read request to accessor, the returning reader will fail if no
+/// data returned before [`OpRead::first_byte_timeout`].
+async fn read_with_first_byte_timeout(
+    acc: &FusedAccessor,
+    path: &str,
+    op: OpRead,
+) -> Result<(RpRead, oio::Reader)> {
+    let timeout = match op.first_byte_timeout() {
+        Some(timeout) => timeout,
+        None => return acc.read(path, op).await,
+    };
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let (rp, r) = tokio::time::timeout_at(deadline, acc.read(path, op))
+        .await
+        .map_err(|_| first_byte_timeout_error(path, timeout))??;
+
+    let r = FirstByteTimeoutReader {
+        inner: r,
+        path: path.to_string(),
+        timeout,
+        sleep: Some(Box::pin(tokio::time::sleep_until(deadline))),
+    };
+    Ok((rp, Box::new(r)))
+}
+
+fn first_byte_timeout_error(path: &str, timeout: Duration) -> Error {
+    Error::new(ErrorKind::Unexpected, "read first byte timeout")
+        .with_operation(oio::ReadOperation::Read)
+        .with_context("path", path)
+        .with_context("timeout", format!("{timeout:?}"))
+        .set_temporary()
+}
+
+/// FirstByteTimeoutReader will return error if the first byte doesn't
+/// arrive before `sleep` finished.
+struct FirstByteTimeoutReader {
+    inner: oio::Reader,
+    path: String,
+    timeout: Duration,
+    /// Will be `None` after the first byte arrived.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl FirstByteTimeoutReader {
+    fn poll_timeout(&mut self, cx: &mut Context<'_>) -> Result<()> {
+        if let Some(sleep) = self.sleep.as_mut() {
+            if sleep.poll_unpin(cx).is_ready() {
+                return Err(first_byte_timeout_error(&self.path, self.timeout));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl oio::Read for FirstByteTimeoutReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self.inner.poll_read(cx, buf) {
+            Poll::Ready(res) => {
+                self.sleep = None;
+                Poll::Ready(res)
+            }
+            Poll::Pending => {
+                self.poll_timeout(cx)?;
+                Poll::Pending
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self.inner.poll_next(cx) {
+            Poll::Ready(res) => {
+                self.sleep = None;
+                Poll::Ready(res)
+            }
+            Poll::Pending => {
+                if let Err(err) = self.poll_timeout(cx) {
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// PeekedReader yields the peeked `first` chunk before reading `inner`.
+struct PeekedReader {
+    first: Bytes,
+    inner: oio::Reader,
+}
+
+impl oio::Read for PeekedReader {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if self.first.is_empty() {
+            return self.inner.poll_read(cx, buf);
+        }
+
+        let n = buf.len().min(self.first.len());
+        buf[..n].copy_from_slice(&self.first[..n]);
+        self.first = self.first.slice(n..);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        // Inner reader has been advanced past the peeked chunk.
+        let pos = match pos {
+            io::SeekFrom::Current(n) => io::SeekFrom::Current(n - self.first.len() as i64),
+            pos => pos,
+        };
+        let res = ready!(self.inner.poll_seek(cx, pos));
+        if res.is_ok() {
+            self.first.clear();
+        }
+        Poll::Ready(res)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if self.first.is_empty() {
+            return self.inner.poll_next(cx);
+        }
+
+        Poll::Ready(Some(Ok(std::mem::take(&mut self.first))))
+    }
+}
+
 /// BlockingReader is designed to read data from given path in an blocking
 /// manner.
+///
+/// # Usage
+///
+/// BlockingReader implements the following APIs:
+///
+/// - `std::io::Read`
+/// - `std::io::Seek`
+/// - `Iterator<Item = <io::Result<Bytes>>>`
+///
+/// So it can be handed to sync consumers like parquet readers directly.
+/// Seeking is served by the underlying reader without reading data.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Read;
+/// # use std::io::Seek;
+/// # use std::io::SeekFrom;
+/// # use anyhow::Result;
+/// # use opendal::BlockingOperator;
+/// # fn test(op: BlockingOperator) -> Result<()> {
+/// let mut r = op.reader("path/to/file")?;
+/// r.seek(SeekFrom::Start(1024))?;
+/// let mut buf = Vec::new();
+/// r.read_to_end(&mut buf)?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct BlockingReader {
     pub(crate) inner: oio::BlockingReader,
 }
@@ -260,14 +553,29 @@ impl Iterator for BlockingReader {
 
 #[cfg(test)]
 mod tests {
+    use std::io;
+    use std::io::IoSliceMut;
+    use std::io::Read;
+    use std::io::Seek;
+    use std::pin::Pin;
+    use std::task::ready;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use futures::FutureExt;
     use rand::rngs::ThreadRng;
     use rand::Rng;
     use rand::RngCore;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
 
+    use crate::ops::*;
+    use crate::raw::*;
     use crate::services;
-    use crate::Operator;
+    use crate::*;
 
     fn gen_random_bytes() -> Vec<u8> {
         let mut rng = ThreadRng::default();
@@ -298,6 +606,67 @@ mod tests {
         assert_eq!(buf, content);
     }
 
+    #[tokio::test]
+    async fn test_reader_async_read_vectored() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        // A frame with 4 bytes header and 8 bytes payload.
+        op.write(path, "HEADpayload!")
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        let mut header = [0; 4];
+        let mut payload = [0; 8];
+        let n = futures::AsyncReadExt::read_vectored(
+            &mut reader,
+            &mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)],
+        )
+        .await
+        .expect("read vectored must succeed");
+
+        assert_eq!(n, 12);
+        assert_eq!(&header, b"HEAD");
+        assert_eq!(&payload, b"payload!");
+    }
+
+    #[cfg(feature = "tokio-buf-read")]
+    #[tokio::test]
+    async fn test_reader_into_tokio() {
+        use tokio::io::AsyncBufReadExt;
+
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+        op.write(path, "first line\nsecond line\n")
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap().into_tokio();
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .await
+            .expect("read line must succeed");
+        assert_eq!(line, "first line\n");
+
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, b"second line\n");
+
+        let n = reader.seek(tokio::io::SeekFrom::Start(6)).await.unwrap();
+        assert_eq!(n, 6, "seek position must be 6");
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, b"line\nsecond line\n");
+    }
+
     #[tokio::test]
     async fn test_reader_async_seek() {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
@@ -326,4 +695,205 @@ mod tests {
             .expect("read to end must succeed");
         assert_eq!(buf, content);
     }
+
+    #[tokio::test]
+    async fn test_reader_peeked_seek() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        // Hedged read returns the reader with its first chunk peeked.
+        let mut reader = op
+            .hedged_read(&[], path, OpRead::new(), Duration::from_secs(1))
+            .await
+            .unwrap();
+        let mut buf = vec![0; 4];
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, content[..4]);
+
+        let n = reader.seek(tokio::io::SeekFrom::Current(0)).await.unwrap();
+        assert_eq!(n, 4, "seek position must be 4");
+        let n = reader.seek(tokio::io::SeekFrom::Current(2)).await.unwrap();
+        assert_eq!(n, 6, "seek position must be 6");
+
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, content[6..]);
+    }
+
+    #[test]
+    fn test_blocking_reader_read() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .blocking();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone()).expect("write must succeed");
+
+        let mut reader = op.reader(path).unwrap();
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .expect("read to end must succeed");
+
+        assert_eq!(buf, content);
+    }
+
+    #[test]
+    fn test_blocking_reader_seek() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .finish()
+            .blocking();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone()).expect("write must succeed");
+
+        let mut reader = op.reader(path).unwrap();
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .expect("read to end must succeed");
+        assert_eq!(buf, content);
+
+        let offset = content.len() as u64 / 2;
+        let n = reader.seek(io::SeekFrom::Start(offset)).unwrap();
+        assert_eq!(n, offset, "seek position must be {offset}");
+
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .expect("read to end must succeed");
+        assert_eq!(buf, content[offset as usize..]);
+    }
+
+    /// MockSlowService returns a byte after every delay.
+    #[derive(Debug, Clone)]
+    struct MockSlowService {
+        delays: Vec<Duration>,
+    }
+
+    struct MockSlowReader {
+        delays: Vec<Duration>,
+        sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    }
+
+    impl oio::Read for MockSlowReader {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            if self.delays.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            let delay = self.delays[0];
+            let sleep = self
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(delay)));
+            ready!(sleep.poll_unpin(cx));
+            self.sleep = None;
+            self.delays.remove(0);
+
+            buf[0] = b'x';
+            Poll::Ready(Ok(1))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: io::SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "MockSlowReader doesn't support seek",
+            )))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            Poll::Ready(Some(Err(Error::new(
+                ErrorKind::Unsupported,
+                "MockSlowReader doesn't support next",
+            ))))
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockSlowService {
+        type Reader = MockSlowReader;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Appender = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_scheme(Scheme::Custom("mock"));
+            am.set_capability(Capability {
+                read: true,
+                read_can_seek: true,
+                read_can_next: true,
+                ..Default::default()
+            });
+            am
+        }
+
+        async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            Ok((
+                RpRead::new(self.delays.len() as u64),
+                MockSlowReader {
+                    delays: self.delays.clone(),
+                    sleep: None,
+                },
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_first_byte_timeout() -> Result<()> {
+        let op = OperatorBuilder::new(MockSlowService {
+            delays: vec![Duration::from_millis(200), Duration::ZERO],
+        })
+        .finish();
+        let args = OpRead::new().with_first_byte_timeout(Duration::from_millis(50));
+
+        let mut r = op.reader_with("file", args.clone()).await?;
+        let err = r.read_to_end(&mut Vec::new()).await.unwrap_err();
+        let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+
+        let err = op.read_with("file", args).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reader_first_byte_timeout_slow_stream() -> Result<()> {
+        // The whole read takes longer than timeout, but the first byte
+        // arrives in time.
+        let op = OperatorBuilder::new(MockSlowService {
+            delays: vec![
+                Duration::ZERO,
+                Duration::from_millis(40),
+                Duration::from_millis(40),
+            ],
+        })
+        .finish();
+        let args = OpRead::new().with_first_byte_timeout(Duration::from_millis(20));
+
+        let mut r = op.reader_with("file", args.clone()).await?;
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"xxx");
+
+        assert_eq!(op.read_with("file", args).await?, b"xxx");
+
+        Ok(())
+    }
 }
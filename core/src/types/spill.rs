@@ -0,0 +1,223 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use backon::BackoffBuilder;
+use backon::ExponentialBackoff;
+use backon::ExponentialBuilder;
+use bytes::Bytes;
+use log::warn;
+use tokio::fs;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWriteExt;
+
+use crate::ops::OpWrite;
+use crate::raw::oio::Write;
+use crate::raw::*;
+use crate::*;
+
+/// The max times of retrying the upload from spill file for every failure.
+const SPILL_MAX_RETRIES: usize = 3;
+
+/// SpillWriter tees written bytes to a local file, and uploads them again
+/// from the start on temporary errors.
+///
+/// The spill file will be removed after the writer is closed, aborted or
+/// dropped.
+pub(crate) struct SpillWriter {
+    acc: FusedAccessor,
+    path: String,
+    op: OpWrite,
+    inner: oio::Writer,
+
+    spill_path: PathBuf,
+    spill: File,
+    /// Whether the spill file has been removed.
+    removed: bool,
+    backoff: ExponentialBuilder,
+}
+
+impl SpillWriter {
+    pub(crate) async fn new(
+        acc: FusedAccessor,
+        path: &str,
+        op: OpWrite,
+        inner: oio::Writer,
+        spill_path: PathBuf,
+    ) -> Result<Self> {
+        let spill = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&spill_path)
+            .await
+            .map_err(|err| new_spill_error(err, "create spill file", &spill_path))?;
+
+        Ok(SpillWriter {
+            acc,
+            path: path.to_string(),
+            op,
+            inner,
+            spill_path,
+            spill,
+            removed: false,
+            backoff: ExponentialBuilder::default()
+                .with_min_delay(Duration::from_millis(100))
+                .with_max_times(SPILL_MAX_RETRIES),
+        })
+    }
+
+    /// Restart the upload and write all bytes in spill file if `err` is
+    /// temporary, otherwise return `err` directly.
+    ///
+    /// Replays are bounded by `backoff`, which is shared by all failures
+    /// of the same write, flush or close.
+    async fn replay(&mut self, mut err: Error, backoff: &mut ExponentialBackoff) -> Result<()> {
+        loop {
+            if !err.is_temporary() {
+                return Err(err);
+            }
+            let Some(delay) = backoff.next() else {
+                return Err(err.with_context("spill", self.spill_path.to_string_lossy()));
+            };
+            warn!(
+                "writer of {} failed, retrying from spill file after {}s: {}",
+                self.path,
+                delay.as_secs_f64(),
+                err
+            );
+            tokio::time::sleep(delay).await;
+
+            // The failed upload will be replaced, ignore its abort error.
+            let _ = self.inner.abort().await;
+            match self.replay_once().await {
+                Ok(()) => return Ok(()),
+                Err(e) => err = e,
+            }
+        }
+    }
+
+    async fn replay_once(&mut self) -> Result<()> {
+        let (_, w) = self.acc.write(&self.path, self.op.clone()).await?;
+        self.inner = w;
+
+        self.spill
+            .seek(SeekFrom::Start(0))
+            .await
+            .map_err(|err| new_spill_error(err, "seek spill file", &self.spill_path))?;
+        loop {
+            let mut buf = vec![0; SPILL_CHUNK_SIZE];
+            let n = self
+                .spill
+                .read(&mut buf)
+                .await
+                .map_err(|err| new_spill_error(err, "read spill file", &self.spill_path))?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.truncate(n);
+            self.inner.write(buf.into()).await?;
+        }
+    }
+
+    async fn remove_spill(&mut self) -> Result<()> {
+        self.removed = true;
+        match fs::remove_file(&self.spill_path).await {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                Err(new_spill_error(err, "remove spill file", &self.spill_path))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for SpillWriter {
+    fn drop(&mut self) {
+        // Writer is dropped without close or abort, remove the spill file
+        // in best effort since we can't await here.
+        if !self.removed {
+            let _ = std::fs::remove_file(&self.spill_path);
+        }
+    }
+}
+
+/// The size of chunks read from spill file while replaying.
+const SPILL_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+fn new_spill_error(err: io::Error, message: &'static str, spill_path: &Path) -> Error {
+    Error::new(ErrorKind::Unexpected, message)
+        .with_context("spill", spill_path.to_string_lossy())
+        .set_source(err)
+}
+
+#[async_trait]
+impl Write for SpillWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        // Seek to the end since replay could move the position.
+        let spill = &mut self.spill;
+        async {
+            spill.seek(SeekFrom::End(0)).await?;
+            spill.write_all(&bs).await?;
+            spill.flush().await
+        }
+        .await
+        .map_err(|err| new_spill_error(err, "write spill file", &self.spill_path))?;
+
+        match self.inner.write(bs).await {
+            Ok(()) => Ok(()),
+            // Every failed write gets the full retry times.
+            Err(err) => self.replay(err, &mut self.backoff.build()).await,
+        }
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await?;
+        self.remove_spill().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        // Flush could keep failing after replays, share the retry times
+        // across them.
+        let mut backoff = self.backoff.build();
+        loop {
+            match self.inner.flush().await {
+                Ok(()) => return Ok(()),
+                Err(err) => self.replay(err, &mut backoff).await?,
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let mut backoff = self.backoff.build();
+        loop {
+            match self.inner.close().await {
+                Ok(()) => break,
+                Err(err) => self.replay(err, &mut backoff).await?,
+            }
+        }
+        self.remove_spill().await
+    }
+}
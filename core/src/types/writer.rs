@@ -32,6 +32,50 @@ use crate::raw::oio::Write;
 use crate::raw::*;
 use crate::*;
 
+/// PartInfo is a part uploaded by a multipart [`Writer`].
+///
+/// It's reported by [`OpWrite::with_part_callback`] and can be passed to
+/// [`OpWrite::with_resume_parts`] to resume the upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartInfo {
+    upload_id: String,
+    part_number: usize,
+    etag: String,
+    size: u64,
+}
+
+impl PartInfo {
+    /// Create a new part info.
+    pub fn new(upload_id: &str, part_number: usize, etag: &str, size: u64) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            part_number,
+            etag: etag.to_string(),
+            size,
+        }
+    }
+
+    /// Id of the multipart upload this part belongs to.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Number of this part, starts from 1.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+
+    /// ETag of this part returned by service.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// Size of this part.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
 /// Writer is designed to write data into given path in an asynchronous
 /// manner.
 ///
@@ -72,7 +116,16 @@ impl Writer {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
-        let (_, w) = acc.write(path, op).await?;
+        let (_, w) = acc.write(path, op.clone()).await?;
+
+        #[cfg(feature = "spill")]
+        let w: oio::Writer = match op.spill() {
+            Some(spill) => {
+                let spill = spill.to_path_buf();
+                Box::new(SpillWriter::new(acc, path, op, w, spill).await?)
+            }
+            None => w,
+        };
 
         Ok(Writer {
             state: State::Idle(Some(w)),
@@ -108,6 +161,28 @@ impl Writer {
         }
     }
 
+    /// Flush buffered data into storage without closing the writer.
+    ///
+    /// Unlike `close`, the writer can still be written after `flush` and
+    /// the file will only be available after `close` has been called.
+    ///
+    /// ## Notes
+    ///
+    /// - Services with multipart upload (like `s3`) will upload buffered
+    ///   data as a new part only if it reaches the minimum part size of
+    ///   services, smaller data is kept in buffer until `close`.
+    /// - Services that write the whole file in one request may treat this as a no-op.
+    pub async fn flush(&mut self) -> Result<()> {
+        if let State::Idle(Some(w)) = &mut self.state {
+            w.flush().await
+        } else {
+            unreachable!(
+                "writer state invalid while flush, expect Idle, actual {}",
+                self.state
+            );
+        }
+    }
+
     /// Close the writer and make sure all data have been committed.
     ///
     /// ## Notes
@@ -73,6 +73,7 @@ macro_rules! behavior_append_tests {
 
                 test_appender_futures_copy,
                 test_fuzz_appender,
+                test_upsert_append,
             );
         )*
     };
@@ -258,3 +259,30 @@ pub async fn test_fuzz_appender(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+/// Test upsert_append creates the file and appends to it afterwards.
+pub async fn test_upsert_append(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content_one, size_one) = gen_bytes();
+    let (content_two, size_two) = gen_bytes();
+
+    let len = op
+        .upsert_append(&path, content_one.clone())
+        .await
+        .expect("upsert append to a not existing file must success");
+    assert_eq!(len, size_one as u64);
+
+    let len = op
+        .upsert_append(&path, content_two.clone())
+        .await
+        .expect("upsert append to an existing file must success");
+    assert_eq!(len, (size_one + size_two) as u64);
+
+    let bs = op.read(&path).await.expect("read file must success");
+    assert_eq!(bs[..size_one], content_one);
+    assert_eq!(bs[size_one..], content_two);
+
+    op.delete(&path).await.expect("delete file must success");
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 use anyhow::Result;
+use futures::future;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use futures::TryStreamExt;
@@ -79,9 +80,13 @@ macro_rules! behavior_list_tests {
                 test_list_nested_dir,
                 test_list_dir_with_file_path,
                 test_list_with_start_after,
+                test_list_nested_with_start_after,
+                test_list_with_prefix,
+                test_list_with_include_self,
                 test_scan,
                 test_scan_root,
                 test_remove_all,
+                test_remove_all_preview,
             );
         )*
     };
@@ -321,6 +326,177 @@ pub async fn test_list_with_start_after(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with start_after should skip entries not after it, even if the
+/// service doesn't support start_after natively.
+pub async fn test_list_nested_with_start_after(op: Operator) -> Result<()> {
+    let dir = format!("{}/", uuid::Uuid::new_v4());
+    for name in ["a/1", "a/2", "b/1", "b/2", "c", "d/1"] {
+        op.write(&format!("{dir}{name}"), "content")
+            .await
+            .expect("write must succeed");
+    }
+
+    let list = |args: OpList| async {
+        let mut paths: Vec<String> = op
+            .list_with(&dir, args)
+            .await?
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await?;
+        paths.sort();
+        Result::<_>::Ok(paths)
+    };
+
+    // Only files are checked since not all services return dirs while
+    // listing without delimiter.
+    let paths: Vec<String> = list(
+        OpList::new()
+            .with_delimiter("")
+            .with_start_after(&format!("{dir}b/1")),
+    )
+    .await?
+    .into_iter()
+    .filter(|path| !path.ends_with('/'))
+    .collect();
+    assert_eq!(
+        paths,
+        ["b/2", "c", "d/1"]
+            .iter()
+            .map(|name| format!("{dir}{name}"))
+            .collect::<Vec<_>>()
+    );
+
+    let paths = list(OpList::new().with_start_after(&format!("{dir}a/2"))).await?;
+    assert_eq!(
+        paths,
+        ["b/", "c", "d/"]
+            .iter()
+            .map(|name| format!("{dir}{name}"))
+            .collect::<Vec<_>>()
+    );
+
+    op.remove_all(&dir).await?;
+    Ok(())
+}
+
+/// List with prefix should only return entries starting with the prefix.
+pub async fn test_list_with_prefix(op: Operator) -> Result<()> {
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+
+    for name in [
+        "2024-01-01.csv",
+        "2024-01-02.csv",
+        "2024-02-01.csv",
+        "2024-01/03.csv",
+    ] {
+        op.write(&format!("{dir}{name}"), "content")
+            .await
+            .expect("write must succeed");
+    }
+
+    let mut actual: Vec<String> = op
+        .list_with(dir, OpList::new().with_prefix("2024-01"))
+        .await?
+        .map_ok(|e| e.path().to_string())
+        .try_collect()
+        .await?;
+    actual.sort();
+    assert_eq!(
+        actual,
+        vec![
+            format!("{dir}2024-01-01.csv"),
+            format!("{dir}2024-01-02.csv"),
+            format!("{dir}2024-01/"),
+        ]
+    );
+
+    let mut actual: Vec<String> = op
+        .list_with(dir, OpList::new().with_prefix("2024-01").with_delimiter(""))
+        .await?
+        .map_ok(|e| e.path().to_string())
+        // Some services will return dirs in scan, ignore them.
+        .try_filter(|p| futures::future::ready(!p.ends_with('/')))
+        .try_collect()
+        .await?;
+    actual.sort();
+    assert_eq!(
+        actual,
+        vec![
+            format!("{dir}2024-01-01.csv"),
+            format!("{dir}2024-01-02.csv"),
+            format!("{dir}2024-01/03.csv"),
+        ]
+    );
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
+/// List with include_self should yield the listing path exactly once.
+pub async fn test_list_with_include_self(op: Operator) -> Result<()> {
+    let dir = format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(&dir).await?;
+    op.write(&format!("{dir}file"), "test_list_with_include_self")
+        .await?;
+    op.write(&format!("{dir}sub/file"), "test_list_with_include_self")
+        .await?;
+
+    for delimiter in ["/", ""] {
+        let paths: Vec<String> = op
+            .list_with(&dir, OpList::new().with_delimiter(delimiter))
+            .await?
+            .map_ok(|de| de.path().to_string())
+            .try_filter(|path| future::ready(path != &dir))
+            .try_collect()
+            .await?;
+
+        let entries: Vec<_> = op
+            .list_with(
+                &dir,
+                OpList::new()
+                    .with_delimiter(delimiter)
+                    .with_include_self(true),
+            )
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(entries[0].path(), dir, "listing path must be the first");
+        assert_eq!(
+            op.metadata(&entries[0], opendal::Metakey::Mode)
+                .await?
+                .mode(),
+            EntryMode::DIR
+        );
+        assert_eq!(
+            entries.iter().filter(|de| de.path() == dir).count(),
+            1,
+            "listing path must be returned exactly once"
+        );
+        assert_eq!(entries.len(), paths.len() + 1);
+    }
+
+    op.remove_all(&dir).await?;
+
+    // Services like kv treat every dir as existing, only check the others.
+    if let Err(err) = op.stat(&dir).await {
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let res = op
+            .list_with(&dir, OpList::new().with_include_self(true))
+            .await;
+        let entries: Vec<_> = match res {
+            Ok(l) => l.try_collect().await?,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        assert!(
+            entries.is_empty(),
+            "not existing dir must not be yielded: {entries:?}"
+        );
+    }
+    Ok(())
+}
+
 pub async fn test_scan_root(op: Operator) -> Result<()> {
     let w = op.scan("").await?;
     let actual = w
@@ -399,3 +575,47 @@ pub async fn test_remove_all(op: Operator) -> Result<()> {
     }
     Ok(())
 }
+
+/// Remove all preview should match what remove all actually removes.
+pub async fn test_remove_all_preview(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = ["keep", "x/", "x/y", "x/x/", "x/x/y", "x/x/x/"];
+    for path in paths.iter() {
+        if path.ends_with('/') {
+            op.create_dir(&format!("{parent}/{path}")).await?;
+        } else {
+            op.write(&format!("{parent}/{path}"), "test_scan").await?;
+        }
+    }
+
+    let target = format!("{parent}/x/");
+    let mut preview: HashSet<String> = op.remove_all_preview(&target).await?.into_iter().collect();
+    // Dir itself is always removed at last, but not all services can tell
+    // whether a dir exists.
+    assert!(preview.remove(&target));
+    assert!(preview.contains(&format!("{parent}/x/x/y")));
+    assert!(!preview.contains(&format!("{parent}/keep")));
+
+    let before = existing_paths(&op, &parent).await?;
+    op.remove_all(&target).await?;
+    let after = existing_paths(&op, &parent).await?;
+
+    let mut deleted: HashSet<String> = before.difference(&after).cloned().collect();
+    deleted.remove(&target);
+    assert_eq!(preview, deleted);
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
+/// Collect all existing paths under parent.
+async fn existing_paths(op: &Operator, parent: &str) -> Result<HashSet<String>> {
+    let paths = op
+        .scan(&format!("{parent}/"))
+        .await?
+        .map_ok(|e| e.path().to_string())
+        .try_collect()
+        .await?;
+    Ok(paths)
+}
@@ -77,10 +77,13 @@ macro_rules! behavior_write_tests {
                 test_create_dir_existing,
                 test_write,
                 test_write_with_dir_path,
+                test_write_with_require_parent,
+                test_write_to_existing_dir,
                 test_write_with_special_chars,
                 test_write_with_cache_control,
                 test_write_with_content_type,
                 test_write_with_content_disposition,
+                test_write_at,
                 test_stat,
                 test_stat_dir,
                 test_stat_with_special_chars,
@@ -173,6 +176,65 @@ pub async fn test_write_with_dir_path(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write with require_parent should fail if and only if parent doesn't exist.
+pub async fn test_write_with_require_parent(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let path = format!("{parent}{}", uuid::Uuid::new_v4());
+    let (content, _) = gen_bytes();
+    let args = OpWrite::new().with_require_parent(true);
+
+    // Services without dir markers may report the parent as existing.
+    let parent_exists = match op.stat(&parent).await {
+        Ok(_) => true,
+        Err(err) if err.kind() == ErrorKind::NotFound => false,
+        Err(err) => return Err(err.into()),
+    };
+    let result = op.write_with(&path, args.clone(), content.clone()).await;
+    if parent_exists {
+        result.expect("write with existing parent must succeed");
+    } else {
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::NotFound);
+        assert!(!op.is_exist(&path).await?);
+    }
+
+    op.create_dir(&parent).await?;
+    op.write_with(&path, args, content.clone())
+        .await
+        .expect("write with created parent must succeed");
+
+    // Parent is not required by default.
+    let other_parent = format!("{}/", uuid::Uuid::new_v4());
+    let other = format!("{other_parent}{}", uuid::Uuid::new_v4());
+    op.write(&other, content)
+        .await
+        .expect("write without parent must succeed");
+
+    for path in [&path, &parent, &other, &other_parent] {
+        op.delete(path).await.expect("delete must succeed");
+    }
+    Ok(())
+}
+
+/// Write to the path of an existing dir must not be reported as trailing slash.
+pub async fn test_write_to_existing_dir(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    op.create_dir(&format!("{path}/")).await?;
+
+    // Services without real dirs may allow the file and dir to coexist.
+    match op.write(&path, "content").await {
+        Ok(()) => op.delete(&path).await.expect("delete must succeed"),
+        Err(err) => assert!(
+            !err.to_string().contains("must not end with `/`"),
+            "existing dir must not be reported as trailing slash: {err}"
+        ),
+    }
+
+    op.delete(&format!("{path}/"))
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Write a single file with special chars should succeed.
 pub async fn test_write_with_special_chars(op: Operator) -> Result<()> {
     // Ignore test for supabase until https://github.com/apache/incubator-opendal/issues/2194 addressed.
@@ -279,6 +341,32 @@ pub async fn test_write_with_content_disposition(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write at offset should only update the given region.
+pub async fn test_write_at(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    if !op.info().capability().write_can_positional {
+        let res = op.write_at(&path, 0, "patch").await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    op.write(&path, vec![b'a'; 16]).await?;
+
+    op.write_at(&path, 4, "patch").await?;
+    let bs = op.read(&path).await?;
+    assert_eq!(bs, b"aaaapatchaaaaaaa");
+
+    // Write beyond the end should extend the file.
+    op.write_at(&path, 16, "tail").await?;
+    let bs = op.range_read(&path, 12..).await?;
+    assert_eq!(bs, b"aaaatail");
+    assert_eq!(op.stat(&path).await?.content_length(), 20);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();